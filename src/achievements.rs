@@ -0,0 +1,331 @@
+use bus::{GameEvent, Listener};
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The condition unlocking an achievement
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Trigger {
+    /// Kill the given amount of monsters of any kind
+    Kills(u64),
+    /// Kill the given amount of monsters with the given name
+    KillsOf(String, u64),
+    /// Walk the given amount of fields
+    Steps(u64),
+    /// Earn the given amount of gold in total
+    Gold(u64),
+    /// Finish any campaign
+    FinishCampaign,
+    /// Finish a campaign without dying since it was started
+    FinishCampaignWithoutDying,
+}
+
+impl Trigger {
+    /// Reads a trigger from a JSON object like `{"type": "Kills", "count": 100}` or
+    /// `{"type": "KillsOf", "monster": "rat", "count": 10}`
+    pub fn from_json(json: &Json) -> Result<Trigger, DataError> {
+        let object = data::as_object(json, "trigger")?;
+
+        match &data::string(object, "type")?[..] {
+            "Kills" => Ok(Trigger::Kills(data::unsigned(object, "count")?)),
+            "KillsOf" => {
+                Ok(Trigger::KillsOf(data::string(object, "monster")?,
+                                    data::unsigned(object, "count")?))
+            }
+            "Steps" => Ok(Trigger::Steps(data::unsigned(object, "count")?)),
+            "Gold" => Ok(Trigger::Gold(data::unsigned(object, "count")?)),
+            "FinishCampaign" => Ok(Trigger::FinishCampaign),
+            "FinishCampaignWithoutDying" => Ok(Trigger::FinishCampaignWithoutDying),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for Trigger {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        let (kind, count) = match *self {
+            Trigger::Kills(count) => ("Kills", Some(count)),
+            Trigger::KillsOf(ref monster, count) => {
+                object.insert("monster".to_owned(), monster.to_json());
+                ("KillsOf", Some(count))
+            }
+            Trigger::Steps(count) => ("Steps", Some(count)),
+            Trigger::Gold(count) => ("Gold", Some(count)),
+            Trigger::FinishCampaign => ("FinishCampaign", None),
+            Trigger::FinishCampaignWithoutDying => ("FinishCampaignWithoutDying", None),
+        };
+        object.insert("type".to_owned(), kind.to_json());
+        if let Some(count) = count {
+            object.insert("count".to_owned(), count.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// An achievement the player can unlock
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Achievement {
+    /// The unique id of the achievement
+    pub id: String,
+    /// The name shown to the player
+    pub name: String,
+    /// The description shown to the player
+    pub description: String,
+    /// The condition unlocking the achievement
+    pub trigger: Trigger,
+}
+
+impl Achievement {
+    /// Creates a new instance of `Achievement`
+    pub fn new(id: &str, name: &str, trigger: Trigger) -> Achievement {
+        Achievement {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            description: String::new(),
+            trigger,
+        }
+    }
+
+    /// A builder method for setting the description of the achievement
+    pub fn description(mut self, description: &str) -> Achievement {
+        self.description = description.to_owned();
+        self
+    }
+
+    /// Reads an achievement from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Achievement, DataError> {
+        let object = data::as_object(json, "achievements")?;
+
+        Ok(Achievement::new(&data::string(object, "id")?,
+                            &data::string(object, "name")?,
+                            Trigger::from_json(data::field(object, "trigger")?)?)
+            .description(&data::optional(object, "description", data::string)?
+                .unwrap_or_default()))
+    }
+}
+
+impl ToJson for Achievement {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.to_json());
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("description".to_owned(), self.description.to_json());
+        object.insert("trigger".to_owned(), self.trigger.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// Tracks the progress towards all achievements of a save by listening to game events
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Achievements {
+    achievements: Vec<Achievement>,
+    unlocked: BTreeSet<String>,
+    kills: BTreeMap<String, u64>,
+    steps: u64,
+    gold: u64,
+    died_in_campaign: bool,
+    newly_unlocked: Vec<String>,
+}
+
+impl Achievements {
+    /// Creates a new instance of `Achievements` without any achievements
+    pub fn new() -> Achievements {
+        Achievements::default()
+    }
+
+    /// Adds an achievement that can be unlocked from now on
+    pub fn add(&mut self, achievement: Achievement) {
+        self.achievements.push(achievement);
+    }
+
+    /// Returns all achievements, locked or not
+    pub fn all(&self) -> &[Achievement] {
+        &self.achievements
+    }
+
+    /// Returns `true` if the achievement with the given id is unlocked
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// Returns all unlocked achievements
+    pub fn unlocked(&self) -> Vec<&Achievement> {
+        self.achievements.iter().filter(|achievement| self.is_unlocked(&achievement.id)).collect()
+    }
+
+    /// Returns the achievements unlocked since the last call, e.g. to show a notification
+    pub fn take_newly_unlocked(&mut self) -> Vec<Achievement> {
+        let ids: Vec<String> = self.newly_unlocked.drain(..).collect();
+
+        self.achievements
+            .iter()
+            .filter(|achievement| ids.contains(&achievement.id))
+            .cloned()
+            .collect()
+    }
+
+    fn total_kills(&self) -> u64 {
+        self.kills.values().sum()
+    }
+
+    fn is_met(&self, trigger: &Trigger, event: &GameEvent) -> bool {
+        match *trigger {
+            Trigger::Kills(count) => self.total_kills() >= count,
+            Trigger::KillsOf(ref monster, count) => {
+                self.kills.get(monster).is_some_and(|kills| *kills >= count)
+            }
+            Trigger::Steps(count) => self.steps >= count,
+            Trigger::Gold(count) => self.gold >= count,
+            Trigger::FinishCampaign => {
+                matches!(*event, GameEvent::CampaignFinished(_))
+            }
+            Trigger::FinishCampaignWithoutDying => {
+                match *event {
+                    GameEvent::CampaignFinished(_) => !self.died_in_campaign,
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Reads the achievements and the progress towards them from a JSON object as written by
+    /// `to_json`
+    pub fn from_json(json: &Json) -> Result<Achievements, DataError> {
+        let object = data::as_object(json, "achievements")?;
+
+        let mut kills = BTreeMap::new();
+        for (monster, count) in data::as_object(data::field(object, "kills")?, "kills")? {
+            kills.insert(monster.clone(),
+                         count.as_u64().ok_or_else(|| DataError::Invalid("kills".to_owned()))?);
+        }
+
+        Ok(Achievements {
+            achievements: data::as_array(data::field(object, "achievements")?, "achievements")?
+                .iter()
+                .map(Achievement::from_json)
+                .collect::<Result<_, _>>()?,
+            unlocked: data::as_array(data::field(object, "unlocked")?, "unlocked")?
+                .iter()
+                .map(|id| data::parse_value(id, "unlocked"))
+                .collect::<Result<_, _>>()?,
+            kills,
+            steps: data::unsigned(object, "steps")?,
+            gold: data::unsigned(object, "gold")?,
+            died_in_campaign: data::boolean(object, "died_in_campaign")?,
+            newly_unlocked: Vec::new(),
+        })
+    }
+}
+
+impl Listener for Achievements {
+    fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::MonsterKilled(ref monster) => {
+                *self.kills.entry(monster.clone()).or_insert(0) += 1;
+            }
+            GameEvent::Walked => self.steps += 1,
+            GameEvent::GoldEarned(gold) => self.gold += gold as u64,
+            GameEvent::CharacterDied => self.died_in_campaign = true,
+            GameEvent::CampaignStarted(_) => self.died_in_campaign = false,
+            _ => {}
+        }
+
+        let met: Vec<String> = self.achievements
+            .iter()
+            .filter(|achievement| {
+                !self.is_unlocked(&achievement.id) && self.is_met(&achievement.trigger, event)
+            })
+            .map(|achievement| achievement.id.clone())
+            .collect();
+
+        for id in met {
+            self.unlocked.insert(id.clone());
+            self.newly_unlocked.push(id);
+        }
+    }
+}
+
+impl ToJson for Achievements {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        let unlocked: Vec<String> = self.unlocked.iter().cloned().collect();
+        let mut kills = Object::new();
+        for (monster, count) in &self.kills {
+            kills.insert(monster.clone(), count.to_json());
+        }
+
+        object.insert("achievements".to_owned(), self.achievements.to_json());
+        object.insert("unlocked".to_owned(), unlocked.to_json());
+        object.insert("kills".to_owned(), Json::Object(kills));
+        object.insert("steps".to_owned(), self.steps.to_json());
+        object.insert("gold".to_owned(), self.gold.to_json());
+        object.insert("died_in_campaign".to_owned(), self.died_in_campaign.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{self, GameEvent, Listener};
+    use rustc_serialize::json::ToJson;
+
+    fn achievements() -> Achievements {
+        let mut achievements = Achievements::new();
+        achievements.add(Achievement::new("exterminator", "Exterminator", Trigger::Kills(3)));
+        achievements.add(Achievement::new("rat_catcher",
+                                          "Rat Catcher",
+                                          Trigger::KillsOf("rat".to_owned(), 2)));
+        achievements.add(Achievement::new("flawless",
+                                          "Flawless",
+                                          Trigger::FinishCampaignWithoutDying)
+            .description("Finish a campaign without dying"));
+        achievements
+    }
+
+    #[test]
+    fn kill_counts() {
+        let mut achievements = achievements();
+
+        achievements.notify(&GameEvent::MonsterKilled("rat".to_owned()));
+        achievements.notify(&GameEvent::MonsterKilled("wolf".to_owned()));
+        assert!(achievements.take_newly_unlocked().is_empty());
+
+        bus::publish(&GameEvent::MonsterKilled("rat".to_owned()), &mut [&mut achievements]);
+        assert!(achievements.is_unlocked("exterminator"));
+        assert!(achievements.is_unlocked("rat_catcher"));
+        assert_eq!(achievements.take_newly_unlocked().len(), 2);
+        assert!(achievements.take_newly_unlocked().is_empty());
+    }
+
+    #[test]
+    fn finish_without_dying() {
+        let mut achievements = achievements();
+
+        achievements.notify(&GameEvent::CampaignStarted("Tutorial".to_owned()));
+        achievements.notify(&GameEvent::CharacterDied);
+        achievements.notify(&GameEvent::CampaignFinished("Tutorial".to_owned()));
+        assert!(!achievements.is_unlocked("flawless"));
+
+        achievements.notify(&GameEvent::CampaignStarted("Quest".to_owned()));
+        achievements.notify(&GameEvent::CampaignFinished("Quest".to_owned()));
+        assert!(achievements.is_unlocked("flawless"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut achievements = achievements();
+        achievements.add(Achievement::new("rich", "Rich", Trigger::Gold(1000)));
+        achievements.notify(&GameEvent::MonsterKilled("rat".to_owned()));
+        achievements.notify(&GameEvent::GoldEarned(20));
+        achievements.take_newly_unlocked();
+
+        assert_eq!(Achievements::from_json(&achievements.to_json()).unwrap(), achievements);
+    }
+}
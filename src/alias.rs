@@ -0,0 +1,159 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeMap;
+
+/// The separator between the commands of a macro, e.g. `loot; go north`
+pub const MACRO_SEPARATOR: char = ';';
+
+/// How deep aliases may expand into other aliases, which stops aliases referring to themselves
+pub const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// The reasons why an alias can not be defined
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AliasError {
+    /// The name is empty or contains whitespace or the macro separator
+    InvalidName,
+    /// The expansion does not contain any command
+    EmptyExpansion,
+}
+
+/// Shortcuts the player defines for commands, e.g. `n` for `go north`. An alias expanding into
+/// several commands separated by `;` is a macro.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Aliases {
+    aliases: BTreeMap<String, String>,
+}
+
+impl Aliases {
+    /// Creates a new instance of `Aliases` without any aliases
+    pub fn new() -> Aliases {
+        Aliases::default()
+    }
+
+    /// Defines the alias, replacing an alias with the same name
+    pub fn define(&mut self, name: &str, expansion: &str) -> Result<(), AliasError> {
+        if name.is_empty() || name.contains(char::is_whitespace) ||
+           name.contains(MACRO_SEPARATOR) {
+            return Err(AliasError::InvalidName);
+        }
+        if expansion.split(MACRO_SEPARATOR).all(|command| command.trim().is_empty()) {
+            return Err(AliasError::EmptyExpansion);
+        }
+
+        self.aliases.insert(name.to_owned(), expansion.trim().to_owned());
+        Ok(())
+    }
+
+    /// Removes the alias and returns its expansion
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.aliases.remove(name)
+    }
+
+    /// Returns the expansion of the alias
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.aliases.get(name).map(|expansion| &expansion[..])
+    }
+
+    /// Returns all aliases with their expansions, sorted by name
+    pub fn iter(&self) -> Vec<(&str, &str)> {
+        self.aliases.iter().map(|(name, expansion)| (&name[..], &expansion[..])).collect()
+    }
+
+    /// Expands a line typed by the player into the commands to execute. The first word of every
+    /// command is replaced if it is an alias, keeping the words following it, e.g. `hp` expands
+    /// into `use healing potion`. Lines without aliases are returned as the only command.
+    pub fn expand(&self, line: &str) -> Vec<String> {
+        self.expand_with_depth(line, 0)
+    }
+
+    fn expand_with_depth(&self, line: &str, depth: usize) -> Vec<String> {
+        line.split(MACRO_SEPARATOR)
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .flat_map(|command| {
+                let mut words = command.splitn(2, char::is_whitespace);
+                let name = words.next().unwrap_or("");
+                let arguments = words.next().map_or("", str::trim);
+
+                match self.aliases.get(name) {
+                    Some(expansion) if depth < MAX_EXPANSION_DEPTH => {
+                        let expansion = if arguments.is_empty() {
+                            expansion.clone()
+                        } else {
+                            format!("{} {}", expansion, arguments)
+                        };
+                        self.expand_with_depth(&expansion, depth + 1)
+                    }
+                    _ => vec![command.to_owned()],
+                }
+            })
+            .collect()
+    }
+
+    /// Reads aliases from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Aliases, DataError> {
+        let mut aliases = Aliases::new();
+
+        for (name, expansion) in data::as_object(json, "aliases")? {
+            let expansion = expansion.as_string()
+                .ok_or_else(|| DataError::Invalid("aliases".to_owned()))?;
+            aliases.define(name, expansion)
+                .map_err(|_| DataError::Invalid("aliases".to_owned()))?;
+        }
+
+        Ok(aliases)
+    }
+}
+
+impl ToJson for Aliases {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        for (name, expansion) in &self.aliases {
+            object.insert(name.clone(), expansion.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn expand() {
+        let mut aliases = Aliases::new();
+        aliases.define("n", "go north").unwrap();
+        aliases.define("hp", "use healing potion").unwrap();
+        aliases.define("flee", "n; n; hp").unwrap();
+
+        assert_eq!(aliases.expand("n"), vec!["go north"]);
+        assert_eq!(aliases.expand("look at n"), vec!["look at n"]);
+        assert_eq!(aliases.expand("flee; loot"),
+                   vec!["go north", "go north", "use healing potion", "loot"]);
+        assert_eq!(aliases.expand("hp 2"), vec!["use healing potion 2"]);
+    }
+
+    #[test]
+    fn recursion() {
+        let mut aliases = Aliases::new();
+        aliases.define("loop", "loop again").unwrap();
+
+        let expanded = aliases.expand("loop");
+        assert_eq!(expanded.len(), 1);
+        assert!(expanded[0].starts_with("loop again again"));
+    }
+
+    #[test]
+    fn define() {
+        let mut aliases = Aliases::new();
+        assert_eq!(aliases.define("go n", "go north"), Err(AliasError::InvalidName));
+        assert_eq!(aliases.define("n", " ; "), Err(AliasError::EmptyExpansion));
+
+        aliases.define("n", "go north").unwrap();
+        assert_eq!(Aliases::from_json(&aliases.to_json()).unwrap(), aliases);
+        assert_eq!(aliases.remove("n"), Some("go north".to_owned()));
+        assert_eq!(aliases.get("n"), None);
+    }
+}
@@ -0,0 +1,210 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::str::FromStr;
+
+/// How far an axis of the alignment reaches in either direction
+pub const ALIGNMENT_LIMIT: i32 = 100;
+
+/// From which value on an axis counts as leaning to one side instead of neutral
+pub const ALIGNMENT_THRESHOLD: i32 = 30;
+
+/// One of the two axes of the alignment
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    /// Good is positive, evil negative
+    Morality,
+    /// Lawful is positive, chaotic negative
+    Order,
+}
+
+impl FromStr for Axis {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Axis, ()> {
+        match s {
+            "Morality" => Ok(Axis::Morality),
+            "Order" => Ok(Axis::Order),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The side of an axis the player leans to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Leaning {
+    /// Good or lawful
+    Positive,
+    /// Neither side reached `ALIGNMENT_THRESHOLD`
+    Neutral,
+    /// Evil or chaotic
+    Negative,
+}
+
+/// Where the choices of the player put them between good and evil and between lawful and chaotic.
+/// Quest choices and actions shift it, dialogues and endings query it.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Alignment {
+    morality: i32,
+    order: i32,
+}
+
+impl Alignment {
+    /// Creates a new, neutral instance of `Alignment`
+    pub fn new() -> Alignment {
+        Alignment::default()
+    }
+
+    /// Returns the value of the axis, between `-ALIGNMENT_LIMIT` and `ALIGNMENT_LIMIT`
+    pub fn get(&self, axis: Axis) -> i32 {
+        match axis {
+            Axis::Morality => self.morality,
+            Axis::Order => self.order,
+        }
+    }
+
+    /// Shifts the axis by the amount, e.g. `-10` morality for robbing a merchant
+    pub fn shift(&mut self, axis: Axis, amount: i32) {
+        let value = match axis {
+            Axis::Morality => &mut self.morality,
+            Axis::Order => &mut self.order,
+        };
+        *value = (*value + amount).clamp(-ALIGNMENT_LIMIT, ALIGNMENT_LIMIT);
+    }
+
+    /// Returns the side of the axis the player leans to
+    pub fn leaning(&self, axis: Axis) -> Leaning {
+        match self.get(axis) {
+            value if value >= ALIGNMENT_THRESHOLD => Leaning::Positive,
+            value if value <= -ALIGNMENT_THRESHOLD => Leaning::Negative,
+            _ => Leaning::Neutral,
+        }
+    }
+
+    /// Returns the alignment in words, e.g. "Lawful Good" or "True Neutral"
+    pub fn describe(&self) -> String {
+        let order = match self.leaning(Axis::Order) {
+            Leaning::Positive => "Lawful",
+            Leaning::Neutral => "Neutral",
+            Leaning::Negative => "Chaotic",
+        };
+        let morality = match self.leaning(Axis::Morality) {
+            Leaning::Positive => "Good",
+            Leaning::Neutral => "Neutral",
+            Leaning::Negative => "Evil",
+        };
+
+        if order == morality {
+            "True Neutral".to_owned()
+        } else {
+            format!("{} {}", order, morality)
+        }
+    }
+
+    /// Reads the alignment from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Alignment, DataError> {
+        let object = data::as_object(json, "alignment")?;
+
+        let mut alignment = Alignment::new();
+        alignment.shift(Axis::Morality, data::signed(object, "morality")? as i32);
+        alignment.shift(Axis::Order, data::signed(object, "order")? as i32);
+        Ok(alignment)
+    }
+}
+
+impl ToJson for Alignment {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("morality".to_owned(), self.morality.to_json());
+        object.insert("order".to_owned(), self.order.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A condition on the alignment, e.g. for a dialogue option only evil characters see or for
+/// choosing the ending of a campaign
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AlignmentCondition {
+    /// The axis is at least the value
+    AtLeast(Axis, i32),
+    /// The axis is at most the value
+    AtMost(Axis, i32),
+}
+
+impl AlignmentCondition {
+    /// Returns `true` if the alignment meets the condition
+    pub fn is_met(&self, alignment: &Alignment) -> bool {
+        match *self {
+            AlignmentCondition::AtLeast(axis, value) => alignment.get(axis) >= value,
+            AlignmentCondition::AtMost(axis, value) => alignment.get(axis) <= value,
+        }
+    }
+
+    /// Reads a condition from a JSON object like
+    /// `{"type": "AtLeast", "axis": "Morality", "value": 30}`
+    pub fn from_json(json: &Json) -> Result<AlignmentCondition, DataError> {
+        let object = data::as_object(json, "condition")?;
+        let axis = data::parse(object, "axis")?;
+        let value = data::signed(object, "value")? as i32;
+
+        match &data::string(object, "type")?[..] {
+            "AtLeast" => Ok(AlignmentCondition::AtLeast(axis, value)),
+            "AtMost" => Ok(AlignmentCondition::AtMost(axis, value)),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for AlignmentCondition {
+    fn to_json(&self) -> Json {
+        let (kind, axis, value) = match *self {
+            AlignmentCondition::AtLeast(axis, value) => ("AtLeast", axis, value),
+            AlignmentCondition::AtMost(axis, value) => ("AtMost", axis, value),
+        };
+
+        let mut object = Object::new();
+        object.insert("type".to_owned(), kind.to_json());
+        object.insert("axis".to_owned(), format!("{:?}", axis).to_json());
+        object.insert("value".to_owned(), value.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn shift() {
+        let mut alignment = Alignment::new();
+        assert_eq!(alignment.describe(), "True Neutral");
+
+        alignment.shift(Axis::Morality, -40);
+        alignment.shift(Axis::Order, 250);
+        assert_eq!(alignment.get(Axis::Order), ALIGNMENT_LIMIT);
+        assert_eq!(alignment.leaning(Axis::Morality), Leaning::Negative);
+        assert_eq!(alignment.describe(), "Lawful Evil");
+    }
+
+    #[test]
+    fn conditions() {
+        let mut alignment = Alignment::new();
+        alignment.shift(Axis::Morality, 35);
+
+        let saint = AlignmentCondition::AtLeast(Axis::Morality, 30);
+        let rebel = AlignmentCondition::AtMost(Axis::Order, -30);
+        assert!(saint.is_met(&alignment));
+        assert!(!rebel.is_met(&alignment));
+        assert_eq!(AlignmentCondition::from_json(&rebel.to_json()).unwrap(), rebel);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut alignment = Alignment::new();
+        alignment.shift(Axis::Morality, -12);
+        alignment.shift(Axis::Order, 7);
+        assert_eq!(Alignment::from_json(&alignment.to_json()).unwrap(), alignment);
+    }
+}
@@ -0,0 +1,270 @@
+use character::{Attribute, Character};
+use combat::{AggressiveMelee, AiBehavior, Combatant, Coward, Healer, RangedKiter, Side, Support,
+             Tank};
+use data::{self, DataError};
+use difficulty::Modifiers;
+use format::Format;
+use loot::LootTable;
+use rustc_serialize::json::Json;
+use std::collections::HashMap;
+use std::path::Path;
+use types::{AttributeValue, Health};
+use world::two_dimensional::FieldType;
+
+/// The definition of a monster, used to spawn any amount of combatants
+#[derive(Clone, PartialEq, Debug)]
+pub struct MonsterDefinition {
+    /// The unique id of the monster
+    pub id: String,
+    /// The displayed name of the monster
+    pub name: String,
+    /// The maximum health
+    pub health: Health,
+    /// The damage of a single attack
+    pub attack_damage: AttributeValue,
+    /// The maximum distance at which the monster can attack
+    pub range: u32,
+    /// The attributes of the monster
+    pub attributes: HashMap<Attribute, AttributeValue>,
+    /// The name of the AI behavior controlling the monster
+    pub ai: String,
+    /// The items the monster may drop
+    pub loot: LootTable,
+    /// The field types the monster spawns on
+    pub biomes: Vec<FieldType>,
+}
+
+impl MonsterDefinition {
+    /// Reads a monster definition from a JSON object. Attributes that are not listed fall back to
+    /// `Character::default_attributes()`.
+    pub fn from_json(json: &Json) -> Result<MonsterDefinition, DataError> {
+        let object = data::as_object(json, "monster")?;
+
+        let mut attributes = Character::default_attributes();
+        if let Some(values) = object.get("attributes") {
+            for (name, value) in data::as_object(values, "attributes")? {
+                let attribute = name.parse().map_err(|_| DataError::Invalid(name.clone()))?;
+                let value = value.as_i64().ok_or_else(|| DataError::Invalid(name.clone()))?;
+                attributes.insert(attribute, value);
+            }
+        }
+
+        let loot = match object.get("loot") {
+            Some(loot) => LootTable::from_json(loot)?,
+            None => LootTable::new(),
+        };
+
+        let mut biomes = Vec::new();
+        if let Some(values) = object.get("biomes") {
+            for biome in data::as_array(values, "biomes")? {
+                biomes.push(data::parse_value(biome, "biomes")?);
+            }
+        }
+
+        Ok(MonsterDefinition {
+            id: data::string(object, "id")?,
+            name: data::string(object, "name")?,
+            health: data::unsigned(object, "health")? as Health,
+            attack_damage: data::signed(object, "attack_damage")?,
+            range: data::optional(object, "range", data::unsigned)?.unwrap_or(0) as u32,
+            attributes,
+            ai: data::optional(object, "ai", data::string)?
+                .unwrap_or_else(|| "aggressive".to_owned()),
+            loot,
+            biomes,
+        })
+    }
+}
+
+/// A registry of all monsters and AI behaviors known to a campaign
+pub struct Bestiary {
+    monsters: HashMap<String, MonsterDefinition>,
+    behaviors: HashMap<String, Box<dyn AiBehavior>>,
+}
+
+impl Default for Bestiary {
+    fn default() -> Bestiary {
+        Bestiary::new()
+    }
+}
+
+impl Bestiary {
+    /// Creates a new, empty instance of `Bestiary` knowing the built-in AI behaviors
+    /// `aggressive`, `ranged`, `coward` and `healer`
+    pub fn new() -> Bestiary {
+        let mut bestiary = Bestiary {
+            monsters: HashMap::new(),
+            behaviors: HashMap::new(),
+        };
+
+        bestiary.register_behavior("aggressive", AggressiveMelee);
+        bestiary.register_behavior("ranged", RangedKiter);
+        bestiary.register_behavior("coward", Coward);
+        bestiary.register_behavior("healer", Healer);
+        bestiary.register_behavior("tank", Tank);
+        bestiary.register_behavior("support", Support);
+
+        bestiary
+    }
+
+    /// Makes a custom AI behavior available to monster definitions under the given name
+    pub fn register_behavior<T: AiBehavior + 'static>(&mut self, name: &str, behavior: T) {
+        self.behaviors.insert(name.to_owned(), Box::new(behavior));
+    }
+
+    /// Adds a monster definition. Fails if the AI behavior of the monster is unknown
+    pub fn add_monster(&mut self, monster: MonsterDefinition) -> Result<(), DataError> {
+        if !self.behaviors.contains_key(&monster.ai) {
+            return Err(DataError::Invalid("ai".to_owned()));
+        }
+
+        self.monsters.insert(monster.id.clone(), monster);
+        Ok(())
+    }
+
+    /// Loads all monster definitions from a JSON object like `{"monsters": [...]}`. A bare array
+    /// of monsters is read as well.
+    pub fn load_json(&mut self, json: &Json) -> Result<(), DataError> {
+        let monsters = match *json {
+            Json::Array(_) => json,
+            _ => data::field(data::as_object(json, "bestiary")?, "monsters")?,
+        };
+
+        for monster in data::as_array(monsters, "monsters")? {
+            self.add_monster(MonsterDefinition::from_json(monster)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads all monster definitions from a file. The format is chosen by the extension, JSON
+    /// by default.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DataError> {
+        let format = Format::from_path(&path).unwrap_or(Format::Json);
+        self.load_json(&format.read_file(path)?)
+    }
+
+    /// Returns the monster definition with the given id
+    pub fn get(&self, id: &str) -> Option<&MonsterDefinition> {
+        self.monsters.get(id)
+    }
+
+    /// Returns all monsters spawning on the given field type
+    pub fn for_biome(&self, field_type: &FieldType) -> Vec<&MonsterDefinition> {
+        self.monsters.values().filter(|monster| monster.biomes.contains(field_type)).collect()
+    }
+
+    /// Creates an enemy combatant from the monster with the given id
+    pub fn spawn(&self, id: &str) -> Option<Combatant> {
+        self.monsters.get(id).map(|monster| self.combatant(monster))
+    }
+
+    /// Creates an enemy combatant from the monster with the given id, scaled by the modifiers of
+    /// a difficulty
+    pub fn spawn_with(&self, id: &str, modifiers: &Modifiers) -> Option<Combatant> {
+        self.monsters.get(id).map(|monster| self.combatant(&modifiers.monster(monster)))
+    }
+
+    fn combatant(&self, monster: &MonsterDefinition) -> Combatant {
+        let mut combatant = Combatant::new(&monster.name, Side::Enemy, monster.health)
+            .attributes(monster.attributes.clone())
+            .attack_damage(monster.attack_damage)
+            .range(monster.range);
+        combatant.set_ai(self.behaviors.get(&monster.ai).cloned());
+
+        combatant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Attribute;
+    use combat::{Action, AiBehavior, Battle};
+    use difficulty::Difficulty;
+    use rustc_serialize::json::Json;
+    use std::{env, fs};
+    use world::two_dimensional::FieldType;
+
+    const MONSTERS: &str = r#"[
+        {"id": "goblin", "name": "Goblin", "health": 12, "attack_damage": 4,
+         "attributes": {"Defense": 2}, "biomes": ["Grass", "Dirt"],
+         "loot": [{"item_type": "WeaponSword", "chance": 0.1}]},
+        {"id": "archer", "name": "Skeleton Archer", "health": 8, "attack_damage": 3,
+         "range": 4, "ai": "ranged", "biomes": ["Stone"]}
+    ]"#;
+
+    #[derive(Clone)]
+    struct Idle;
+
+    impl AiBehavior for Idle {
+        fn decide(&self, _: usize, _: &Battle) -> Action {
+            Action::Wait
+        }
+    }
+
+    #[test]
+    fn load_monsters() {
+        let mut bestiary = Bestiary::new();
+        bestiary.load_json(&Json::from_str(MONSTERS).unwrap()).unwrap();
+
+        let goblin = bestiary.get("goblin").unwrap();
+        assert_eq!(goblin.health, 12);
+        assert_eq!(goblin.ai, "aggressive");
+        assert_eq!(goblin.attributes[&Attribute::Defense], 2);
+        assert_eq!(goblin.attributes[&Attribute::Strength], 20);
+        assert_eq!(goblin.loot.entries.len(), 1);
+
+        assert_eq!(bestiary.for_biome(&FieldType::Stone)[0].id, "archer");
+        assert!(bestiary.for_biome(&FieldType::Water).is_empty());
+    }
+
+    #[test]
+    fn spawn() {
+        let mut bestiary = Bestiary::new();
+        bestiary.load_json(&Json::from_str(MONSTERS).unwrap()).unwrap();
+
+        let archer = bestiary.spawn("archer").unwrap();
+        assert_eq!(archer.name, "Skeleton Archer");
+        assert_eq!(archer.range, 4);
+        assert!(archer.get_ai().is_some());
+
+        assert!(bestiary.spawn("dragon").is_none());
+    }
+
+    #[test]
+    fn spawn_with_difficulty() {
+        let mut bestiary = Bestiary::new();
+        bestiary.load_json(&Json::from_str(MONSTERS).unwrap()).unwrap();
+
+        let goblin = bestiary.spawn_with("goblin", &Difficulty::Nightmare.modifiers()).unwrap();
+        assert_eq!(goblin.max_health, 24);
+        assert_eq!(goblin.attack_damage, 6);
+    }
+
+    #[test]
+    fn load_file() {
+        let path = env::temp_dir().join("txtrpg_bestiary.toml");
+        fs::write(&path,
+                  "[[monsters]]\nid = \"wolf\"\nname = \"Wolf\"\nhealth = 10\n\
+                   attack_damage = 3\nai = \"aggressive\"\n")
+            .unwrap();
+
+        let mut bestiary = Bestiary::new();
+        bestiary.load_file(&path).unwrap();
+        assert_eq!(bestiary.get("wolf").map(|wolf| wolf.health), Some(10));
+    }
+
+    #[test]
+    fn custom_behavior() {
+        let json = Json::from_str(r#"[{"id": "statue", "name": "Statue", "health": 100,
+                                       "attack_damage": 0, "ai": "idle"}]"#)
+            .unwrap();
+
+        let mut bestiary = Bestiary::new();
+        assert!(bestiary.load_json(&json).is_err());
+
+        bestiary.register_behavior("idle", Idle);
+        assert!(bestiary.load_json(&json).is_ok());
+    }
+}
@@ -0,0 +1,384 @@
+use bus::{GameEvent, Listener};
+use character::Character;
+use data::{self, DataError};
+use inventory::Inventory;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use template;
+use types::{Experience, Gold};
+
+/// The gold a bounty pays per character level and unit of work
+const GOLD_PER_LEVEL: Gold = 10;
+
+/// The experience a bounty grants per character level and unit of work
+const EXPERIENCE_PER_LEVEL: Experience = 25;
+
+/// The amount of bounties a board shows by default
+pub const DEFAULT_BOARD_SIZE: usize = 3;
+
+/// What the player has to do to earn a bounty
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Objective {
+    /// Kill the amount of monsters with the name
+    Kill(String, u32),
+    /// Bring the amount of items with the name
+    Fetch(String, u32),
+    /// Enter the world with the id
+    Explore(String),
+}
+
+impl Objective {
+    /// Returns the objective as a sentence, e.g. "Kill 3 wolves."
+    pub fn describe(&self) -> String {
+        match *self {
+            Objective::Kill(ref monster, count) => {
+                format!("Kill {} {}.", count, template::plural(monster, count as u64))
+            }
+            Objective::Fetch(ref item, count) => {
+                format!("Bring {} {}.", count, template::plural(item, count as u64))
+            }
+            Objective::Explore(ref world) => format!("Explore {}.", world),
+        }
+    }
+
+    /// Reads an objective from a JSON object like `{"type": "Kill", "target": "wolf", "count": 3}`
+    pub fn from_json(json: &Json) -> Result<Objective, DataError> {
+        let object = data::as_object(json, "objective")?;
+        let target = data::string(object, "target")?;
+        let count = || data::unsigned(object, "count").map(|count| count as u32);
+
+        match &data::string(object, "type")?[..] {
+            "Kill" => Ok(Objective::Kill(target, count()?)),
+            "Fetch" => Ok(Objective::Fetch(target, count()?)),
+            "Explore" => Ok(Objective::Explore(target)),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for Objective {
+    fn to_json(&self) -> Json {
+        let (kind, target, count) = match *self {
+            Objective::Kill(ref monster, count) => ("Kill", monster, Some(count)),
+            Objective::Fetch(ref item, count) => ("Fetch", item, Some(count)),
+            Objective::Explore(ref world) => ("Explore", world, None),
+        };
+
+        let mut object = Object::new();
+        object.insert("type".to_owned(), kind.to_json());
+        object.insert("target".to_owned(), target.to_json());
+        if let Some(count) = count {
+            object.insert("count".to_owned(), count.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// A side quest posted at a bounty board
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bounty {
+    /// What the player has to do
+    pub objective: Objective,
+    /// The gold paid when the bounty is claimed
+    pub gold: Gold,
+    /// The experience granted when the bounty is claimed
+    pub experience: Experience,
+    /// The monsters killed or worlds entered since the bounty was accepted
+    pub progress: u32,
+}
+
+impl Bounty {
+    /// Creates a new instance of `Bounty`
+    pub fn new(objective: Objective, gold: Gold, experience: Experience) -> Bounty {
+        Bounty {
+            objective,
+            gold,
+            experience,
+            progress: 0,
+        }
+    }
+
+    /// Returns `true` if the objective is met. Fetched items have to be in the inventory.
+    pub fn is_complete(&self, inventory: &Inventory) -> bool {
+        match self.objective {
+            Objective::Kill(_, count) => self.progress >= count,
+            Objective::Fetch(ref item, count) => inventory.count_by_name(item) >= count as usize,
+            Objective::Explore(_) => self.progress >= 1,
+        }
+    }
+
+    /// Returns the bounty as shown on the board, e.g. "Kill 3 wolves. (60 gold, 150 XP)"
+    pub fn describe(&self) -> String {
+        format!("{} ({} gold, {} XP)", self.objective.describe(), self.gold, self.experience)
+    }
+
+    /// Reads a bounty from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Bounty, DataError> {
+        let object = data::as_object(json, "bounty")?;
+
+        Ok(Bounty {
+            objective: Objective::from_json(data::field(object, "objective")?)?,
+            gold: data::unsigned(object, "gold")? as Gold,
+            experience: data::unsigned(object, "experience")?,
+            progress: data::optional(object, "progress", data::unsigned)?.unwrap_or(0) as u32,
+        })
+    }
+}
+
+impl ToJson for Bounty {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("objective".to_owned(), self.objective.to_json());
+        object.insert("gold".to_owned(), self.gold.to_json());
+        object.insert("experience".to_owned(), self.experience.to_json());
+        object.insert("progress".to_owned(), self.progress.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// Generates bounties from the monsters, items and worlds of a campaign, scaled to the level of
+/// the character
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct BountyGenerator {
+    monsters: Vec<String>,
+    items: Vec<String>,
+    worlds: Vec<String>,
+}
+
+impl BountyGenerator {
+    /// Creates a new instance of `BountyGenerator` without any targets
+    pub fn new() -> BountyGenerator {
+        BountyGenerator::default()
+    }
+
+    /// A builder method for adding a monster that can be hunted
+    pub fn monster(mut self, monster: &str) -> BountyGenerator {
+        self.monsters.push(monster.to_owned());
+        self
+    }
+
+    /// A builder method for adding an item that can be fetched
+    pub fn item(mut self, item: &str) -> BountyGenerator {
+        self.items.push(item.to_owned());
+        self
+    }
+
+    /// A builder method for adding a world that can be explored
+    pub fn world(mut self, world: &str) -> BountyGenerator {
+        self.worlds.push(world.to_owned());
+        self
+    }
+
+    /// Generates a bounty for a character of the level. More monsters and items are asked for
+    /// at higher levels, and rewards grow with the level and the amount. Returns `None` if the
+    /// generator has no targets.
+    pub fn gen<R: Rng>(&self, level: u32, rng: &mut R) -> Option<Bounty> {
+        let count = 2 + level / 3;
+        let mut objectives = Vec::new();
+        objectives.extend(self.monsters.iter().map(|name| Objective::Kill(name.clone(), count)));
+        objectives.extend(self.items.iter().map(|name| Objective::Fetch(name.clone(), count)));
+        objectives.extend(self.worlds.iter().map(|id| Objective::Explore(id.clone())));
+
+        let objective = rng.choose(&objectives)?.clone();
+        let work = match objective {
+            Objective::Explore(_) => 3,
+            _ => count,
+        };
+
+        Some(Bounty::new(objective,
+                         GOLD_PER_LEVEL * level as Gold * work as Gold,
+                         EXPERIENCE_PER_LEVEL * level as Experience * work as Experience))
+    }
+}
+
+/// A board bounties are posted at, e.g. in a town square
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BountyBoard {
+    /// The generator of the posted bounties
+    pub generator: BountyGenerator,
+    /// The amount of bounties shown at once
+    pub size: usize,
+    posted: Vec<Bounty>,
+}
+
+impl BountyBoard {
+    /// Creates a new, empty instance of `BountyBoard`
+    pub fn new(generator: BountyGenerator) -> BountyBoard {
+        BountyBoard {
+            generator,
+            size: DEFAULT_BOARD_SIZE,
+            posted: Vec::new(),
+        }
+    }
+
+    /// A builder method for setting the amount of bounties shown at once
+    pub fn size(mut self, size: usize) -> BountyBoard {
+        self.size = size;
+        self
+    }
+
+    /// Returns the posted bounties
+    pub fn posted(&self) -> &[Bounty] {
+        &self.posted
+    }
+
+    /// Posts new bounties for a character of the level until the board is full
+    pub fn refresh<R: Rng>(&mut self, level: u32, rng: &mut R) {
+        while self.posted.len() < self.size {
+            match self.generator.gen(level, rng) {
+                Some(bounty) => self.posted.push(bounty),
+                None => return,
+            }
+        }
+    }
+
+    /// Takes the posted bounty with the index from the board
+    pub fn accept(&mut self, index: usize) -> Option<Bounty> {
+        if index < self.posted.len() {
+            Some(self.posted.remove(index))
+        } else {
+            None
+        }
+    }
+}
+
+/// The bounties the player accepted, tracking their progress by listening to game events
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Bounties {
+    accepted: Vec<Bounty>,
+}
+
+impl Bounties {
+    /// Creates a new instance of `Bounties` without any bounties
+    pub fn new() -> Bounties {
+        Bounties::default()
+    }
+
+    /// Adds an accepted bounty
+    pub fn accept(&mut self, bounty: Bounty) {
+        self.accepted.push(bounty);
+    }
+
+    /// Returns the accepted bounties
+    pub fn all(&self) -> &[Bounty] {
+        &self.accepted
+    }
+
+    /// Claims all completed bounties: fetched items are handed over, the gold and experience
+    /// are given to the character. Returns the claimed bounties.
+    pub fn claim(&mut self, character: &mut Character) -> Vec<Bounty> {
+        let (completed, open): (Vec<Bounty>, Vec<Bounty>) = self.accepted
+            .drain(..)
+            .partition(|bounty| bounty.is_complete(character.inventory()));
+        self.accepted = open;
+
+        for bounty in &completed {
+            if let Objective::Fetch(ref item, count) = bounty.objective {
+                for _ in 0..count {
+                    character.inventory_mut().take_by_name(item);
+                }
+            }
+            character.inventory_mut().add_gold(bounty.gold);
+            character.gain_experience(bounty.experience);
+        }
+
+        completed
+    }
+
+    /// Reads the bounties from a JSON array as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Bounties, DataError> {
+        Ok(Bounties {
+            accepted: data::as_array(json, "bounties")?
+                .iter()
+                .map(Bounty::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Listener for Bounties {
+    fn notify(&mut self, event: &GameEvent) {
+        for bounty in &mut self.accepted {
+            let progressed = match (&bounty.objective, event) {
+                (Objective::Kill(monster, _), GameEvent::MonsterKilled(killed)) => {
+                    monster == killed
+                }
+                (Objective::Explore(world), GameEvent::WorldEntered(entered)) => {
+                    world == entered
+                }
+                _ => false,
+            };
+
+            if progressed {
+                bounty.progress += 1;
+            }
+        }
+    }
+}
+
+impl ToJson for Bounties {
+    fn to_json(&self) -> Json {
+        self.accepted.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{GameEvent, Listener};
+    use character::Character;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn board() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut board = BountyBoard::new(BountyGenerator::new().monster("wolf")).size(2);
+        board.refresh(6, &mut rng);
+
+        assert_eq!(board.posted().len(), 2);
+        assert_eq!(board.posted()[0].describe(), "Kill 4 wolves. (240 gold, 600 XP)");
+        assert!(board.accept(0).is_some());
+        assert!(board.accept(1).is_none());
+
+        let mut empty = BountyBoard::new(BountyGenerator::new());
+        empty.refresh(1, &mut rng);
+        assert!(empty.posted().is_empty());
+    }
+
+    #[test]
+    fn describe_objectives() {
+        assert_eq!(Objective::Kill("thief".to_owned(), 1).describe(), "Kill 1 thief.");
+        assert_eq!(Objective::Kill("goblin".to_owned(), 2).describe(), "Kill 2 goblins.");
+        assert_eq!(Objective::Fetch("berry".to_owned(), 5).describe(), "Bring 5 berries.");
+    }
+
+    #[test]
+    fn claim() {
+        let mut character = Character::new("Michael");
+        let mut bounties = Bounties::new();
+        bounties.accept(Bounty::new(Objective::Kill("wolf".to_owned(), 2), 50, 100));
+        bounties.accept(Bounty::new(Objective::Fetch("Pelt".to_owned(), 1), 20, 10));
+
+        bounties.notify(&GameEvent::MonsterKilled("wolf".to_owned()));
+        assert!(bounties.claim(&mut character).is_empty());
+        bounties.notify(&GameEvent::MonsterKilled("wolf".to_owned()));
+        character.inventory_mut().add_item(ItemGenerator::new().name("Pelt").gen()).unwrap();
+
+        assert_eq!(bounties.claim(&mut character).len(), 2);
+        assert_eq!(character.inventory().gold(), 70);
+        assert_eq!(character.experience(), 110);
+        assert_eq!(character.inventory().count_by_name("Pelt"), 0);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut bounties = Bounties::new();
+        bounties.accept(Bounty::new(Objective::Explore("caves".to_owned()), 30, 75));
+        bounties.notify(&GameEvent::WorldEntered("caves".to_owned()));
+        assert_eq!(Bounties::from_json(&bounties.to_json()).unwrap(), bounties);
+    }
+}
@@ -0,0 +1,131 @@
+use template::{Template, TemplateContext};
+use types::{Gold, Health};
+
+/// Something that happened during the game, published to all interested subsystems
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    /// The player killed the monster with the given name
+    MonsterKilled(String),
+    /// The player character died
+    CharacterDied,
+    /// The player dealt the given amount of damage
+    DamageDealt(Health),
+    /// The player took the given amount of damage
+    DamageTaken(Health),
+    /// The player walked onto the next field
+    Walked,
+    /// The player earned the given amount of gold
+    GoldEarned(Gold),
+    /// The player crafted the item with the given name
+    ItemCrafted(String),
+    /// The player started the campaign with the given title
+    CampaignStarted(String),
+    /// The player finished the campaign with the given title
+    CampaignFinished(String),
+    /// The player entered the world with the given id
+    WorldEntered(String),
+    /// The player character reached the given level
+    LevelReached(u32),
+    /// The player opened a door
+    DoorOpened,
+    /// The player found the item with the given name
+    ItemFound(String),
+}
+
+impl GameEvent {
+    /// Returns the name of the kind of the event, e.g. `GoldEarned`
+    pub fn kind(&self) -> String {
+        let debug = format!("{:?}", self);
+        debug.split('(').next().unwrap_or_default().to_owned()
+    }
+
+    /// Returns the value the event carries as text, e.g. the name of the killed monster, or an
+    /// empty string
+    pub fn value(&self) -> String {
+        match *self {
+            GameEvent::MonsterKilled(ref name) |
+            GameEvent::ItemCrafted(ref name) |
+            GameEvent::ItemFound(ref name) |
+            GameEvent::CampaignStarted(ref name) |
+            GameEvent::CampaignFinished(ref name) |
+            GameEvent::WorldEntered(ref name) => name.clone(),
+            GameEvent::DamageDealt(damage) |
+            GameEvent::DamageTaken(damage) => damage.to_string(),
+            GameEvent::GoldEarned(gold) => gold.to_string(),
+            GameEvent::LevelReached(level) => level.to_string(),
+            GameEvent::CharacterDied | GameEvent::Walked | GameEvent::DoorOpened => String::new(),
+        }
+    }
+
+    /// Returns a sentence telling the player about the event, e.g. "You killed a rat."
+    pub fn narrate(&self) -> String {
+        let text = match *self {
+            GameEvent::MonsterKilled(_) => "You killed {value|a}.",
+            GameEvent::CharacterDied => "You died.",
+            GameEvent::DamageDealt(_) => "You dealt {value} damage.",
+            GameEvent::DamageTaken(_) => "You took {value} damage.",
+            GameEvent::Walked => "You walked on.",
+            GameEvent::GoldEarned(_) => "You earned {value} gold.",
+            GameEvent::ItemCrafted(_) => "You crafted {value|a}.",
+            GameEvent::ItemFound(_) => "You found {value|a}.",
+            GameEvent::CampaignStarted(_) => "You started {value}.",
+            GameEvent::CampaignFinished(_) => "You finished {value}.",
+            GameEvent::WorldEntered(_) => "You entered {value}.",
+            GameEvent::LevelReached(_) => "You reached level {value}.",
+            GameEvent::DoorOpened => "You opened the door.",
+        };
+
+        Template::new(text).render(&TemplateContext::new().value("value", self.value()))
+    }
+}
+
+/// Something that reacts to game events, e.g. achievements or statistics
+pub trait Listener {
+    /// Called once for every published event
+    fn notify(&mut self, event: &GameEvent);
+}
+
+/// Notifies all given listeners about the event
+pub fn publish(event: &GameEvent, listeners: &mut [&mut dyn Listener]) {
+    for listener in listeners.iter_mut() {
+        listener.notify(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Kills {
+        count: u32,
+    }
+
+    impl Listener for Kills {
+        fn notify(&mut self, event: &GameEvent) {
+            if let GameEvent::MonsterKilled(_) = *event {
+                self.count += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn notify_listeners() {
+        let mut first = Kills { count: 0 };
+        let mut second = Kills { count: 5 };
+
+        publish(&GameEvent::MonsterKilled("rat".to_owned()),
+                &mut [&mut first, &mut second]);
+        publish(&GameEvent::Walked, &mut [&mut first, &mut second]);
+
+        assert_eq!(first.count, 1);
+        assert_eq!(second.count, 6);
+    }
+
+    #[test]
+    fn narrate() {
+        assert_eq!(GameEvent::MonsterKilled("orc".to_owned()).narrate(), "You killed an orc.");
+        assert_eq!(GameEvent::GoldEarned(12).narrate(), "You earned 12 gold.");
+        assert_eq!(GameEvent::GoldEarned(12).kind(), "GoldEarned");
+        assert_eq!(GameEvent::DoorOpened.kind(), "DoorOpened");
+    }
+}
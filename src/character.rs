@@ -1,15 +1,121 @@
-use item::{Item, ItemType, ItemInfluence};
+use condition::{Condition, ConditionKind};
+use data::{self, DataError};
+use dice::Dice;
+use difficulty::{Difficulty, Modifiers};
+use event::Event;
+use hotbar::{Activation, Hotbar, HotbarError};
+use injury::{self, BodyPart, Injury};
+use item::{InfluenceKind, Item, ItemType, ItemInfluence};
 use std::collections::HashMap;
+use std::str::FromStr;
 use inventory::Inventory;
-use types::{Health, AttributeValue};
+use types::{Health, AttributeValue, Experience};
+use time::{Tick, WorldClock};
+use skill_check::{self, CheckOutcome};
+use survival::Needs;
+use template::Pronouns;
+use rand::Rng;
+use rustc_serialize::base64::{self, FromBase64, ToBase64};
+use rustc_serialize::json::{Json, Object, ToJson};
 
 /// The influence the `Attribute::Dexterity` has on the attack_damage of the character
 const DEXTERITY_INFLUENCE: f64 = 0.2;
 
+/// The amount of turns it takes a character to regenerate one point of health
+const REGENERATION_INTERVAL: u64 = 5;
+
+/// The amount of experience needed to reach level 2. Every further level needs quadratically more
+const EXPERIENCE_BASE: Experience = 100;
+
+/// Whether a character is still alive
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CharacterState {
+    /// The character is alive
+    Alive,
+    /// The character's health dropped to zero
+    Dead,
+}
+
+impl FromStr for CharacterState {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<CharacterState, ()> {
+        match s {
+            "Alive" => Ok(CharacterState::Alive),
+            "Dead" => Ok(CharacterState::Dead),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A slot a character can equip an item in
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EquipmentSlot {
+    /// The head armor slot
+    Head,
+    /// The chest armor slot
+    Chest,
+    /// The legs armor slot
+    Legs,
+    /// The feet armor slot
+    Feet,
+    /// The left weapon slot
+    LeftHand,
+    /// The right weapon slot
+    RightHand,
+}
+
+impl EquipmentSlot {
+    /// Returns `true` if items of the given type can be equipped in the slot
+    pub fn accepts(&self, item_type: &ItemType) -> bool {
+        match *self {
+            EquipmentSlot::LeftHand | EquipmentSlot::RightHand => true,
+            slot => item_type.slot() == Some(slot),
+        }
+    }
+}
+
+/// A closure scoring an item for a character, see `EquipStrategy::Custom`
+pub type ItemScorer = Box<dyn Fn(&Character, &Item) -> i64>;
+
+/// How `Character::auto_equip` scores the items to find the best one per slot
+pub enum EquipStrategy {
+    /// Prefers the items adding the most attack damage
+    Damage,
+    /// Prefers the items adding the most defense
+    Defense,
+    /// Prefers the items the closure scores highest
+    Custom(ItemScorer),
+}
+
+impl EquipStrategy {
+    /// Returns the score of the item for the character
+    pub fn score(&self, character: &Character, item: &Item) -> i64 {
+        match *self {
+            EquipStrategy::Damage => item.damage_for(character),
+            EquipStrategy::Defense => item.defense_for(character),
+            EquipStrategy::Custom(ref score) => score(character, item),
+        }
+    }
+}
+
+/// The reasons why the equipment of a character can not be changed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EquipError {
+    /// The item does not fit into the slot
+    WrongSlot,
+    /// The slot holds a cursed item, which has to be uncursed first
+    Cursed,
+}
+
 /// The character the player is impersonating
+#[derive(Clone, Debug)]
 pub struct Character {
     name: String,
+    pronouns: Pronouns,
     health: Health,
+    stamina: u32,
+    state: CharacterState,
     attributes: HashMap<Attribute, AttributeValue>,
     armor_slot_head: Option<Item>,
     armor_slot_chest: Option<Item>,
@@ -18,6 +124,12 @@ pub struct Character {
     weapon_slot_left: Option<Item>,
     weapon_slot_right: Option<Item>,
     inventory: Inventory,
+    sneaking: bool,
+    experience: Experience,
+    injuries: Vec<Injury>,
+    conditions: Vec<Condition>,
+    needs: Option<Needs>,
+    hotbar: Hotbar,
 }
 
 impl Character {
@@ -36,7 +148,10 @@ impl Character {
         let attribute_map = Self::default_attributes();
         Character {
             name: name.to_owned(),
+            pronouns: Pronouns::default(),
             health: (&attribute_map)[&Attribute::Constitution] as Health,
+            stamina: (&attribute_map)[&Attribute::Constitution] as u32,
+            state: CharacterState::Alive,
             attributes: attribute_map,
             armor_slot_head: None,
             armor_slot_chest: None,
@@ -45,9 +160,30 @@ impl Character {
             weapon_slot_left: None,
             weapon_slot_right: None,
             inventory: Inventory::new(30),
+            sneaking: false,
+            experience: 0,
+            injuries: Vec::new(),
+            conditions: Vec::new(),
+            needs: None,
+            hotbar: Hotbar::default(),
         }
     }
 
+    /// Returns the name of the character
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the pronouns narration uses for the character, `Pronouns::They` by default
+    pub fn pronouns(&self) -> Pronouns {
+        self.pronouns
+    }
+
+    /// Sets the pronouns narration uses for the character
+    pub fn set_pronouns(&mut self, pronouns: Pronouns) {
+        self.pronouns = pronouns;
+    }
+
     /// Updates the given attribute
     pub fn update_attribute(&mut self, attribute: &Attribute, value: AttributeValue) {
         *self.attributes.get_mut(attribute).unwrap() = value;
@@ -55,50 +191,410 @@ impl Character {
 
     /// Calculates and returns the current attack damage of the character based on the attibutes
     pub fn attack_damage(&self) -> AttributeValue {
-        let base_dexterity = self.attributes
-            .get(&Attribute::Dexterity)
-            .expect("Unable to find attribute: Attribute::Dexterity");
-
-        let base_dexterity = ((*base_dexterity as f64) * DEXTERITY_INFLUENCE) as AttributeValue;
-
-        let base_strength = self.attributes
-            .get(&Attribute::Strength)
-            .expect("Unable to find attribute: Attribute::Strength");
-
-        let mut additional_damage: i64 = 0;
-        if let Some(ref inner_item) = self.weapon_slot_left {
-            if let Some(ItemInfluence { ref attribute, ref amount }) = inner_item.influence {
-                let influence = if attribute == &Attribute::Dexterity {
-                    DEXTERITY_INFLUENCE
-                } else {
-                    1_f64
-                };
-
-                additional_damage += ((*amount as f64) * influence) as i64;
-            }
+        let base_dexterity = self.get_attribute_value(&Attribute::Dexterity);
+        let base_dexterity = ((base_dexterity as f64) * DEXTERITY_INFLUENCE) as AttributeValue;
+
+        let base_strength = self.get_attribute_value(&Attribute::Strength);
+
+        let additional_damage: i64 = self.weapon_slot_left
+            .iter()
+            .chain(self.weapon_slot_right.iter())
+            .map(|inner_item| self.additional_damage(&inner_item.influences()))
+            .sum();
+
+        (((base_strength + base_dexterity + additional_damage) as f64) * self.wound_factor()) as i64
+    }
+
+    /// Returns the damage dice of the wielded weapons
+    pub fn weapon_dice(&self) -> Vec<Dice> {
+        self.weapon_slot_left
+            .iter()
+            .chain(self.weapon_slot_right.iter())
+            .filter_map(|item| item.damage)
+            .collect()
+    }
+
+    /// Rolls the damage of a single attack: the `attack_damage` plus the damage dice of the
+    /// wielded weapons
+    pub fn roll_attack_damage<R: Rng>(&self, rng: &mut R) -> AttributeValue {
+        let rolled: i64 = self.weapon_dice().iter().map(|dice| dice.roll(rng)).sum();
+        self.attack_damage() + ((rolled as f64) * self.wound_factor()) as AttributeValue
+    }
+
+    /// Returns the attack damage a weapon with the given influences adds for the character
+    pub fn weapon_damage(&self, influences: &[&ItemInfluence]) -> AttributeValue {
+        ((self.additional_damage(influences) as f64) * self.wound_factor()) as AttributeValue
+    }
+
+    /// Adds up the influences on damage. Percentages refer to the attribute of the character.
+    fn additional_damage(&self, influences: &[&ItemInfluence]) -> AttributeValue {
+        let mut additional_damage = 0;
+        for influence in influences {
+            let factor = if influence.attribute == Attribute::Dexterity {
+                DEXTERITY_INFLUENCE
+            } else {
+                1_f64
+            };
+
+            let amount = influence.modifier(self.get_attribute_value(&influence.attribute));
+            additional_damage += ((amount as f64) * factor) as i64;
         }
 
-        if let Some(ref inner_item) = self.weapon_slot_right {
-            if let Some(ItemInfluence { ref attribute, ref amount }) = inner_item.influence {
-                let influence = if attribute == &Attribute::Dexterity {
-                    DEXTERITY_INFLUENCE
-                } else {
-                    1_f64
-                };
+        additional_damage
+    }
 
-                additional_damage += ((*amount as f64) * influence) as i64;
-            }
+    /// Returns the factor the damage of the character is scaled with, reduced by every arm wound
+    pub fn wound_factor(&self) -> f64 {
+        let arm_wounds = self.injuries
+            .iter()
+            .filter(|injury| injury.body_part == BodyPart::Arm)
+            .count();
+        let wound_factor = 1.0 - injury::ARM_WOUND_PENALTY * (arm_wounds as f64);
+        if wound_factor < 0.0 { 0.0 } else { wound_factor }
+    }
+
+    /// Returns the injuries of the character
+    pub fn injuries(&self) -> &[Injury] {
+        &self.injuries
+    }
+
+    /// Injures the given part of the body
+    pub fn injure(&mut self, body_part: BodyPart) {
+        self.injuries.push(Injury::new(body_part));
+    }
+
+    /// Treats an injury at the given part of the body using the matching consumable from the
+    /// inventory. Returns `false` if there is no such injury or consumable.
+    pub fn treat(&mut self, body_part: BodyPart) -> bool {
+        let index = match self.injuries.iter().position(|injury| injury.body_part == body_part) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        if self.inventory.take_by_name(body_part.cure()).is_none() {
+            return false;
+        }
+
+        self.injuries.remove(index);
+        true
+    }
+
+    /// Returns the conditions the character suffers from
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Afflicts the character with the condition. A condition of the same kind is replaced.
+    pub fn afflict(&mut self, condition: Condition) {
+        self.conditions.retain(|other| other.kind != condition.kind);
+        self.conditions.push(condition);
+    }
+
+    /// Cures the condition of the given kind using the matching consumable from the inventory.
+    /// Returns `false` if there is no such condition or consumable.
+    pub fn cure(&mut self, kind: ConditionKind) -> bool {
+        if !self.conditions.iter().any(|condition| condition.kind == kind) ||
+           self.inventory.take_by_name(kind.cure()).is_none() {
+            return false;
+        }
+
+        self.conditions.retain(|condition| condition.kind != kind);
+        true
+    }
+
+    /// Turns the survival needs of the character on or off
+    pub fn set_survival(&mut self, enabled: bool) {
+        self.needs = if enabled { Some(Needs::new()) } else { None };
+    }
+
+    /// Returns the survival needs of the character, if survival is turned on
+    pub fn needs(&self) -> Option<&Needs> {
+        self.needs.as_ref()
+    }
+
+    /// Returns the survival needs of the character as mutable, if survival is turned on
+    pub fn needs_mut(&mut self) -> Option<&mut Needs> {
+        self.needs.as_mut()
+    }
+
+    /// Eats a piece of food from the inventory. Returns `false` if survival is turned off or
+    /// there is nothing to eat.
+    pub fn eat(&mut self) -> bool {
+        if self.needs.is_none() ||
+           self.inventory.take_by_type(&ItemType::ConsumableFood).is_none() {
+            return false;
+        }
+
+        if let Some(ref mut needs) = self.needs {
+            needs.eat();
+        }
+        true
+    }
+
+    /// Drinks something from the inventory. Returns `false` if survival is turned off or there is
+    /// nothing to drink.
+    pub fn drink(&mut self) -> bool {
+        if self.needs.is_none() ||
+           self.inventory.take_by_type(&ItemType::ConsumableDrink).is_none() {
+            return false;
+        }
+
+        if let Some(ref mut needs) = self.needs {
+            needs.drink();
+        }
+        true
+    }
+
+    /// Returns the amount of turns a single step takes. Every leg wound adds another turn
+    pub fn movement_cost(&self) -> u32 {
+        let leg_wounds = self.injuries
+            .iter()
+            .filter(|injury| injury.body_part == BodyPart::Leg)
+            .count();
+
+        1 + leg_wounds as u32
+    }
+
+    /// Returns the equipped ranged weapon, preferring the right weapon slot
+    pub fn ranged_weapon(&self) -> Option<&Item> {
+        self.weapon_slot_right
+            .iter()
+            .chain(self.weapon_slot_left.iter())
+            .find(|item| item.item_type.range() > 0)
+    }
+
+    /// Returns the amount of fields the character can reach with the equipped weapons
+    pub fn attack_range(&self) -> u32 {
+        self.ranged_weapon().map_or(0, |item| item.item_type.range())
+    }
+
+    /// Returns the hotbar of the character
+    pub fn hotbar(&self) -> &Hotbar {
+        &self.hotbar
+    }
+
+    /// Returns the hotbar of the character as mutable
+    pub fn hotbar_mut(&mut self) -> &mut Hotbar {
+        &mut self.hotbar
+    }
+
+    /// Uses the slot of the hotbar with the given index, taking items out of the inventory
+    pub fn use_hotbar(&mut self, slot: usize) -> Result<Activation, HotbarError> {
+        self.hotbar.activate(slot, &mut self.inventory)
+    }
+
+    /// Returns the inventory of the character
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// Returns the inventory of the character as mutable
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// Returns the experience the character collected so far
+    pub fn experience(&self) -> Experience {
+        self.experience
+    }
+
+    /// Adds experience to the character
+    pub fn gain_experience(&mut self, experience: Experience) {
+        self.experience += experience;
+    }
+
+    /// Removes experience from the character, without going below zero
+    pub fn lose_experience(&mut self, experience: Experience) {
+        self.experience = self.experience.saturating_sub(experience);
+    }
+
+    /// Returns the level of the character, starting at level `1`. Reaching level `n` requires
+    /// `EXPERIENCE_BASE * (n - 1)²` experience.
+    pub fn level(&self) -> u32 {
+        let mut level = 1;
+        while Self::experience_for_level(level + 1) <= self.experience {
+            level += 1;
+        }
 
+        level
+    }
+
+    /// Returns the experience needed to reach the given level
+    pub fn experience_for_level(level: u32) -> Experience {
+        let steps = level.saturating_sub(1) as Experience;
+        EXPERIENCE_BASE * steps * steps
+    }
+
+    /// Returns `true` if the character is sneaking
+    pub fn is_sneaking(&self) -> bool {
+        self.sneaking
+    }
+
+    /// Starts or stops sneaking
+    pub fn set_sneaking(&mut self, sneaking: bool) {
+        self.sneaking = sneaking;
+    }
+
+    /// Returns the current health of the character
+    pub fn health(&self) -> Health {
+        self.health
+    }
+
+    /// Returns the current stamina of the character
+    pub fn stamina(&self) -> u32 {
+        self.stamina
+    }
+
+    /// Returns the maximum stamina of the character, which is based on `Attribute::Constitution`
+    pub fn max_stamina(&self) -> u32 {
+        let constitution = self.get_attribute_value(&Attribute::Constitution);
+
+        if constitution < 0 { 0 } else { constitution as u32 }
+    }
+
+    /// Uses up stamina. Returns `false` if the character was already exhausted.
+    pub fn drain_stamina(&mut self, amount: u32) -> bool {
+        if self.stamina == 0 {
+            return false;
         }
 
-        base_strength + base_dexterity + additional_damage
+        self.stamina = self.stamina.saturating_sub(amount);
+        true
+    }
+
+    /// Recovers stamina up to the maximum stamina
+    pub fn recover_stamina(&mut self, amount: u32) {
+        let max_stamina = self.max_stamina();
+        self.stamina = if self.stamina + amount > max_stamina {
+            max_stamina
+        } else {
+            self.stamina + amount
+        };
+    }
+
+    /// Returns the state of the character
+    pub fn state(&self) -> CharacterState {
+        self.state
+    }
+
+    /// Returns `true` if the character is dead
+    pub fn is_dead(&self) -> bool {
+        self.state == CharacterState::Dead
     }
 
-    /// Returns the value of the specified attribute
+    /// Deals damage to the character. Returns `Event::Death` if the character died because of
+    /// it, `Event::Nothing` otherwise.
+    pub fn take_damage(&mut self, damage: Health) -> Event {
+        if self.is_dead() {
+            return Event::Nothing;
+        }
+
+        self.health = self.health.saturating_sub(damage);
+
+        if self.health == 0 {
+            self.state = CharacterState::Dead;
+            Event::Death
+        } else {
+            Event::Nothing
+        }
+    }
+
+    /// Heals the character up to its maximum health. Dead characters can not be healed.
+    pub fn heal(&mut self, amount: Health) {
+        if self.is_dead() {
+            return;
+        }
+
+        let max_health = self.max_health();
+        self.health = if self.health + amount > max_health {
+            max_health
+        } else {
+            self.health + amount
+        };
+    }
+
+    /// Brings a dead character back to life with the given health. Returns `false` if the
+    /// character is not dead.
+    pub fn revive(&mut self, health: Health) -> bool {
+        if !self.is_dead() {
+            return false;
+        }
+
+        self.state = CharacterState::Alive;
+        self.health = if health < 1 { 1 } else { health };
+        true
+    }
+
+    /// Returns the maximum health of the character, which is based on `Attribute::Constitution`
+    pub fn max_health(&self) -> Health {
+        let constitution = self.get_attribute_value(&Attribute::Constitution);
+
+        if constitution < 0 { 0 } else { constitution as Health }
+    }
+
+    /// Returns the value of the specified attribute, lowered by neglected needs
     pub fn get_attribute_value(&self, attribute: &Attribute) -> AttributeValue {
+        let penalty = self.needs.as_ref().map_or(0, |needs| needs.penalty(attribute));
+        self.get_base_attribute_value(attribute) - penalty
+    }
+
+    /// Returns the value of the specified attribute including the influences of the equipped
+    /// items. Fixed amounts are added before percentages are applied, so `+10% Strength` also
+    /// grows the `+5 Strength` of another item.
+    pub fn equipped_attribute_value(&self, attribute: &Attribute) -> AttributeValue {
+        let influences: Vec<&ItemInfluence> = self.equipment()
+            .iter()
+            .filter_map(|&(_, item)| item)
+            .flat_map(|item| item.influences())
+            .filter(|influence| &influence.attribute == attribute)
+            .collect();
+
+        let flat: AttributeValue = influences.iter()
+            .filter(|influence| influence.kind == InfluenceKind::Flat)
+            .map(|influence| influence.amount)
+            .sum();
+        let value = self.get_attribute_value(attribute) + flat;
+        let percent: AttributeValue = influences.iter()
+            .filter(|influence| influence.kind == InfluenceKind::Percent)
+            .map(|influence| influence.amount)
+            .sum();
+
+        value + value * percent / 100
+    }
+
+    /// Returns the value of the specified attribute without any temporary penalties
+    pub fn get_base_attribute_value(&self, attribute: &Attribute) -> AttributeValue {
         *self.attributes.get(attribute).unwrap()
     }
 
+    /// Performs a skill check using the given attribute against the given difficulty.
+    /// `Attribute::Luck` widens the range of critical outcomes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate rpg;
+    /// # use rpg::character::{Attribute, Character};
+    /// # use rpg::skill_check::DIFFICULTY_EASY;
+    /// # fn main() {
+    /// let character = Character::new("Michael");
+    /// let outcome = character.skill_check(&Attribute::Dexterity,
+    ///                                     DIFFICULTY_EASY,
+    ///                                     &mut rand::thread_rng());
+    /// # }
+    /// ```
+    pub fn skill_check<R: Rng>(&self,
+                               attribute: &Attribute,
+                               difficulty: AttributeValue,
+                               rng: &mut R)
+                               -> CheckOutcome {
+        skill_check::roll(self.get_attribute_value(attribute),
+                          self.get_attribute_value(&Attribute::Luck),
+                          difficulty,
+                          rng)
+    }
+
     /// A setter method for the head armor slot.
     ///
     /// # Panics
@@ -163,6 +659,126 @@ impl Character {
         self.weapon_slot_left = item;
     }
 
+    /// Returns the item equipped in the given slot
+    pub fn equipped(&self, slot: EquipmentSlot) -> Option<&Item> {
+        match slot {
+            EquipmentSlot::Head => self.armor_slot_head.as_ref(),
+            EquipmentSlot::Chest => self.armor_slot_chest.as_ref(),
+            EquipmentSlot::Legs => self.armor_slot_legs.as_ref(),
+            EquipmentSlot::Feet => self.armor_slot_feet.as_ref(),
+            EquipmentSlot::LeftHand => self.weapon_slot_left.as_ref(),
+            EquipmentSlot::RightHand => self.weapon_slot_right.as_ref(),
+        }
+    }
+
+    fn slot_mut(&mut self, slot: EquipmentSlot) -> &mut Option<Item> {
+        match slot {
+            EquipmentSlot::Head => &mut self.armor_slot_head,
+            EquipmentSlot::Chest => &mut self.armor_slot_chest,
+            EquipmentSlot::Legs => &mut self.armor_slot_legs,
+            EquipmentSlot::Feet => &mut self.armor_slot_feet,
+            EquipmentSlot::LeftHand => &mut self.weapon_slot_left,
+            EquipmentSlot::RightHand => &mut self.weapon_slot_right,
+        }
+    }
+
+    /// Equips the item in the given slot and returns the item equipped before. Equipping a
+    /// cursed item reveals its influence. Fails if the item does not fit or the slot holds a
+    /// cursed item.
+    pub fn equip(&mut self,
+                 slot: EquipmentSlot,
+                 mut item: Item)
+                 -> Result<Option<Item>, EquipError> {
+        if !slot.accepts(&item.item_type) {
+            return Err(EquipError::WrongSlot);
+        }
+        if item.cursed {
+            item.identify();
+        }
+
+        let previous = self.unequip(slot)?;
+        *self.slot_mut(slot) = Some(item);
+
+        Ok(previous)
+    }
+
+    /// Equips the best items of the inventory according to the strategy, putting the replaced
+    /// items into the inventory. Empty slots are always filled; cursed items stay where they are.
+    /// Returns the slots that were changed.
+    pub fn auto_equip(&mut self,
+                      inventory: &mut Inventory,
+                      strategy: &EquipStrategy)
+                      -> Vec<EquipmentSlot> {
+        let slots = [EquipmentSlot::Head,
+                     EquipmentSlot::Chest,
+                     EquipmentSlot::Legs,
+                     EquipmentSlot::Feet,
+                     EquipmentSlot::RightHand,
+                     EquipmentSlot::LeftHand];
+        let mut changed = Vec::new();
+
+        for &slot in &slots {
+            let best = inventory.items()
+                .into_iter()
+                .filter(|item| fits(slot, &item.item_type))
+                .max_by_key(|item| strategy.score(self, item))
+                .cloned();
+            let best = match best {
+                Some(best) => best,
+                None => continue,
+            };
+
+            let better = match self.equipped(slot) {
+                Some(current) if current.cursed => false,
+                Some(current) => strategy.score(self, &best) > strategy.score(self, current),
+                None => true,
+            };
+            if !better {
+                continue;
+            }
+
+            let item = inventory.take(&best).expect("Unable to find the best item");
+            match self.equip(slot, item) {
+                Ok(Some(previous)) => {
+                    if let Err(previous) = inventory.add_item(previous) {
+                        // There is no room for the replaced item, so the swap is undone
+                        let item = self.slot_mut(slot).take().expect("Unable to find item");
+                        *self.slot_mut(slot) = Some(*previous);
+                        inventory.add_item(item).expect("Unable to return item");
+                        continue;
+                    }
+                }
+                Ok(None) => {}
+                Err(_) => unreachable!("The slot accepts the item and is not cursed"),
+            }
+
+            changed.push(slot);
+        }
+
+        changed
+    }
+
+    /// Removes the item from the given slot and returns it. Fails if the item is cursed.
+    pub fn unequip(&mut self, slot: EquipmentSlot) -> Result<Option<Item>, EquipError> {
+        if self.equipped(slot).is_some_and(|item| item.cursed) {
+            return Err(EquipError::Cursed);
+        }
+
+        Ok(self.slot_mut(slot).take())
+    }
+
+    /// Lifts the curse of the item in the given slot, so it can be unequipped. Returns `false`
+    /// if the item was not cursed.
+    pub fn lift_curse(&mut self, slot: EquipmentSlot) -> bool {
+        match *self.slot_mut(slot) {
+            Some(ref mut item) if item.cursed => {
+                item.cursed = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the default attributes for a character
     pub fn default_attributes() -> HashMap<Attribute, AttributeValue> {
         let mut attribute_map = HashMap::new();
@@ -182,6 +798,296 @@ impl Character {
     }
 }
 
+impl Character {
+    /// Passes one turn for the character, letting the needs grow as fast as the difficulty
+    /// demands. `Tick::tick` does the same on `Difficulty::Normal`.
+    pub fn tick_with(&mut self, clock: &WorldClock, modifiers: &Modifiers) {
+        let starving = match self.needs {
+            Some(ref mut needs) => {
+                needs.grow(clock, modifiers);
+                needs.is_critical()
+            }
+            None => false,
+        };
+
+        self.recover_stamina(1);
+
+        if clock.turn().is_multiple_of(REGENERATION_INTERVAL) {
+            if starving {
+                self.take_damage(1);
+            } else {
+                self.heal(1);
+            }
+        }
+
+        for injury in &mut self.injuries {
+            injury.turns_left = injury.turns_left.saturating_sub(1);
+        }
+        self.injuries.retain(|injury| injury.turns_left > 0);
+
+        let damage: Health = self.conditions
+            .iter()
+            .filter(|condition| clock.turn().is_multiple_of(condition.interval))
+            .map(|condition| condition.damage)
+            .sum();
+        self.take_damage(damage);
+
+        for condition in &mut self.conditions {
+            condition.turns_left = condition.turns_left.saturating_sub(1);
+        }
+        self.conditions.retain(|condition| condition.turns_left > 0);
+    }
+}
+
+impl Character {
+    /// Returns a compact code of the character and its equipment, which can be shared and turned
+    /// back into a character with `Character::import_code`. The inventory, needs and injuries are
+    /// not part of the code.
+    pub fn export_code(&self) -> String {
+        let mut attributes = Object::new();
+        for (attribute, value) in &self.attributes {
+            attributes.insert(format!("{:?}", attribute), value.to_json());
+        }
+
+        let mut equipment = Object::new();
+        for &(slot, item) in &self.equipment() {
+            if let Some(item) = item {
+                equipment.insert(slot.to_owned(), item.to_json());
+            }
+        }
+
+        let mut object = Object::new();
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("pronouns".to_owned(), format!("{:?}", self.pronouns).to_json());
+        object.insert("experience".to_owned(), self.experience.to_json());
+        object.insert("attributes".to_owned(), Json::Object(attributes));
+        object.insert("equipment".to_owned(), Json::Object(equipment));
+
+        Json::Object(object).to_string().as_bytes().to_base64(base64::URL_SAFE)
+    }
+
+    /// Creates a fully healed character from a code written by `Character::export_code`. The
+    /// equipment gets new ids created by the rng, so importing a code twice gives distinct items.
+    pub fn import_code<R: Rng>(code: &str, rng: &mut R) -> Result<Character, DataError> {
+        let bytes = code.trim()
+            .from_base64()
+            .map_err(|err| DataError::Syntax(err.to_string()))?;
+        let text = String::from_utf8(bytes).map_err(|err| DataError::Syntax(err.to_string()))?;
+        let json = Json::from_str(&text)?;
+        let object = data::as_object(&json, "character")?;
+
+        let mut character = Character::new(&data::string(object, "name")?);
+        character.pronouns = data::optional(object, "pronouns", data::parse)?.unwrap_or_default();
+        character.experience = data::unsigned(object, "experience")?;
+        for (attribute, value) in data::as_object(data::field(object, "attributes")?,
+                                                  "attributes")? {
+            let attribute = attribute.parse()
+                .map_err(|_| DataError::Invalid("attributes".to_owned()))?;
+            let value = value.as_i64()
+                .ok_or_else(|| DataError::Invalid("attributes".to_owned()))?;
+            character.attributes.insert(attribute, value as AttributeValue);
+        }
+
+        for (slot, item) in data::as_object(data::field(object, "equipment")?, "equipment")? {
+            let item = Item::from_json(item)?.renewed(rng);
+            let expected = match &slot[..] {
+                "head" => Some(ItemType::ArmorHead),
+                "chest" => Some(ItemType::ArmorChest),
+                "legs" => Some(ItemType::ArmorLegs),
+                "feet" => Some(ItemType::ArmorFeet),
+                "left" | "right" => None,
+                _ => return Err(DataError::Invalid("equipment".to_owned())),
+            };
+            if expected.is_some_and(|item_type| item_type != item.item_type) {
+                return Err(DataError::Invalid(slot.clone()));
+            }
+
+            match &slot[..] {
+                "head" => character.armor_slot_head = Some(item),
+                "chest" => character.armor_slot_chest = Some(item),
+                "legs" => character.armor_slot_legs = Some(item),
+                "feet" => character.armor_slot_feet = Some(item),
+                "left" => character.weapon_slot_left = Some(item),
+                _ => character.weapon_slot_right = Some(item),
+            }
+        }
+
+        character.health = character.max_health();
+        character.stamina = character.max_stamina();
+
+        Ok(character)
+    }
+
+    /// Reads a character from a JSON object as written by `to_json`, e.g. the player of a saved
+    /// game
+    pub fn from_json(json: &Json) -> Result<Character, DataError> {
+        let object = data::as_object(json, "character")?;
+
+        let mut character = Character::new(&data::string(object, "name")?);
+        character.pronouns = data::parse(object, "pronouns")?;
+        character.health = data::unsigned(object, "health")? as Health;
+        character.stamina = data::unsigned(object, "stamina")? as u32;
+        character.state = data::parse(object, "state")?;
+        character.sneaking = data::boolean(object, "sneaking")?;
+        character.experience = data::unsigned(object, "experience")?;
+        character.inventory = Inventory::from_json(data::field(object, "inventory")?)?;
+        character.hotbar = Hotbar::from_json(data::field(object, "hotbar")?)?;
+
+        for (attribute, value) in data::as_object(data::field(object, "attributes")?,
+                                                  "attributes")? {
+            let attribute = attribute.parse()
+                .map_err(|_| DataError::Invalid("attributes".to_owned()))?;
+            let value = value.as_i64()
+                .ok_or_else(|| DataError::Invalid("attributes".to_owned()))?;
+            character.attributes.insert(attribute, value as AttributeValue);
+        }
+
+        for (slot, item) in data::as_object(data::field(object, "equipment")?, "equipment")? {
+            let item = Some(Item::from_json(item)?);
+            match &slot[..] {
+                "head" => character.armor_slot_head = item,
+                "chest" => character.armor_slot_chest = item,
+                "legs" => character.armor_slot_legs = item,
+                "feet" => character.armor_slot_feet = item,
+                "left" => character.weapon_slot_left = item,
+                "right" => character.weapon_slot_right = item,
+                _ => return Err(DataError::Invalid("equipment".to_owned())),
+            }
+        }
+
+        for injury in data::as_array(data::field(object, "injuries")?, "injuries")? {
+            let injury = data::as_object(injury, "injuries")?;
+            character.injuries.push(Injury {
+                body_part: data::parse(injury, "body_part")?,
+                turns_left: data::unsigned(injury, "turns_left")?,
+            });
+        }
+
+        for condition in data::as_array(data::field(object, "conditions")?, "conditions")? {
+            let condition = data::as_object(condition, "conditions")?;
+            let interval = data::unsigned(condition, "interval")?;
+            if interval == 0 {
+                return Err(DataError::Invalid("interval".to_owned()));
+            }
+            character.conditions.push(Condition {
+                kind: data::parse(condition, "kind")?,
+                damage: data::unsigned(condition, "damage")? as Health,
+                interval,
+                turns_left: data::unsigned(condition, "turns_left")?,
+            });
+        }
+
+        character.needs = match object.get("needs") {
+            None | Some(&Json::Null) => None,
+            Some(needs) => {
+                let needs = data::as_object(needs, "needs")?;
+                Some(Needs {
+                    hunger: data::unsigned(needs, "hunger")? as u32,
+                    thirst: data::unsigned(needs, "thirst")? as u32,
+                    fatigue: data::unsigned(needs, "fatigue")? as u32,
+                })
+            }
+        };
+
+        Ok(character)
+    }
+
+    /// Returns all equipped items
+    pub fn equipped_items(&self) -> Vec<&Item> {
+        self.equipment().iter().filter_map(|&(_, item)| item).collect()
+    }
+
+    fn equipment(&self) -> [(&'static str, Option<&Item>); 6] {
+        [("head", self.armor_slot_head.as_ref()),
+         ("chest", self.armor_slot_chest.as_ref()),
+         ("legs", self.armor_slot_legs.as_ref()),
+         ("feet", self.armor_slot_feet.as_ref()),
+         ("left", self.weapon_slot_left.as_ref()),
+         ("right", self.weapon_slot_right.as_ref())]
+    }
+}
+
+impl ToJson for Character {
+    fn to_json(&self) -> Json {
+        let mut attributes = Object::new();
+        for (attribute, value) in &self.attributes {
+            attributes.insert(format!("{:?}", attribute), value.to_json());
+        }
+
+        let mut equipment = Object::new();
+        for &(slot, item) in &self.equipment() {
+            if let Some(item) = item {
+                equipment.insert(slot.to_owned(), item.to_json());
+            }
+        }
+
+        let injuries: Vec<Json> = self.injuries
+            .iter()
+            .map(|injury| {
+                let mut object = Object::new();
+                object.insert("body_part".to_owned(), format!("{:?}", injury.body_part).to_json());
+                object.insert("turns_left".to_owned(), injury.turns_left.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let conditions: Vec<Json> = self.conditions
+            .iter()
+            .map(|condition| {
+                let mut object = Object::new();
+                object.insert("kind".to_owned(), format!("{:?}", condition.kind).to_json());
+                object.insert("damage".to_owned(), condition.damage.to_json());
+                object.insert("interval".to_owned(), condition.interval.to_json());
+                object.insert("turns_left".to_owned(), condition.turns_left.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let needs = self.needs.as_ref().map(|needs| {
+            let mut object = Object::new();
+            object.insert("hunger".to_owned(), needs.hunger.to_json());
+            object.insert("thirst".to_owned(), needs.thirst.to_json());
+            object.insert("fatigue".to_owned(), needs.fatigue.to_json());
+            Json::Object(object)
+        });
+
+        let mut object = Object::new();
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("pronouns".to_owned(), format!("{:?}", self.pronouns).to_json());
+        object.insert("health".to_owned(), self.health.to_json());
+        object.insert("stamina".to_owned(), self.stamina.to_json());
+        object.insert("state".to_owned(), format!("{:?}", self.state).to_json());
+        object.insert("attributes".to_owned(), Json::Object(attributes));
+        object.insert("equipment".to_owned(), Json::Object(equipment));
+        object.insert("inventory".to_owned(), self.inventory.to_json());
+        object.insert("sneaking".to_owned(), self.sneaking.to_json());
+        object.insert("experience".to_owned(), self.experience.to_json());
+        object.insert("injuries".to_owned(), Json::Array(injuries));
+        object.insert("conditions".to_owned(), Json::Array(conditions));
+        object.insert("needs".to_owned(), needs.to_json());
+        object.insert("hotbar".to_owned(), self.hotbar.to_json());
+
+        Json::Object(object)
+    }
+}
+
+impl Tick for Character {
+    fn tick(&mut self, clock: &WorldClock) {
+        self.tick_with(clock, &Difficulty::Normal.modifiers());
+    }
+}
+
+/// Returns `true` if `Character::auto_equip` may put items of the given type into the slot
+fn fits(slot: EquipmentSlot, item_type: &ItemType) -> bool {
+    match item_type.slot() {
+        Some(EquipmentSlot::RightHand) => {
+            slot == EquipmentSlot::LeftHand || slot == EquipmentSlot::RightHand
+        }
+        Some(item_slot) => item_slot == slot,
+        None => false,
+    }
+}
+
 /// A list of all possible attributes
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub enum Attribute {
@@ -207,6 +1113,42 @@ pub enum Attribute {
     Wisdom,
 }
 
+impl Attribute {
+    /// Returns all attributes in alphabetical order
+    pub fn all() -> Vec<Attribute> {
+        vec![Attribute::Charisma,
+             Attribute::Constitution,
+             Attribute::Defense,
+             Attribute::Dexterity,
+             Attribute::Intelligence,
+             Attribute::Luck,
+             Attribute::Perception,
+             Attribute::Strength,
+             Attribute::Willpower,
+             Attribute::Wisdom]
+    }
+}
+
+impl FromStr for Attribute {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Attribute, ()> {
+        match s {
+            "Charisma" => Ok(Attribute::Charisma),
+            "Constitution" => Ok(Attribute::Constitution),
+            "Defense" => Ok(Attribute::Defense),
+            "Dexterity" => Ok(Attribute::Dexterity),
+            "Intelligence" => Ok(Attribute::Intelligence),
+            "Luck" => Ok(Attribute::Luck),
+            "Perception" => Ok(Attribute::Perception),
+            "Strength" => Ok(Attribute::Strength),
+            "Willpower" => Ok(Attribute::Willpower),
+            "Wisdom" => Ok(Attribute::Wisdom),
+            _ => Err(()),
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -214,6 +1156,9 @@ mod tests {
 
     use item_generator;
     use item::{ItemType, ItemInfluence};
+    use time::WorldClock;
+    use skill_check::CheckOutcome;
+    use rand::{SeedableRng, XorShiftRng};
 
     #[test]
     fn set_armor_slot_head() {
@@ -309,6 +1254,15 @@ mod tests {
         assert_eq!(character.get_attribute_value(&Attribute::Dexterity), 42);
     }
 
+    #[test]
+    fn parse_attribute() {
+        for attribute in Character::default_attributes().keys() {
+            assert_eq!(format!("{:?}", attribute).parse(), Ok(attribute.clone()));
+        }
+
+        assert_eq!("Agility".parse::<Attribute>(), Err(()));
+    }
+
     #[test]
     fn basic_attack_damage() {
         let character = Character::new("Wil Wheaton");
@@ -331,4 +1285,230 @@ mod tests {
 
         assert_eq!(character.attack_damage(), 42);
     }
+
+    #[test]
+    fn percent_influences() {
+        let mut character = Character::new("Wil Wheaton");
+        character.update_attribute(&Attribute::Strength, 20);
+
+        let helmet = item_generator::ItemGenerator::new()
+            .item_type(ItemType::ArmorHead)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, 10)))
+            .gen();
+        let boots = item_generator::ItemGenerator::new()
+            .item_type(ItemType::ArmorFeet)
+            .influence(Some(ItemInfluence::percent(Attribute::Strength, 50)))
+            .gen();
+        character.set_armor_slot_head(Some(helmet));
+        character.set_armor_slot_feet(Some(boots.clone()));
+        assert_eq!(character.equipped_attribute_value(&Attribute::Strength), 45);
+
+        let base_damage = character.attack_damage();
+        character.set_weapon_slot_left(Some(boots));
+        assert_eq!(character.attack_damage(), base_damage + 10);
+    }
+
+    #[test]
+    fn auto_equip() {
+        let weapon = |amount| {
+            item_generator::ItemGenerator::new()
+                .item_type(ItemType::WeaponSword)
+                .influence(Some(ItemInfluence::new(Attribute::Strength, amount)))
+                .damage(None)
+                .gen()
+        };
+        let helmet = |amount| {
+            item_generator::ItemGenerator::new()
+                .item_type(ItemType::ArmorHead)
+                .influence(Some(ItemInfluence::new(Attribute::Defense, amount)))
+                .gen()
+        };
+
+        let mut character = Character::new("Wil Wheaton");
+        character.set_weapon_slot_right(Some(weapon(5)));
+        let mut inventory = Inventory::new(10);
+        for item in [weapon(2), weapon(8), weapon(1), helmet(3), helmet(9)] {
+            inventory.add_item(item).unwrap();
+        }
+
+        let changed = character.auto_equip(&mut inventory, &EquipStrategy::Defense);
+        assert_eq!(changed, vec![EquipmentSlot::Head, EquipmentSlot::LeftHand]);
+        assert_eq!(character.equipped(EquipmentSlot::Head).unwrap().influence,
+                   Some(ItemInfluence::new(Attribute::Defense, 9)));
+
+        let mut character = Character::new("Wil Wheaton");
+        character.set_weapon_slot_right(Some(weapon(5)));
+        let mut inventory = Inventory::new(10);
+        for item in [weapon(2), weapon(8), weapon(1)] {
+            inventory.add_item(item).unwrap();
+        }
+        character.auto_equip(&mut inventory, &EquipStrategy::Damage);
+        assert_eq!(character.equipped(EquipmentSlot::RightHand).unwrap().influence,
+                   Some(ItemInfluence::new(Attribute::Strength, 8)));
+        assert_eq!(character.equipped(EquipmentSlot::LeftHand).unwrap().influence,
+                   Some(ItemInfluence::new(Attribute::Strength, 5)));
+        assert_eq!(inventory.items().len(), 2);
+    }
+
+    #[test]
+    fn roll_attack_damage() {
+        let mut character = Character::new("Wil Wheaton");
+        let weapon = item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponHammer)
+            .damage(Some(Dice::new(2, 4).modifier(1)))
+            .gen();
+        character.set_weapon_slot_left(Some(weapon));
+
+        assert_eq!(character.weapon_dice(), vec![Dice::new(2, 4).modifier(1)]);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert_eq!(character.roll_attack_damage(&mut rng) - character.attack_damage(), 7);
+    }
+
+    #[test]
+    fn skill_check() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Wil Wheaton");
+
+        character.update_attribute(&Attribute::Dexterity, 1000);
+        for _ in 0..100 {
+            let outcome = character.skill_check(&Attribute::Dexterity, 50, &mut rng);
+            assert!(outcome != CheckOutcome::Failure);
+        }
+
+        character.update_attribute(&Attribute::Dexterity, 0);
+        for _ in 0..100 {
+            let outcome = character.skill_check(&Attribute::Dexterity, 50, &mut rng);
+            assert!(outcome != CheckOutcome::Success);
+        }
+    }
+
+    #[test]
+    fn experience_and_level() {
+        let mut character = Character::new("Wil Wheaton");
+        assert_eq!(character.level(), 1);
+
+        character.gain_experience(99);
+        assert_eq!(character.level(), 1);
+
+        character.gain_experience(1);
+        assert_eq!(character.level(), 2);
+
+        character.gain_experience(800);
+        assert_eq!(character.experience(), 900);
+        assert_eq!(character.level(), 4);
+    }
+
+    #[test]
+    fn regeneration() {
+        let mut character = Character::new("Wil Wheaton");
+        character.health = 10;
+
+        let mut clock = WorldClock::new();
+        for _ in 0..REGENERATION_INTERVAL {
+            clock.advance_with(&mut [&mut character]);
+        }
+
+        assert_eq!(character.health(), 11);
+
+        for _ in 0..(REGENERATION_INTERVAL * 100) {
+            clock.advance_with(&mut [&mut character]);
+        }
+
+        assert_eq!(character.health(), character.max_health());
+    }
+
+    #[test]
+    fn share_code() {
+        let mut character = Character::new("Bilbo");
+        character.gain_experience(250);
+        character.update_attribute(&Attribute::Luck, 7);
+        character.set_armor_slot_feet(Some(item_generator::ItemGenerator::new()
+            .item_type(ItemType::ArmorFeet)
+            .gen()));
+        character.set_weapon_slot_right(Some(item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponSword)
+            .gen()));
+        character.take_damage(5);
+        character.set_pronouns(Pronouns::She);
+
+        let code = character.export_code();
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let imported = Character::import_code(&code, &mut rng).unwrap();
+        assert_eq!(imported.name(), "Bilbo");
+        assert_eq!(imported.pronouns(), Pronouns::She);
+        assert_eq!(imported.experience(), 250);
+        assert_eq!(imported.get_attribute_value(&Attribute::Luck), 7);
+        let imported_feet = imported.armor_slot_feet.as_ref().unwrap();
+        assert!(imported_feet.stacks_with(character.armor_slot_feet.as_ref().unwrap()));
+        assert_ne!(imported_feet.id, character.armor_slot_feet.as_ref().unwrap().id);
+        let imported_sword = imported.weapon_slot_right.as_ref().unwrap();
+        assert!(imported_sword.stacks_with(character.weapon_slot_right.as_ref().unwrap()));
+        assert_eq!(imported.armor_slot_head, None);
+        assert_eq!(imported.health(), imported.max_health());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut character = Character::new("Eowyn");
+        character.set_pronouns(Pronouns::She);
+        character.set_weapon_slot_right(Some(item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponSword)
+            .gen()));
+        character.injure(BodyPart::Arm);
+        character.afflict(Condition::poison());
+        character.set_survival(true);
+        character.needs_mut().unwrap().thirst = 40;
+        character.take_damage(3);
+
+        let loaded = Character::from_json(&character.to_json()).unwrap();
+        assert_eq!(loaded.to_json(), character.to_json());
+        assert_eq!(loaded.health(), character.health());
+        assert_eq!(loaded.weapon_slot_right, character.weapon_slot_right);
+        assert_eq!(loaded.needs().map(|needs| needs.thirst), Some(40));
+    }
+
+    #[test]
+    fn invalid_code() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert!(Character::import_code("not a code!", &mut rng).is_err());
+
+        let code = concat!(r#"{"name":"Bilbo","experience":0,"attributes":{},"equipment":{"#,
+                           r#""head":{"name":"Sting","item_type":"WeaponSword","stack_size":1,"#,
+                           r#""rarity":"Epic"}}}"#)
+            .as_bytes()
+            .to_base64(base64::URL_SAFE);
+        match Character::import_code(&code, &mut rng) {
+            Err(DataError::Invalid(slot)) => assert_eq!(slot, "head"),
+            _ => panic!("expected an invalid slot"),
+        }
+    }
+
+    #[test]
+    fn equip_slots() {
+        let mut character = Character::new("Aragorn");
+        let helmet = item_generator::ItemGenerator::new().item_type(ItemType::ArmorHead).gen();
+        let sword = item_generator::ItemGenerator::new().item_type(ItemType::WeaponSword).gen();
+
+        assert_eq!(character.equip(EquipmentSlot::Chest, helmet.clone()),
+                   Err(EquipError::WrongSlot));
+        assert_eq!(character.equip(EquipmentSlot::Head, helmet.clone()), Ok(None));
+        assert_eq!(character.equip(EquipmentSlot::Head, helmet.clone()), Ok(Some(helmet)));
+        assert_eq!(character.equip(EquipmentSlot::LeftHand, sword.clone()), Ok(None));
+        assert_eq!(character.unequip(EquipmentSlot::LeftHand), Ok(Some(sword)));
+        assert_eq!(character.equipped(EquipmentSlot::LeftHand), None);
+    }
+
+    #[test]
+    fn setter_ignores_curse() {
+        let mut character = Character::new("Aragorn");
+        let mut sword = item_generator::ItemGenerator::new().item_type(ItemType::WeaponSword).gen();
+        sword.cursed = true;
+
+        character.set_weapon_slot_right(Some(sword));
+        assert_eq!(character.unequip(EquipmentSlot::RightHand), Err(EquipError::Cursed));
+        character.set_weapon_slot_right(None);
+        assert_eq!(character.equipped(EquipmentSlot::RightHand), None);
+    }
 }
@@ -0,0 +1,271 @@
+use super::{Action, Battle};
+
+/// The share of health below which a coward tries to get away
+const COWARD_HEALTH_RATIO: f64 = 0.3;
+
+/// The share of health below which a healer heals an ally
+const HEALER_HEALTH_RATIO: f64 = 0.5;
+
+/// The share of health below which a tank guards instead of fighting
+const TANK_HEALTH_RATIO: f64 = 0.3;
+
+/// Decides which action an AI controlled combatant takes during its turn
+pub trait AiBehavior: AiBehaviorClone {
+    /// Returns the action of the combatant with the index `me` in the given battle
+    fn decide(&self, me: usize, battle: &Battle) -> Action;
+}
+
+/// A helper trait for cloning `AiBehavior` objects
+pub trait AiBehaviorClone {
+    /// A clone method that returns a boxed AI behavior
+    fn clone_box(&self) -> Box<dyn AiBehavior>;
+}
+
+impl<T> AiBehaviorClone for T
+    where T: 'static + AiBehavior + Clone
+{
+    fn clone_box(&self) -> Box<dyn AiBehavior> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AiBehavior> {
+    fn clone(&self) -> Box<dyn AiBehavior> {
+        self.clone_box()
+    }
+}
+
+/// Walks up to the most threatening enemy and attacks it
+#[derive(Clone)]
+pub struct AggressiveMelee;
+
+impl AiBehavior for AggressiveMelee {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        match battle.preferred_target(me) {
+            Some(enemy) if battle.in_range(me, enemy) => Action::Attack(enemy),
+            Some(_) => Action::Advance,
+            None => Action::Wait,
+        }
+    }
+}
+
+/// Attacks from afar and backs off as soon as an enemy comes too close
+#[derive(Clone)]
+pub struct RangedKiter;
+
+impl AiBehavior for RangedKiter {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        let (nearest, target) = match (battle.nearest_enemy(me), battle.preferred_target(me)) {
+            (Some(nearest), Some(target)) => (nearest, target),
+            _ => return Action::Wait,
+        };
+
+        if battle.distance(me, nearest) < battle.combatant(me).range {
+            Action::Retreat
+        } else if battle.in_range(me, target) {
+            Action::Attack(target)
+        } else {
+            Action::Advance
+        }
+    }
+}
+
+/// Fights like `AggressiveMelee` until it is badly hurt, then runs away
+#[derive(Clone)]
+pub struct Coward;
+
+impl AiBehavior for Coward {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        if battle.combatant(me).health_ratio() < COWARD_HEALTH_RATIO {
+            Action::Retreat
+        } else {
+            AggressiveMelee.decide(me, battle)
+        }
+    }
+}
+
+/// Heals the most wounded ally and attacks if nobody needs healing
+#[derive(Clone)]
+pub struct Healer;
+
+impl AiBehavior for Healer {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        let wounded = battle.allies_of(me)
+            .into_iter()
+            .filter(|&ally| battle.combatant(ally).health_ratio() < HEALER_HEALTH_RATIO)
+            .min_by_key(|&ally| battle.combatant(ally).health);
+
+        match wounded {
+            Some(ally) => Action::Heal(ally),
+            None => AggressiveMelee.decide(me, battle),
+        }
+    }
+}
+
+/// Taunts every enemy that is after one of its allies, guards when badly hurt and otherwise
+/// fights like `AggressiveMelee`
+#[derive(Clone)]
+pub struct Tank;
+
+impl AiBehavior for Tank {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        let distracted = battle.enemies_of(me)
+            .into_iter()
+            .any(|enemy| battle.preferred_target(enemy).is_some_and(|target| target != me));
+
+        if distracted {
+            Action::Taunt
+        } else if battle.combatant(me).health_ratio() < TANK_HEALTH_RATIO {
+            Action::Guard
+        } else {
+            AggressiveMelee.decide(me, battle)
+        }
+    }
+}
+
+/// Hands its consumables to the most wounded ally, helps an ally that can reach an enemy and
+/// attacks if there is nothing else to do
+#[derive(Clone)]
+pub struct Support;
+
+impl AiBehavior for Support {
+    fn decide(&self, me: usize, battle: &Battle) -> Action {
+        let allies = battle.allies_of(me);
+        let wounded = allies.iter()
+            .cloned()
+            .filter(|&ally| battle.combatant(ally).health_ratio() < HEALER_HEALTH_RATIO)
+            .min_by_key(|&ally| battle.combatant(ally).health);
+        let item = battle.combatant(me)
+            .items
+            .iter()
+            .position(|item| item.item_type.is_consumable());
+
+        if let (Some(ally), Some(item)) = (wounded, item) {
+            return Action::UseItem(item, ally);
+        }
+
+        let fighter = allies.into_iter().find(|&ally| {
+            ally != me && !battle.is_helped(ally) &&
+            battle.preferred_target(ally).is_some_and(|enemy| battle.in_range(ally, enemy))
+        });
+
+        match fighter {
+            Some(ally) => Action::Help(ally),
+            None => AggressiveMelee.decide(me, battle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combat::{Action, Battle, Combatant, Side};
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[derive(Clone)]
+    struct Lazy;
+
+    impl AiBehavior for Lazy {
+        fn decide(&self, _: usize, _: &Battle) -> Action {
+            Action::Wait
+        }
+    }
+
+    fn battle_with<T: AiBehavior + 'static>(ai: T) -> Battle {
+        let mut battle = Battle::new();
+        battle.add_combatant(Combatant::new("Hero", Side::Player, 30));
+        battle.add_combatant(Combatant::new("Monster", Side::Enemy, 30).distance(1).ai(ai));
+        battle
+    }
+
+    #[test]
+    fn aggressive_melee() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = battle_with(AggressiveMelee);
+
+        assert_eq!(battle.decide(1), Some(Action::Advance));
+        battle.run_ai_turns(&mut rng);
+        assert_eq!(battle.decide(1), Some(Action::Attack(0)));
+        assert_eq!(battle.decide(0), None);
+    }
+
+    #[test]
+    fn attacks_by_threat() {
+        let mut battle = battle_with(AggressiveMelee);
+        let tank = battle.add_combatant(Combatant::new("Tank", Side::Player, 30).distance(1));
+        battle.combatant_mut(1).distance = 0;
+
+        assert_eq!(battle.decide(1), Some(Action::Attack(0)));
+
+        battle.add_threat(tank, 100);
+        assert_eq!(battle.decide(1), Some(Action::Advance));
+    }
+
+    #[test]
+    fn ranged_kiter() {
+        let mut battle = battle_with(RangedKiter);
+        battle.combatant_mut(1).range = 3;
+
+        assert_eq!(battle.decide(1), Some(Action::Retreat));
+
+        battle.combatant_mut(1).distance = 3;
+        assert_eq!(battle.decide(1), Some(Action::Attack(0)));
+
+        battle.combatant_mut(1).distance = 5;
+        assert_eq!(battle.decide(1), Some(Action::Advance));
+    }
+
+    #[test]
+    fn coward() {
+        let mut battle = battle_with(Coward);
+        battle.combatant_mut(1).health = 5;
+
+        assert_eq!(battle.decide(1), Some(Action::Retreat));
+    }
+
+    #[test]
+    fn healer() {
+        let mut battle = battle_with(Healer);
+        let ally = battle.add_combatant(Combatant::new("Orc", Side::Enemy, 30));
+        battle.combatant_mut(ally).health = 3;
+
+        assert_eq!(battle.decide(1), Some(Action::Heal(ally)));
+    }
+
+    #[test]
+    fn tank() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = battle_with(Tank);
+        battle.add_combatant(Combatant::new("Orc", Side::Enemy, 30));
+
+        assert_eq!(battle.decide(1), Some(Action::Taunt));
+        battle.execute(1, Action::Taunt, &mut rng).unwrap();
+        assert_eq!(battle.decide(1), Some(Action::Advance));
+
+        battle.combatant_mut(1).health = 5;
+        assert_eq!(battle.decide(1), Some(Action::Guard));
+    }
+
+    #[test]
+    fn support() {
+        let mut battle = battle_with(Support);
+        let ally = battle.add_combatant(Combatant::new("Orc", Side::Enemy, 30));
+        battle.combatant_mut(ally).health = 3;
+
+        assert_eq!(battle.decide(1), Some(Action::Help(ally)));
+
+        let potion = ItemGenerator::new().item_type(ItemType::ConsumablePotion).gen();
+        battle.combatant_mut(1).items.push(potion);
+        assert_eq!(battle.decide(1), Some(Action::UseItem(0, ally)));
+    }
+
+    #[test]
+    fn custom_behavior() {
+        let battle = battle_with(Lazy);
+        let cloned = battle.clone();
+
+        assert_eq!(cloned.decide(1), Some(Action::Wait));
+    }
+}
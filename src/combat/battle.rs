@@ -0,0 +1,862 @@
+use character::Attribute;
+use rand::Rng;
+use super::{CombatLogEntry, Combatant, LogFormatter, Outcome, Side, Threat, ThreatTable};
+use types::{AttributeValue, Health};
+use ui::Bar;
+
+/// The amount of defense points that reduce the damage of an attack by one
+const DEFENSE_POINTS_PER_REDUCTION: AttributeValue = 5;
+
+/// The amount of `Attribute::Wisdom` points that heal one point of health
+const WISDOM_POINTS_PER_HEAL: AttributeValue = 1;
+
+/// The threat generated per point of damage dealt
+const THREAT_PER_DAMAGE: Threat = 2;
+
+/// The threat generated per point of health healed
+const THREAT_PER_HEAL: Threat = 1;
+
+/// The threat a taunt generates towards the taunting combatant
+const THREAT_PER_TAUNT: Threat = 20;
+
+/// The divisor of the damage a guarding combatant takes
+const GUARD_DAMAGE_DIVISOR: Health = 2;
+
+/// The damage added to the next attack of a combatant that was helped by an ally
+const HELP_DAMAGE_BONUS: Health = 3;
+
+/// The chance in percent to flee from an equally fast enemy
+const FLEE_BASE_CHANCE: AttributeValue = 50;
+
+/// The chance in percent gained per point of `Attribute::Dexterity` and `Attribute::Luck` the
+/// fleeing combatant has over the fastest enemy
+const FLEE_CHANCE_PER_POINT: AttributeValue = 2;
+
+/// The lowest and highest chance in percent to flee
+const FLEE_CHANCE_BOUNDS: (AttributeValue, AttributeValue) = (5, 95);
+
+/// The reasons why a combatant can not execute an action
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActionError {
+    /// The acting combatant does not take part in the battle
+    InvalidActor,
+    /// The action targets a combatant that does not take part in the battle
+    InvalidTarget,
+}
+
+/// An action a combatant can take during its turn
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    /// Attack the combatant with the given index
+    Attack(usize),
+    /// Heal the combatant with the given index
+    Heal(usize),
+    /// Move one step closer to the front line
+    Advance,
+    /// Move one step away from the front line
+    Retreat,
+    /// Try to escape from the battle
+    Flee,
+    /// Do nothing
+    Wait,
+    /// Draw the attention of all enemies by generating threat
+    Taunt,
+    /// Take less damage until the next turn
+    Guard,
+    /// Help the ally with the given index, strengthening its next attack
+    Help(usize),
+    /// Use the item with the first index on the combatant with the second index, e.g. drink a
+    /// potion or hand it to an ally
+    UseItem(usize, usize),
+}
+
+/// A fight between the player's side and the enemies
+#[derive(Clone)]
+pub struct Battle {
+    combatants: Vec<Combatant>,
+    threat: Vec<ThreatTable>,
+    guarding: Vec<bool>,
+    helped: Vec<bool>,
+    round: u32,
+    log: Vec<CombatLogEntry>,
+}
+
+impl Default for Battle {
+    fn default() -> Battle {
+        Battle::new()
+    }
+}
+
+impl Battle {
+    /// Creates a new, empty instance of `Battle`
+    pub fn new() -> Battle {
+        Battle {
+            combatants: Vec::new(),
+            threat: Vec::new(),
+            guarding: Vec::new(),
+            helped: Vec::new(),
+            round: 0,
+            log: Vec::new(),
+        }
+    }
+
+    /// Adds a combatant to the battle and returns its index. Combatants take their turns in the
+    /// order they were added.
+    pub fn add_combatant(&mut self, combatant: Combatant) -> usize {
+        self.combatants.push(combatant);
+        self.threat.push(ThreatTable::new());
+        self.guarding.push(false);
+        self.helped.push(false);
+        self.combatants.len() - 1
+    }
+
+    /// Adds a combatant summoned by another one to the side and at the distance of the summoner
+    /// and returns its index. Give it a `lifespan` to let it vanish after some rounds.
+    pub fn summon(&mut self, summoner: usize, mut combatant: Combatant) -> usize {
+        combatant.side = self.combatants[summoner].side;
+        combatant.distance = self.combatants[summoner].distance;
+        self.add_combatant(combatant)
+    }
+
+    /// Returns the threat table of the combatant with the given index
+    pub fn threat_table(&self, index: usize) -> &ThreatTable {
+        &self.threat[index]
+    }
+
+    /// Adds threat towards the source to the threat tables of all its living enemies
+    pub fn add_threat(&mut self, source: usize, amount: Threat) {
+        for enemy in self.enemies_of(source) {
+            self.threat[enemy].add(source, amount);
+        }
+    }
+
+    /// Returns the enemy the given combatant wants to attack: the one that generated the most
+    /// threat, or the nearest one if nobody did.
+    pub fn preferred_target(&self, index: usize) -> Option<usize> {
+        let enemies = self.enemies_of(index);
+
+        self.threat[index]
+            .highest(&enemies)
+            .or_else(|| self.nearest_enemy(index))
+    }
+
+    /// Returns `true` if the given combatant guards until its next turn
+    pub fn is_guarding(&self, index: usize) -> bool {
+        self.guarding[index]
+    }
+
+    /// Returns `true` if the next attack of the given combatant is strengthened by an ally
+    pub fn is_helped(&self, index: usize) -> bool {
+        self.helped[index]
+    }
+
+    /// Returns all combatants, including the defeated ones
+    pub fn combatants(&self) -> &[Combatant] {
+        &self.combatants
+    }
+
+    /// Returns the combatant with the given index
+    pub fn combatant(&self, index: usize) -> &Combatant {
+        &self.combatants[index]
+    }
+
+    /// Returns the combatant with the given index as mutable
+    pub fn combatant_mut(&mut self, index: usize) -> &mut Combatant {
+        &mut self.combatants[index]
+    }
+
+    /// Returns the amount of rounds that have been fought
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Returns the combat log containing every action taken so far
+    pub fn log(&self) -> &[CombatLogEntry] {
+        &self.log
+    }
+
+    /// Renders the combat log to readable text using the given formatter
+    pub fn narrate<F: LogFormatter>(&self, formatter: &F) -> Vec<String> {
+        self.log.iter().map(|entry| formatter.format(entry, &self.combatants)).collect()
+    }
+
+    /// Renders the health of every combatant as a bar behind its name, e.g.
+    /// `Goblin [███░░░░░░░] 3/10 HP`
+    pub fn status(&self, bar: &Bar) -> Vec<String> {
+        let width = self.combatants.iter().map(|combatant| combatant.name.len()).max().unwrap_or(0);
+
+        self.combatants
+            .iter()
+            .map(|combatant| {
+                format!("{:<width$} {}",
+                        combatant.name,
+                        bar.render(combatant.health as u64, combatant.max_health as u64),
+                        width = width)
+            })
+            .collect()
+    }
+
+    /// Returns the indices of all combatants that escaped from the battle. They are free to move
+    /// on the map again.
+    pub fn fled(&self) -> Vec<usize> {
+        (0..self.combatants.len()).filter(|&index| self.combatants[index].fled).collect()
+    }
+
+    /// Returns the indices of all living enemies of the given combatant
+    pub fn enemies_of(&self, index: usize) -> Vec<usize> {
+        let side = self.combatants[index].side;
+        self.living(|combatant| combatant.side != side)
+    }
+
+    /// Returns the indices of all living allies of the given combatant, including itself
+    pub fn allies_of(&self, index: usize) -> Vec<usize> {
+        let side = self.combatants[index].side;
+        self.living(|combatant| combatant.side == side)
+    }
+
+    fn living<F>(&self, filter: F) -> Vec<usize>
+        where F: Fn(&Combatant) -> bool
+    {
+        self.combatants
+            .iter()
+            .enumerate()
+            .filter(|&(_, combatant)| combatant.is_fighting() && filter(combatant))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the distance between two combatants
+    pub fn distance(&self, from: usize, to: usize) -> u32 {
+        self.combatants[from].distance + self.combatants[to].distance
+    }
+
+    /// Returns `true` if the attacker can reach the target
+    pub fn in_range(&self, attacker: usize, target: usize) -> bool {
+        self.distance(attacker, target) <= self.combatants[attacker].range
+    }
+
+    /// Returns the nearest living enemy of the given combatant
+    pub fn nearest_enemy(&self, index: usize) -> Option<usize> {
+        self.enemies_of(index).into_iter().min_by_key(|&enemy| self.distance(index, enemy))
+    }
+
+    /// Returns the side that won the battle, or `None` if both sides are still standing. A side
+    /// only wins if all of its enemies were defeated, not if some of them fled.
+    pub fn winner(&self) -> Option<Side> {
+        let player_standing = self.is_standing(Side::Player);
+        let enemy_standing = self.is_standing(Side::Enemy);
+
+        match (player_standing, enemy_standing) {
+            (true, false) if self.is_defeated(Side::Enemy) => Some(Side::Player),
+            (false, true) if self.is_defeated(Side::Player) => Some(Side::Enemy),
+            _ => None,
+        }
+    }
+
+    fn is_standing(&self, side: Side) -> bool {
+        self.combatants.iter().any(|combatant| combatant.side == side && combatant.is_fighting())
+    }
+
+    fn is_defeated(&self, side: Side) -> bool {
+        self.combatants.iter().all(|combatant| {
+            combatant.side != side || !combatant.is_alive() || combatant.has_vanished()
+        })
+    }
+
+    /// Returns `true` if one of the sides has no combatants left
+    pub fn is_over(&self) -> bool {
+        !self.is_standing(Side::Player) || !self.is_standing(Side::Enemy)
+    }
+
+    /// Returns the living enemy with the highest `Attribute::Dexterity`
+    pub fn fastest_enemy(&self, index: usize) -> Option<usize> {
+        self.enemies_of(index)
+            .into_iter()
+            .max_by_key(|&enemy| self.combatants[enemy].get_attribute_value(&Attribute::Dexterity))
+    }
+
+    /// Returns the chance in percent that the given combatant escapes, based on its
+    /// `Attribute::Dexterity` and `Attribute::Luck` compared to those of the fastest enemy
+    pub fn flee_chance(&self, index: usize) -> AttributeValue {
+        let agility = |combatant: &Combatant| {
+            combatant.get_attribute_value(&Attribute::Dexterity) +
+            combatant.get_attribute_value(&Attribute::Luck)
+        };
+
+        let pursuer = match self.fastest_enemy(index) {
+            Some(pursuer) => pursuer,
+            None => return 100,
+        };
+
+        let chance = FLEE_BASE_CHANCE +
+                     (agility(&self.combatants[index]) - agility(&self.combatants[pursuer])) *
+                     FLEE_CHANCE_PER_POINT;
+
+        let (lowest, highest) = FLEE_CHANCE_BOUNDS;
+        if chance < lowest {
+            lowest
+        } else if chance > highest {
+            highest
+        } else {
+            chance
+        }
+    }
+
+    /// Lets the given combatant try to escape from the battle. On success the fastest enemy gets
+    /// a parting hit if it can reach the combatant. Returns `true` if the combatant escaped.
+    pub fn flee<R: Rng>(&mut self, actor: usize, rng: &mut R) -> bool {
+        if !self.combatants[actor].is_fighting() {
+            return false;
+        }
+
+        let roll = rng.gen_range(0, 100);
+        if roll >= self.flee_chance(actor) {
+            self.log.push(CombatLogEntry::new(self.round, actor, Action::Flee, Outcome::Caught)
+                .roll(roll));
+            return false;
+        }
+
+        let parting_damage = match self.fastest_enemy(actor) {
+            Some(pursuer) if self.in_range(pursuer, actor) => {
+                self.roll_damage(pursuer, actor, rng)
+            }
+            _ => 0,
+        };
+
+        let combatant = &mut self.combatants[actor];
+        combatant.health = combatant.health.saturating_sub(parting_damage);
+        combatant.fled = true;
+
+        self.log.push(CombatLogEntry::new(self.round, actor, Action::Flee, Outcome::Fled)
+            .roll(roll)
+            .damage(parting_damage));
+        true
+    }
+
+    /// Returns the damage an attack of the attacker would deal to the target without rolling the
+    /// damage dice of its weapons
+    pub fn damage(&self, attacker: usize, target: usize) -> Health {
+        self.reduced_damage(self.combatants[attacker].attack_damage, target)
+    }
+
+    /// Rolls the damage an attack of the attacker deals to the target, including the damage dice
+    /// of its weapons
+    pub fn roll_damage<R: Rng>(&self, attacker: usize, target: usize, rng: &mut R) -> Health {
+        let attacker = &self.combatants[attacker];
+        let rolled: AttributeValue = attacker.weapon_dice.iter().map(|dice| dice.roll(rng)).sum();
+        let rolled = ((rolled as f64) * attacker.wound_factor) as AttributeValue;
+
+        self.reduced_damage(attacker.attack_damage + rolled, target)
+    }
+
+    /// Reduces the damage of an attack by the defense of the target
+    fn reduced_damage(&self, attack: AttributeValue, target: usize) -> Health {
+        let defense = self.combatants[target].get_attribute_value(&Attribute::Defense);
+
+        let damage = attack - defense / DEFENSE_POINTS_PER_REDUCTION;
+
+        if damage < 1 { 1 } else { damage as Health }
+    }
+
+    /// Lets the combatant with the given index execute the given action. Defeated or fled
+    /// combatants and attacks out of range do nothing. The damage is rolled with the given rng.
+    /// Returns an error if the actor or the target of the action is not part of the battle.
+    pub fn execute<R: Rng>(&mut self,
+                           actor: usize,
+                           action: Action,
+                           rng: &mut R)
+                           -> Result<(), ActionError> {
+        if actor >= self.combatants.len() {
+            return Err(ActionError::InvalidActor);
+        }
+
+        match action {
+            Action::Attack(target) |
+            Action::Heal(target) |
+            Action::Help(target) |
+            Action::UseItem(_, target) if target >= self.combatants.len() => {
+                return Err(ActionError::InvalidTarget)
+            }
+            _ => {}
+        }
+
+        if !self.combatants[actor].is_fighting() {
+            return Ok(());
+        }
+
+        // A guard only lasts until the next turn of the guarding combatant
+        self.guarding[actor] = false;
+        let entry = CombatLogEntry::new(self.round, actor, action, Outcome::Waited);
+
+        let entry = match action {
+            Action::Attack(target) => {
+                if !self.combatants[target].is_fighting() || !self.in_range(actor, target) {
+                    CombatLogEntry { outcome: Outcome::Missed, ..entry }
+                } else if self.combatants[target].is_immune(self.round) {
+                    CombatLogEntry { outcome: Outcome::Immune, ..entry }
+                } else {
+                    let mut damage = self.roll_damage(actor, target, rng);
+                    if self.helped[actor] {
+                        self.helped[actor] = false;
+                        damage += HELP_DAMAGE_BONUS;
+                    }
+                    if self.guarding[target] {
+                        damage = if damage / GUARD_DAMAGE_DIVISOR < 1 {
+                            1
+                        } else {
+                            damage / GUARD_DAMAGE_DIVISOR
+                        };
+                    }
+                    let round = self.round;
+                    self.threat[target].add(actor, (damage as Threat) * THREAT_PER_DAMAGE);
+
+                    let combatant = &mut self.combatants[target];
+                    combatant.health = combatant.health.saturating_sub(damage);
+
+                    let outcome = match combatant.advance_phase(round) {
+                        Some(phase) => Outcome::PhaseChanged(phase),
+                        None => Outcome::Hit,
+                    };
+
+                    CombatLogEntry { outcome, ..entry }.damage(damage)
+                }
+            }
+            Action::Heal(target) => {
+                let amount = self.combatants[actor].get_attribute_value(&Attribute::Wisdom) /
+                             WISDOM_POINTS_PER_HEAL;
+                let amount = if amount < 1 { 1 } else { amount as Health };
+
+                let healed = {
+                    let target = &mut self.combatants[target];
+                    if target.is_fighting() {
+                        let before = target.health;
+                        target.health = if target.health + amount > target.max_health {
+                            target.max_health
+                        } else {
+                            target.health + amount
+                        };
+                        Some(target.health - before)
+                    } else {
+                        None
+                    }
+                };
+
+                match healed {
+                    Some(healed) => {
+                        self.add_threat(actor, (healed as Threat) * THREAT_PER_HEAL);
+                        CombatLogEntry { outcome: Outcome::Healed(healed), ..entry }
+                    }
+                    None => CombatLogEntry { outcome: Outcome::Missed, ..entry },
+                }
+            }
+            Action::Advance => {
+                let combatant = &mut self.combatants[actor];
+                combatant.distance = combatant.distance.saturating_sub(1);
+                CombatLogEntry { outcome: Outcome::Moved, ..entry }
+            }
+            Action::Retreat => {
+                self.combatants[actor].distance += 1;
+                CombatLogEntry { outcome: Outcome::Moved, ..entry }
+            }
+            Action::Flee => {
+                // Fleeing writes its own entry, including the roll
+                self.flee(actor, rng);
+                return Ok(());
+            }
+            Action::Wait => entry,
+            Action::Taunt => {
+                for enemy in self.enemies_of(actor) {
+                    self.threat[enemy].add(actor, THREAT_PER_TAUNT);
+                }
+                CombatLogEntry { outcome: Outcome::Taunted, ..entry }
+            }
+            Action::Guard => {
+                self.guarding[actor] = true;
+                CombatLogEntry { outcome: Outcome::Guarded, ..entry }
+            }
+            Action::Help(ally) => {
+                if ally == actor || !self.allies_of(actor).contains(&ally) {
+                    CombatLogEntry { outcome: Outcome::Missed, ..entry }
+                } else {
+                    self.helped[ally] = true;
+                    CombatLogEntry { outcome: Outcome::Helped, ..entry }
+                }
+            }
+            Action::UseItem(item, target) => {
+                let usable = self.combatants[target].is_fighting() &&
+                             self.combatants[actor]
+                    .items
+                    .get(item)
+                    .is_some_and(|item| item.item_type.is_consumable());
+
+                if usable {
+                    let item = self.combatants[actor].items.remove(item);
+                    let outcome = self.combatants[target].apply_item(&item);
+                    self.combatants[actor].used_items.push(item);
+                    CombatLogEntry { outcome, ..entry }
+                } else {
+                    CombatLogEntry { outcome: Outcome::Missed, ..entry }
+                }
+            }
+        };
+
+        self.log.push(entry);
+        Ok(())
+    }
+
+    /// Returns the action a command of the player stands for, e.g. `attack goblin`, `guard`,
+    /// `help wolf` or `use potion on hero`. Combatants and items are referred to by their names,
+    /// ignoring case; `use` without a target uses the item on the actor itself.
+    pub fn parse_command(&self, actor: usize, line: &str) -> Option<Action> {
+        let line = line.trim().to_lowercase();
+        let (command, rest) = match line.find(' ') {
+            Some(index) => (&line[..index], line[index + 1..].trim()),
+            None => (&line[..], ""),
+        };
+        let combatant = |name: &str| {
+            self.combatants.iter().position(|combatant| {
+                combatant.is_fighting() && combatant.name.to_lowercase() == name
+            })
+        };
+
+        match (command, rest) {
+            ("attack", target) => combatant(target).map(Action::Attack),
+            ("heal", target) => combatant(target).map(Action::Heal),
+            ("help", ally) => combatant(ally).map(Action::Help),
+            ("use", rest) => {
+                let (item, target) = match rest.find(" on ") {
+                    Some(index) => (&rest[..index], combatant(rest[index + 4..].trim())?),
+                    None => (rest, actor),
+                };
+                self.combatants[actor]
+                    .items
+                    .iter()
+                    .position(|candidate| candidate.name.to_lowercase() == item)
+                    .map(|item| Action::UseItem(item, target))
+            }
+            ("advance", "") => Some(Action::Advance),
+            ("retreat", "") => Some(Action::Retreat),
+            ("flee", "") => Some(Action::Flee),
+            ("wait", "") => Some(Action::Wait),
+            ("taunt", "") => Some(Action::Taunt),
+            ("guard", "") => Some(Action::Guard),
+            _ => None,
+        }
+    }
+
+    /// Asks the AI of the given combatant for its next action. Returns `None` for combatants
+    /// controlled by the player
+    pub fn decide(&self, index: usize) -> Option<Action> {
+        self.combatants[index].get_ai().map(|ai| ai.decide(index, self))
+    }
+
+    /// Lets every living, AI controlled combatant take its turn and finishes the round
+    pub fn run_ai_turns<R: Rng>(&mut self, rng: &mut R) {
+        for index in 0..self.combatants.len() {
+            if self.is_over() {
+                break;
+            }
+
+            if let Some(action) = self.decide(index) {
+                // The AI only decides on combatants of the battle
+                let _ = self.execute(index, action, rng);
+            }
+        }
+
+        self.expire_summons();
+        self.round += 1;
+    }
+
+    /// Counts down the lifespans of summoned combatants, letting those whose lifespan ran out
+    /// vanish
+    fn expire_summons(&mut self) {
+        for index in 0..self.combatants.len() {
+            if !self.combatants[index].is_fighting() {
+                continue;
+            }
+
+            let vanished = match self.combatants[index].lifespan {
+                Some(ref mut rounds) => {
+                    *rounds = rounds.saturating_sub(1);
+                    *rounds == 0
+                }
+                None => false,
+            };
+            if vanished {
+                let entry = CombatLogEntry::new(self.round, index, Action::Wait, Outcome::Vanished);
+                self.log.push(entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Attribute;
+    use combat::{CombatLogEntry, Combatant, Narrator, Outcome, Side};
+    use dice::Dice;
+    use item::{ItemInfluence, ItemType};
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn skirmish() -> Battle {
+        let mut battle = Battle::new();
+        battle.add_combatant(Combatant::new("Hero", Side::Player, 30).attack_damage(10));
+        battle.add_combatant(Combatant::new("Goblin", Side::Enemy, 10)
+            .attack_damage(4)
+            .distance(2));
+        battle
+    }
+
+    #[test]
+    fn attack_in_range() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+        assert_eq!(battle.combatant(1).health, 10);
+
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+
+        // The default defense of 15 reduces the damage by 3
+        assert_eq!(battle.combatant(1).health, 3);
+    }
+
+    #[test]
+    fn heal() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+        battle.combatant_mut(0).health = 10;
+
+        battle.execute(0, Action::Heal(0), &mut rng).unwrap();
+
+        assert_eq!(battle.combatant(0).health, 15);
+    }
+
+    #[test]
+    fn threat() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+        let cleric = battle.add_combatant(Combatant::new("Cleric", Side::Player, 30));
+        battle.combatant_mut(1).distance = 0;
+
+        assert_eq!(battle.preferred_target(1), Some(0));
+
+        battle.combatant_mut(0).health = 20;
+        battle.execute(cleric, Action::Heal(0), &mut rng).unwrap();
+
+        assert_eq!(battle.threat_table(1).get(cleric), 5);
+        assert_eq!(battle.preferred_target(1), Some(cleric));
+
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+
+        assert_eq!(battle.threat_table(1).get(0), 14);
+        assert_eq!(battle.preferred_target(1), Some(0));
+    }
+
+    #[test]
+    fn flee() {
+        let mut battle = skirmish();
+        battle.combatant_mut(0).attributes.insert(Attribute::Dexterity, 100);
+        assert_eq!(battle.flee_chance(0), 95);
+        assert_eq!(battle.flee_chance(1), 5);
+
+        battle.combatant_mut(0).attributes.insert(Attribute::Dexterity, 15);
+        assert_eq!(battle.flee_chance(0), 60);
+
+        battle.combatant_mut(1).distance = 0;
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert!(battle.flee(0, &mut rng));
+
+        assert_eq!(battle.fled(), vec![0]);
+        assert_eq!(battle.combatant(0).health, 29);
+        assert!(battle.is_over());
+        assert_eq!(battle.winner(), None);
+        let entry = battle.log().last().unwrap();
+        assert_eq!(entry.outcome, Outcome::Fled);
+        assert_eq!(entry.damage, 1);
+    }
+
+    #[test]
+    fn invalid_target() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+
+        assert_eq!(battle.execute(0, Action::Attack(2), &mut rng),
+                   Err(ActionError::InvalidTarget));
+        assert_eq!(battle.execute(0, Action::Heal(5), &mut rng),
+                   Err(ActionError::InvalidTarget));
+        assert_eq!(battle.execute(0, Action::Help(2), &mut rng),
+                   Err(ActionError::InvalidTarget));
+        assert_eq!(battle.execute(0, Action::UseItem(0, 3), &mut rng),
+                   Err(ActionError::InvalidTarget));
+        assert_eq!(battle.execute(2, Action::Attack(0), &mut rng),
+                   Err(ActionError::InvalidActor));
+        assert_eq!(battle.execute(7, Action::Wait, &mut rng), Err(ActionError::InvalidActor));
+        assert!(battle.log().is_empty());
+    }
+
+    #[test]
+    fn flee_is_seeded() {
+        let flee = |seed| {
+            let mut battle = skirmish();
+            battle.combatant_mut(0).attributes.insert(Attribute::Dexterity, 15);
+            battle.combatant_mut(1).distance = 0;
+            let mut rng = XorShiftRng::from_seed(seed);
+            for _ in 0..5 {
+                battle.execute(0, Action::Flee, &mut rng).unwrap();
+            }
+            battle.log().iter().map(|entry| entry.roll).collect::<Vec<_>>()
+        };
+
+        assert_eq!(flee([1, 2, 3, 4]), flee([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn weapon_dice() {
+        let mut battle = Battle::new();
+        battle.add_combatant(Combatant::new("Hero", Side::Player, 30)
+            .attack_damage(10)
+            .weapon_dice(Dice::new(1, 6).modifier(2)));
+        battle.add_combatant(Combatant::new("Orc", Side::Enemy, 100));
+
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert_eq!(battle.roll_damage(0, 1, &mut rng) - battle.damage(0, 1), 6);
+    }
+
+    #[test]
+    fn combat_log() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+
+        assert_eq!(battle.log().len(), 4);
+        assert_eq!(battle.log()[3],
+                   CombatLogEntry::new(0, 0, Action::Attack(1), Outcome::Hit).damage(7));
+        assert_eq!(battle.narrate(&Narrator),
+                   vec!["Hero misses Goblin.",
+                        "Goblin advances.",
+                        "Goblin advances.",
+                        "Hero hits Goblin for 7 damage."]);
+    }
+
+    #[test]
+    fn status() {
+        let mut battle = skirmish();
+        battle.combatant_mut(1).health = 3;
+
+        let bar = Bar::health().chars('#', '-').color(None);
+        assert_eq!(battle.status(&bar),
+                   vec!["Hero   [##########] 30/30 HP", "Goblin [###-------] 3/10 HP"]);
+    }
+
+    #[test]
+    fn winner() {
+        let mut battle = skirmish();
+        assert_eq!(battle.winner(), None);
+        assert_eq!(battle.nearest_enemy(0), Some(1));
+
+        battle.combatant_mut(1).health = 0;
+
+        assert_eq!(battle.winner(), Some(Side::Player));
+        assert!(battle.is_over());
+        assert_eq!(battle.nearest_enemy(0), None);
+    }
+
+    #[test]
+    fn taunt_and_guard() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+        let squire = battle.add_combatant(Combatant::new("Squire", Side::Player, 20));
+        battle.add_threat(0, 10);
+
+        battle.execute(squire, Action::Taunt, &mut rng).unwrap();
+        assert_eq!(battle.preferred_target(1), Some(squire));
+
+        battle.execute(squire, Action::Guard, &mut rng).unwrap();
+        assert!(battle.is_guarding(squire));
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.execute(1, Action::Advance, &mut rng).unwrap();
+        battle.combatant_mut(1).attack_damage = 10;
+        let damage = battle.damage(1, squire);
+        battle.execute(1, Action::Attack(squire), &mut rng).unwrap();
+        assert_eq!(battle.combatant(squire).health, 20 - damage / 2);
+
+        battle.execute(squire, Action::Wait, &mut rng).unwrap();
+        assert!(!battle.is_guarding(squire));
+    }
+
+    #[test]
+    fn help_and_use_item() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+        let potion = ItemGenerator::new()
+            .item_type(ItemType::ConsumablePotion)
+            .influence(Some(ItemInfluence::new(Attribute::Constitution, 8)))
+            .gen();
+        let tonic = ItemGenerator::new()
+            .name("Tonic")
+            .item_type(ItemType::ConsumableDrink)
+            .influence(Some(ItemInfluence::percent(Attribute::Strength, 50)))
+            .gen();
+        let squire = battle.add_combatant(Combatant::new("Squire", Side::Player, 20)
+            .item(potion.clone())
+            .item(tonic));
+
+        assert_eq!(battle.parse_command(squire, "help goblin"), Some(Action::Help(1)));
+        battle.execute(squire, Action::Help(1), &mut rng).unwrap();
+        assert!(!battle.is_helped(1));
+        battle.execute(squire, Action::Help(0), &mut rng).unwrap();
+        assert!(battle.is_helped(0));
+
+        battle.combatant_mut(1).distance = 0;
+        let damage = battle.damage(0, 1);
+        battle.execute(0, Action::Attack(1), &mut rng).unwrap();
+        assert_eq!(battle.log().last().unwrap().damage, damage + 3);
+        assert!(!battle.is_helped(0));
+
+        battle.combatant_mut(0).health = 15;
+        let name = battle.combatant(squire).items[0].name.to_lowercase();
+        let action = battle.parse_command(squire, &format!("use {} on Hero", name));
+        assert_eq!(action, Some(Action::UseItem(0, 0)));
+        battle.execute(squire, action.unwrap(), &mut rng).unwrap();
+        assert_eq!(battle.combatant(0).health, 23);
+        assert_eq!(battle.log().last().unwrap().outcome, Outcome::Healed(8));
+        assert_eq!(battle.combatant(squire).used_items, vec![potion]);
+
+        let strength = battle.combatant(0).get_attribute_value(&Attribute::Strength);
+        let action = battle.parse_command(squire, "use tonic on hero");
+        battle.execute(squire, action.unwrap(), &mut rng).unwrap();
+        assert_eq!(battle.log().last().unwrap().outcome, Outcome::Strengthened);
+        assert_eq!(battle.combatant(0).get_attribute_value(&Attribute::Strength),
+                   strength + strength / 2);
+        assert!(battle.combatant(squire).items.is_empty());
+        assert_eq!(battle.parse_command(squire, "guard"), Some(Action::Guard));
+    }
+
+    #[test]
+    fn summon() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = skirmish();
+        let wolf = battle.summon(0, Combatant::new("Wolf", Side::Enemy, 8).lifespan(2));
+        assert_eq!(battle.combatant(wolf).side, Side::Player);
+        assert_eq!(battle.allies_of(0), vec![0, wolf]);
+
+        battle.run_ai_turns(&mut rng);
+        assert!(battle.combatant(wolf).is_fighting());
+        battle.run_ai_turns(&mut rng);
+        assert!(battle.combatant(wolf).has_vanished());
+        assert_eq!(battle.allies_of(0), vec![0]);
+        assert_eq!(battle.log().last().unwrap().outcome, Outcome::Vanished);
+
+        battle.combatant_mut(0).health = 0;
+        assert_eq!(battle.winner(), Some(Side::Enemy));
+    }
+}
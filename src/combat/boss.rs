@@ -0,0 +1,190 @@
+use item::Item;
+use loot::LootTable;
+use super::AiBehavior;
+use types::AttributeValue;
+
+/// A phase of a boss fight. A phase starts as soon as the health of the boss drops to or below
+/// its threshold.
+#[derive(Clone)]
+pub struct BossPhase {
+    /// The share of health (`0.0` to `1.0`) at which the phase starts
+    pub threshold: f64,
+    /// The AI controlling the boss during the phase
+    pub ai: Box<dyn AiBehavior>,
+    /// The attack damage during the phase. Unchanged if not set
+    pub attack_damage: Option<AttributeValue>,
+    /// The range during the phase. Unchanged if not set
+    pub range: Option<u32>,
+    /// The amount of rounds the boss can not be damaged after the phase started
+    pub immunity_rounds: u32,
+}
+
+impl BossPhase {
+    /// Creates a new instance of `BossPhase`
+    pub fn new<T: AiBehavior + 'static>(threshold: f64, ai: T) -> BossPhase {
+        BossPhase {
+            threshold,
+            ai: Box::new(ai),
+            attack_damage: None,
+            range: None,
+            immunity_rounds: 0,
+        }
+    }
+
+    /// A builder method for setting the attack damage during the phase
+    pub fn attack_damage(mut self, attack_damage: AttributeValue) -> BossPhase {
+        self.attack_damage = Some(attack_damage);
+        self
+    }
+
+    /// A builder method for setting the range during the phase
+    pub fn range(mut self, range: u32) -> BossPhase {
+        self.range = Some(range);
+        self
+    }
+
+    /// A builder method for setting the amount of rounds the boss is immune to damage
+    pub fn immunity_rounds(mut self, immunity_rounds: u32) -> BossPhase {
+        self.immunity_rounds = immunity_rounds;
+        self
+    }
+}
+
+/// The phases and guaranteed loot of a boss monster
+#[derive(Clone)]
+pub struct Boss {
+    phases: Vec<BossPhase>,
+    current_phase: usize,
+    immune_until: u32,
+    /// The loot that is always dropped when the boss is defeated
+    pub loot: LootTable,
+}
+
+impl Default for Boss {
+    fn default() -> Boss {
+        Boss::new()
+    }
+}
+
+impl Boss {
+    /// Creates a new instance of `Boss` without any phases
+    pub fn new() -> Boss {
+        Boss {
+            phases: Vec::new(),
+            current_phase: 0,
+            immune_until: 0,
+            loot: LootTable::new(),
+        }
+    }
+
+    /// A builder method for adding a phase. Phases have to be added in order of descending
+    /// thresholds.
+    pub fn phase(mut self, phase: BossPhase) -> Boss {
+        self.phases.push(phase);
+        self
+    }
+
+    /// A builder method for setting the guaranteed loot
+    pub fn loot(mut self, loot: LootTable) -> Boss {
+        self.loot = loot;
+        self
+    }
+
+    /// Returns the index of the current phase
+    pub fn current_phase(&self) -> usize {
+        self.current_phase
+    }
+
+    /// Returns the phase with the given index
+    pub fn get_phase(&self, index: usize) -> Option<&BossPhase> {
+        self.phases.get(index)
+    }
+
+    /// Returns `true` if the boss can not be damaged in the given round
+    pub fn is_immune(&self, round: u32) -> bool {
+        round < self.immune_until
+    }
+
+    /// Moves on to the last phase whose threshold has been reached. Returns the index of the new
+    /// phase if it changed.
+    pub fn advance(&mut self, health_ratio: f64, round: u32) -> Option<usize> {
+        let next = (self.current_phase + 1..self.phases.len())
+            .rfind(|&index| health_ratio <= self.phases[index].threshold);
+
+        if let Some(next) = next {
+            self.current_phase = next;
+            self.immune_until = round + self.phases[next].immunity_rounds;
+        }
+
+        next
+    }
+
+    /// Generates every item of the loot table, regardless of its chance
+    pub fn drop_loot(&self) -> Vec<Item> {
+        self.loot.roll_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combat::{Action, AggressiveMelee, Battle, Combatant, Coward, Outcome, Side};
+    use item::ItemType;
+    use loot::{LootEntry, LootTable};
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn dragon() -> Boss {
+        Boss::new()
+            .phase(BossPhase::new(1.0, AggressiveMelee))
+            .phase(BossPhase::new(0.5, AggressiveMelee).attack_damage(50).immunity_rounds(2))
+            .phase(BossPhase::new(0.2, Coward))
+            .loot(LootTable::new().entry(LootEntry::new(ItemType::WeaponSword, 0.0)))
+    }
+
+    #[test]
+    fn advance() {
+        let mut boss = dragon();
+
+        assert_eq!(boss.advance(0.8, 0), None);
+        assert_eq!(boss.advance(0.4, 3), Some(1));
+        assert!(boss.is_immune(4));
+        assert!(!boss.is_immune(5));
+        assert_eq!(boss.advance(0.4, 3), None);
+        assert_eq!(boss.advance(0.1, 6), Some(2));
+    }
+
+    #[test]
+    fn skipping_phases() {
+        let mut boss = dragon();
+
+        assert_eq!(boss.advance(0.1, 0), Some(2));
+        assert!(!boss.is_immune(0));
+    }
+
+    #[test]
+    fn guaranteed_loot() {
+        assert_eq!(dragon().drop_loot().len(), 1);
+    }
+
+    #[test]
+    fn boss_battle() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = Battle::new();
+        battle.add_combatant(Combatant::new("Hero", Side::Player, 30).attack_damage(63));
+        let dragon =
+            battle.add_combatant(Combatant::new("Dragon", Side::Enemy, 100).boss(dragon()));
+
+        battle.execute(0, Action::Attack(dragon), &mut rng).unwrap();
+
+        assert_eq!(battle.combatant(dragon).health, 40);
+        assert_eq!(battle.combatant(dragon).attack_damage, 50);
+        assert_eq!(battle.log().len(), 1);
+        assert_eq!(battle.log()[0].outcome, Outcome::PhaseChanged(1));
+
+        battle.execute(0, Action::Attack(dragon), &mut rng).unwrap();
+
+        assert_eq!(battle.combatant(dragon).health, 40);
+        assert_eq!(battle.log()[1].action, Action::Attack(dragon));
+        assert_eq!(battle.log()[1].outcome, Outcome::Immune);
+    }
+}
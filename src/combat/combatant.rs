@@ -0,0 +1,432 @@
+use character::{Attribute, Character};
+use dice::Dice;
+use entity::Entity;
+use inventory::Inventory;
+use item::{Item, ItemCategory};
+use loot::LootTable;
+use rand::Rng;
+use std::collections::HashMap;
+use super::{AiBehavior, Boss, BossPhase, Outcome};
+use template::Pronouns;
+use types::{AttributeValue, Health};
+
+/// The prefix of the name of a corpse, e.g. `Corpse of Goblin`
+pub const CORPSE_PREFIX: &str = "Corpse of ";
+
+/// The side a combatant is fighting on
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Side {
+    /// The player and their allies
+    Player,
+    /// The enemies of the player
+    Enemy,
+}
+
+/// A participant of a battle
+#[derive(Clone)]
+pub struct Combatant {
+    /// The name of the combatant
+    pub name: String,
+    /// The pronouns narration uses for the combatant
+    pub pronouns: Pronouns,
+    /// The side the combatant is fighting on
+    pub side: Side,
+    /// The current health
+    pub health: Health,
+    /// The maximum health
+    pub max_health: Health,
+    /// The attributes of the combatant
+    pub attributes: HashMap<Attribute, AttributeValue>,
+    /// The damage of a single attack
+    pub attack_damage: AttributeValue,
+    /// The damage dice of the wielded weapons, rolled per attack on top of the `attack_damage`
+    pub weapon_dice: Vec<Dice>,
+    /// The factor the rolled damage dice are scaled with, reduced by wounds
+    pub wound_factor: f64,
+    /// The maximum distance at which the combatant can attack
+    pub range: u32,
+    /// The distance of the combatant to the front line
+    pub distance: u32,
+    /// `true` if the combatant escaped from the battle
+    pub fled: bool,
+    /// The items the combatant wears and wields, left in its corpse
+    pub equipment: Vec<Item>,
+    /// The items the combatant may drop when it dies
+    pub loot: LootTable,
+    /// The consumables the combatant can use during the battle
+    pub items: Vec<Item>,
+    /// The consumables the combatant used up during the battle, see `remove_used_items`
+    pub used_items: Vec<Item>,
+    /// The rounds a summoned combatant has left before it vanishes (optional)
+    pub lifespan: Option<u32>,
+    ai: Option<Box<dyn AiBehavior>>,
+    boss: Option<Boss>,
+}
+
+impl Combatant {
+    /// Creates a new instance of `Combatant` with the default character attributes, referred to
+    /// as `Pronouns::It`
+    pub fn new(name: &str, side: Side, health: Health) -> Combatant {
+        Combatant {
+            name: name.to_owned(),
+            pronouns: Pronouns::It,
+            side,
+            health,
+            max_health: health,
+            attributes: Character::default_attributes(),
+            attack_damage: 1,
+            weapon_dice: Vec::new(),
+            wound_factor: 1.0,
+            range: 0,
+            distance: 0,
+            fled: false,
+            equipment: Vec::new(),
+            loot: LootTable::new(),
+            items: Vec::new(),
+            used_items: Vec::new(),
+            lifespan: None,
+            ai: None,
+            boss: None,
+        }
+    }
+
+    /// Creates a new combatant from a character. The character's `Attribute::Constitution`
+    /// determines the maximum health, the consumables of its inventory can be used during the
+    /// battle.
+    pub fn from_character(character: &Character, side: Side) -> Combatant {
+        let mut attributes = HashMap::new();
+        for attribute in Character::default_attributes().keys() {
+            attributes.insert(attribute.clone(), character.get_attribute_value(attribute));
+        }
+
+        let items = character.inventory()
+            .stacks_in(ItemCategory::Consumable)
+            .into_iter()
+            .flat_map(|(item, amount)| vec![item.clone(); amount])
+            .collect();
+
+        Combatant {
+            name: character.name().to_owned(),
+            pronouns: character.pronouns(),
+            side,
+            health: character.health(),
+            max_health: character.max_health(),
+            attributes,
+            attack_damage: character.attack_damage(),
+            weapon_dice: character.weapon_dice(),
+            wound_factor: character.wound_factor(),
+            range: character.attack_range(),
+            distance: 0,
+            fled: false,
+            equipment: character.equipped_items().into_iter().cloned().collect(),
+            loot: LootTable::new(),
+            items,
+            used_items: Vec::new(),
+            lifespan: None,
+            ai: None,
+            boss: None,
+        }
+    }
+
+    /// A builder method for setting the attributes
+    pub fn attributes(mut self, attributes: HashMap<Attribute, AttributeValue>) -> Combatant {
+        self.attributes = attributes;
+        self
+    }
+
+    /// A builder method for setting the pronouns
+    pub fn pronouns(mut self, pronouns: Pronouns) -> Combatant {
+        self.pronouns = pronouns;
+        self
+    }
+
+    /// A builder method for setting the attack damage
+    pub fn attack_damage(mut self, attack_damage: AttributeValue) -> Combatant {
+        self.attack_damage = attack_damage;
+        self
+    }
+
+    /// A builder method for adding the damage dice of a weapon
+    pub fn weapon_dice(mut self, dice: Dice) -> Combatant {
+        self.weapon_dice.push(dice);
+        self
+    }
+
+    /// A builder method for setting the loot table
+    pub fn loot(mut self, loot: LootTable) -> Combatant {
+        self.loot = loot;
+        self
+    }
+
+    /// Creates the corpse of the combatant: a container named like `Corpse of Goblin` holding
+    /// the rolled loot, the guaranteed loot of a boss and the equipment
+    pub fn corpse<R: Rng>(&self, rng: &mut R) -> Entity {
+        let mut items = self.loot.roll(rng);
+        if let Some(ref boss) = self.boss {
+            items.extend(boss.drop_loot());
+        }
+        items.extend(self.equipment.iter().cloned());
+
+        Entity::new(&format!("{}{}", CORPSE_PREFIX, self.name)).container(items)
+    }
+
+    /// A builder method for setting the range. A range of `0` means melee only
+    pub fn range(mut self, range: u32) -> Combatant {
+        self.range = range;
+        self
+    }
+
+    /// A builder method for adding a consumable the combatant can use during the battle
+    pub fn item(mut self, item: Item) -> Combatant {
+        self.items.push(item);
+        self
+    }
+
+    /// Applies the influences of a consumable to the combatant. An influence on
+    /// `Attribute::Constitution` heals it, all others change its attributes for the rest of the
+    /// battle.
+    pub fn apply_item(&mut self, item: &Item) -> Outcome {
+        let mut heals = item.influences().is_empty();
+        let mut healed = 0;
+
+        for influence in item.influences() {
+            if influence.attribute == Attribute::Constitution {
+                let amount = influence.modifier(self.max_health as AttributeValue).max(0);
+                let health = (self.health + amount as Health).min(self.max_health);
+                healed += health - self.health;
+                self.health = health;
+                heals = true;
+            } else {
+                let value = self.get_attribute_value(&influence.attribute);
+                self.attributes.insert(influence.attribute.clone(),
+                                       value + influence.modifier(value));
+            }
+        }
+
+        if heals {
+            Outcome::Healed(healed)
+        } else {
+            Outcome::Strengthened
+        }
+    }
+
+    /// Takes the consumables used up during the battle out of the inventory they came from, e.g.
+    /// the inventory of the character the combatant was created from
+    pub fn remove_used_items(&self, inventory: &mut Inventory) {
+        for item in &self.used_items {
+            inventory.take_by_id(&item.id);
+        }
+    }
+
+    /// A builder method for letting a summoned combatant vanish after the given amount of rounds
+    pub fn lifespan(mut self, rounds: u32) -> Combatant {
+        self.lifespan = Some(rounds);
+        self
+    }
+
+    /// A builder method for setting the distance to the front line
+    pub fn distance(mut self, distance: u32) -> Combatant {
+        self.distance = distance;
+        self
+    }
+
+    /// A builder method for letting an AI control the combatant
+    pub fn ai<T: AiBehavior + 'static>(mut self, ai: T) -> Combatant {
+        self.ai = Some(Box::new(ai));
+        self
+    }
+
+    /// A builder method for turning the combatant into a boss. The first phase of the boss takes
+    /// effect immediately
+    pub fn boss(mut self, boss: Boss) -> Combatant {
+        if let Some(phase) = boss.get_phase(0) {
+            self.apply_phase(phase.clone());
+        }
+
+        self.boss = Some(boss);
+        self
+    }
+
+    /// Returns the boss data of the combatant (optional)
+    pub fn get_boss(&self) -> Option<&Boss> {
+        self.boss.as_ref()
+    }
+
+    /// Returns `true` if the combatant can not be damaged in the given round
+    pub fn is_immune(&self, round: u32) -> bool {
+        self.boss.as_ref().is_some_and(|boss| boss.is_immune(round))
+    }
+
+    /// Moves a boss to the phase matching its health. Returns the index of the new phase if it
+    /// changed
+    pub fn advance_phase(&mut self, round: u32) -> Option<usize> {
+        let health_ratio = self.health_ratio();
+
+        let phase = match self.boss {
+            Some(ref mut boss) => {
+                boss.advance(health_ratio, round)
+                    .and_then(|index| boss.get_phase(index).cloned().map(|phase| (index, phase)))
+            }
+            None => None,
+        };
+
+        phase.map(|(index, phase)| {
+            self.apply_phase(phase);
+            index
+        })
+    }
+
+    fn apply_phase(&mut self, phase: BossPhase) {
+        self.ai = Some(phase.ai);
+
+        if let Some(attack_damage) = phase.attack_damage {
+            self.attack_damage = attack_damage;
+        }
+
+        if let Some(range) = phase.range {
+            self.range = range;
+        }
+    }
+
+    /// Returns the AI controlling the combatant. Combatants without an AI are controlled by the
+    /// player
+    pub fn get_ai(&self) -> Option<&dyn AiBehavior> {
+        self.ai.as_ref().map(|ai| ai.as_ref())
+    }
+
+    /// Replaces the AI controlling the combatant
+    pub fn set_ai(&mut self, ai: Option<Box<dyn AiBehavior>>) {
+        self.ai = ai;
+    }
+
+    /// Returns the value of the specified attribute. Missing attributes count as `0`
+    pub fn get_attribute_value(&self, attribute: &Attribute) -> AttributeValue {
+        *self.attributes.get(attribute).unwrap_or(&0)
+    }
+
+    /// Returns `true` if the combatant is still standing
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    /// Returns `true` if the lifespan of a summoned combatant ran out
+    pub fn has_vanished(&self) -> bool {
+        self.lifespan == Some(0)
+    }
+
+    /// Returns `true` if the combatant is still standing, did not flee and did not vanish
+    pub fn is_fighting(&self) -> bool {
+        self.is_alive() && !self.fled && !self.has_vanished()
+    }
+
+    /// Returns the health in relation to the maximum health (`0.0` to `1.0`)
+    pub fn health_ratio(&self) -> f64 {
+        if self.max_health == 0 {
+            0.0
+        } else {
+            (self.health as f64) / (self.max_health as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use injury::BodyPart;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use loot::{LootEntry, LootTable};
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn from_character() {
+        let character = Character::new("Wil Wheaton");
+        let combatant = Combatant::from_character(&character, Side::Player);
+
+        assert_eq!(combatant.name, "Wil Wheaton");
+        assert_eq!(combatant.health, character.health());
+        assert_eq!(combatant.attack_damage, character.attack_damage());
+        assert_eq!(combatant.get_attribute_value(&Attribute::Luck),
+                   character.get_attribute_value(&Attribute::Luck));
+        assert!(combatant.get_ai().is_none());
+    }
+
+    #[test]
+    fn used_items() {
+        let mut character = Character::new("Sam");
+        let bread = ItemGenerator::new()
+            .name("Lembas")
+            .item_type(ItemType::ConsumableFood)
+            .stack_size(5)
+            .gen();
+        character.inventory_mut().add_item(bread.clone()).unwrap();
+        character.inventory_mut().add_item(bread.clone()).unwrap();
+
+        let mut combatant = Combatant::from_character(&character, Side::Player);
+        assert_eq!(combatant.items, vec![bread.clone(), bread]);
+
+        let eaten = combatant.items.remove(0);
+        combatant.used_items.push(eaten);
+        combatant.remove_used_items(character.inventory_mut());
+        assert_eq!(character.inventory().count_by_name("Lembas"), 1);
+    }
+
+    #[test]
+    fn items_of_full_stacks() {
+        let mut character = Character::new("Pippin");
+        let apple = ItemGenerator::new()
+            .name("Apple")
+            .item_type(ItemType::ConsumableFood)
+            .stack_size(2)
+            .gen();
+        for _ in 0..3 {
+            character.inventory_mut().add_item(apple.clone()).unwrap();
+        }
+
+        let combatant = Combatant::from_character(&character, Side::Player);
+        assert_eq!(combatant.items, vec![apple.clone(), apple.clone(), apple]);
+    }
+
+    #[test]
+    fn from_wounded_character() {
+        let mut character = Character::new("Jaime");
+        character.injure(BodyPart::Arm);
+        let combatant = Combatant::from_character(&character, Side::Player);
+
+        assert_eq!(combatant.wound_factor, character.wound_factor());
+        assert!(combatant.wound_factor < 1.0);
+    }
+
+    #[test]
+    fn corpse() {
+        let mut character = Character::new("Boromir");
+        let shield = ItemGenerator::new()
+            .name("Shield of Gondor")
+            .item_type(ItemType::ArmorChest)
+            .gen();
+        character.set_armor_slot_chest(Some(shield.clone()));
+
+        let combatant = Combatant::from_character(&character, Side::Player)
+            .loot(LootTable::new().entry(LootEntry::new(ItemType::Usable, 1.0)));
+        let mut corpse = combatant.corpse(&mut XorShiftRng::from_seed([1, 2, 3, 4]));
+
+        assert_eq!(corpse.name(), "Corpse of Boromir");
+        assert_eq!(corpse.contents().map(|items| items.len()), Some(2));
+        assert_eq!(corpse.take_item("Shield of Gondor"), Some(shield));
+        assert_eq!(corpse.take_all().len(), 1);
+        assert_eq!(corpse.contents(), Some(&[][..]));
+    }
+
+    #[test]
+    fn health_ratio() {
+        let mut combatant = Combatant::new("Goblin", Side::Enemy, 20);
+        assert!(combatant.is_alive());
+
+        combatant.health = 5;
+        assert_eq!(combatant.health_ratio(), 0.25);
+
+        combatant.health = 0;
+        assert!(!combatant.is_alive());
+    }
+}
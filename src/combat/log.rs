@@ -0,0 +1,399 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use super::{Action, Combatant};
+use locale::Catalog;
+use template::{Template, TemplateContext};
+use types::Health;
+
+/// The result of an action taken during a battle
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// An attack dealt damage
+    Hit,
+    /// An attack did not reach its target or the target was already down
+    Missed,
+    /// An attack did not hurt an immune combatant
+    Immune,
+    /// An attack pushed a boss into the phase with the given index
+    PhaseChanged(usize),
+    /// The target was healed by the given amount
+    Healed(Health),
+    /// The combatant moved towards or away from the front line
+    Moved,
+    /// The combatant escaped from the battle
+    Fled,
+    /// The combatant tried to flee but was caught
+    Caught,
+    /// The combatant did nothing
+    Waited,
+    /// The lifespan of a summoned combatant ran out and it left the battle
+    Vanished,
+    /// The combatant drew the attention of its enemies
+    Taunted,
+    /// The combatant guards until its next turn
+    Guarded,
+    /// The combatant strengthened the next attack of an ally
+    Helped,
+    /// The target was strengthened by an item
+    Strengthened,
+}
+
+/// A single line of the combat log
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CombatLogEntry {
+    /// The round in which the action was taken
+    pub round: u32,
+    /// The index of the acting combatant
+    pub actor: usize,
+    /// The action taken
+    pub action: Action,
+    /// The dice roll deciding the action (optional)
+    pub roll: Option<i64>,
+    /// The damage dealt, including the parting hit taken while fleeing
+    pub damage: Health,
+    /// The result of the action
+    pub outcome: Outcome,
+}
+
+impl CombatLogEntry {
+    /// Creates a new instance of `CombatLogEntry` without a roll or damage
+    pub fn new(round: u32, actor: usize, action: Action, outcome: Outcome) -> CombatLogEntry {
+        CombatLogEntry {
+            round,
+            actor,
+            action,
+            roll: None,
+            damage: 0,
+            outcome,
+        }
+    }
+
+    /// A builder method for setting the roll
+    pub fn roll(mut self, roll: i64) -> CombatLogEntry {
+        self.roll = Some(roll);
+        self
+    }
+
+    /// A builder method for setting the damage
+    pub fn damage(mut self, damage: Health) -> CombatLogEntry {
+        self.damage = damage;
+        self
+    }
+
+    /// Reads a log entry from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<CombatLogEntry, DataError> {
+        let object = data::as_object(json, "entry")?;
+        let target = || data::unsigned(object, "target").map(|target| target as usize);
+
+        let action = match &data::string(object, "action")?[..] {
+            "Attack" => Action::Attack(target()?),
+            "Heal" => Action::Heal(target()?),
+            "Advance" => Action::Advance,
+            "Retreat" => Action::Retreat,
+            "Flee" => Action::Flee,
+            "Wait" => Action::Wait,
+            "Taunt" => Action::Taunt,
+            "Guard" => Action::Guard,
+            "Help" => Action::Help(target()?),
+            "UseItem" => {
+                Action::UseItem(data::unsigned(object, "item")? as usize, target()?)
+            }
+            _ => return Err(DataError::Invalid("action".to_owned())),
+        };
+
+        let value = || data::unsigned(object, "value").map(|value| value as usize);
+
+        let outcome = match &data::string(object, "outcome")?[..] {
+            "Hit" => Outcome::Hit,
+            "Missed" => Outcome::Missed,
+            "Immune" => Outcome::Immune,
+            "PhaseChanged" => Outcome::PhaseChanged(value()?),
+            "Healed" => Outcome::Healed(value()?),
+            "Moved" => Outcome::Moved,
+            "Fled" => Outcome::Fled,
+            "Caught" => Outcome::Caught,
+            "Waited" => Outcome::Waited,
+            "Vanished" => Outcome::Vanished,
+            "Taunted" => Outcome::Taunted,
+            "Guarded" => Outcome::Guarded,
+            "Helped" => Outcome::Helped,
+            "Strengthened" => Outcome::Strengthened,
+            _ => return Err(DataError::Invalid("outcome".to_owned())),
+        };
+
+        Ok(CombatLogEntry {
+            round: data::unsigned(object, "round")? as u32,
+            actor: data::unsigned(object, "actor")? as usize,
+            action,
+            roll: data::optional(object, "roll", data::signed)?,
+            damage: data::unsigned(object, "damage")? as Health,
+            outcome,
+        })
+    }
+}
+
+impl ToJson for CombatLogEntry {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        let (action, target) = match self.action {
+            Action::Attack(target) => ("Attack", Some(target)),
+            Action::Heal(target) => ("Heal", Some(target)),
+            Action::Advance => ("Advance", None),
+            Action::Retreat => ("Retreat", None),
+            Action::Flee => ("Flee", None),
+            Action::Wait => ("Wait", None),
+            Action::Taunt => ("Taunt", None),
+            Action::Guard => ("Guard", None),
+            Action::Help(ally) => ("Help", Some(ally)),
+            Action::UseItem(item, target) => {
+                object.insert("item".to_owned(), item.to_json());
+                ("UseItem", Some(target))
+            }
+        };
+
+        let (outcome, value) = match self.outcome {
+            Outcome::Hit => ("Hit", None),
+            Outcome::Missed => ("Missed", None),
+            Outcome::Immune => ("Immune", None),
+            Outcome::PhaseChanged(phase) => ("PhaseChanged", Some(phase)),
+            Outcome::Healed(amount) => ("Healed", Some(amount)),
+            Outcome::Moved => ("Moved", None),
+            Outcome::Fled => ("Fled", None),
+            Outcome::Caught => ("Caught", None),
+            Outcome::Waited => ("Waited", None),
+            Outcome::Vanished => ("Vanished", None),
+            Outcome::Taunted => ("Taunted", None),
+            Outcome::Guarded => ("Guarded", None),
+            Outcome::Helped => ("Helped", None),
+            Outcome::Strengthened => ("Strengthened", None),
+        };
+
+        object.insert("round".to_owned(), self.round.to_json());
+        object.insert("actor".to_owned(), self.actor.to_json());
+        object.insert("action".to_owned(), action.to_json());
+        object.insert("target".to_owned(), target.to_json());
+        object.insert("roll".to_owned(), self.roll.to_json());
+        object.insert("damage".to_owned(), self.damage.to_json());
+        object.insert("outcome".to_owned(), outcome.to_json());
+        object.insert("value".to_owned(), value.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// Turns combat log entries into readable text
+pub trait LogFormatter {
+    /// Returns the narration of a single entry. The combatants are those of the battle the entry
+    /// was taken from.
+    fn format(&self, entry: &CombatLogEntry, combatants: &[Combatant]) -> String;
+}
+
+/// A formatter producing short sentences like "Hero hits Goblin for 7 damage."
+#[derive(Clone, Copy, Debug)]
+pub struct Narrator;
+
+impl LogFormatter for Narrator {
+    fn format(&self, entry: &CombatLogEntry, combatants: &[Combatant]) -> String {
+        let name = |index: usize| &combatants[index].name;
+        let actor = name(entry.actor);
+
+        let target = match entry.action {
+            Action::Attack(target) |
+            Action::Heal(target) |
+            Action::Help(target) |
+            Action::UseItem(_, target) => name(target),
+            _ => actor,
+        };
+
+        match entry.outcome {
+            Outcome::Hit => format!("{} hits {} for {} damage.", actor, target, entry.damage),
+            Outcome::Missed => format!("{} misses {}.", actor, target),
+            Outcome::Immune => format!("{} is immune to the attack of {}.", target, actor),
+            Outcome::PhaseChanged(phase) => {
+                format!("{} hits {} for {} damage. {} enters phase {}!",
+                        actor,
+                        target,
+                        entry.damage,
+                        target,
+                        phase + 1)
+            }
+            Outcome::Healed(amount) => format!("{} heals {} by {}.", actor, target, amount),
+            Outcome::Moved if entry.action == Action::Retreat => format!("{} falls back.", actor),
+            Outcome::Moved => format!("{} advances.", actor),
+            Outcome::Fled if entry.damage > 0 => {
+                format!("{} flees, taking {} damage on the way out.", actor, entry.damage)
+            }
+            Outcome::Fled => format!("{} flees.", actor),
+            Outcome::Caught => format!("{} tries to flee but is caught.", actor),
+            Outcome::Waited => format!("{} waits.", actor),
+            Outcome::Vanished => format!("{} vanishes.", actor),
+            Outcome::Taunted => format!("{} taunts the enemies.", actor),
+            Outcome::Guarded => format!("{} raises a guard.", actor),
+            Outcome::Helped => format!("{} helps {}.", actor, target),
+            Outcome::Strengthened => format!("{} strengthens {}.", actor, target),
+        }
+    }
+}
+
+/// A formatter filling in a template per kind of outcome. The templates can refer to the
+/// `{actor}` and `{target}` including their pronouns, e.g. `{target.their}`, the `{damage}`, the
+/// healed `{amount}` and the boss `{phase}`.
+#[derive(Clone, Debug)]
+pub struct TemplateNarrator {
+    templates: HashMap<&'static str, Template>,
+}
+
+impl TemplateNarrator {
+    /// Creates a new instance of `TemplateNarrator` with templates producing the same sentences
+    /// as `Narrator`
+    pub fn new() -> TemplateNarrator {
+        TemplateNarrator::localized(&Catalog::english())
+    }
+
+    /// Creates a new instance of `TemplateNarrator` using the templates of the catalog, which
+    /// are stored under keys like `combat.Hit`
+    pub fn localized(catalog: &Catalog) -> TemplateNarrator {
+        let templates = OUTCOMES.iter()
+            .map(|&outcome| {
+                (outcome, Template::new(catalog.get(&format!("combat.{}", outcome))))
+            })
+            .collect();
+
+        TemplateNarrator { templates }
+    }
+
+    /// A builder method for replacing the template of an outcome: `Hit`, `Missed`, `Immune`,
+    /// `PhaseChanged`, `Healed`, `Advanced`, `Retreated`, `Fled`, `FledHurt` (fled, but took a
+    /// parting hit), `Caught`, `Waited`, `Vanished`, `Taunted`, `Guarded` or `Helped`. Other
+    /// names are ignored.
+    pub fn template(mut self, outcome: &str, template: Template) -> TemplateNarrator {
+        if let Some(inner_template) = self.templates.get_mut(outcome) {
+            *inner_template = template;
+        }
+        self
+    }
+}
+
+/// The names of the outcomes `TemplateNarrator` has a template for
+const OUTCOMES: [&str; 16] = ["Hit",
+                              "Missed",
+                              "Immune",
+                              "PhaseChanged",
+                              "Healed",
+                              "Advanced",
+                              "Retreated",
+                              "Fled",
+                              "FledHurt",
+                              "Caught",
+                              "Waited",
+                              "Vanished",
+                              "Taunted",
+                              "Guarded",
+                              "Helped",
+                              "Strengthened"];
+
+impl Default for TemplateNarrator {
+    fn default() -> TemplateNarrator {
+        TemplateNarrator::new()
+    }
+}
+
+impl LogFormatter for TemplateNarrator {
+    fn format(&self, entry: &CombatLogEntry, combatants: &[Combatant]) -> String {
+        let actor = &combatants[entry.actor];
+        let target = match entry.action {
+            Action::Attack(target) |
+            Action::Heal(target) |
+            Action::Help(target) |
+            Action::UseItem(_, target) => &combatants[target],
+            _ => actor,
+        };
+
+        let mut context = TemplateContext::new()
+            .person("actor", &actor.name, actor.pronouns)
+            .person("target", &target.name, target.pronouns)
+            .value("damage", entry.damage);
+
+        let outcome = match entry.outcome {
+            Outcome::Hit => "Hit",
+            Outcome::Missed => "Missed",
+            Outcome::Immune => "Immune",
+            Outcome::PhaseChanged(phase) => {
+                context = context.value("phase", phase + 1);
+                "PhaseChanged"
+            }
+            Outcome::Healed(amount) => {
+                context = context.value("amount", amount);
+                "Healed"
+            }
+            Outcome::Moved if entry.action == Action::Retreat => "Retreated",
+            Outcome::Moved => "Advanced",
+            Outcome::Fled if entry.damage > 0 => "FledHurt",
+            Outcome::Fled => "Fled",
+            Outcome::Caught => "Caught",
+            Outcome::Waited => "Waited",
+            Outcome::Vanished => "Vanished",
+            Outcome::Taunted => "Taunted",
+            Outcome::Guarded => "Guarded",
+            Outcome::Helped => "Helped",
+            Outcome::Strengthened => "Strengthened",
+        };
+
+        self.templates[outcome].render(&context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combat::{Action, Combatant, Side};
+    use rustc_serialize::json::ToJson;
+    use template::{Pronouns, Template};
+
+    #[test]
+    fn json_round_trip() {
+        let entries = vec![CombatLogEntry::new(3, 1, Action::Attack(0), Outcome::PhaseChanged(2))
+                               .damage(7),
+                           CombatLogEntry::new(0, 0, Action::Flee, Outcome::Caught).roll(87),
+                           CombatLogEntry::new(2, 0, Action::UseItem(1, 2), Outcome::Healed(5))];
+
+        for entry in entries {
+            assert_eq!(CombatLogEntry::from_json(&entry.to_json()).unwrap(), entry);
+        }
+    }
+
+    #[test]
+    fn narrate() {
+        let combatants = vec![Combatant::new("Hero", Side::Player, 30),
+                              Combatant::new("Goblin", Side::Enemy, 10)];
+
+        let hit = CombatLogEntry::new(0, 0, Action::Attack(1), Outcome::Hit).damage(7);
+        assert_eq!(Narrator.format(&hit, &combatants), "Hero hits Goblin for 7 damage.");
+
+        let retreat = CombatLogEntry::new(0, 1, Action::Retreat, Outcome::Moved);
+        assert_eq!(Narrator.format(&retreat, &combatants), "Goblin falls back.");
+    }
+
+    #[test]
+    fn narrate_templates() {
+        let combatants = vec![Combatant::new("Hero", Side::Player, 30).pronouns(Pronouns::She),
+                              Combatant::new("Goblin", Side::Enemy, 10)];
+        let entries = vec![CombatLogEntry::new(0, 0, Action::Attack(1), Outcome::PhaseChanged(1))
+                               .damage(7),
+                           CombatLogEntry::new(1, 0, Action::Flee, Outcome::Fled).damage(2),
+                           CombatLogEntry::new(1, 1, Action::Heal(1), Outcome::Healed(4)),
+                           CombatLogEntry::new(2, 1, Action::Help(0), Outcome::Helped)];
+
+        for entry in &entries {
+            assert_eq!(TemplateNarrator::new().format(entry, &combatants),
+                       Narrator.format(entry, &combatants));
+        }
+
+        let narrator = TemplateNarrator::new()
+            .template("Hit", Template::new("{actor} swings {actor.their} sword at {target}!"));
+        let hit = CombatLogEntry::new(0, 0, Action::Attack(1), Outcome::Hit).damage(7);
+        assert_eq!(narrator.format(&hit, &combatants), "Hero swings her sword at Goblin!");
+    }
+}
@@ -0,0 +1,13 @@
+pub use self::ai::*;
+pub use self::battle::*;
+pub use self::boss::*;
+pub use self::combatant::*;
+pub use self::log::*;
+pub use self::threat::*;
+
+mod ai;
+mod battle;
+mod boss;
+mod combatant;
+mod log;
+mod threat;
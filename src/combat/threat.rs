@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+/// The type used for threat values
+pub type Threat = u64;
+
+/// The threat a combatant feels towards each of its opponents. AI controlled combatants attack
+/// whoever generated the most threat.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ThreatTable {
+    threat: HashMap<usize, Threat>,
+}
+
+impl Default for ThreatTable {
+    fn default() -> ThreatTable {
+        ThreatTable::new()
+    }
+}
+
+impl ThreatTable {
+    /// Creates a new, empty instance of `ThreatTable`
+    pub fn new() -> ThreatTable {
+        ThreatTable { threat: HashMap::new() }
+    }
+
+    /// Adds threat towards the combatant with the given index
+    pub fn add(&mut self, source: usize, amount: Threat) {
+        *self.threat.entry(source).or_insert(0) += amount;
+    }
+
+    /// Returns the threat towards the combatant with the given index
+    pub fn get(&self, source: usize) -> Threat {
+        *self.threat.get(&source).unwrap_or(&0)
+    }
+
+    /// Forgets all threat towards the combatant with the given index
+    pub fn clear(&mut self, source: usize) {
+        self.threat.remove(&source);
+    }
+
+    /// Returns the candidate with the highest threat. Returns `None` if none of the candidates
+    /// generated any threat. Ties are resolved in favor of the earlier candidate.
+    pub fn highest(&self, candidates: &[usize]) -> Option<usize> {
+        let mut highest: Option<(usize, Threat)> = None;
+
+        for &candidate in candidates {
+            let threat = self.get(candidate);
+            if threat == 0 {
+                continue;
+            }
+
+            match highest {
+                Some((_, highest_threat)) if highest_threat >= threat => (),
+                _ => highest = Some((candidate, threat)),
+            }
+        }
+
+        highest.map(|(candidate, _)| candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highest() {
+        let mut table = ThreatTable::new();
+        assert_eq!(table.highest(&[0, 1, 2]), None);
+
+        table.add(1, 10);
+        table.add(2, 10);
+        assert_eq!(table.highest(&[0, 1, 2]), Some(1));
+
+        table.add(2, 1);
+        assert_eq!(table.highest(&[0, 1, 2]), Some(2));
+        assert_eq!(table.highest(&[0, 1]), Some(1));
+
+        table.clear(2);
+        assert_eq!(table.get(2), 0);
+        assert_eq!(table.highest(&[0, 1, 2]), Some(1));
+    }
+}
@@ -0,0 +1,159 @@
+use character::Character;
+use combat::{AiBehavior, Combatant, Side};
+use event::Event;
+use world::two_dimensional::World2d;
+
+/// A non-player character that can be recruited to follow the party and fight on its side
+#[derive(Clone)]
+pub struct Companion {
+    /// The character of the companion, including its own equipment
+    pub character: Character,
+    /// The position of the companion on the map
+    pub position: (usize, usize),
+    /// The distance the companion keeps to the character it follows
+    pub follow_distance: usize,
+    /// What the companion says when joining the party
+    pub join_line: String,
+    /// What the companion says when leaving the party
+    pub leave_line: String,
+    ai: Box<dyn AiBehavior>,
+    recruited: bool,
+}
+
+impl Companion {
+    /// Creates a new instance of `Companion` fighting with the given AI
+    pub fn new<T: AiBehavior + 'static>(character: Character, ai: T) -> Companion {
+        Companion {
+            character,
+            position: (0, 0),
+            follow_distance: 1,
+            join_line: "Lead the way!".to_owned(),
+            leave_line: "Farewell.".to_owned(),
+            ai: Box::new(ai),
+            recruited: false,
+        }
+    }
+
+    /// A builder method for setting the position on the map
+    pub fn position(mut self, position: (usize, usize)) -> Companion {
+        self.position = position;
+        self
+    }
+
+    /// A builder method for setting the lines spoken when joining and leaving
+    pub fn lines(mut self, join_line: &str, leave_line: &str) -> Companion {
+        self.join_line = join_line.to_owned();
+        self.leave_line = leave_line.to_owned();
+        self
+    }
+
+    /// Returns `true` if the companion agreed to join the party
+    pub fn is_recruited(&self) -> bool {
+        self.recruited
+    }
+
+    /// Talks to the companion. `Event::Recruit` and `Event::Dismiss` make the companion join or
+    /// leave, everything else is ignored.
+    pub fn talk(&mut self, event: Event) -> Event {
+        match event {
+            Event::Recruit if !self.recruited => {
+                self.recruited = true;
+                Event::Tell(self.join_line.clone())
+            }
+            Event::Dismiss if self.recruited => {
+                self.recruited = false;
+                Event::Tell(self.leave_line.clone())
+            }
+            _ => Event::Nothing,
+        }
+    }
+
+    /// Moves the companion one step towards the given position, unless it is already within its
+    /// follow distance or the way is blocked
+    pub fn follow(&mut self, target: (usize, usize), world: &World2d) {
+        let (x, y) = self.position;
+        let (target_x, target_y) = target;
+
+        let distance_x = x.abs_diff(target_x);
+        let distance_y = y.abs_diff(target_y);
+
+        if distance_x <= self.follow_distance && distance_y <= self.follow_distance {
+            return;
+        }
+
+        let step = |from: usize, to: usize| if from < to {
+            from + 1
+        } else if from > to {
+            from - 1
+        } else {
+            from
+        };
+
+        let candidates = [(step(x, target_x), step(y, target_y)),
+                          (step(x, target_x), y),
+                          (x, step(y, target_y))];
+
+        for &candidate in &candidates {
+            if candidate != self.position && world.is_passable(candidate) {
+                self.position = candidate;
+                return;
+            }
+        }
+    }
+
+    /// Creates a combatant fighting on the player's side
+    pub fn combatant(&self) -> Combatant {
+        let mut combatant = Combatant::from_character(&self.character, Side::Player);
+        combatant.set_ai(Some(self.ai.clone()));
+        combatant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use combat::AggressiveMelee;
+    use event::Event;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn dog() -> Companion {
+        Companion::new(Character::new("Dogmeat"), AggressiveMelee).lines("Woof!", "Whimper.")
+    }
+
+    #[test]
+    fn recruit_and_dismiss() {
+        let mut companion = dog();
+
+        assert_eq!(companion.talk(Event::Dismiss), Event::Nothing);
+        assert_eq!(companion.talk(Event::Recruit), Event::Tell("Woof!".to_owned()));
+        assert!(companion.is_recruited());
+        assert_eq!(companion.talk(Event::Recruit), Event::Nothing);
+        assert_eq!(companion.talk(Event::Dismiss), Event::Tell("Whimper.".to_owned()));
+        assert!(!companion.is_recruited());
+    }
+
+    #[test]
+    fn follow() {
+        let mut world = World2d::new("Wasteland", (10, 10));
+        let mut companion = dog().position((0, 0));
+
+        companion.follow((1, 1), &world);
+        assert_eq!(companion.position, (0, 0));
+
+        companion.follow((5, 5), &world);
+        assert_eq!(companion.position, (1, 1));
+
+        world.set_field(Field::new(FieldType::StoneWall), (2, 2));
+        companion.follow((5, 5), &world);
+        assert_eq!(companion.position, (2, 1));
+    }
+
+    #[test]
+    fn combatant() {
+        let combatant = dog().combatant();
+
+        assert_eq!(combatant.side, Side::Player);
+        assert!(combatant.get_ai().is_some());
+    }
+}
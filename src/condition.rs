@@ -0,0 +1,185 @@
+use character::Character;
+use rand::Rng;
+use std::str::FromStr;
+use types::Health;
+use world::two_dimensional::FieldType;
+
+/// The name of the consumable that cures poison
+pub const ANTIDOTE: &str = "Antidote";
+
+/// The name of the consumable that cures diseases
+pub const REMEDY: &str = "Remedy";
+
+/// The kind of a damage-over-time condition
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConditionKind {
+    /// Fast and short, e.g. from a snake bite
+    Poison,
+    /// Slow and long, e.g. from drinking swamp water
+    Disease,
+}
+
+impl ConditionKind {
+    /// Returns the name of the consumable that cures this condition
+    pub fn cure(&self) -> &'static str {
+        match *self {
+            ConditionKind::Poison => ANTIDOTE,
+            ConditionKind::Disease => REMEDY,
+        }
+    }
+}
+
+impl FromStr for ConditionKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<ConditionKind, ()> {
+        match s {
+            "Poison" => Ok(ConditionKind::Poison),
+            "Disease" => Ok(ConditionKind::Disease),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A condition dealing damage over time
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Condition {
+    /// The kind of the condition
+    pub kind: ConditionKind,
+    /// The damage dealt every `interval` turns
+    pub damage: Health,
+    /// The amount of turns between two doses of damage
+    pub interval: u64,
+    /// The amount of turns until the condition wears off
+    pub turns_left: u64,
+}
+
+impl Condition {
+    /// Creates a new instance of `Condition`
+    ///
+    /// # Panics
+    /// Panics if `interval` is `0`
+    pub fn new(kind: ConditionKind, damage: Health, interval: u64, duration: u64) -> Condition {
+        assert!(interval > 0);
+        Condition {
+            kind,
+            damage,
+            interval,
+            turns_left: duration,
+        }
+    }
+
+    /// A poison dealing 1 damage every 2 turns for 20 turns
+    pub fn poison() -> Condition {
+        Condition::new(ConditionKind::Poison, 1, 2, 20)
+    }
+
+    /// A disease dealing 1 damage every 10 turns for 300 turns
+    pub fn disease() -> Condition {
+        Condition::new(ConditionKind::Disease, 1, 10, 300)
+    }
+}
+
+/// Afflicts the character with the condition with the given chance (`0.0` to `1.0`), e.g. when
+/// hit by a venomous enemy. Returns `true` if the character contracted the condition.
+pub fn contract<R: Rng>(character: &mut Character,
+                        condition: Condition,
+                        chance: f64,
+                        rng: &mut R)
+                        -> bool {
+    if character.is_dead() || rng.gen::<f64>() >= chance {
+        return false;
+    }
+
+    character.afflict(condition);
+    true
+}
+
+/// Lets the character contract the condition lurking in a field of the given type, if any.
+/// Returns the kind of the contracted condition.
+pub fn contract_from_field<R: Rng>(character: &mut Character,
+                                   field_type: &FieldType,
+                                   rng: &mut R)
+                                   -> Option<ConditionKind> {
+    let (condition, chance) = match *field_type {
+        FieldType::SwampWater => (Condition::disease(), 0.2),
+        FieldType::Quicksand => (Condition::poison(), 0.1),
+        _ => return None,
+    };
+
+    let kind = condition.kind;
+    if contract(character, condition, chance, rng) {
+        Some(kind)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::{Json, ToJson};
+    use time::{Tick, WorldClock};
+    use world::two_dimensional::FieldType;
+
+    #[test]
+    fn contract_from_terrain() {
+        let mut character = Character::new("Shrek");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        assert_eq!(contract_from_field(&mut character, &FieldType::Grass, &mut rng), None);
+        assert!((0..50).any(|_| {
+            contract_from_field(&mut character, &FieldType::SwampWater, &mut rng).is_some()
+        }));
+
+        assert_eq!(character.conditions(), &[Condition::disease()]);
+    }
+
+    #[test]
+    fn damage_over_time() {
+        let mut character = Character::new("Snow White");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert!(contract(&mut character, Condition::poison(), 1.0, &mut rng));
+
+        let health = character.health();
+        let mut clock = WorldClock::new();
+        for _ in 0..4 {
+            clock.advance();
+            character.tick(&clock);
+        }
+
+        // Poison deals 2 damage in 4 turns, regeneration heals 0
+        assert_eq!(character.health(), health - 2);
+
+        for _ in 0..16 {
+            clock.advance();
+            character.tick(&clock);
+        }
+        assert!(character.conditions().is_empty());
+    }
+
+    #[test]
+    fn cure() {
+        let mut character = Character::new("Snow White");
+        character.afflict(Condition::poison());
+
+        assert!(!character.cure(ConditionKind::Poison));
+        let _ = character.inventory_mut().add_item(ItemGenerator::new().name(ANTIDOTE).gen());
+        assert!(!character.cure(ConditionKind::Disease));
+        assert!(character.cure(ConditionKind::Poison));
+        assert!(character.conditions().is_empty());
+    }
+
+    #[test]
+    fn reject_zero_interval() {
+        let mut character = Character::new("Snow White");
+        character.afflict(Condition::poison());
+
+        let json = character.to_json().to_string().replace("\"interval\":2", "\"interval\":0");
+        assert!(json.contains("\"interval\":0"));
+        assert!(Character::from_json(&Json::from_str(&json).unwrap()).is_err());
+    }
+}
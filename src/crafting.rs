@@ -0,0 +1,272 @@
+use data::{self, DataError};
+use inventory::Inventory;
+use item::Item;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::str::FromStr;
+use world::World;
+use world::two_dimensional::World2d;
+
+/// The reasons why a recipe can not be crafted
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CraftError {
+    /// The inventory lacks some of the ingredients
+    MissingIngredients,
+    /// The inventory has no room for the crafted item
+    InventoryFull,
+    /// The recipe needs a crafting station the character is not next to
+    NoStation,
+}
+
+/// A map object recipes of some categories can only be crafted next to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Station {
+    /// An anvil and furnace for smithing
+    Forge,
+    /// A table with flasks and burners for alchemy
+    AlchemyTable,
+    /// A fire for cooking
+    Campfire,
+}
+
+impl Station {
+    /// Returns the name of the station, e.g. `Alchemy table`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Station::Forge => "Forge",
+            Station::AlchemyTable => "Alchemy table",
+            Station::Campfire => "Campfire",
+        }
+    }
+}
+
+impl FromStr for Station {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Station, ()> {
+        match s {
+            "Forge" => Ok(Station::Forge),
+            "AlchemyTable" => Ok(Station::AlchemyTable),
+            "Campfire" => Ok(Station::Campfire),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The kind of a recipe, which decides the station it is crafted at
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Category {
+    /// Crafted anywhere, e.g. a rope
+    General,
+    /// Weapons and armor, crafted at a forge
+    Smithing,
+    /// Potions and salves, crafted at an alchemy table
+    Alchemy,
+    /// Food, crafted at a campfire
+    Cooking,
+}
+
+impl Category {
+    /// Returns the station recipes of the category need (optional)
+    pub fn station(&self) -> Option<Station> {
+        match *self {
+            Category::General => None,
+            Category::Smithing => Some(Station::Forge),
+            Category::Alchemy => Some(Station::AlchemyTable),
+            Category::Cooking => Some(Station::Campfire),
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Category, ()> {
+        match s {
+            "General" => Ok(Category::General),
+            "Smithing" => Ok(Category::Smithing),
+            "Alchemy" => Ok(Category::Alchemy),
+            "Cooking" => Ok(Category::Cooking),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Returns the stations on the field of the player and the fields around it
+pub fn stations_nearby(world: &World2d) -> Vec<Station> {
+    let (x, y) = world.get_position();
+    let (width, height) = world.size();
+    let mut stations = Vec::new();
+
+    for nx in x.saturating_sub(1)..(x + 2).min(width) {
+        for ny in y.saturating_sub(1)..(y + 2).min(height) {
+            if let Some(station) = world.get_field((nx, ny)).station {
+                stations.push(station);
+            }
+        }
+    }
+
+    stations
+}
+
+/// A recipe turning ingredients from the inventory, e.g. gathered herbs, into a new item
+#[derive(Clone, PartialEq, Debug)]
+pub struct Recipe {
+    /// The item crafted
+    pub output: Item,
+    /// The names of the items used up and how many of each
+    pub ingredients: Vec<(String, usize)>,
+    /// The category of the recipe
+    pub category: Category,
+}
+
+impl Recipe {
+    /// Creates a new instance of `Recipe` without ingredients, which can be crafted anywhere
+    pub fn new(output: Item) -> Recipe {
+        Recipe {
+            output,
+            ingredients: Vec::new(),
+            category: Category::General,
+        }
+    }
+
+    /// A builder method for setting the category of the recipe
+    pub fn category(mut self, category: Category) -> Recipe {
+        self.category = category;
+        self
+    }
+
+    /// A builder method for adding an ingredient
+    pub fn ingredient(mut self, name: &str, amount: usize) -> Recipe {
+        self.ingredients.push((name.to_owned(), amount));
+        self
+    }
+
+    /// Returns `true` if the inventory holds all ingredients
+    pub fn can_craft(&self, inventory: &Inventory) -> bool {
+        self.ingredients.iter().all(|&(ref name, amount)| inventory.count_by_name(name) >= amount)
+    }
+
+    /// Crafts the item into the inventory, using up the ingredients. The station of the category
+    /// has to be among the `stations_nearby`. The crafted item gets a new id created by the rng.
+    /// Publish `GameEvent::ItemCrafted` afterwards.
+    pub fn craft<R: Rng>(&self,
+                         stations: &[Station],
+                         inventory: &mut Inventory,
+                         rng: &mut R)
+                         -> Result<(), CraftError> {
+        if self.category.station().is_some_and(|station| !stations.contains(&station)) {
+            return Err(CraftError::NoStation);
+        }
+        if !self.can_craft(inventory) {
+            return Err(CraftError::MissingIngredients);
+        }
+        if inventory.is_full() && self.ingredients.iter().all(|&(_, amount)| amount == 0) {
+            return Err(CraftError::InventoryFull);
+        }
+
+        for &(ref name, amount) in &self.ingredients {
+            for _ in 0..amount {
+                inventory.take_by_name(name);
+            }
+        }
+        inventory.add_item(self.output.renewed(rng)).expect("Unable to add crafted item");
+        Ok(())
+    }
+
+    /// Reads a recipe from a JSON object like
+    /// `{"output": {...}, "ingredients": [{"name": "Herb", "amount": 2}]}` with an optional
+    /// `category`
+    pub fn from_json(json: &Json) -> Result<Recipe, DataError> {
+        let object = data::as_object(json, "recipe")?;
+
+        let mut recipe = Recipe::new(Item::from_json(data::field(object, "output")?)?)
+            .category(data::optional(object, "category", data::parse)?
+                .unwrap_or(Category::General));
+        for ingredient in data::as_array(data::field(object, "ingredients")?, "ingredients")? {
+            let ingredient = data::as_object(ingredient, "ingredients")?;
+            recipe = recipe.ingredient(&data::string(ingredient, "name")?,
+                                       data::unsigned(ingredient, "amount")? as usize);
+        }
+
+        Ok(recipe)
+    }
+}
+
+impl ToJson for Recipe {
+    fn to_json(&self) -> Json {
+        let ingredients: Vec<Json> = self.ingredients
+            .iter()
+            .map(|&(ref name, amount)| {
+                let mut ingredient = Object::new();
+                ingredient.insert("name".to_owned(), name.to_json());
+                ingredient.insert("amount".to_owned(), amount.to_json());
+                Json::Object(ingredient)
+            })
+            .collect();
+
+        let mut object = Object::new();
+        object.insert("output".to_owned(), self.output.to_json());
+        object.insert("ingredients".to_owned(), Json::Array(ingredients));
+        object.insert("category".to_owned(), format!("{:?}", self.category).to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inventory::Inventory;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::ToJson;
+    use world::Teleport;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn salve() -> Recipe {
+        Recipe::new(ItemGenerator::new()
+                .name("Healing Salve")
+                .item_type(ItemType::ConsumablePotion)
+                .gen())
+            .ingredient("Herb", 2)
+            .category(Category::Alchemy)
+    }
+
+    #[test]
+    fn craft() {
+        let recipe = salve();
+        let mut inventory = Inventory::new(5);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        inventory.add_item(ItemGenerator::new().name("Herb").gen()).unwrap();
+
+        let stations = [Station::AlchemyTable];
+        assert_eq!(recipe.craft(&stations, &mut inventory, &mut rng),
+                   Err(CraftError::MissingIngredients));
+        inventory.add_item(ItemGenerator::new().name("Herb").gen()).unwrap();
+        assert_eq!(recipe.craft(&[Station::Campfire], &mut inventory, &mut rng),
+                   Err(CraftError::NoStation));
+        assert_eq!(recipe.craft(&stations, &mut inventory, &mut rng), Ok(()));
+        assert_eq!(inventory.count_by_name("Herb"), 0);
+        assert_eq!(inventory.count_by_name("Healing Salve"), 1);
+    }
+
+    #[test]
+    fn stations() {
+        let mut world = World2d::new("Village", (4, 4));
+        world.set_field(Field::new(FieldType::Dirt).station(Station::Forge), (2, 2));
+        world.set_field(Field::new(FieldType::Dirt).station(Station::Campfire), (0, 0));
+
+        world.set_position((3, 3));
+        assert_eq!(stations_nearby(&world), vec![Station::Forge]);
+        world.set_position((1, 1));
+        assert_eq!(stations_nearby(&world), vec![Station::Campfire, Station::Forge]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let recipe = salve();
+        assert_eq!(Recipe::from_json(&recipe.to_json()).unwrap(), recipe);
+    }
+}
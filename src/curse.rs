@@ -0,0 +1,118 @@
+use character::{Character, EquipmentSlot};
+use item::{Item, ItemRarity};
+use types::Gold;
+
+/// The name of the consumable that lifts the curse of an equipped item
+pub const REMOVE_CURSE_SCROLL: &str = "Scroll of Remove Curse";
+
+/// The reasons why a curse can not be lifted
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CurseError {
+    /// The slot does not hold a cursed item
+    NotCursed,
+    /// The character does not carry a `REMOVE_CURSE_SCROLL`
+    NoScroll,
+    /// The character can not pay the price of the service
+    NotEnoughGold,
+}
+
+/// Returns the price an NPC asks for lifting the curse of the item, which grows with its rarity
+pub fn price(item: &Item) -> Gold {
+    match item.rarity {
+        ItemRarity::Common => 50,
+        ItemRarity::Uncommon => 100,
+        ItemRarity::Rare => 250,
+        ItemRarity::Epic => 600,
+        ItemRarity::Legendary => 1500,
+    }
+}
+
+/// Lifts the curse of the item in the given slot by reading a `REMOVE_CURSE_SCROLL`, which is
+/// used up
+pub fn with_scroll(character: &mut Character, slot: EquipmentSlot) -> Result<(), CurseError> {
+    find(character, slot)?;
+
+    if character.inventory_mut().take_by_name(REMOVE_CURSE_SCROLL).is_none() {
+        return Err(CurseError::NoScroll);
+    }
+
+    character.lift_curse(slot);
+    Ok(())
+}
+
+/// Lifts the curse of the item in the given slot as a service of an NPC, paying its `price`
+pub fn at_service(character: &mut Character, slot: EquipmentSlot) -> Result<Gold, CurseError> {
+    let price = price(find(character, slot)?);
+
+    if !character.inventory_mut().spend_gold(price) {
+        return Err(CurseError::NotEnoughGold);
+    }
+
+    character.lift_curse(slot);
+    Ok(price)
+}
+
+fn find(character: &Character, slot: EquipmentSlot) -> Result<&Item, CurseError> {
+    match character.equipped(slot) {
+        Some(item) if item.cursed => Ok(item),
+        _ => Err(CurseError::NotCursed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character, EquipError, EquipmentSlot};
+    use item::{ItemInfluence, ItemRarity, ItemType};
+    use item_generator::ItemGenerator;
+
+    fn cursed_helmet() -> Item {
+        let mut helmet = ItemGenerator::new()
+            .name("Crown of Thorns")
+            .item_type(ItemType::ArmorHead)
+            .rarity(ItemRarity::Common)
+            .influence(Some(ItemInfluence::new(Attribute::Charisma, -5)))
+            .gen();
+        helmet.cursed = true;
+        helmet.identified = false;
+        helmet
+    }
+
+    #[test]
+    fn stuck_until_lifted() {
+        let mut character = Character::new("Boromir");
+        character.equip(EquipmentSlot::Head, cursed_helmet()).unwrap();
+
+        assert!(character.equipped(EquipmentSlot::Head).unwrap().identified);
+        assert_eq!(character.unequip(EquipmentSlot::Head), Err(EquipError::Cursed));
+        assert_eq!(character.equip(EquipmentSlot::Head, cursed_helmet()),
+                   Err(EquipError::Cursed));
+        assert_eq!(with_scroll(&mut character, EquipmentSlot::Head),
+                   Err(CurseError::NoScroll));
+
+        let scroll = ItemGenerator::new()
+            .name(REMOVE_CURSE_SCROLL)
+            .item_type(ItemType::Usable)
+            .gen();
+        character.inventory_mut().add_item(scroll).unwrap();
+        assert_eq!(with_scroll(&mut character, EquipmentSlot::Head), Ok(()));
+        assert!(character.unequip(EquipmentSlot::Head).unwrap().is_some());
+        assert_eq!(with_scroll(&mut character, EquipmentSlot::Head),
+                   Err(CurseError::NotCursed));
+    }
+
+    #[test]
+    fn service() {
+        let mut character = Character::new("Boromir");
+        character.equip(EquipmentSlot::Head, cursed_helmet()).unwrap();
+
+        character.inventory_mut().add_gold(20);
+        assert_eq!(at_service(&mut character, EquipmentSlot::Head),
+                   Err(CurseError::NotEnoughGold));
+
+        character.inventory_mut().add_gold(100);
+        let price = at_service(&mut character, EquipmentSlot::Head).unwrap();
+        assert_eq!(character.inventory().gold(), 120 - price);
+        assert!(!character.equipped(EquipmentSlot::Head).unwrap().cursed);
+    }
+}
@@ -0,0 +1,349 @@
+use data::{self, DataError};
+use item::Item;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::path::Path;
+use world::progress::CampaignProgress;
+
+/// What starts a cutscene
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CutsceneTrigger {
+    /// The player steps onto the position in the world with the given id
+    Tile(String, (usize, usize)),
+    /// The quest flag is set
+    Flag(String),
+}
+
+impl CutsceneTrigger {
+    /// Reads a trigger from a JSON object like
+    /// `{"type": "Tile", "world": "village", "position": [3, 4]}` or
+    /// `{"type": "Flag", "flag": "ring_found"}`
+    pub fn from_json(json: &Json) -> Result<CutsceneTrigger, DataError> {
+        let object = data::as_object(json, "trigger")?;
+
+        match &data::string(object, "type")?[..] {
+            "Tile" => {
+                Ok(CutsceneTrigger::Tile(data::string(object, "world")?,
+                                         data::position(object, "position")?))
+            }
+            "Flag" => Ok(CutsceneTrigger::Flag(data::string(object, "flag")?)),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for CutsceneTrigger {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        match *self {
+            CutsceneTrigger::Tile(ref world, position) => {
+                object.insert("type".to_owned(), "Tile".to_json());
+                object.insert("world".to_owned(), world.to_json());
+                object.insert("position".to_owned(), position.to_json());
+            }
+            CutsceneTrigger::Flag(ref flag) => {
+                object.insert("type".to_owned(), "Flag".to_json());
+                object.insert("flag".to_owned(), flag.to_json());
+            }
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// One step of a cutscene
+#[derive(Clone, PartialEq, Debug)]
+pub enum Step {
+    /// Text told by the narrator
+    Narration(String),
+    /// Moves the view of the map to the position, without moving the player
+    Camera((usize, usize)),
+    /// A line the speaker says, which the player can not answer
+    Dialogue(String, String),
+    /// Gives the item to the player
+    Grant(Item),
+}
+
+impl Step {
+    /// Reads a step from a JSON object like `{"type": "Narration", "text": "..."}`,
+    /// `{"type": "Camera", "position": [3, 4]}`,
+    /// `{"type": "Dialogue", "speaker": "Gandalf", "text": "..."}` or
+    /// `{"type": "Grant", "item": {...}}`
+    pub fn from_json(json: &Json) -> Result<Step, DataError> {
+        let object = data::as_object(json, "step")?;
+
+        match &data::string(object, "type")?[..] {
+            "Narration" => Ok(Step::Narration(data::string(object, "text")?)),
+            "Camera" => Ok(Step::Camera(data::position(object, "position")?)),
+            "Dialogue" => {
+                Ok(Step::Dialogue(data::string(object, "speaker")?, data::string(object, "text")?))
+            }
+            "Grant" => Ok(Step::Grant(Item::from_json(data::field(object, "item")?)?)),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for Step {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        let kind = match *self {
+            Step::Narration(ref text) => {
+                object.insert("text".to_owned(), text.to_json());
+                "Narration"
+            }
+            Step::Camera(position) => {
+                object.insert("position".to_owned(), position.to_json());
+                "Camera"
+            }
+            Step::Dialogue(ref speaker, ref text) => {
+                object.insert("speaker".to_owned(), speaker.to_json());
+                object.insert("text".to_owned(), text.to_json());
+                "Dialogue"
+            }
+            Step::Grant(ref item) => {
+                object.insert("item".to_owned(), item.to_json());
+                "Grant"
+            }
+        };
+        object.insert("type".to_owned(), kind.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A scripted sequence played once when its trigger fires, e.g. the arrival in a village
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cutscene {
+    /// The unique id of the cutscene
+    pub id: String,
+    /// What starts the cutscene
+    pub trigger: CutsceneTrigger,
+    /// The steps played in order
+    pub steps: Vec<Step>,
+    /// If `false`, the player has to watch the cutscene to the end
+    pub skippable: bool,
+}
+
+impl Cutscene {
+    /// Creates a new instance of `Cutscene` without any steps
+    pub fn new(id: &str, trigger: CutsceneTrigger) -> Cutscene {
+        Cutscene {
+            id: id.to_owned(),
+            trigger,
+            steps: Vec::new(),
+            skippable: true,
+        }
+    }
+
+    /// A builder method for adding a step
+    pub fn step(mut self, step: Step) -> Cutscene {
+        self.steps.push(step);
+        self
+    }
+
+    /// A builder method for setting whether the player may skip the cutscene
+    pub fn skippable(mut self, skippable: bool) -> Cutscene {
+        self.skippable = skippable;
+        self
+    }
+
+    /// Returns the quest flag marking the cutscene as played, e.g. `cutscene.arrival`
+    pub fn played_flag(&self) -> String {
+        format!("cutscene.{}", self.id)
+    }
+
+    /// Starts playing the cutscene and marks it as played in the progress
+    pub fn play(&self, progress: &mut CampaignProgress) -> Playback<'_> {
+        progress.set_flag(&self.played_flag());
+        Playback {
+            cutscene: self,
+            next: 0,
+        }
+    }
+
+    /// Reads a cutscene from a JSON object as written by `to_json`. `skippable` is optional
+    /// and defaults to `true`.
+    pub fn from_json(json: &Json) -> Result<Cutscene, DataError> {
+        let object = data::as_object(json, "cutscene")?;
+
+        Ok(Cutscene {
+            id: data::string(object, "id")?,
+            trigger: CutsceneTrigger::from_json(data::field(object, "trigger")?)?,
+            steps: data::as_array(data::field(object, "steps")?, "steps")?
+                .iter()
+                .map(Step::from_json)
+                .collect::<Result<_, _>>()?,
+            skippable: data::optional(object, "skippable", data::boolean)?.unwrap_or(true),
+        })
+    }
+}
+
+impl ToJson for Cutscene {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.to_json());
+        object.insert("trigger".to_owned(), self.trigger.to_json());
+        object.insert("steps".to_owned(), self.steps.to_json());
+        object.insert("skippable".to_owned(), self.skippable.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A cutscene being played
+pub struct Playback<'a> {
+    cutscene: &'a Cutscene,
+    next: usize,
+}
+
+impl<'a> Playback<'a> {
+    /// Returns the next step, or `None` once the cutscene is over
+    pub fn next_step(&mut self) -> Option<&'a Step> {
+        let step = self.cutscene.steps.get(self.next);
+        self.next += 1;
+        step
+    }
+
+    /// Returns `true` once all steps were played
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.cutscene.steps.len()
+    }
+
+    /// Skips the rest of the cutscene and returns the items of the skipped steps, which the
+    /// player still receives. Returns `None` if the cutscene can not be skipped.
+    pub fn skip(&mut self) -> Option<Vec<&'a Item>> {
+        if !self.cutscene.skippable {
+            return None;
+        }
+
+        let rest = &self.cutscene.steps[self.next.min(self.cutscene.steps.len())..];
+        self.next = self.cutscene.steps.len();
+        Some(rest.iter()
+            .filter_map(|step| match *step {
+                Step::Grant(ref item) => Some(item),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+/// All cutscenes of a campaign
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Cutscenes {
+    cutscenes: Vec<Cutscene>,
+}
+
+impl Cutscenes {
+    /// Creates a new, empty instance of `Cutscenes`
+    pub fn new() -> Cutscenes {
+        Cutscenes::default()
+    }
+
+    /// Adds a cutscene
+    pub fn add(&mut self, cutscene: Cutscene) {
+        self.cutscenes.push(cutscene);
+    }
+
+    /// Returns the cutscene with the given id
+    pub fn get(&self, id: &str) -> Option<&Cutscene> {
+        self.cutscenes.iter().find(|cutscene| cutscene.id == id)
+    }
+
+    /// Returns the first cutscene not played yet whose trigger fires: either the player stands
+    /// on its tile in the active world or its quest flag is set
+    pub fn triggered(&self,
+                     progress: &CampaignProgress,
+                     position: (usize, usize))
+                     -> Option<&Cutscene> {
+        self.cutscenes.iter().find(|cutscene| {
+            !progress.has_flag(&cutscene.played_flag()) &&
+            match cutscene.trigger {
+                CutsceneTrigger::Tile(ref world, tile) => {
+                    *world == progress.active && tile == position
+                }
+                CutsceneTrigger::Flag(ref flag) => progress.has_flag(flag),
+            }
+        })
+    }
+
+    /// Reads the cutscenes from a JSON array of cutscenes
+    pub fn from_json(json: &Json) -> Result<Cutscenes, DataError> {
+        Ok(Cutscenes {
+            cutscenes: data::as_array(json, "cutscenes")?
+                .iter()
+                .map(Cutscene::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Reads the cutscenes from a JSON file
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Cutscenes, DataError> {
+        Cutscenes::from_json(&data::read_json(path)?)
+    }
+}
+
+impl ToJson for Cutscenes {
+    fn to_json(&self) -> Json {
+        self.cutscenes.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item_generator::ItemGenerator;
+    use rustc_serialize::json::ToJson;
+    use world::progress::CampaignProgress;
+
+    fn arrival() -> Cutscene {
+        Cutscene::new("arrival", CutsceneTrigger::Tile("village".to_owned(), (3, 4)))
+            .step(Step::Narration("The gates creak open.".to_owned()))
+            .step(Step::Camera((5, 1)))
+            .step(Step::Dialogue("Elder".to_owned(), "Welcome, traveler.".to_owned()))
+            .step(Step::Grant(ItemGenerator::new().name("Map").gen()))
+    }
+
+    #[test]
+    fn trigger_once() {
+        let mut cutscenes = Cutscenes::new();
+        cutscenes.add(arrival());
+        cutscenes.add(Cutscene::new("farewell", CutsceneTrigger::Flag("done".to_owned())));
+        let mut progress = CampaignProgress::new("village");
+
+        assert_eq!(cutscenes.triggered(&progress, (0, 0)), None);
+        let cutscene = cutscenes.triggered(&progress, (3, 4)).unwrap();
+        assert_eq!(cutscene.id, "arrival");
+
+        let mut playback = cutscene.play(&mut progress);
+        let narration = Step::Narration("The gates creak open.".to_owned());
+        assert_eq!(playback.next_step(), Some(&narration));
+        assert_eq!(cutscenes.triggered(&progress, (3, 4)), None);
+
+        progress.set_flag("done");
+        assert_eq!(cutscenes.triggered(&progress, (3, 4)).map(|c| &c.id[..]), Some("farewell"));
+    }
+
+    #[test]
+    fn skip() {
+        let cutscene = arrival();
+        let mut progress = CampaignProgress::new("village");
+        let mut playback = cutscene.play(&mut progress);
+        playback.next_step();
+
+        let granted = playback.skip().unwrap();
+        assert_eq!(granted.len(), 1);
+        assert_eq!(granted[0].name, "Map");
+        assert!(playback.is_finished());
+        assert_eq!(playback.next_step(), None);
+
+        let forced = arrival().skippable(false);
+        assert_eq!(forced.play(&mut progress).skip(), None);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut cutscenes = Cutscenes::new();
+        cutscenes.add(arrival().skippable(false));
+        assert_eq!(Cutscenes::from_json(&cutscenes.to_json()).unwrap(), cutscenes);
+    }
+}
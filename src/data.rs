@@ -0,0 +1,210 @@
+use rustc_serialize::json::{self, Json};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The error returned when a data file could not be loaded
+#[derive(Debug)]
+pub enum DataError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file is not valid JSON
+    Parse(json::ParserError),
+    /// The file is not valid in another data format, e.g. RON or TOML
+    Syntax(String),
+    /// A required field is missing
+    Missing(String),
+    /// A field has an invalid value
+    Invalid(String),
+    /// The data requires a different version of this crate
+    Incompatible(String),
+    /// The checksum of a save does not match its contents. Contains the path of the file.
+    Corrupt(String),
+    /// The save belongs to a game that ended in permadeath mode. Contains the final summary.
+    Locked(String),
+    /// The save is obfuscated, but the `obfuscation` feature is disabled. Contains the path of
+    /// the file.
+    Obfuscated(String),
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DataError::Io(ref err) => write!(f, "unable to read data file: {}", err),
+            DataError::Parse(ref err) => write!(f, "unable to parse data file: {}", err),
+            DataError::Syntax(ref err) => write!(f, "unable to parse data file: {}", err),
+            DataError::Missing(ref field) => write!(f, "missing field `{}`", field),
+            DataError::Invalid(ref field) => write!(f, "invalid value for field `{}`", field),
+            DataError::Incompatible(ref version) => write!(f, "requires version {}", version),
+            DataError::Corrupt(ref path) => {
+                write!(f, "the save `{}` is corrupt or was modified", path)
+            }
+            DataError::Locked(_) => write!(f, "the game is over"),
+            DataError::Obfuscated(ref path) => {
+                write!(f, "the save `{}` requires the `obfuscation` feature", path)
+            }
+        }
+    }
+}
+
+impl Error for DataError {
+    fn description(&self) -> &str {
+        match *self {
+            DataError::Io(_) => "unable to read data file",
+            DataError::Parse(_) | DataError::Syntax(_) => "unable to parse data file",
+            DataError::Missing(_) => "missing field",
+            DataError::Invalid(_) => "invalid field",
+            DataError::Incompatible(_) => "incompatible version",
+            DataError::Corrupt(_) => "corrupt save",
+            DataError::Locked(_) => "game over",
+            DataError::Obfuscated(_) => "obfuscated save",
+        }
+    }
+}
+
+impl From<io::Error> for DataError {
+    fn from(err: io::Error) -> DataError {
+        DataError::Io(err)
+    }
+}
+
+impl From<json::ParserError> for DataError {
+    fn from(err: json::ParserError) -> DataError {
+        DataError::Parse(err)
+    }
+}
+
+/// Computes the 64 bit FNV-1a hash of the given bytes, used to detect corrupted files
+pub fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Reads and parses the JSON file at the given path
+pub fn read_json<P: AsRef<Path>>(path: P) -> Result<Json, DataError> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    Ok(Json::from_str(&contents)?)
+}
+
+/// Returns the given JSON value as object
+pub fn as_object<'a>(json: &'a Json, name: &str) -> Result<&'a json::Object, DataError> {
+    json.as_object().ok_or_else(|| DataError::Invalid(name.to_owned()))
+}
+
+/// Returns the given JSON value as array
+pub fn as_array<'a>(json: &'a Json, name: &str) -> Result<&'a json::Array, DataError> {
+    json.as_array().ok_or_else(|| DataError::Invalid(name.to_owned()))
+}
+
+/// Returns the field with the given key
+pub fn field<'a>(object: &'a json::Object, key: &str) -> Result<&'a Json, DataError> {
+    object.get(key).ok_or_else(|| DataError::Missing(key.to_owned()))
+}
+
+/// Returns the string field with the given key
+pub fn string(object: &json::Object, key: &str) -> Result<String, DataError> {
+    field(object, key)?
+        .as_string()
+        .map(|string| string.to_owned())
+        .ok_or_else(|| DataError::Invalid(key.to_owned()))
+}
+
+/// Returns the unsigned integer field with the given key
+pub fn unsigned(object: &json::Object, key: &str) -> Result<u64, DataError> {
+    field(object, key)?.as_u64().ok_or_else(|| DataError::Invalid(key.to_owned()))
+}
+
+/// Returns the integer field with the given key
+pub fn signed(object: &json::Object, key: &str) -> Result<i64, DataError> {
+    field(object, key)?.as_i64().ok_or_else(|| DataError::Invalid(key.to_owned()))
+}
+
+/// Returns the number field with the given key
+pub fn float(object: &json::Object, key: &str) -> Result<f64, DataError> {
+    field(object, key)?.as_f64().ok_or_else(|| DataError::Invalid(key.to_owned()))
+}
+
+/// Returns the boolean field with the given key
+pub fn boolean(object: &json::Object, key: &str) -> Result<bool, DataError> {
+    field(object, key)?.as_boolean().ok_or_else(|| DataError::Invalid(key.to_owned()))
+}
+
+/// Returns the position field with the given key, stored as an array of two numbers
+pub fn position(object: &json::Object, key: &str) -> Result<(usize, usize), DataError> {
+    let values = as_array(field(object, key)?, key)?;
+
+    match (values.first().and_then(Json::as_u64), values.get(1).and_then(Json::as_u64)) {
+        (Some(x), Some(y)) if values.len() == 2 => Ok((x as usize, y as usize)),
+        _ => Err(DataError::Invalid(key.to_owned())),
+    }
+}
+
+/// Parses the string field with the given key, e.g. into an `ItemType`
+pub fn parse<T: FromStr>(object: &json::Object, key: &str) -> Result<T, DataError> {
+    string(object, key)?.parse().map_err(|_| DataError::Invalid(key.to_owned()))
+}
+
+/// Parses the given JSON string value, e.g. into an `ItemType`
+pub fn parse_value<T: FromStr>(json: &Json, name: &str) -> Result<T, DataError> {
+    json.as_string()
+        .and_then(|string| string.parse().ok())
+        .ok_or_else(|| DataError::Invalid(name.to_owned()))
+}
+
+/// Returns the value of an optional field. Missing fields and `null` result in `None`
+pub fn optional<T, F>(object: &json::Object, key: &str, read: F) -> Result<Option<T>, DataError>
+    where F: Fn(&json::Object, &str) -> Result<T, DataError>
+{
+    match object.get(key) {
+        None | Some(&Json::Null) => Ok(None),
+        Some(_) => read(object, key).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+
+    #[test]
+    fn read_fields() {
+        let json = Json::from_str(r#"{"name": "Goblin", "health": 12, "bonus": -2,
+                                      "chance": 0.5, "boss": false, "type": "Grass",
+                                      "position": [3, 4]}"#)
+            .unwrap();
+        let object = as_object(&json, "monster").unwrap();
+
+        assert_eq!(string(object, "name").unwrap(), "Goblin");
+        assert_eq!(unsigned(object, "health").unwrap(), 12);
+        assert_eq!(signed(object, "bonus").unwrap(), -2);
+        assert_eq!(float(object, "chance").unwrap(), 0.5);
+        assert!(!boolean(object, "boss").unwrap());
+        assert_eq!(position(object, "position").unwrap(), (3, 4));
+        assert_eq!(optional(object, "missing", unsigned).unwrap(), None);
+        assert_eq!(optional(object, "health", unsigned).unwrap(), Some(12));
+    }
+
+    #[test]
+    fn errors() {
+        let json = Json::from_str(r#"{"name": 3}"#).unwrap();
+        let object = as_object(&json, "monster").unwrap();
+
+        match string(object, "name") {
+            Err(DataError::Invalid(ref field)) => assert_eq!(field, "name"),
+            _ => panic!("expected an invalid field"),
+        }
+
+        match unsigned(object, "health") {
+            Err(DataError::Missing(ref field)) => assert_eq!(field, "health"),
+            _ => panic!("expected a missing field"),
+        }
+
+        assert!(read_json("/does/not/exist.json").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use character::{Attribute, Character};
+use engine::EngineContext;
+use inventory::Inventory;
+use types::{AttributeValue, Experience, Health};
+
+/// The reasons why a character could not be resurrected
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResurrectError {
+    /// The character is not dead
+    NotDead,
+    /// The game is played in permadeath mode
+    Permadeath,
+    /// The item needed for the resurrection is missing
+    MissingItem,
+}
+
+/// A way to bring a dead character back to life, e.g. a scroll or a spell, together with the
+/// penalties the character suffers
+#[derive(Clone, PartialEq, Debug)]
+pub struct Resurrection {
+    /// The share of the maximum health (`0.0` to `1.0`) the character comes back with
+    pub health: f64,
+    /// The share of the collected experience (`0.0` to `1.0`) the character loses
+    pub experience_loss: f64,
+    /// An attribute permanently lowered by the given amount (optional)
+    pub weakness: Option<(Attribute, AttributeValue)>,
+    /// The name of the item used up by the resurrection (optional)
+    pub item: Option<String>,
+}
+
+impl Resurrection {
+    /// Creates a new instance of `Resurrection` without any penalties
+    pub fn new(health: f64) -> Resurrection {
+        Resurrection {
+            health,
+            experience_loss: 0.0,
+            weakness: None,
+            item: None,
+        }
+    }
+
+    /// A builder method for setting the share of experience lost
+    pub fn experience_loss(mut self, experience_loss: f64) -> Resurrection {
+        self.experience_loss = experience_loss;
+        self
+    }
+
+    /// A builder method for permanently lowering an attribute
+    pub fn weakness(mut self, attribute: Attribute, amount: AttributeValue) -> Resurrection {
+        self.weakness = Some((attribute, amount));
+        self
+    }
+
+    /// A builder method for setting the name of the item used up by the resurrection
+    pub fn item(mut self, item: &str) -> Resurrection {
+        self.item = Some(item.to_owned());
+        self
+    }
+
+    /// Brings the dead character back to life and applies the penalties. If an item is needed, it
+    /// is taken from the given inventory.
+    pub fn resurrect(&self,
+                     target: &mut Character,
+                     inventory: Option<&mut Inventory>,
+                     context: &EngineContext)
+                     -> Result<(), ResurrectError> {
+        if !target.is_dead() {
+            return Err(ResurrectError::NotDead);
+        }
+
+        if context.permadeath {
+            return Err(ResurrectError::Permadeath);
+        }
+
+        if let Some(ref item) = self.item {
+            let taken = inventory.and_then(|inventory| inventory.take_by_name(item));
+            if taken.is_none() {
+                return Err(ResurrectError::MissingItem);
+            }
+        }
+
+        target.revive(((target.max_health() as f64) * self.health) as Health);
+
+        let loss = ((target.experience() as f64) * self.experience_loss) as Experience;
+        target.lose_experience(loss);
+
+        if let Some((ref attribute, amount)) = self.weakness {
+            let value = target.get_base_attribute_value(attribute);
+            target.update_attribute(attribute, value - amount);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use engine::EngineContext;
+    use event::Event;
+    use inventory::Inventory;
+    use item_generator::ItemGenerator;
+
+    fn dead_character() -> Character {
+        let mut character = Character::new("Boromir");
+        character.gain_experience(200);
+
+        assert_eq!(character.take_damage(10), Event::Nothing);
+        assert_eq!(character.take_damage(100), Event::Death);
+        assert_eq!(character.take_damage(1), Event::Nothing);
+        assert!(character.is_dead());
+
+        character
+    }
+
+    #[test]
+    fn resurrect_with_penalties() {
+        let mut character = dead_character();
+        let spell = Resurrection::new(0.5).experience_loss(0.25).weakness(Attribute::Strength, 2);
+
+        assert_eq!(spell.resurrect(&mut character, None, &EngineContext::new()), Ok(()));
+        assert!(!character.is_dead());
+        assert_eq!(character.health(), 15);
+        assert_eq!(character.experience(), 150);
+        assert_eq!(character.get_attribute_value(&Attribute::Strength), 18);
+
+        assert_eq!(spell.resurrect(&mut character, None, &EngineContext::new()),
+                   Err(ResurrectError::NotDead));
+    }
+
+    #[test]
+    fn resurrect_with_item() {
+        let mut character = dead_character();
+        let scroll = Resurrection::new(0.1).item("Scroll of Life");
+        let mut inventory = Inventory::new(10);
+
+        assert_eq!(scroll.resurrect(&mut character, Some(&mut inventory), &EngineContext::new()),
+                   Err(ResurrectError::MissingItem));
+
+        let _ = inventory.add_item(ItemGenerator::new().name("Scroll of Life").gen());
+        assert_eq!(scroll.resurrect(&mut character, Some(&mut inventory), &EngineContext::new()),
+                   Ok(()));
+        assert!(inventory.find_by_name("Scroll of Life").is_none());
+    }
+
+    #[test]
+    fn permadeath() {
+        let mut character = dead_character();
+        let context = EngineContext { permadeath: true, ..EngineContext::new() };
+
+        assert_eq!(Resurrection::new(1.0).resurrect(&mut character, None, &context),
+                   Err(ResurrectError::Permadeath));
+    }
+}
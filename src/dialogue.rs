@@ -0,0 +1,432 @@
+use alignment::{Alignment, AlignmentCondition};
+use character::{Attribute, Character};
+use data::{self, DataError};
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use skill_check::CheckOutcome;
+use std::path::Path;
+use types::{AttributeValue, Gold};
+
+/// How a dialogue option is resolved
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionKind {
+    /// Always succeeds
+    Say,
+    /// A charisma check against the difficulty
+    Persuade(AttributeValue),
+    /// A strength check against the difficulty
+    Intimidate(AttributeValue),
+    /// Pays the amount of gold, which always succeeds
+    Bribe(Gold),
+}
+
+impl OptionKind {
+    /// Returns the tag shown in front of the option, e.g. `[Persuade]`, or an empty string for
+    /// `Say`
+    pub fn tag(&self) -> String {
+        match *self {
+            OptionKind::Say => String::new(),
+            OptionKind::Persuade(_) => "[Persuade] ".to_owned(),
+            OptionKind::Intimidate(_) => "[Intimidate] ".to_owned(),
+            OptionKind::Bribe(gold) => format!("[Bribe: {} gold] ", gold),
+        }
+    }
+
+    /// Reads the kind from a JSON object like `{"type": "Persuade", "difficulty": 15}` or
+    /// `{"type": "Bribe", "gold": 50}`
+    pub fn from_json(json: &Json) -> Result<OptionKind, DataError> {
+        let object = data::as_object(json, "kind")?;
+        let difficulty = || data::signed(object, "difficulty").map(|value| value as AttributeValue);
+
+        match &data::string(object, "type")?[..] {
+            "Say" => Ok(OptionKind::Say),
+            "Persuade" => Ok(OptionKind::Persuade(difficulty()?)),
+            "Intimidate" => Ok(OptionKind::Intimidate(difficulty()?)),
+            "Bribe" => Ok(OptionKind::Bribe(data::unsigned(object, "gold")? as Gold)),
+            _ => Err(DataError::Invalid("type".to_owned())),
+        }
+    }
+}
+
+impl ToJson for OptionKind {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        let kind = match *self {
+            OptionKind::Say => "Say",
+            OptionKind::Persuade(difficulty) => {
+                object.insert("difficulty".to_owned(), difficulty.to_json());
+                "Persuade"
+            }
+            OptionKind::Intimidate(difficulty) => {
+                object.insert("difficulty".to_owned(), difficulty.to_json());
+                "Intimidate"
+            }
+            OptionKind::Bribe(gold) => {
+                object.insert("gold".to_owned(), gold.to_json());
+                "Bribe"
+            }
+        };
+        object.insert("type".to_owned(), kind.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// An answer the player can give
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DialogueOption {
+    /// The text of the answer
+    pub text: String,
+    /// How the answer is resolved
+    pub kind: OptionKind,
+    /// The id of the node the dialogue continues with if the answer succeeds
+    pub success: String,
+    /// The id of the node the dialogue continues with if the answer fails. Without it, a failed
+    /// answer ends the dialogue.
+    pub failure: Option<String>,
+    /// The alignment the player needs to see the answer (optional)
+    pub condition: Option<AlignmentCondition>,
+}
+
+impl DialogueOption {
+    /// Creates a new instance of `DialogueOption` that always succeeds
+    pub fn new(text: &str, success: &str) -> DialogueOption {
+        DialogueOption {
+            text: text.to_owned(),
+            kind: OptionKind::Say,
+            success: success.to_owned(),
+            failure: None,
+            condition: None,
+        }
+    }
+
+    /// A builder method for setting how the answer is resolved
+    pub fn kind(mut self, kind: OptionKind) -> DialogueOption {
+        self.kind = kind;
+        self
+    }
+
+    /// A builder method for setting the node the dialogue continues with on failure
+    pub fn failure(mut self, failure: &str) -> DialogueOption {
+        self.failure = Some(failure.to_owned());
+        self
+    }
+
+    /// A builder method for only showing the answer to players of the alignment
+    pub fn condition(mut self, condition: AlignmentCondition) -> DialogueOption {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Returns the answer as shown to the player, e.g. `[Intimidate] Move aside.`
+    pub fn label(&self) -> String {
+        format!("{}{}", self.kind.tag(), self.text)
+    }
+
+    /// Reads an option from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<DialogueOption, DataError> {
+        let object = data::as_object(json, "option")?;
+
+        Ok(DialogueOption {
+            text: data::string(object, "text")?,
+            kind: match object.get("kind") {
+                None => OptionKind::Say,
+                Some(kind) => OptionKind::from_json(kind)?,
+            },
+            success: data::string(object, "success")?,
+            failure: data::optional(object, "failure", data::string)?,
+            condition: match object.get("condition") {
+                None | Some(&Json::Null) => None,
+                Some(condition) => Some(AlignmentCondition::from_json(condition)?),
+            },
+        })
+    }
+}
+
+impl ToJson for DialogueOption {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("text".to_owned(), self.text.to_json());
+        object.insert("kind".to_owned(), self.kind.to_json());
+        object.insert("success".to_owned(), self.success.to_json());
+        if let Some(ref failure) = self.failure {
+            object.insert("failure".to_owned(), failure.to_json());
+        }
+        if let Some(ref condition) = self.condition {
+            object.insert("condition".to_owned(), condition.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// Something a character says, followed by the answers of the player. A node without answers
+/// ends the dialogue.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DialogueNode {
+    /// The unique id of the node within the dialogue
+    pub id: String,
+    /// The name of the character speaking
+    pub speaker: String,
+    /// What the character says
+    pub text: String,
+    /// The answers of the player
+    pub options: Vec<DialogueOption>,
+}
+
+impl DialogueNode {
+    /// Creates a new instance of `DialogueNode` without any answers
+    pub fn new(id: &str, speaker: &str, text: &str) -> DialogueNode {
+        DialogueNode {
+            id: id.to_owned(),
+            speaker: speaker.to_owned(),
+            text: text.to_owned(),
+            options: Vec::new(),
+        }
+    }
+
+    /// A builder method for adding an answer
+    pub fn option(mut self, option: DialogueOption) -> DialogueNode {
+        self.options.push(option);
+        self
+    }
+
+    /// Reads a node from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<DialogueNode, DataError> {
+        let object = data::as_object(json, "node")?;
+
+        Ok(DialogueNode {
+            id: data::string(object, "id")?,
+            speaker: data::string(object, "speaker")?,
+            text: data::string(object, "text")?,
+            options: match object.get("options") {
+                None => Vec::new(),
+                Some(options) => {
+                    data::as_array(options, "options")?
+                        .iter()
+                        .map(DialogueOption::from_json)
+                        .collect::<Result<_, _>>()?
+                }
+            },
+        })
+    }
+}
+
+impl ToJson for DialogueNode {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.to_json());
+        object.insert("speaker".to_owned(), self.speaker.to_json());
+        object.insert("text".to_owned(), self.text.to_json());
+        object.insert("options".to_owned(), self.options.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// The reasons why an answer can not be given
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DialogueError {
+    /// The dialogue is over
+    Finished,
+    /// There is no visible answer with the given index
+    UnknownOption,
+    /// The player can not pay the bribe
+    NotEnoughGold,
+}
+
+/// A tree of dialogue nodes, starting with the first node
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Dialogue {
+    nodes: Vec<DialogueNode>,
+}
+
+impl Dialogue {
+    /// Creates a new instance of `Dialogue` starting with the node
+    pub fn new(start: DialogueNode) -> Dialogue {
+        Dialogue { nodes: vec![start] }
+    }
+
+    /// A builder method for adding a node
+    pub fn node(mut self, node: DialogueNode) -> Dialogue {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Returns the node with the given id
+    pub fn get(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Starts the dialogue at its first node
+    pub fn start(&self) -> Conversation<'_> {
+        Conversation {
+            dialogue: self,
+            current: self.nodes.first(),
+        }
+    }
+
+    /// Reads a dialogue from a JSON array of nodes, starting with the first one
+    pub fn from_json(json: &Json) -> Result<Dialogue, DataError> {
+        let nodes: Vec<DialogueNode> = data::as_array(json, "dialogue")?
+            .iter()
+            .map(DialogueNode::from_json)
+            .collect::<Result<_, _>>()?;
+
+        if nodes.is_empty() {
+            return Err(DataError::Invalid("dialogue".to_owned()));
+        }
+        Ok(Dialogue { nodes })
+    }
+
+    /// Reads a dialogue from a JSON file
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Dialogue, DataError> {
+        Dialogue::from_json(&data::read_json(path)?)
+    }
+}
+
+impl ToJson for Dialogue {
+    fn to_json(&self) -> Json {
+        self.nodes.to_json()
+    }
+}
+
+/// A dialogue being held with the player
+pub struct Conversation<'a> {
+    dialogue: &'a Dialogue,
+    current: Option<&'a DialogueNode>,
+}
+
+impl<'a> Conversation<'a> {
+    /// Returns the node being shown, or `None` once the dialogue is over
+    pub fn current(&self) -> Option<&'a DialogueNode> {
+        self.current
+    }
+
+    /// Returns the answers the player of the alignment can give
+    pub fn options(&self, alignment: &Alignment) -> Vec<&'a DialogueOption> {
+        self.current
+            .map(|node| {
+                node.options
+                    .iter()
+                    .filter(|option| {
+                        option.condition.is_none_or(|condition| condition.is_met(alignment))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gives the answer with the index among the visible `options`. Checks are rolled for the
+    /// character and bribes are paid from its inventory. The dialogue continues with the
+    /// success or failure node of the answer; the outcome of the check is returned, if there was
+    /// one.
+    pub fn choose<R: Rng>(&mut self,
+                          index: usize,
+                          alignment: &Alignment,
+                          character: &mut Character,
+                          rng: &mut R)
+                          -> Result<Option<CheckOutcome>, DialogueError> {
+        if self.current.is_none() {
+            return Err(DialogueError::Finished);
+        }
+        let option = *self.options(alignment).get(index).ok_or(DialogueError::UnknownOption)?;
+
+        let outcome = match option.kind {
+            OptionKind::Say => None,
+            OptionKind::Persuade(difficulty) => {
+                Some(character.skill_check(&Attribute::Charisma, difficulty, rng))
+            }
+            OptionKind::Intimidate(difficulty) => {
+                Some(character.skill_check(&Attribute::Strength, difficulty, rng))
+            }
+            OptionKind::Bribe(gold) => {
+                if !character.inventory_mut().spend_gold(gold) {
+                    return Err(DialogueError::NotEnoughGold);
+                }
+                None
+            }
+        };
+
+        let next = if outcome.is_none_or(|outcome| outcome.is_success()) {
+            Some(&option.success)
+        } else {
+            option.failure.as_ref()
+        };
+        self.current = next.and_then(|id| self.dialogue.get(id));
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alignment::{Alignment, AlignmentCondition, Axis};
+    use character::Character;
+    use rand::{SeedableRng, XorShiftRng};
+    use skill_check::CheckOutcome;
+    use rustc_serialize::json::ToJson;
+
+    fn guard() -> Dialogue {
+        Dialogue::new(DialogueNode::new("gate", "Guard", "None shall pass.")
+                .option(DialogueOption::new("Please, it is urgent.", "open")
+                    .kind(OptionKind::Persuade(1000))
+                    .failure("angry"))
+                .option(DialogueOption::new("Move aside.", "open")
+                    .kind(OptionKind::Intimidate(-1000)))
+                .option(DialogueOption::new("For your trouble.", "open")
+                    .kind(OptionKind::Bribe(50)))
+                .option(DialogueOption::new("Hail the king!", "open")
+                    .condition(AlignmentCondition::AtLeast(Axis::Order, 30))))
+            .node(DialogueNode::new("open", "Guard", "Go on, then."))
+            .node(DialogueNode::new("angry", "Guard", "Begone!"))
+    }
+
+    #[test]
+    fn checks() {
+        let dialogue = guard();
+        let alignment = Alignment::new();
+        let mut character = Character::new("Michael");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let mut conversation = dialogue.start();
+        assert_eq!(conversation.options(&alignment).len(), 3);
+        assert_eq!(conversation.options(&alignment)[1].label(), "[Intimidate] Move aside.");
+
+        assert_eq!(conversation.choose(0, &alignment, &mut character, &mut rng),
+                   Ok(Some(CheckOutcome::Failure)));
+        assert_eq!(conversation.current().unwrap().id, "angry");
+
+        let mut conversation = dialogue.start();
+        assert_eq!(conversation.choose(1, &alignment, &mut character, &mut rng),
+                   Ok(Some(CheckOutcome::Success)));
+        assert_eq!(conversation.current().unwrap().id, "open");
+
+        let mut lawful = Alignment::new();
+        lawful.shift(Axis::Order, 40);
+        assert_eq!(dialogue.start().options(&lawful).len(), 4);
+    }
+
+    #[test]
+    fn bribe() {
+        let dialogue = guard();
+        let alignment = Alignment::new();
+        let mut character = Character::new("Michael");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let mut conversation = dialogue.start();
+        assert_eq!(conversation.choose(2, &alignment, &mut character, &mut rng),
+                   Err(DialogueError::NotEnoughGold));
+        character.inventory_mut().add_gold(60);
+        assert_eq!(conversation.choose(2, &alignment, &mut character, &mut rng), Ok(None));
+        assert_eq!(character.inventory().gold(), 10);
+        assert_eq!(conversation.current().unwrap().text, "Go on, then.");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let dialogue = guard();
+        assert_eq!(Dialogue::from_json(&dialogue.to_json()).unwrap(), dialogue);
+    }
+}
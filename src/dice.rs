@@ -0,0 +1,271 @@
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Decides how often a dice expression is rolled and which result is kept
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RollMode {
+    /// Roll once
+    Normal,
+    /// Roll twice and keep the higher result
+    Advantage,
+    /// Roll twice and keep the lower result
+    Disadvantage,
+}
+
+/// A dice expression in the standard notation, e.g. `2d6+3`, `d20` or `3d6!` (exploding)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Dice {
+    /// The amount of dice to roll
+    pub count: u32,
+    /// The amount of sides every die has
+    pub sides: u32,
+    /// A constant that is added to the sum of all dice
+    pub modifier: i64,
+    /// Whether a die that rolls its highest side is rolled again and added to the result
+    pub exploding: bool,
+}
+
+impl Dice {
+    /// Creates a new instance of `Dice` without a modifier.
+    ///
+    /// # Panics
+    ///
+    /// **Panics** if the dice have no sides
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rpg::dice::Dice;
+    /// let dice = Dice::new(2, 6).modifier(3);
+    /// assert_eq!(dice, "2d6+3".parse().unwrap());
+    /// ```
+    pub fn new(count: u32, sides: u32) -> Dice {
+        assert!(sides > 0);
+        Dice {
+            count,
+            sides,
+            modifier: 0,
+            exploding: false,
+        }
+    }
+
+    /// A builder method for setting the modifier
+    pub fn modifier(mut self, modifier: i64) -> Dice {
+        self.modifier = modifier;
+        self
+    }
+
+    /// A builder method for making the dice explode
+    pub fn exploding(mut self, exploding: bool) -> Dice {
+        self.exploding = exploding;
+        self
+    }
+
+    /// Returns the lowest possible result
+    pub fn min(&self) -> i64 {
+        self.count as i64 + self.modifier
+    }
+
+    /// Returns the highest possible result. Exploding dice may exceed this value
+    pub fn max(&self) -> i64 {
+        (self.count as i64) * (self.sides as i64) + self.modifier
+    }
+
+    /// Rolls the dice once
+    pub fn roll<R: Rng>(&self, rng: &mut R) -> i64 {
+        let mut sum = self.modifier;
+
+        for _ in 0..self.count {
+            loop {
+                // Shifted by one, so the upper bound does not overflow for `u32::MAX` sides
+                let result = rng.gen_range(0, self.sides) + 1;
+                sum += result as i64;
+
+                // A single sided die would explode forever
+                if !self.exploding || self.sides == 1 || result < self.sides {
+                    break;
+                }
+            }
+        }
+
+        sum
+    }
+
+    /// Rolls the dice according to the given mode
+    pub fn roll_with<R: Rng>(&self, mode: RollMode, rng: &mut R) -> i64 {
+        match mode {
+            RollMode::Normal => self.roll(rng),
+            RollMode::Advantage => {
+                let first = self.roll(rng);
+                let second = self.roll(rng);
+                if first > second { first } else { second }
+            }
+            RollMode::Disadvantage => {
+                let first = self.roll(rng);
+                let second = self.roll(rng);
+                if first < second { first } else { second }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+
+        if self.exploding {
+            write!(f, "!")?;
+        }
+
+        if self.modifier > 0 {
+            write!(f, "+{}", self.modifier)?;
+        } else if self.modifier < 0 {
+            write!(f, "{}", self.modifier)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned when a dice expression could not be parsed
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseDiceError {
+    /// The expression is missing the `d` separator
+    MissingSeparator,
+    /// The amount of dice is not a number
+    InvalidCount,
+    /// The amount of sides is not a positive number
+    InvalidSides,
+    /// The modifier is not a number
+    InvalidModifier,
+}
+
+impl ParseDiceError {
+    fn message(&self) -> &'static str {
+        match *self {
+            ParseDiceError::MissingSeparator => "missing 'd' in dice expression",
+            ParseDiceError::InvalidCount => "invalid amount of dice",
+            ParseDiceError::InvalidSides => "invalid amount of sides",
+            ParseDiceError::InvalidModifier => "invalid modifier",
+        }
+    }
+}
+
+impl fmt::Display for ParseDiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Error for ParseDiceError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl FromStr for Dice {
+    type Err = ParseDiceError;
+
+    fn from_str(s: &str) -> Result<Dice, ParseDiceError> {
+        let expression: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let expression = expression.to_lowercase();
+
+        let separator = expression.find('d').ok_or(ParseDiceError::MissingSeparator)?;
+        let (count, rest) = expression.split_at(separator);
+        let rest = &rest[1..];
+
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse::<u32>().map_err(|_| ParseDiceError::InvalidCount)?
+        };
+
+        let modifier_start = rest.find(['+', '-']).unwrap_or(rest.len());
+        let (sides, modifier) = rest.split_at(modifier_start);
+
+        let exploding = sides.ends_with('!');
+        let sides = if exploding { &sides[..sides.len() - 1] } else { sides };
+
+        let sides = sides.parse::<u32>().map_err(|_| ParseDiceError::InvalidSides)?;
+        if sides == 0 {
+            return Err(ParseDiceError::InvalidSides);
+        }
+
+        let modifier = if modifier.is_empty() {
+            0
+        } else if let Some(positive) = modifier.strip_prefix('+') {
+            positive.parse::<i64>().map_err(|_| ParseDiceError::InvalidModifier)?
+        } else {
+            modifier.parse::<i64>().map_err(|_| ParseDiceError::InvalidModifier)?
+        };
+
+        Ok(Dice::new(count, sides).modifier(modifier).exploding(exploding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn parse() {
+        assert_eq!("2d6+3".parse(), Ok(Dice::new(2, 6).modifier(3)));
+        assert_eq!("d20".parse(), Ok(Dice::new(1, 20)));
+        assert_eq!("1D8 - 1".parse(), Ok(Dice::new(1, 8).modifier(-1)));
+        assert_eq!("3d6!".parse(), Ok(Dice::new(3, 6).exploding(true)));
+
+        assert_eq!("20".parse::<Dice>(), Err(ParseDiceError::MissingSeparator));
+        assert_eq!("xd6".parse::<Dice>(), Err(ParseDiceError::InvalidCount));
+        assert_eq!("2d0".parse::<Dice>(), Err(ParseDiceError::InvalidSides));
+        assert_eq!("2d6+x".parse::<Dice>(), Err(ParseDiceError::InvalidModifier));
+    }
+
+    #[test]
+    fn display() {
+        for expression in &["2d6+3", "1d20", "4d4-2", "3d6!+1"] {
+            let dice: Dice = expression.parse().unwrap();
+            assert_eq!(&dice.to_string(), expression);
+        }
+    }
+
+    #[test]
+    fn roll_bounds() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let dice = Dice::new(2, 6).modifier(3);
+
+        for _ in 0..1000 {
+            let result = dice.roll(&mut rng);
+            assert!(result >= dice.min() && result <= dice.max());
+        }
+
+        let huge = Dice::new(1, u32::MAX);
+        let result = huge.roll(&mut rng);
+        assert!(result >= huge.min() && result <= huge.max());
+    }
+
+    #[test]
+    fn advantage_and_disadvantage() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let dice = Dice::new(1, 20);
+
+        let advantage: i64 = (0..2000).map(|_| dice.roll_with(RollMode::Advantage, &mut rng)).sum();
+        let disadvantage: i64 =
+            (0..2000).map(|_| dice.roll_with(RollMode::Disadvantage, &mut rng)).sum();
+
+        assert!(advantage > disadvantage);
+    }
+
+    #[test]
+    fn exploding() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let dice = Dice::new(1, 2).exploding(true);
+
+        let exploded = (0..1000).any(|_| dice.roll(&mut rng) > dice.max());
+
+        assert!(exploded);
+        assert_eq!(Dice::new(3, 1).exploding(true).roll(&mut rng), 3);
+    }
+}
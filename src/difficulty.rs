@@ -0,0 +1,149 @@
+use bestiary::MonsterDefinition;
+use loot::LootTable;
+use std::str::FromStr;
+use types::{AttributeValue, Experience, Health};
+
+/// How hard the game is. All numbers it changes are listed in its `Modifiers`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Difficulty {
+    /// Weaker enemies, more loot and experience
+    Easy,
+    /// The game as designed
+    #[default]
+    Normal,
+    /// Stronger enemies, less loot and experience
+    Hard,
+    /// Much stronger enemies and needs that grow twice as fast
+    Nightmare,
+}
+
+
+impl Difficulty {
+    /// Returns the multipliers of this difficulty
+    pub fn modifiers(&self) -> Modifiers {
+        let (enemy_health, enemy_damage, loot, experience, survival) = match *self {
+            Difficulty::Easy => (0.75, 0.75, 1.5, 1.25, 0.75),
+            Difficulty::Normal => (1.0, 1.0, 1.0, 1.0, 1.0),
+            Difficulty::Hard => (1.5, 1.25, 0.75, 0.9, 1.25),
+            Difficulty::Nightmare => (2.0, 1.5, 0.5, 0.75, 2.0),
+        };
+
+        Modifiers {
+            enemy_health,
+            enemy_damage,
+            loot,
+            experience,
+            survival,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Difficulty, ()> {
+        match s {
+            "Easy" => Ok(Difficulty::Easy),
+            "Normal" => Ok(Difficulty::Normal),
+            "Hard" => Ok(Difficulty::Hard),
+            "Nightmare" => Ok(Difficulty::Nightmare),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The multipliers a difficulty applies to the numbers of the game
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Modifiers {
+    /// The multiplier for the health of enemies
+    pub enemy_health: f64,
+    /// The multiplier for the damage of enemies
+    pub enemy_damage: f64,
+    /// The multiplier for the chance of every loot drop
+    pub loot: f64,
+    /// The multiplier for gained experience
+    pub experience: f64,
+    /// How much faster hunger, thirst and fatigue grow
+    pub survival: f64,
+}
+
+impl Modifiers {
+    /// Scales the health of an enemy. Enemies always keep at least 1 health.
+    pub fn enemy_health(&self, health: Health) -> Health {
+        ((health as f64 * self.enemy_health).round() as Health).max(1)
+    }
+
+    /// Scales the damage of an enemy attack
+    pub fn enemy_damage(&self, damage: AttributeValue) -> AttributeValue {
+        (damage as f64 * self.enemy_damage).round() as AttributeValue
+    }
+
+    /// Scales the chances of all drops of a loot table, without exceeding `1.0`
+    pub fn loot(&self, table: &LootTable) -> LootTable {
+        let mut table = table.clone();
+        for entry in &mut table.entries {
+            entry.chance = (entry.chance * self.loot).min(1.0);
+        }
+        for drop in &mut table.uniques {
+            drop.chance = (drop.chance * self.loot).min(1.0);
+        }
+
+        table
+    }
+
+    /// Scales gained experience
+    pub fn experience(&self, experience: Experience) -> Experience {
+        (experience as f64 * self.experience).round() as Experience
+    }
+
+    /// Scales the amount of turns after which a need grows. Needs grow at most once per turn.
+    pub fn need_interval(&self, interval: u64) -> u64 {
+        ((interval as f64 / self.survival).round() as u64).max(1)
+    }
+
+    /// Returns a copy of the monster with scaled health, damage and loot
+    pub fn monster(&self, monster: &MonsterDefinition) -> MonsterDefinition {
+        let mut monster = monster.clone();
+        monster.health = self.enemy_health(monster.health);
+        monster.attack_damage = self.enemy_damage(monster.attack_damage);
+        monster.loot = self.loot(&monster.loot);
+
+        monster
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::ItemType;
+    use loot::{LootEntry, LootTable};
+
+    #[test]
+    fn normal_changes_nothing() {
+        let modifiers = Difficulty::default().modifiers();
+
+        assert_eq!(modifiers.enemy_health(12), 12);
+        assert_eq!(modifiers.enemy_damage(4), 4);
+        assert_eq!(modifiers.experience(100), 100);
+        assert_eq!(modifiers.need_interval(10), 10);
+    }
+
+    #[test]
+    fn nightmare() {
+        let modifiers = Difficulty::Nightmare.modifiers();
+        let table = LootTable::new()
+            .entry(LootEntry::new(ItemType::WeaponSword, 0.5))
+            .entry(LootEntry::new(ItemType::Prop, 1.0));
+
+        assert_eq!(modifiers.enemy_health(12), 24);
+        assert_eq!(modifiers.need_interval(6), 3);
+        assert_eq!(modifiers.loot(&table).entries[0].chance, 0.25);
+        assert_eq!(Difficulty::Easy.modifiers().loot(&table).entries[1].chance, 1.0);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("Hard".parse(), Ok(Difficulty::Hard));
+        assert_eq!("Impossible".parse::<Difficulty>(), Err(()));
+    }
+}
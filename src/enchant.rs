@@ -0,0 +1,203 @@
+use character::Attribute;
+use inventory::Inventory;
+use item::{Item, ItemInfluence, ItemRarity};
+use rand::Rng;
+use types::{AttributeValue, Gold};
+
+/// The name of the material used up when enchanting at a crafting station
+pub const ENCHANTING_DUST: &str = "Enchanting Dust";
+
+/// The amount an enchantment adds to the influence of an item
+pub const ENCHANT_STEP: AttributeValue = 5;
+
+/// The chance of enchanting an item without an influence
+pub const BASE_CHANCE: f64 = 0.9;
+
+/// The factor the chance is multiplied with for every enchantment the item already has
+pub const CHANCE_FALLOFF: f64 = 0.7;
+
+/// The reasons why an item can not be enchanted
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EnchantError {
+    /// Only identified weapons and armor can be enchanted
+    NotEnchantable,
+    /// The item already has an influence on another attribute
+    OtherAttribute,
+    /// The character can not pay the price of the shop
+    NotEnoughGold,
+    /// The character does not carry enough `ENCHANTING_DUST`
+    NotEnoughMaterials,
+}
+
+/// Returns the amount of enchantments the item has, judged by the strength of its influence
+pub fn level(item: &Item) -> u32 {
+    match item.influence {
+        Some(ref influence) if influence.amount > 0 => (influence.amount / ENCHANT_STEP) as u32,
+        _ => 0,
+    }
+}
+
+/// Returns the chance of the next enchantment of the item to succeed, which shrinks with every
+/// enchantment
+pub fn chance(item: &Item) -> f64 {
+    BASE_CHANCE * CHANCE_FALLOFF.powi(level(item) as i32)
+}
+
+/// Returns the price a shop asks for the next enchantment of the item, which grows with its
+/// rarity and level
+pub fn price(item: &Item) -> Gold {
+    let base = match item.rarity {
+        ItemRarity::Common => 40,
+        ItemRarity::Uncommon => 80,
+        ItemRarity::Rare => 200,
+        ItemRarity::Epic => 500,
+        ItemRarity::Legendary => 1200,
+    };
+
+    base * (level(item) as Gold + 1)
+}
+
+/// Returns the amount of `ENCHANTING_DUST` the next enchantment of the item uses up
+pub fn materials(item: &Item) -> usize {
+    level(item) as usize + 1
+}
+
+/// Tries to add or upgrade the influence of the item on the given attribute by `ENCHANT_STEP`.
+/// Returns `Ok(false)` if the enchantment failed, which leaves the item unchanged.
+pub fn enchant<R: Rng>(item: &mut Item,
+                       attribute: &Attribute,
+                       rng: &mut R)
+                       -> Result<bool, EnchantError> {
+    check(item, attribute)?;
+
+    if rng.gen::<f64>() >= chance(item) {
+        return Ok(false);
+    }
+
+    match item.influence {
+        Some(ref mut influence) => influence.amount += ENCHANT_STEP,
+        None => item.influence = Some(ItemInfluence::new(attribute.clone(), ENCHANT_STEP)),
+    }
+
+    Ok(true)
+}
+
+/// Enchants the item at a shop, paying its `price` from the inventory even if the enchantment
+/// fails
+pub fn at_shop<R: Rng>(item: &mut Item,
+                       attribute: &Attribute,
+                       inventory: &mut Inventory,
+                       rng: &mut R)
+                       -> Result<bool, EnchantError> {
+    check(item, attribute)?;
+
+    if !inventory.spend_gold(price(item)) {
+        return Err(EnchantError::NotEnoughGold);
+    }
+
+    enchant(item, attribute, rng)
+}
+
+/// Enchants the item at a crafting station, using up its `materials` even if the enchantment
+/// fails
+pub fn with_materials<R: Rng>(item: &mut Item,
+                              attribute: &Attribute,
+                              inventory: &mut Inventory,
+                              rng: &mut R)
+                              -> Result<bool, EnchantError> {
+    check(item, attribute)?;
+
+    let materials = materials(item);
+    if inventory.count_by_name(ENCHANTING_DUST) < materials {
+        return Err(EnchantError::NotEnoughMaterials);
+    }
+
+    for _ in 0..materials {
+        inventory.take_by_name(ENCHANTING_DUST);
+    }
+
+    enchant(item, attribute, rng)
+}
+
+fn check(item: &Item, attribute: &Attribute) -> Result<(), EnchantError> {
+    if !item.identified || !item.item_type.is_equipable() {
+        return Err(EnchantError::NotEnchantable);
+    }
+
+    match item.influence {
+        Some(ref influence) if &influence.attribute != attribute => {
+            Err(EnchantError::OtherAttribute)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Attribute;
+    use inventory::Inventory;
+    use item::{Item, ItemInfluence, ItemRarity, ItemType};
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn sword(amount: AttributeValue) -> Item {
+        ItemGenerator::new()
+            .name("Anduril")
+            .item_type(ItemType::WeaponSword)
+            .rarity(ItemRarity::Common)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, amount)))
+            .gen()
+    }
+
+    #[test]
+    fn diminishing_chance() {
+        assert_eq!(level(&sword(3)), 0);
+        assert_eq!(level(&sword(10)), 2);
+        assert!(chance(&sword(10)) < chance(&sword(5)));
+        assert_eq!(price(&sword(10)), 120);
+        assert_eq!(materials(&sword(10)), 3);
+    }
+
+    #[test]
+    fn enchant_item() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut sword = sword(5);
+        assert_eq!(enchant(&mut sword, &Attribute::Wisdom, &mut rng),
+                   Err(EnchantError::OtherAttribute));
+
+        while !enchant(&mut sword, &Attribute::Strength, &mut rng).unwrap() {}
+        assert_eq!(sword.influence, Some(ItemInfluence::new(Attribute::Strength, 10)));
+
+        let mut apple = ItemGenerator::new().item_type(ItemType::ConsumableFood).gen();
+        assert_eq!(enchant(&mut apple, &Attribute::Strength, &mut rng),
+                   Err(EnchantError::NotEnchantable));
+    }
+
+    #[test]
+    fn shop_and_materials() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut inventory = Inventory::new(10);
+        let mut sword = sword(0);
+        sword.influence = None;
+
+        assert_eq!(at_shop(&mut sword, &Attribute::Dexterity, &mut inventory, &mut rng),
+                   Err(EnchantError::NotEnoughGold));
+        inventory.add_gold(50);
+        at_shop(&mut sword, &Attribute::Dexterity, &mut inventory, &mut rng).unwrap();
+        assert_eq!(inventory.gold(), 10);
+
+        let dust = ItemGenerator::new()
+            .name(ENCHANTING_DUST)
+            .item_type(ItemType::Usable)
+            .stack_size(10)
+            .gen();
+        assert_eq!(with_materials(&mut sword, &Attribute::Dexterity, &mut inventory, &mut rng),
+                   Err(EnchantError::NotEnoughMaterials));
+        for _ in 0..materials(&sword) {
+            inventory.add_item(dust.clone()).unwrap();
+        }
+        with_materials(&mut sword, &Attribute::Dexterity, &mut inventory, &mut rng).unwrap();
+        assert_eq!(inventory.count_by_name(ENCHANTING_DUST), 0);
+    }
+}
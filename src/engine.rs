@@ -1,9 +1,84 @@
+use achievements::Achievements;
+use alias::Aliases;
+use alignment::Alignment;
+use bounty::Bounties;
+use bus::{self, GameEvent, Listener};
+use character::Character;
+use data::{self, DataError};
+use difficulty::Difficulty;
+use faction::Reputation;
+use format::Format;
+use hint::Hints;
+use journal::Journal;
+use minigame::Gambling;
+use mount::Stable;
+use notes::Notes;
+use plugin::{Content, ContentPlugin};
+use replay::Replay;
+use rand::XorShiftRng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use saves;
+use sound::Sounds;
+use statistics::Statistics;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use transcript::Transcript;
+use world::progress::CampaignProgress;
+use world::two_dimensional::World2d;
+
 /// The state of the whole game
 #[allow(missing_docs)]
 pub struct EngineContext {
     pub maps: Vec<u32>,
     pub running: bool,
     pub invalid: bool,
+    /// If `true`, dead characters can not be resurrected
+    pub permadeath: bool,
+    /// If `true`, the save is deleted instead of locked when the game ends in permadeath mode
+    pub delete_on_death: bool,
+    /// `true` once the character died in permadeath mode. The save can not be continued.
+    pub game_over: bool,
+    /// The amount of turns the player may undo at once. `0` disables undoing.
+    pub undo_limit: usize,
+    /// How hard the game is
+    pub difficulty: Difficulty,
+    /// The progress in the campaign being played (optional)
+    pub progress: Option<CampaignProgress>,
+    /// The notes the player pinned to the map
+    pub notes: Notes,
+    /// The adventure log of the player
+    pub journal: Journal,
+    /// The achievements of the player and the progress towards them
+    pub achievements: Achievements,
+    /// The numbers collected over the whole save
+    pub statistics: Statistics,
+    /// The ids of the unique items found in this save, see `LootTable::roll_with_uniques`
+    pub uniques_found: BTreeSet<String>,
+    /// The commands given since the game was started and the seed of its randomness
+    pub replay: Replay,
+    /// The shortcuts the player defined for commands
+    pub aliases: Aliases,
+    /// The tips shown to the player and which of them were seen
+    pub hints: Hints,
+    /// Where the choices of the player put them between good and evil
+    pub alignment: Alignment,
+    /// The reputation of the player with every faction
+    pub reputation: Reputation,
+    /// The bounties the player accepted
+    pub bounties: Bounties,
+    /// The mounts of the player and the one they ride
+    pub mounts: Stable,
+    /// The cards the gamblers dealt for high-low
+    pub gambling: Gambling,
+    /// The file the commands and their output are recorded to (optional, not saved)
+    pub transcript: Option<Transcript>,
+    /// The sound cues played for game events (optional, not saved)
+    pub sounds: Option<Sounds>,
+    /// The character of the player commands like `gamble` act on (optional)
+    pub player: Option<Character>,
+    /// The world the player is in, including the items lying on the ground (optional)
+    pub world: Option<World2d>,
 }
 
 impl EngineContext {
@@ -13,8 +88,288 @@ impl EngineContext {
             maps: Vec::new(),
             running: false,
             invalid: false,
+            permadeath: false,
+            delete_on_death: false,
+            game_over: false,
+            undo_limit: 0,
+            difficulty: Difficulty::Normal,
+            progress: None,
+            notes: Notes::new(),
+            journal: Journal::new(),
+            achievements: Achievements::new(),
+            statistics: Statistics::new(),
+            uniques_found: BTreeSet::new(),
+            replay: Replay::random(),
+            aliases: Aliases::new(),
+            hints: Hints::built_in(),
+            alignment: Alignment::new(),
+            reputation: Reputation::new(),
+            bounties: Bounties::new(),
+            mounts: Stable::new(),
+            gambling: Gambling::new(),
+            transcript: None,
+            sounds: None,
+            player: None,
+            world: None,
         }
     }
+
+    /// Publishes a game event to all subsystems of the state. A death in permadeath mode ends the
+    /// game.
+    pub fn publish(&mut self, event: GameEvent) {
+        bus::publish(&event,
+                     &mut [&mut self.achievements,
+                           &mut self.statistics,
+                           &mut self.hints,
+                           &mut self.bounties]);
+        if let Some(ref mut sounds) = self.sounds {
+            sounds.notify(&event);
+        }
+
+        if event == GameEvent::CharacterDied && self.permadeath {
+            self.game_over = true;
+        }
+    }
+
+    /// Undoes the last turns by replaying the session without them on the state the session
+    /// started with. Returns the amount of undone turns, which is limited by `undo_limit`.
+    /// Nothing can be undone in permadeath mode.
+    pub fn undo<F>(&mut self, turns: usize, start: EngineContext, step: F) -> usize
+        where F: FnMut(&mut EngineContext, &str, &mut XorShiftRng)
+    {
+        if self.permadeath {
+            return 0;
+        }
+
+        let turns = turns.min(self.undo_limit).min(self.replay.commands().len());
+        if turns == 0 {
+            return 0;
+        }
+
+        let mut context = start;
+        self.replay.truncated(turns).play(&mut context, step);
+        context.running = self.running;
+        context.invalid = true;
+        context.undo_limit = self.undo_limit;
+        context.transcript = self.transcript.take();
+        context.sounds = self.sounds.take();
+        *self = context;
+
+        turns
+    }
+
+    /// Starts recording the commands executed by `Content::execute` and their output to a
+    /// transcript file, stamped with the turn, which is the amount of recorded commands
+    pub fn start_transcript<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DataError> {
+        self.transcript = Some(Transcript::create(path)?);
+        Ok(())
+    }
+
+    /// Returns the statistics of the game for the end screen, once the game is over
+    pub fn final_summary(&self) -> Option<String> {
+        if self.game_over {
+            Some(format!("Game Over\n{}", self.statistics.summary()))
+        } else {
+            None
+        }
+    }
+
+    /// Writes the state into a file in the given format, keeping the previous save as backup
+    /// (see `saves::write_save`). Once the game is over, the file is locked or, if
+    /// `delete_on_death` is set, deleted together with its backup.
+    pub fn save_file<P: AsRef<Path>>(&self, path: P, format: Format) -> Result<(), DataError> {
+        if self.game_over && self.delete_on_death {
+            for path in &[saves::backup_path(&path), path.as_ref().to_path_buf()] {
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            }
+            return Ok(());
+        }
+
+        saves::write_save(path, format, &self.to_json())
+    }
+
+    /// Reads the state from a file in the given format. Fails with `DataError::Corrupt` if the
+    /// file was damaged, in which case the backup at `saves::backup_path` can be loaded instead,
+    /// and with `DataError::Locked` if the game saved in the file is over.
+    pub fn load_file<P: AsRef<Path>>(path: P, format: Format) -> Result<EngineContext, DataError> {
+        let context = EngineContext::from_json(&saves::read_save(path, format)?)?;
+
+        match context.final_summary() {
+            Some(summary) => Err(DataError::Locked(summary)),
+            None => Ok(context),
+        }
+    }
+
+    /// Returns the journal as text, followed by the notes of the player
+    pub fn journal_text(&self) -> String {
+        let journal = self.journal.render();
+        if journal.is_empty() {
+            self.notes.journal_section()
+        } else {
+            format!("{}\n{}", journal, self.notes.journal_section())
+        }
+    }
+
+    /// Reads the saved parts of the state from a JSON object as written by `to_json`. The engine
+    /// is neither running nor invalid afterwards.
+    pub fn from_json(json: &Json) -> Result<EngineContext, DataError> {
+        let object = data::as_object(json, "context")?;
+
+        let maps = data::as_array(data::field(object, "maps")?, "maps")?
+            .iter()
+            .map(|map| {
+                map.as_u64()
+                    .map(|map| map as u32)
+                    .ok_or_else(|| DataError::Invalid("maps".to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let progress = match object.get("progress") {
+            None | Some(&Json::Null) => None,
+            Some(progress) => Some(CampaignProgress::from_json(progress)?),
+        };
+
+        let notes = match object.get("notes") {
+            None => Notes::new(),
+            Some(notes) => Notes::from_json(notes)?,
+        };
+
+        let journal = match object.get("journal") {
+            None => Journal::new(),
+            Some(journal) => Journal::from_json(journal)?,
+        };
+
+        let achievements = match object.get("achievements") {
+            None => Achievements::new(),
+            Some(achievements) => Achievements::from_json(achievements)?,
+        };
+
+        let statistics = match object.get("statistics") {
+            None => Statistics::new(),
+            Some(statistics) => Statistics::from_json(statistics)?,
+        };
+
+        let uniques_found = match object.get("uniques_found") {
+            None => BTreeSet::new(),
+            Some(found) => {
+                data::as_array(found, "uniques_found")?
+                    .iter()
+                    .map(|id| data::parse_value(id, "uniques_found"))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        let replay = match object.get("replay") {
+            None => Replay::random(),
+            Some(replay) => Replay::from_json(replay)?,
+        };
+
+        let aliases = match object.get("aliases") {
+            None => Aliases::new(),
+            Some(aliases) => Aliases::from_json(aliases)?,
+        };
+
+        let hints = match object.get("hints") {
+            None => Hints::built_in(),
+            Some(hints) => Hints::from_json(hints)?,
+        };
+
+        let alignment = match object.get("alignment") {
+            None => Alignment::new(),
+            Some(alignment) => Alignment::from_json(alignment)?,
+        };
+
+        let reputation = match object.get("reputation") {
+            None => Reputation::new(),
+            Some(reputation) => Reputation::from_json(reputation)?,
+        };
+
+        let bounties = match object.get("bounties") {
+            None => Bounties::new(),
+            Some(bounties) => Bounties::from_json(bounties)?,
+        };
+
+        let mounts = match object.get("mounts") {
+            None => Stable::new(),
+            Some(mounts) => Stable::from_json(mounts)?,
+        };
+
+        let player = match object.get("player") {
+            None | Some(&Json::Null) => None,
+            Some(player) => Some(Character::from_json(player)?),
+        };
+
+        let world = match object.get("world") {
+            None | Some(&Json::Null) => None,
+            Some(world) => Some(World2d::from_json(world)?),
+        };
+
+        let gambling = match object.get("gambling") {
+            None => Gambling::new(),
+            Some(gambling) => Gambling::from_json(gambling)?,
+        };
+
+        Ok(EngineContext {
+            maps,
+            permadeath: data::optional(object, "permadeath", data::boolean)?.unwrap_or(false),
+            delete_on_death: data::optional(object, "delete_on_death", data::boolean)?
+                .unwrap_or(false),
+            game_over: data::optional(object, "game_over", data::boolean)?.unwrap_or(false),
+            undo_limit: data::optional(object, "undo_limit", data::unsigned)?.unwrap_or(0) as usize,
+            difficulty: data::optional(object, "difficulty", data::parse)?.unwrap_or_default(),
+            progress,
+            notes,
+            journal,
+            achievements,
+            statistics,
+            uniques_found,
+            replay,
+            aliases,
+            hints,
+            alignment,
+            reputation,
+            bounties,
+            mounts,
+            gambling,
+            player,
+            world,
+            ..EngineContext::new()
+        })
+    }
+}
+
+impl ToJson for EngineContext {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("maps".to_owned(), self.maps.to_json());
+        object.insert("permadeath".to_owned(), self.permadeath.to_json());
+        object.insert("delete_on_death".to_owned(), self.delete_on_death.to_json());
+        object.insert("game_over".to_owned(), self.game_over.to_json());
+        object.insert("undo_limit".to_owned(), self.undo_limit.to_json());
+        object.insert("difficulty".to_owned(), format!("{:?}", self.difficulty).to_json());
+        object.insert("progress".to_owned(), self.progress.to_json());
+        object.insert("notes".to_owned(), self.notes.to_json());
+        object.insert("journal".to_owned(), self.journal.to_json());
+        object.insert("achievements".to_owned(), self.achievements.to_json());
+        object.insert("statistics".to_owned(), self.statistics.to_json());
+        let uniques_found: Vec<String> = self.uniques_found.iter().cloned().collect();
+        object.insert("uniques_found".to_owned(), uniques_found.to_json());
+        object.insert("replay".to_owned(), self.replay.to_json());
+        object.insert("aliases".to_owned(), self.aliases.to_json());
+        object.insert("hints".to_owned(), self.hints.to_json());
+        object.insert("alignment".to_owned(), self.alignment.to_json());
+        object.insert("reputation".to_owned(), self.reputation.to_json());
+        object.insert("bounties".to_owned(), self.bounties.to_json());
+        object.insert("mounts".to_owned(), self.mounts.to_json());
+        object.insert("gambling".to_owned(), self.gambling.to_json());
+        object.insert("player".to_owned(), self.player.to_json());
+        object.insert("world".to_owned(), self.world.to_json());
+
+        Json::Object(object)
+    }
 }
 
 /// The engine to run the game
@@ -22,6 +377,7 @@ pub struct Engine {
     setup: Option<Box<Fn(EngineContext) -> EngineContext>>,
     update: Option<Box<Fn(EngineContext) -> EngineContext>>,
     draw: Option<Box<Fn(EngineContext) -> EngineContext>>,
+    content: Content,
 }
 
 impl Engine {
@@ -31,6 +387,7 @@ impl Engine {
             setup: None,
             update: None,
             draw: None,
+            content: Content::new(),
         }
     }
 
@@ -55,6 +412,22 @@ impl Engine {
         self.draw = Some(Box::new(draw));
     }
 
+    /// Installs a plugin adding its content to the game. Fails if the plugin was installed before
+    /// or its content is invalid.
+    pub fn plugin<T: ContentPlugin>(&mut self, plugin: T) -> Result<(), DataError> {
+        self.content.install(&plugin)
+    }
+
+    /// Returns all content known to the game, including the content of plugins
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// Returns all content known to the game for adding content directly
+    pub fn content_mut(&mut self) -> &mut Content {
+        &mut self.content
+    }
+
     /// Start the engine
     pub fn start(&self) {
         let setup = self.setup.as_ref().unwrap();
@@ -83,7 +456,13 @@ impl Engine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use achievements::{Achievement, Trigger};
+    use bus::GameEvent;
     use character::Character;
+    use journal::EntryKind;
+    use rustc_serialize::json::{Json, ToJson};
+    use std::env;
+    use world::progress::CampaignProgress;
 
 
     #[test]
@@ -115,4 +494,92 @@ mod tests {
         // Start the engine => run the game
         engine.start();
     }
+
+    #[test]
+    fn publish_events() {
+        let mut context = EngineContext::new();
+        context.achievements.add(Achievement::new("first_blood", "First Blood", Trigger::Kills(1)));
+
+        context.publish(GameEvent::MonsterKilled("goblin".to_owned()));
+        let loaded = EngineContext::from_json(&context.to_json()).unwrap();
+
+        assert!(loaded.achievements.is_unlocked("first_blood"));
+        assert_eq!(loaded.statistics.monsters_killed, 1);
+    }
+
+    #[test]
+    fn undo() {
+        fn step(context: &mut EngineContext, command: &str, _: &mut XorShiftRng) {
+            if command == "walk" {
+                context.publish(GameEvent::Walked);
+            }
+        }
+
+        let mut context = EngineContext::new();
+        for _ in 0..5 {
+            let mut rng = context.replay.record("walk");
+            step(&mut context, "walk", &mut rng);
+        }
+        assert_eq!(context.undo(2, EngineContext::new(), step), 0);
+
+        context.undo_limit = 3;
+        assert_eq!(context.undo(10, EngineContext::new(), step), 3);
+        assert_eq!(context.statistics.tiles_walked, 2);
+        assert_eq!(context.replay.commands().len(), 2);
+        assert_eq!(context.undo_limit, 3);
+
+        context.permadeath = true;
+        assert_eq!(context.undo(1, EngineContext::new(), step), 0);
+    }
+
+    #[test]
+    fn hardcore() {
+        let path = env::temp_dir().join("txtrpg_hardcore.json");
+
+        let mut context = EngineContext::new();
+        context.publish(GameEvent::CharacterDied);
+        assert_eq!(context.final_summary(), None);
+
+        context.permadeath = true;
+        context.publish(GameEvent::CharacterDied);
+        context.save_file(&path, Format::Json).unwrap();
+        match EngineContext::load_file(&path, Format::Json) {
+            Err(DataError::Locked(summary)) => assert!(summary.contains("Deaths: 2")),
+            _ => panic!("expected a locked save"),
+        }
+
+        context.delete_on_death = true;
+        context.save_file(&path, Format::Json).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn legacy_save() {
+        let loaded = EngineContext::from_json(&Json::from_str(r#"{"maps": [1]}"#).unwrap())
+            .unwrap();
+
+        assert!(!loaded.permadeath);
+        assert!(!loaded.game_over);
+    }
+
+    #[test]
+    fn save_progress() {
+        let mut context = EngineContext::new();
+        context.permadeath = true;
+        context.difficulty = Difficulty::Hard;
+        context.progress = Some(CampaignProgress::new("tavern"));
+        context.notes.pin("tavern", (2, 3), "the bard owes me gold");
+        context.journal.record(7, EntryKind::Dialogue, "The bard sang of a dragon");
+
+        let loaded = EngineContext::from_json(&context.to_json()).unwrap();
+
+        assert!(loaded.permadeath);
+        assert_eq!(loaded.difficulty, Difficulty::Hard);
+        assert_eq!(loaded.progress, context.progress);
+        assert_eq!(loaded.notes, context.notes);
+        assert_eq!(loaded.journal, context.journal);
+        assert_eq!(loaded.journal_text(),
+                   "Dialogue\n- [Turn 7] The bard sang of a dragon\n\nNotes\n- tavern (2, 3): \
+                    the bard owes me gold\n");
+    }
 }
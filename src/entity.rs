@@ -1,11 +1,21 @@
 use behaviour::Behaviour;
+use data::{self, DataError};
 use event::Event;
+use item::Item;
+use lock::Lock;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::mem;
+
+/// The response of an entity that is opened while being locked
+const LOCKED_RESPONSE: &str = "It is locked.";
 
 /// Any non-character element
 #[derive(Clone)]
 pub struct Entity {
     name: String,
     behaviour: Vec<Box<Behaviour>>,
+    lock: Option<Lock>,
+    contents: Option<Vec<Item>>,
 }
 
 impl Entity {
@@ -14,18 +24,112 @@ impl Entity {
         Entity {
             name: name.to_owned(),
             behaviour: Vec::new(),
+            lock: None,
+            contents: None,
         }
     }
 
+    /// Returns the name of the entity
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A builder method for putting a lock on the entity, e.g. a door or a chest
+    pub fn with_lock(mut self, lock: Lock) -> Entity {
+        self.lock = Some(lock);
+        self
+    }
+
+    /// A builder method for turning the entity into a container holding the given items, e.g. a
+    /// chest or a corpse
+    pub fn container(mut self, items: Vec<Item>) -> Entity {
+        self.contents = Some(items);
+        self
+    }
+
+    /// Returns the items of a container (optional)
+    pub fn contents(&self) -> Option<&[Item]> {
+        self.contents.as_ref().map(|items| &items[..])
+    }
+
+    /// Takes the item with the given name out of the container
+    pub fn take_item(&mut self, name: &str) -> Option<Item> {
+        let items = self.contents.as_mut()?;
+        let index = items.iter().position(|item| item.name == name)?;
+
+        Some(items.remove(index))
+    }
+
+    /// Takes all items out of the container
+    pub fn take_all(&mut self) -> Vec<Item> {
+        self.contents.as_mut().map_or_else(Vec::new, mem::take)
+    }
+
+    /// Returns the lock of the entity (optional)
+    pub fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+
+    /// Returns the lock of the entity as mutable (optional)
+    pub fn lock_mut(&mut self) -> Option<&mut Lock> {
+        self.lock.as_mut()
+    }
+
+    /// Returns `true` if the entity has a lock that is locked
+    pub fn is_locked(&self) -> bool {
+        self.lock.as_ref().is_some_and(|lock| lock.is_locked())
+    }
+
     /// Adds a behaviour ot the behaviour chain of the entity
     pub fn append_behaviour<T: Behaviour + 'static>(&mut self, behaviour: T) {
         self.behaviour.push(Box::new(behaviour));
     }
 
-    /// Sends and event to the entity
+    /// Sends and event to the entity. Locked entities refuse to be opened, opened containers
+    /// offer their items.
     pub fn send_event(&self, event: Event) -> Event {
+        if event == Event::Open && self.is_locked() {
+            return Event::Tell(LOCKED_RESPONSE.to_owned());
+        }
+        if let (&Event::Open, Some(items)) = (&event, self.contents()) {
+            return Event::Options(items.iter().cloned().map(Event::Give).collect());
+        }
+
         self.handle_event(event)
     }
+
+    /// Reads an entity from a JSON object as written by `to_json`. Behaviours are not saved, a
+    /// loaded entity only responds through its lock and contents.
+    pub fn from_json(json: &Json) -> Result<Entity, DataError> {
+        let object = data::as_object(json, "entity")?;
+
+        let mut entity = Entity::new(&data::string(object, "name")?);
+        if let Some(lock) = object.get("lock") {
+            entity.lock = Some(Lock::from_json(lock)?);
+        }
+        if let Some(contents) = object.get("contents") {
+            let items = data::as_array(contents, "contents")?;
+            entity.contents = Some(items.iter().map(Item::from_json).collect::<Result<_, _>>()?);
+        }
+
+        Ok(entity)
+    }
+}
+
+impl ToJson for Entity {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("name".to_owned(), self.name.to_json());
+        if let Some(ref lock) = self.lock {
+            object.insert("lock".to_owned(), lock.to_json());
+        }
+        if let Some(ref contents) = self.contents {
+            object.insert("contents".to_owned(), contents.to_json());
+        }
+
+        Json::Object(object)
+    }
 }
 
 impl Behaviour for Entity {
@@ -51,6 +155,8 @@ mod tests {
     use super::*;
     use behaviour::DefaultResponse;
     use event::Event;
+    use item_generator::ItemGenerator;
+    use lock::Lock;
 
     #[test]
     fn append_behaviour() {
@@ -65,6 +171,15 @@ mod tests {
         assert_eq!(res, Event::Tell("Response!".to_owned()));
     }
 
+    #[test]
+    fn open_container() {
+        let coin = ItemGenerator::new().name("Coin").gen();
+        let chest = Entity::new("Chest").container(vec![coin.clone()]);
+
+        assert_eq!(chest.send_event(Event::Open), Event::Options(vec![Event::Give(coin)]));
+        assert_eq!(Entity::new("Rock").contents(), None);
+    }
+
     #[test]
     fn clone_entity() {
         let entity = Entity::new("TestSubject");
@@ -88,4 +203,19 @@ mod tests {
 
         assert_eq!(res, Event::Tell("Response 2!".to_owned()));
     }
+
+    #[test]
+    fn locked_entity() {
+        let key = ItemGenerator::new().name("Chest Key").gen();
+        let mut chest = Entity::new("Chest").with_lock(Lock::new(15).key(&key.id));
+        chest.append_behaviour(DefaultResponse::new("The chest creaks open."));
+
+        assert_eq!(chest.send_event(Event::Open),
+                   Event::Tell(LOCKED_RESPONSE.to_owned()));
+
+        chest.lock_mut().unwrap().unlock_with(&key);
+
+        assert_eq!(chest.send_event(Event::Open),
+                   Event::Tell("The chest creaks open.".to_owned()));
+    }
 }
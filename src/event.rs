@@ -17,6 +17,12 @@ pub enum Event {
     Open,
     /// Close an entity
     Close,
+    /// Ask an entity to join the party
+    Recruit,
+    /// Ask an entity to leave the party
+    Dismiss,
+    /// A character died
+    Death,
     /// Do nothing
     Nothing,
 }
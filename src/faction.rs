@@ -0,0 +1,125 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// How far the reputation with a faction reaches in either direction
+pub const REPUTATION_LIMIT: i32 = 100;
+
+/// How a faction treats the player, from the worst to the best
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Standing {
+    /// Refuses to deal with the player
+    Hostile,
+    /// Deals with the player reluctantly
+    Unfriendly,
+    /// Neither likes nor dislikes the player
+    Neutral,
+    /// Likes the player
+    Friendly,
+    /// Counts the player as one of their own
+    Allied,
+}
+
+impl Standing {
+    /// Returns the standing for the reputation value
+    pub fn of(value: i32) -> Standing {
+        match value {
+            value if value <= -50 => Standing::Hostile,
+            value if value <= -10 => Standing::Unfriendly,
+            value if value < 10 => Standing::Neutral,
+            value if value < 50 => Standing::Friendly,
+            _ => Standing::Allied,
+        }
+    }
+}
+
+impl FromStr for Standing {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Standing, ()> {
+        match s {
+            "Hostile" => Ok(Standing::Hostile),
+            "Unfriendly" => Ok(Standing::Unfriendly),
+            "Neutral" => Ok(Standing::Neutral),
+            "Friendly" => Ok(Standing::Friendly),
+            "Allied" => Ok(Standing::Allied),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The reputation of the player with every faction, e.g. a town or a guild. Factions the player
+/// never dealt with are neutral.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Reputation {
+    factions: BTreeMap<String, i32>,
+}
+
+impl Reputation {
+    /// Creates a new instance of `Reputation`, neutral with every faction
+    pub fn new() -> Reputation {
+        Reputation::default()
+    }
+
+    /// Returns the reputation with the faction, between `-REPUTATION_LIMIT` and
+    /// `REPUTATION_LIMIT`
+    pub fn get(&self, faction: &str) -> i32 {
+        self.factions.get(faction).cloned().unwrap_or(0)
+    }
+
+    /// Changes the reputation with the faction by the amount
+    pub fn change(&mut self, faction: &str, amount: i32) {
+        let value = self.factions.entry(faction.to_owned()).or_insert(0);
+        *value = (*value + amount).clamp(-REPUTATION_LIMIT, REPUTATION_LIMIT);
+    }
+
+    /// Returns how the faction treats the player
+    pub fn standing(&self, faction: &str) -> Standing {
+        Standing::of(self.get(faction))
+    }
+
+    /// Reads the reputation from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Reputation, DataError> {
+        let mut reputation = Reputation::new();
+
+        for (faction, value) in data::as_object(json, "reputation")? {
+            let value = value.as_i64()
+                .ok_or_else(|| DataError::Invalid("reputation".to_owned()))?;
+            reputation.change(faction, value as i32);
+        }
+
+        Ok(reputation)
+    }
+}
+
+impl ToJson for Reputation {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        for (faction, value) in &self.factions {
+            object.insert(faction.clone(), value.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn standing() {
+        let mut reputation = Reputation::new();
+        assert_eq!(reputation.standing("Thieves Guild"), Standing::Neutral);
+
+        reputation.change("Thieves Guild", -60);
+        reputation.change("Riverwood", 30);
+        reputation.change("Riverwood", 300);
+        assert_eq!(reputation.standing("Thieves Guild"), Standing::Hostile);
+        assert_eq!(reputation.get("Riverwood"), REPUTATION_LIMIT);
+        assert!(reputation.standing("Riverwood") > Standing::Friendly);
+        assert_eq!(Reputation::from_json(&reputation.to_json()).unwrap(), reputation);
+    }
+}
@@ -0,0 +1,90 @@
+use character::{Attribute, Character};
+use rand::Rng;
+use skill_check::{CheckOutcome, DIFFICULTY_EASY};
+use types::{AttributeValue, Health};
+use world::two_dimensional::World2d;
+
+/// The highest drop a character can jump down without getting hurt
+pub const SAFE_DROP: u32 = 2;
+
+/// The damage taken per level of height beyond `SAFE_DROP`
+pub const DAMAGE_PER_LEVEL: Health = 4;
+
+/// Returns the damage of an unbroken fall from the given height
+pub fn fall_damage(drop: u32) -> Health {
+    if drop > SAFE_DROP {
+        ((drop - SAFE_DROP) as Health) * DAMAGE_PER_LEVEL
+    } else {
+        0
+    }
+}
+
+/// Returns the damage of a fall after an `Attribute::Dexterity` check with the given outcome. A
+/// success halves the damage, a critical success avoids it and a critical failure doubles it.
+pub fn reduce_damage(damage: Health, outcome: CheckOutcome) -> Health {
+    match outcome {
+        CheckOutcome::CriticalSuccess => 0,
+        CheckOutcome::Success => damage / 2,
+        CheckOutcome::Failure => damage,
+        CheckOutcome::CriticalFailure => damage * 2,
+    }
+}
+
+/// Lets the character step from one position to the other and deals fall damage if the drop is
+/// too high. The higher the drop, the harder the `Attribute::Dexterity` check to land safely.
+/// Returns the damage taken.
+pub fn fall<R: Rng>(character: &mut Character,
+                    world: &World2d,
+                    from: (usize, usize),
+                    to: (usize, usize),
+                    rng: &mut R)
+                    -> Health {
+    let drop = world.height_drop(from, to);
+    let damage = fall_damage(drop);
+    if damage == 0 {
+        return 0;
+    }
+
+    let difficulty = DIFFICULTY_EASY + drop as AttributeValue;
+    let outcome = character.skill_check(&Attribute::Dexterity, difficulty, rng);
+    let damage = reduce_damage(damage, outcome);
+
+    character.take_damage(damage);
+    damage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use rand::{SeedableRng, XorShiftRng};
+    use skill_check::CheckOutcome;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    #[test]
+    fn damage() {
+        assert_eq!(fall_damage(SAFE_DROP), 0);
+        assert_eq!(fall_damage(SAFE_DROP + 3), 3 * DAMAGE_PER_LEVEL);
+
+        assert_eq!(reduce_damage(8, CheckOutcome::CriticalSuccess), 0);
+        assert_eq!(reduce_damage(8, CheckOutcome::Success), 4);
+        assert_eq!(reduce_damage(8, CheckOutcome::CriticalFailure), 16);
+    }
+
+    #[test]
+    fn falling() {
+        let mut world = World2d::new("Cliffs of Insanity", (3, 3));
+        world.set_field(Field::new(FieldType::Stone).height(5), (0, 0));
+        world.set_field(Field::new(FieldType::Stone).height(4), (1, 0));
+
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Westley");
+        let health = character.health();
+
+        assert_eq!(fall(&mut character, &world, (0, 0), (1, 0), &mut rng), 0);
+        assert_eq!(fall(&mut character, &world, (2, 0), (0, 0), &mut rng), 0);
+
+        assert_eq!(fall(&mut character, &world, (0, 0), (2, 0), &mut rng), 12);
+        assert_eq!(character.health(), health - 12);
+    }
+}
@@ -0,0 +1,134 @@
+use data::DataError;
+use rustc_serialize::json::Json;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Reading and writing data in Rusty Object Notation
+pub mod ron;
+/// Reading and writing data in TOML
+pub mod toml;
+
+/// A text format game data can be stored in. All formats are converted from and to the same
+/// JSON values, so everything that can be loaded from JSON can be loaded from the other formats
+/// as well.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    /// JavaScript Object Notation
+    Json,
+    /// Rusty Object Notation, which allows comments and trailing commas
+    Ron,
+    /// Tom's Obvious, Minimal Language, which is easy to edit by hand
+    Toml,
+}
+
+impl Format {
+    /// Returns the usual file extension of the format
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            Format::Json => "json",
+            Format::Ron => "ron",
+            Format::Toml => "toml",
+        }
+    }
+
+    /// Returns the format belonging to the extension of the given path
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        path.as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.parse().ok())
+    }
+
+    /// Parses the given text
+    pub fn parse(&self, text: &str) -> Result<Json, DataError> {
+        match *self {
+            Format::Json => Ok(Json::from_str(text)?),
+            Format::Ron => ron::parse(text),
+            Format::Toml => toml::parse(text),
+        }
+    }
+
+    /// Writes the given value as text. Fails if the format cannot express the value, e.g.
+    /// `null` in TOML.
+    pub fn write(&self, value: &Json) -> Result<String, DataError> {
+        match *self {
+            Format::Json => Ok(format!("{}", value.pretty())),
+            Format::Ron => Ok(ron::write(value)),
+            Format::Toml => toml::write(value),
+        }
+    }
+
+    /// Reads and parses the file at the given path
+    pub fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Json, DataError> {
+        let mut contents = String::new();
+        File::open(path)?.read_to_string(&mut contents)?;
+
+        self.parse(&contents)
+    }
+
+    /// Writes the given value into a file at the given path. The value is written into a
+    /// temporary file first, which then replaces the file, so a failed write never leaves a
+    /// truncated file behind.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P, value: &Json) -> Result<(), DataError> {
+        write_atomic(path, &self.write(value)?)
+    }
+}
+
+/// Writes the text into a temporary file next to the given path, which then replaces the file
+pub fn write_atomic<P: AsRef<Path>>(path: P, text: &str) -> Result<(), DataError> {
+    let path = path.as_ref();
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    {
+        let mut file = File::create(&temporary)?;
+        file.write_all(text.as_bytes())?;
+        file.sync_all()?;
+    }
+    fs::rename(&temporary, path)?;
+
+    Ok(())
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Format, ()> {
+        match &s.to_lowercase()[..] {
+            "json" => Ok(Format::Json),
+            "ron" => Ok(Format::Ron),
+            "toml" => Ok(Format::Toml),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+
+    #[test]
+    fn extensions() {
+        assert_eq!(Format::from_path("levels/village.toml"), Some(Format::Toml));
+        assert_eq!(Format::from_path("campaign.RON"), Some(Format::Ron));
+        assert_eq!(Format::from_path("notes.txt"), None);
+        assert_eq!(Format::Json.extension(), "json");
+    }
+
+    #[test]
+    fn same_values() {
+        let json = Json::from_str(r#"{"name": "Village", "size": [2, 1], "dark": false,
+                                      "fields": [{"type": "Grass", "height": 0},
+                                                 {"type": "Stone", "height": -1}],
+                                      "spawn": {"monster": "rat", "chance": 0.25}}"#)
+            .unwrap();
+
+        for format in &[Format::Json, Format::Ron, Format::Toml] {
+            let text = format.write(&json).unwrap();
+            assert_eq!(format.parse(&text).unwrap(), json);
+        }
+    }
+}
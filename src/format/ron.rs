@@ -0,0 +1,541 @@
+use data::DataError;
+use rustc_serialize::json::{Json, Object};
+
+/// The amount of characters up to which an object of plain values is written on a single line
+const INLINE_WIDTH: usize = 60;
+
+/// Writes a value in RON. Objects are written as structs like `(name: "Village")`, or as maps
+/// like `{"first key": 1}` if their keys are no identifiers. `null` is written as `None`.
+pub fn write(value: &Json) -> String {
+    let mut out = String::new();
+    write_value(value, 0, &mut out);
+    out.push('\n');
+    out
+}
+
+/// Parses RON into the same values `write` takes. Structs and maps become objects, tuples and
+/// lists become arrays, unit variants like `Grass` become strings and `Some(value)` becomes the
+/// value itself.
+pub fn parse(text: &str) -> Result<Json, DataError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+    };
+
+    let value = parser.value()?;
+    parser.skip_whitespace()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+
+    Ok(value)
+}
+
+fn write_value(value: &Json, indent: usize, out: &mut String) {
+    match *value {
+        Json::Null => out.push_str("None"),
+        Json::Boolean(value) => out.push_str(&value.to_string()),
+        Json::I64(value) => out.push_str(&value.to_string()),
+        Json::U64(value) => out.push_str(&value.to_string()),
+        Json::F64(value) => out.push_str(&format!("{:?}", value)),
+        Json::String(ref value) => write_string(value, out),
+        Json::Array(ref values) => {
+            if values.iter().all(is_plain) {
+                out.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    write_value(value, indent, out);
+                }
+                out.push(']');
+                return;
+            }
+
+            out.push_str("[\n");
+            for value in values {
+                push_indent(indent + 1, out);
+                write_value(value, indent + 1, out);
+                out.push_str(",\n");
+            }
+            push_indent(indent, out);
+            out.push(']');
+        }
+        Json::Object(ref object) => write_object(object, indent, out),
+    }
+}
+
+fn write_object(object: &Object, indent: usize, out: &mut String) {
+    let is_struct = object.keys().all(|key| is_identifier(key));
+    let (open, close) = if is_struct { ('(', ')') } else { ('{', '}') };
+
+    let write_key = |key: &str, out: &mut String| if is_struct {
+        out.push_str(key);
+    } else {
+        write_string(key, out);
+    };
+
+    if object.values().all(is_plain) {
+        let mut line = String::new();
+        line.push(open);
+        for (index, (key, value)) in object.iter().enumerate() {
+            if index > 0 {
+                line.push_str(", ");
+            }
+            write_key(key, &mut line);
+            line.push_str(": ");
+            write_value(value, indent, &mut line);
+        }
+        line.push(close);
+
+        if line.len() <= INLINE_WIDTH {
+            out.push_str(&line);
+            return;
+        }
+    }
+
+    out.push(open);
+    out.push('\n');
+    for (key, value) in object {
+        push_indent(indent + 1, out);
+        write_key(key, out);
+        out.push_str(": ");
+        write_value(value, indent + 1, out);
+        out.push_str(",\n");
+    }
+    push_indent(indent, out);
+    out.push(close);
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            character if character.is_control() => {
+                out.push_str(&format!("\\u{{{:x}}}", character as u32))
+            }
+            character => out.push(character),
+        }
+    }
+    out.push('"');
+}
+
+fn push_indent(indent: usize, out: &mut String) {
+    for _ in 0..indent {
+        out.push_str("    ");
+    }
+}
+
+/// Returns `true` if the value contains no arrays or objects
+fn is_plain(value: &Json) -> bool {
+    !matches!(*value, Json::Array(_) | Json::Object(_))
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' => {
+            chars.all(|character| character.is_alphanumeric() || character == '_')
+        }
+        _ => false,
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek();
+        self.position += 1;
+        character
+    }
+
+    fn error(&self, message: &str) -> DataError {
+        let line = self.chars[..self.position.min(self.chars.len())]
+            .iter()
+            .filter(|&&character| character == '\n')
+            .count() + 1;
+
+        DataError::Syntax(format!("{} at line {}", message, line))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), DataError> {
+        self.skip_whitespace()?;
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected)))
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), DataError> {
+        loop {
+            match (self.peek(), self.chars.get(self.position + 1).cloned()) {
+                (Some(character), _) if character.is_whitespace() => self.position += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|character| character != '\n') {
+                        self.position += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.position += 2;
+                    while self.peek().is_some() &&
+                          !self.chars[self.position..].starts_with(&['*', '/']) {
+                        self.position += 1;
+                    }
+                    if self.peek().is_none() {
+                        return Err(self.error("unterminated comment"));
+                    }
+                    self.position += 2;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, DataError> {
+        self.skip_whitespace()?;
+
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                self.struct_body()
+            }
+            Some('{') => self.map(),
+            Some('[') => self.list(),
+            Some('"') => self.string().map(Json::String),
+            Some('\'') => {
+                self.position += 1;
+                let character = match self.next() {
+                    Some('\\') => self.escape()?,
+                    Some(character) => character,
+                    None => return Err(self.error("unterminated character")),
+                };
+                if self.next() != Some('\'') {
+                    return Err(self.error("unterminated character"));
+                }
+                Ok(Json::String(character.to_string()))
+            }
+            Some('r') if self.is_raw_string() => self.raw_string().map(Json::String),
+            Some(character) if character.is_ascii_digit() || character == '-' || character == '+' ||
+                               character == '.' => self.number(),
+            Some(character) if character.is_alphabetic() || character == '_' => {
+                let identifier = self.identifier();
+
+                match &identifier[..] {
+                    "true" => Ok(Json::Boolean(true)),
+                    "false" => Ok(Json::Boolean(false)),
+                    "None" => Ok(Json::Null),
+                    "inf" => Ok(Json::F64(f64::INFINITY)),
+                    "NaN" => Ok(Json::F64(f64::NAN)),
+                    _ => {
+                        self.skip_whitespace()?;
+                        if self.peek() == Some('(') {
+                            self.position += 1;
+                            self.struct_body()
+                        } else {
+                            Ok(Json::String(identifier))
+                        }
+                    }
+                }
+            }
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut identifier = String::new();
+
+        while let Some(character) = self.peek() {
+            if !character.is_alphanumeric() && character != '_' {
+                break;
+            }
+            identifier.push(character);
+            self.position += 1;
+        }
+
+        identifier
+    }
+
+    /// Parses the rest of a struct like `name: "Village")` or a tuple like `1, 2)`
+    fn struct_body(&mut self) -> Result<Json, DataError> {
+        self.skip_whitespace()?;
+
+        let start = self.position;
+        let is_struct = match self.peek() {
+            Some(character) if character.is_alphabetic() || character == '_' => {
+                self.identifier();
+                self.skip_whitespace()?;
+                self.peek() == Some(':')
+            }
+            Some(')') => true,
+            _ => false,
+        };
+        self.position = start;
+
+        if is_struct {
+            let mut object = Object::new();
+            while !self.closes(')')? {
+                let key = self.identifier();
+                self.expect(':')?;
+                let value = self.value()?;
+                if object.insert(key, value).is_some() {
+                    return Err(self.error("duplicate field"));
+                }
+                self.separator(')')?;
+            }
+            return Ok(Json::Object(object));
+        }
+
+        let mut values = Vec::new();
+        while !self.closes(')')? {
+            values.push(self.value()?);
+            self.separator(')')?;
+        }
+
+        if values.len() == 1 {
+            Ok(values.remove(0))
+        } else {
+            Ok(Json::Array(values))
+        }
+    }
+
+    fn map(&mut self) -> Result<Json, DataError> {
+        self.expect('{')?;
+
+        let mut object = Object::new();
+        while !self.closes('}')? {
+            let key = match self.value()? {
+                Json::String(key) => key,
+                _ => return Err(self.error("map keys have to be strings")),
+            };
+            self.expect(':')?;
+            let value = self.value()?;
+            if object.insert(key, value).is_some() {
+                return Err(self.error("duplicate key"));
+            }
+            self.separator('}')?;
+        }
+
+        Ok(Json::Object(object))
+    }
+
+    fn list(&mut self) -> Result<Json, DataError> {
+        self.expect('[')?;
+
+        let mut values = Vec::new();
+        while !self.closes(']')? {
+            values.push(self.value()?);
+            self.separator(']')?;
+        }
+
+        Ok(Json::Array(values))
+    }
+
+    /// Consumes the closing character and returns `true` if it comes next
+    fn closes(&mut self, close: char) -> Result<bool, DataError> {
+        self.skip_whitespace()?;
+        if self.peek() == Some(close) {
+            self.position += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Consumes the comma between two elements, which may only be left out before the end
+    fn separator(&mut self, close: char) -> Result<(), DataError> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(character) if character == close => Ok(()),
+            _ => Err(self.error(&format!("expected `,` or `{}`", close))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, DataError> {
+        self.position += 1;
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(character) => string.push(character),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn is_raw_string(&self) -> bool {
+        matches!(self.chars.get(self.position + 1), Some(&'"') | Some(&'#'))
+    }
+
+    fn raw_string(&mut self) -> Result<String, DataError> {
+        self.position += 1;
+        let mut hashes = 0;
+        while self.peek() == Some('#') {
+            hashes += 1;
+            self.position += 1;
+        }
+        if self.next() != Some('"') {
+            return Err(self.error("expected `\"`"));
+        }
+
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') if self.closing_hashes() >= hashes => {
+                    self.position += hashes;
+                    return Ok(string);
+                }
+                Some(character) => string.push(character),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn closing_hashes(&self) -> usize {
+        self.chars[self.position..].iter().take_while(|&&character| character == '#').count()
+    }
+
+    fn escape(&mut self) -> Result<char, DataError> {
+        match self.next() {
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('0') => Ok('\0'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some(character @ '"') | Some(character @ '\'') | Some(character @ '\\') |
+            Some(character @ '/') => Ok(character),
+            Some('u') => {
+                let braced = self.peek() == Some('{');
+                if braced {
+                    self.position += 1;
+                }
+
+                let mut digits = String::new();
+                while let Some(character) = self.peek() {
+                    if !character.is_ascii_hexdigit() || (!braced && digits.len() == 4) {
+                        break;
+                    }
+                    digits.push(character);
+                    self.position += 1;
+                }
+                if braced && self.next() != Some('}') {
+                    return Err(self.error("invalid unicode escape"));
+                }
+
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(::std::char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape"))
+            }
+            _ => Err(self.error("invalid escape")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, DataError> {
+        let mut number = String::new();
+
+        while let Some(character) = self.peek() {
+            match character {
+                '0'...'9' | '-' | '+' | '.' | 'e' | 'E' => number.push(character),
+                '_' => {}
+                _ => break,
+            }
+            self.position += 1;
+        }
+
+        if self.peek() == Some('i') && (number == "-" || number == "+") {
+            let identifier = self.identifier();
+            if identifier == "inf" {
+                let sign = if number == "-" { -1.0 } else { 1.0 };
+                return Ok(Json::F64(sign * f64::INFINITY));
+            }
+        }
+
+        let number = number.strip_prefix('+').unwrap_or(&number);
+        let value = if number.contains(['.', 'e', 'E']) {
+            number.parse().ok().map(Json::F64)
+        } else if number.starts_with('-') {
+            number.parse().ok().map(Json::I64)
+        } else {
+            number.parse().ok().map(Json::U64)
+        };
+
+        value.ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+
+    #[test]
+    fn parse_ron() {
+        let text = r##"
+            // The first level
+            Level(
+                name: "Village",
+                size: (3, 2),
+                start: Some((0, 1)),
+                fields: [(type: Grass, height: -1), (type: "Water",)],
+                weather: None,
+                chance: 0.5,
+                names: {"the hero": r#"Bob "the" Builder"#},
+            )
+        "##;
+        let json = Json::from_str(r#"{"name": "Village", "size": [3, 2], "start": [0, 1],
+                                      "fields": [{"type": "Grass", "height": -1},
+                                                 {"type": "Water"}],
+                                      "weather": null, "chance": 0.5,
+                                      "names": {"the hero": "Bob \"the\" Builder"}}"#)
+            .unwrap();
+
+        assert_eq!(parse(text).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = Json::from_str(r#"{"title": "Quest\n\"One\"", "levels": [
+                                        {"id": "village", "successors": [{"id": "forest",
+                                         "required_flags": ["a", "b"]}]}],
+                                      "odd keys": {"with space": true, "": []}}"#)
+            .unwrap();
+
+        assert_eq!(parse(&write(&json)).unwrap(), json);
+    }
+
+    #[test]
+    fn errors() {
+        match parse("(name: \"Village\"\n size: 3)") {
+            Err(DataError::Syntax(ref message)) => {
+                assert_eq!(message, "expected `,` or `)` at line 2")
+            }
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("(a: 1, a: 2)").is_err());
+    }
+}
@@ -0,0 +1,645 @@
+use data::DataError;
+use rustc_serialize::json::{Json, Object};
+
+/// Writes an object in TOML. Nested objects become tables and lists of nested objects become
+/// arrays of tables, while lists of flat objects like fields are written as inline tables.
+/// Fails if the value is no object or contains `null` inside of a list, as TOML has no `null`.
+/// `null` values of objects are left out.
+pub fn write(value: &Json) -> Result<String, DataError> {
+    let table = match *value {
+        Json::Object(ref table) => table,
+        _ => return Err(DataError::Invalid("root".to_owned())),
+    };
+
+    let mut out = String::new();
+    write_table(&mut Vec::new(), table, &mut out)?;
+    Ok(out)
+}
+
+/// Parses TOML into the same values `write` takes. Dates and times are not supported.
+pub fn parse(text: &str) -> Result<Json, DataError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+    };
+
+    parser.document().map(Json::Object)
+}
+
+fn write_table(path: &mut Vec<String>, table: &Object, out: &mut String) -> Result<(), DataError> {
+    for (key, value) in table {
+        if value.is_object() || is_table_array(value) || value.is_null() {
+            continue;
+        }
+
+        out.push_str(&key_text(key));
+        out.push_str(" = ");
+        write_inline(value, true, out)?;
+        out.push('\n');
+    }
+
+    for (key, value) in table {
+        path.push(key.clone());
+        let header = path.iter().map(|key| key_text(key)).collect::<Vec<_>>().join(".");
+
+        match *value {
+            Json::Object(ref table) => {
+                out.push_str(&format!("\n[{}]\n", header));
+                write_table(path, table, out)?;
+            }
+            Json::Array(ref values) if is_table_array(value) => {
+                for table in values.iter().filter_map(Json::as_object) {
+                    out.push_str(&format!("\n[[{}]]\n", header));
+                    write_table(path, table, out)?;
+                }
+            }
+            _ => {}
+        }
+
+        path.pop();
+    }
+
+    Ok(())
+}
+
+fn write_inline(value: &Json, top_level: bool, out: &mut String) -> Result<(), DataError> {
+    match *value {
+        Json::Null => return Err(DataError::Invalid("null".to_owned())),
+        Json::Boolean(value) => out.push_str(&value.to_string()),
+        Json::I64(value) => out.push_str(&value.to_string()),
+        Json::U64(value) => out.push_str(&value.to_string()),
+        Json::F64(value) if value.is_nan() => out.push_str("nan"),
+        Json::F64(value) if value.is_infinite() => {
+            out.push_str(if value > 0.0 { "inf" } else { "-inf" })
+        }
+        Json::F64(value) => out.push_str(&format!("{:?}", value)),
+        Json::String(ref value) => write_string(value, out),
+        Json::Array(ref values) => {
+            let nested = values.iter().any(|value| value.is_array() || value.is_object());
+            let multiline = top_level && nested && !values.is_empty();
+
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if multiline {
+                    out.push_str("\n    ");
+                } else if index > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(value, false, out)?;
+                if multiline {
+                    out.push(',');
+                }
+            }
+            if multiline {
+                out.push('\n');
+            }
+            out.push(']');
+        }
+        Json::Object(ref table) => {
+            let entries: Vec<(&String, &Json)> =
+                table.iter().filter(|&(_, value)| !value.is_null()).collect();
+            if entries.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+
+            out.push_str("{ ");
+            for (index, &(key, value)) in entries.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&key_text(key));
+                out.push_str(" = ");
+                write_inline(value, false, out)?;
+            }
+            out.push_str(" }");
+        }
+    }
+
+    Ok(())
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            character if character.is_control() => {
+                out.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => out.push(character),
+        }
+    }
+    out.push('"');
+}
+
+/// Returns the key as it is written in TOML, quoted if necessary
+fn key_text(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(is_bare_key_char) {
+        key.to_owned()
+    } else {
+        let mut quoted = String::new();
+        write_string(key, &mut quoted);
+        quoted
+    }
+}
+
+fn is_bare_key_char(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '_' || character == '-'
+}
+
+/// Returns `true` if the value is a list of objects containing further lists or objects, which
+/// are too large to be written inline
+fn is_table_array(value: &Json) -> bool {
+    let values = match *value {
+        Json::Array(ref values) if !values.is_empty() => values,
+        _ => return false,
+    };
+
+    values.iter().all(Json::is_object) &&
+    values.iter().filter_map(Json::as_object).any(|table| {
+        table.values().any(|value| {
+            value.is_object() ||
+            value.as_array().is_some_and(|values| values.iter().any(Json::is_object))
+        })
+    })
+}
+
+/// Returns the table at the given path, creating missing tables. Lists of tables resolve to
+/// their last table.
+fn table_at<'a>(table: &'a mut Object, path: &[String]) -> Option<&'a mut Object> {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return Some(table),
+    };
+
+    let next = match *table.entry(first.clone()).or_insert_with(|| Json::Object(Object::new())) {
+        Json::Object(ref mut table) => table,
+        Json::Array(ref mut values) => {
+            match values.last_mut() {
+                Some(&mut Json::Object(ref mut table)) => table,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    table_at(next, rest)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek();
+        self.position += 1;
+        character
+    }
+
+    fn starts_with(&self, pattern: &str) -> bool {
+        pattern.chars().enumerate().all(|(index, character)| {
+            self.chars.get(self.position + index) == Some(&character)
+        })
+    }
+
+    fn error(&self, message: &str) -> DataError {
+        let line = self.chars[..self.position.min(self.chars.len())]
+            .iter()
+            .filter(|&&character| character == '\n')
+            .count() + 1;
+
+        DataError::Syntax(format!("{} at line {}", message, line))
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), DataError> {
+        self.skip_spaces();
+        if self.starts_with(expected) {
+            self.position += expected.chars().count();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected)))
+        }
+    }
+
+    /// Skips spaces and tabs, but not line breaks
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(' ') || self.peek() == Some('\t') {
+            self.position += 1;
+        }
+    }
+
+    /// Skips whitespace including line breaks, and comments
+    fn skip_blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(character) if character.is_whitespace() => self.position += 1,
+                Some('#') => {
+                    while self.peek().is_some_and(|character| character != '\n') {
+                        self.position += 1;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), DataError> {
+        self.skip_spaces();
+        if self.peek() == Some('#') {
+            while self.peek().is_some_and(|character| character != '\n') {
+                self.position += 1;
+            }
+        }
+
+        match self.peek() {
+            None | Some('\n') => Ok(()),
+            Some('\r') if self.chars.get(self.position + 1) == Some(&'\n') => Ok(()),
+            _ => Err(self.error("expected the end of the line")),
+        }
+    }
+
+    fn document(&mut self) -> Result<Object, DataError> {
+        let mut root = Object::new();
+        let mut current = Vec::new();
+
+        loop {
+            self.skip_blank();
+
+            if self.peek().is_none() {
+                return Ok(root);
+            } else if self.starts_with("[[") {
+                self.position += 2;
+                let path = self.key()?;
+                self.expect("]]")?;
+
+                let (last, parent) = path.split_last().expect("Keys are never empty");
+                let tables = table_at(&mut root, parent)
+                    .ok_or_else(|| self.error("invalid table"))?
+                    .entry(last.clone())
+                    .or_insert_with(|| Json::Array(Vec::new()));
+                match *tables {
+                    Json::Array(ref mut tables) => tables.push(Json::Object(Object::new())),
+                    _ => return Err(self.error("invalid array of tables")),
+                }
+                current = path;
+            } else if self.peek() == Some('[') {
+                self.position += 1;
+                let path = self.key()?;
+                self.expect("]")?;
+
+                table_at(&mut root, &path).ok_or_else(|| self.error("invalid table"))?;
+                current = path;
+            } else {
+                let mut path = current.clone();
+                path.extend(self.key()?);
+                self.expect("=")?;
+                let value = self.value()?;
+                self.insert(&mut root, &path, value)?;
+            }
+
+            self.end_of_line()?;
+        }
+    }
+
+    fn insert(&self, table: &mut Object, path: &[String], value: Json) -> Result<(), DataError> {
+        let (last, parent) = path.split_last().expect("Keys are never empty");
+        let table = table_at(table, parent).ok_or_else(|| self.error("invalid table"))?;
+
+        if table.insert(last.clone(), value).is_some() {
+            return Err(self.error("duplicate key"));
+        }
+
+        Ok(())
+    }
+
+    /// Parses a key like `name`, `"first name"` or `levels.village`
+    fn key(&mut self) -> Result<Vec<String>, DataError> {
+        let mut path = Vec::new();
+
+        loop {
+            self.skip_spaces();
+            let part = match self.peek() {
+                Some('"') => self.basic_string()?,
+                Some('\'') => self.literal_string()?,
+                _ => {
+                    let mut part = String::new();
+                    while let Some(character) = self.peek() {
+                        if !is_bare_key_char(character) {
+                            break;
+                        }
+                        part.push(character);
+                        self.position += 1;
+                    }
+                    if part.is_empty() {
+                        return Err(self.error("expected a key"));
+                    }
+                    part
+                }
+            };
+            path.push(part);
+
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(path);
+            }
+            self.position += 1;
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, DataError> {
+        self.skip_spaces();
+
+        match self.peek() {
+            Some('"') if self.starts_with("\"\"\"") => self.multiline_string().map(Json::String),
+            Some('"') => self.basic_string().map(Json::String),
+            Some('\'') if self.starts_with("'''") => {
+                self.multiline_literal_string().map(Json::String)
+            }
+            Some('\'') => self.literal_string().map(Json::String),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some(_) if self.starts_with("true") => {
+                self.position += 4;
+                Ok(Json::Boolean(true))
+            }
+            Some(_) if self.starts_with("false") => {
+                self.position += 5;
+                Ok(Json::Boolean(false))
+            }
+            Some(_) => self.number(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, DataError> {
+        self.position += 1;
+        let mut values = Vec::new();
+
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(Json::Array(values));
+            }
+
+            values.push(self.value()?);
+
+            self.skip_blank();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Json, DataError> {
+        self.position += 1;
+        let mut table = Object::new();
+
+        self.skip_spaces();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(table));
+        }
+
+        loop {
+            let path = self.key()?;
+            self.expect("=")?;
+            let value = self.value()?;
+            self.insert(&mut table, &path, value)?;
+
+            self.skip_spaces();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(table)),
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, DataError> {
+        self.position += 1;
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(character) => string.push(character),
+            }
+        }
+    }
+
+    fn multiline_string(&mut self) -> Result<String, DataError> {
+        self.position += 3;
+        self.skip_first_line_break();
+        let mut string = String::new();
+
+        loop {
+            if self.starts_with("\"\"\"") {
+                self.position += 3;
+                return Ok(string);
+            }
+
+            match self.next() {
+                Some('\\') if self.peek().is_some_and(char::is_whitespace) => {
+                    while self.peek().is_some_and(char::is_whitespace) {
+                        self.position += 1;
+                    }
+                }
+                Some('\\') => string.push(self.escape()?),
+                Some(character) => string.push(character),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, DataError> {
+        self.position += 1;
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(string),
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(character) => string.push(character),
+            }
+        }
+    }
+
+    fn multiline_literal_string(&mut self) -> Result<String, DataError> {
+        self.position += 3;
+        self.skip_first_line_break();
+        let mut string = String::new();
+
+        loop {
+            if self.starts_with("'''") {
+                self.position += 3;
+                return Ok(string);
+            }
+
+            match self.next() {
+                Some(character) => string.push(character),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn skip_first_line_break(&mut self) {
+        if self.starts_with("\r\n") {
+            self.position += 2;
+        } else if self.peek() == Some('\n') {
+            self.position += 1;
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, DataError> {
+        let digits = match self.next() {
+            Some('n') => return Ok('\n'),
+            Some('r') => return Ok('\r'),
+            Some('t') => return Ok('\t'),
+            Some('b') => return Ok('\u{8}'),
+            Some('f') => return Ok('\u{c}'),
+            Some('"') => return Ok('"'),
+            Some('\\') => return Ok('\\'),
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("invalid escape")),
+        };
+
+        let code: String = self.chars.iter().skip(self.position).take(digits).cloned().collect();
+        self.position += digits;
+
+        u32::from_str_radix(&code, 16)
+            .ok()
+            .and_then(::std::char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn number(&mut self) -> Result<Json, DataError> {
+        let mut text = String::new();
+        while let Some(character) = self.peek() {
+            if !character.is_ascii_alphanumeric() && !"+-._:".contains(character) {
+                break;
+            }
+            text.push(character);
+            self.position += 1;
+        }
+
+        let text = text.replace('_', "");
+        let (negative, digits) = match text.chars().next() {
+            Some('-') => (true, &text[1..]),
+            Some('+') => (false, &text[1..]),
+            _ => (false, &text[..]),
+        };
+        let sign = if negative { -1.0 } else { 1.0 };
+
+        let radix = match digits.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            Some("0b") => Some(2),
+            _ => None,
+        };
+
+        let value = if digits == "inf" {
+            Some(Json::F64(sign * f64::INFINITY))
+        } else if digits == "nan" {
+            Some(Json::F64(f64::NAN))
+        } else if let Some(radix) = radix {
+            u64::from_str_radix(&digits[2..], radix).ok().map(Json::U64)
+        } else if digits.contains(['.', 'e', 'E']) {
+            text.parse().ok().map(Json::F64)
+        } else if negative {
+            text.parse().ok().map(Json::I64)
+        } else {
+            digits.parse().ok().map(Json::U64)
+        };
+
+        value.ok_or_else(|| self.error("invalid value"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+
+    #[test]
+    fn parse_toml() {
+        let text = r#"
+            # The first level
+            title = "Long Journey"
+            "odd key" = 'C:\path'
+            size = [3, 2]
+            manifest.author = "Tolkien"
+
+            [[levels]]
+            id = "shire"
+            successors = [
+                { id = "bree", required_flags = [] },  # always open
+            ]
+
+            [[levels]]
+            id = "bree"
+            height = -1_000
+            chance = 2.5e-1
+            successors = []
+
+            [levels.notes]
+            text = """
+Roses are red,\
+ violets are blue"""
+        "#;
+        let json = Json::from_str(r#"{"title": "Long Journey", "odd key": "C:\\path",
+                                      "size": [3, 2], "manifest": {"author": "Tolkien"},
+                                      "levels": [
+                                          {"id": "shire", "successors": [
+                                              {"id": "bree", "required_flags": []}]},
+                                          {"id": "bree", "height": -1000, "chance": 0.25,
+                                           "successors": [],
+                                           "notes": {"text": "Roses are red,violets are blue"}}
+                                      ]}"#)
+            .unwrap();
+
+        assert_eq!(parse(text).unwrap(), json);
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = Json::from_str(r#"{"title": "Quest \"One\"", "levels": [
+                                        {"id": "village", "portal": {"to": [1, 2]},
+                                         "fields": [{"type": "Grass", "height": 0}]}],
+                                      "columns": [[{"type": "Sand"}], []],
+                                      "settings": {"survival": true, "day length": 1440}}"#)
+            .unwrap();
+        let text = write(&json).unwrap();
+
+        assert!(text.contains("[[levels]]"));
+        assert_eq!(parse(&text).unwrap(), json);
+    }
+
+    #[test]
+    fn errors() {
+        match parse("name = \"Village\"\nname = \"Town\"") {
+            Err(DataError::Syntax(ref message)) => assert_eq!(message, "duplicate key at line 2"),
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert!(parse("date = 1979-05-27").is_err());
+        assert!(write(&Json::from_str("[1, 2]").unwrap()).is_err());
+        assert!(write(&Json::from_str(r#"{"list": [null]}"#).unwrap()).is_err());
+    }
+}
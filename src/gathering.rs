@@ -0,0 +1,319 @@
+use character::{Attribute, Character};
+use data::{self, DataError};
+use item::{Item, ItemType};
+use item_generator::ItemGenerator;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use skill_check::{CheckOutcome, DIFFICULTY_EASY};
+use std::collections::BTreeMap;
+use time::WorldClock;
+use types::AttributeValue;
+use world::World;
+use world::two_dimensional::{FieldType, World2d};
+
+/// The default amount of turns until a tile can be gathered from again
+pub const DEFAULT_COOLDOWN: u64 = 720;
+
+/// The reasons why nothing was gathered
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GatherError {
+    /// The tile is neither the one of the player nor next to it
+    OutOfReach,
+    /// Nothing can be gathered from the type of the tile
+    NothingToGather,
+    /// The tile was gathered from recently, it grows back in the given amount of turns
+    Depleted(u64),
+    /// The check failed, the player may try again
+    Failed(CheckOutcome),
+    /// The inventory of the character is full
+    InventoryFull,
+}
+
+/// Something that can be gathered from a field type, e.g. fish from water or herbs from grass
+#[derive(Clone, PartialEq, Debug)]
+pub struct Resource {
+    /// The field type the resource is found on
+    pub field_type: FieldType,
+    /// The item gathered
+    pub item: Item,
+    /// The attribute checked when gathering
+    pub attribute: Attribute,
+    /// The difficulty of the check
+    pub difficulty: AttributeValue,
+    /// The amount of turns until the tile can be gathered from again
+    pub cooldown: u64,
+}
+
+impl Resource {
+    /// Creates a new instance of `Resource` with an easy check and the default cooldown
+    pub fn new(field_type: FieldType, item: Item, attribute: Attribute) -> Resource {
+        Resource {
+            field_type,
+            item,
+            attribute,
+            difficulty: DIFFICULTY_EASY,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// A builder method for setting the difficulty of the check
+    pub fn difficulty(mut self, difficulty: AttributeValue) -> Resource {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// A builder method for setting the cooldown of a tile
+    pub fn cooldown(mut self, turns: u64) -> Resource {
+        self.cooldown = turns;
+        self
+    }
+
+    /// Fish caught from water with a Dexterity check
+    pub fn fish() -> Resource {
+        let fish = ItemGenerator::new()
+            .name("Fish")
+            .item_type(ItemType::ConsumableFood)
+            .influence(None)
+            .gen();
+        Resource::new(FieldType::Water, fish, Attribute::Dexterity)
+    }
+
+    /// Herbs picked from grass with a Perception check, used as ingredient for crafting
+    pub fn herbs() -> Resource {
+        let herb = ItemGenerator::new()
+            .name("Herb")
+            .item_type(ItemType::Prop)
+            .influence(None)
+            .gen();
+        Resource::new(FieldType::Grass, herb, Attribute::Perception)
+    }
+
+    /// Reads a resource from a JSON object as written by `to_json`. `difficulty` and `cooldown`
+    /// are optional.
+    pub fn from_json(json: &Json) -> Result<Resource, DataError> {
+        let object = data::as_object(json, "resource")?;
+
+        Ok(Resource::new(data::parse(object, "field_type")?,
+                         Item::from_json(data::field(object, "item")?)?,
+                         data::parse(object, "attribute")?)
+            .difficulty(data::optional(object, "difficulty", data::signed)?
+                .unwrap_or(DIFFICULTY_EASY))
+            .cooldown(data::optional(object, "cooldown", data::unsigned)?
+                .unwrap_or(DEFAULT_COOLDOWN)))
+    }
+}
+
+impl ToJson for Resource {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("field_type".to_owned(), self.field_type.to_string().to_json());
+        object.insert("item".to_owned(), self.item.to_json());
+        object.insert("attribute".to_owned(), format!("{:?}", self.attribute).to_json());
+        object.insert("difficulty".to_owned(), self.difficulty.to_json());
+        object.insert("cooldown".to_owned(), self.cooldown.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// Fishing and foraging: the resources of the field types and the tiles that were gathered from
+/// recently, which grow back once their cooldown passed
+#[derive(Clone, PartialEq, Debug)]
+pub struct Gathering {
+    /// The resources that can be gathered
+    pub resources: Vec<Resource>,
+    gathered: BTreeMap<(String, (usize, usize)), u64>,
+}
+
+impl Gathering {
+    /// Creates a new instance of `Gathering` without resources
+    pub fn new() -> Gathering {
+        Gathering {
+            resources: Vec::new(),
+            gathered: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a new instance of `Gathering` with fish in water and herbs on grass
+    pub fn built_in() -> Gathering {
+        Gathering::new().resource(Resource::fish()).resource(Resource::herbs())
+    }
+
+    /// A builder method for adding a resource
+    pub fn resource(mut self, resource: Resource) -> Gathering {
+        self.resources.push(resource);
+        self
+    }
+
+    /// Returns the resource found on the field type (optional)
+    pub fn get(&self, field_type: &FieldType) -> Option<&Resource> {
+        self.resources.iter().find(|resource| &resource.field_type == field_type)
+    }
+
+    /// Returns the amount of turns until the tile of the world can be gathered from again
+    pub fn ready_in(&self, world: &str, position: (usize, usize), clock: &WorldClock) -> u64 {
+        self.gathered
+            .get(&(world.to_owned(), position))
+            .map_or(0, |&ready| ready.saturating_sub(clock.turn()))
+    }
+
+    /// Gathers from the tile at the position, which is the one of the player or next to it, e.g.
+    /// fishing from the shore. The item is put into the inventory of the character and the tile
+    /// is depleted for the cooldown of the resource.
+    pub fn gather<R: Rng>(&mut self,
+                          position: (usize, usize),
+                          world: &World2d,
+                          character: &mut Character,
+                          clock: &WorldClock,
+                          rng: &mut R)
+                          -> Result<&Item, GatherError> {
+        let (x, y) = world.get_position();
+        let reach = |a: usize, b: usize| a.abs_diff(b);
+        if reach(position.0, x) > 1 || reach(position.1, y) > 1 {
+            return Err(GatherError::OutOfReach);
+        }
+
+        let index = {
+            let field_type = &world.get_field(position).field_type;
+            self.resources
+                .iter()
+                .position(|resource| &resource.field_type == field_type)
+                .ok_or(GatherError::NothingToGather)?
+        };
+        let turns = self.ready_in(&world.name, position, clock);
+        if turns > 0 {
+            return Err(GatherError::Depleted(turns));
+        }
+        if character.inventory().is_full() {
+            return Err(GatherError::InventoryFull);
+        }
+
+        let resource = &self.resources[index];
+        let outcome = character.skill_check(&resource.attribute, resource.difficulty, rng);
+        if !outcome.is_success() {
+            return Err(GatherError::Failed(outcome));
+        }
+
+        character.inventory_mut()
+            .add_item(resource.item.renewed(rng))
+            .expect("Unable to add gathered item");
+        self.gathered.insert((world.name.clone(), position), clock.turn() + resource.cooldown);
+        Ok(&resource.item)
+    }
+
+    /// Reads the resources and depleted tiles from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Gathering, DataError> {
+        let object = data::as_object(json, "gathering")?;
+
+        let mut gathering = Gathering::new();
+        for resource in data::as_array(data::field(object, "resources")?, "resources")? {
+            gathering = gathering.resource(Resource::from_json(resource)?);
+        }
+        for tile in data::as_array(data::field(object, "gathered")?, "gathered")? {
+            let tile = data::as_object(tile, "gathered")?;
+            gathering.gathered.insert((data::string(tile, "world")?,
+                                       data::position(tile, "position")?),
+                                      data::unsigned(tile, "ready")?);
+        }
+
+        Ok(gathering)
+    }
+}
+
+impl Default for Gathering {
+    fn default() -> Gathering {
+        Gathering::built_in()
+    }
+}
+
+impl ToJson for Gathering {
+    fn to_json(&self) -> Json {
+        let gathered: Vec<Json> = self.gathered
+            .iter()
+            .map(|(&(ref world, position), ready)| {
+                let mut tile = Object::new();
+                tile.insert("world".to_owned(), world.to_json());
+                tile.insert("position".to_owned(), position.to_json());
+                tile.insert("ready".to_owned(), ready.to_json());
+                Json::Object(tile)
+            })
+            .collect();
+
+        let mut object = Object::new();
+        object.insert("resources".to_owned(), self.resources.to_json());
+        object.insert("gathered".to_owned(), Json::Array(gathered));
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use crafting::Recipe;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::ToJson;
+    use time::WorldClock;
+    use world::Teleport;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn shore() -> World2d {
+        let mut world = World2d::new("Shore", (3, 3));
+        world.set_field(Field::new(FieldType::Water), (1, 0));
+        world.set_field(Field::new(FieldType::Stone), (0, 1));
+        world.set_field(Field::new(FieldType::Grass), (2, 2));
+        world
+    }
+
+    #[test]
+    fn gather() {
+        let mut gathering = Gathering::new()
+            .resource(Resource::fish().difficulty(-100).cooldown(10))
+            .resource(Resource::herbs().difficulty(-100));
+        let world = shore();
+        let mut character = Character::new("Michael");
+        let mut clock = WorldClock::new();
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        assert_eq!(gathering.gather((2, 2), &world, &mut character, &clock, &mut rng).err(),
+                   Some(GatherError::OutOfReach));
+        assert_eq!(gathering.gather((0, 1), &world, &mut character, &clock, &mut rng).err(),
+                   Some(GatherError::NothingToGather));
+        assert_eq!(gathering.gather((1, 0), &world, &mut character, &clock, &mut rng)
+                       .map(|item| item.name.clone()),
+                   Ok("Fish".to_owned()));
+        assert_eq!(gathering.gather((1, 0), &world, &mut character, &clock, &mut rng).err(),
+                   Some(GatherError::Depleted(10)));
+
+        clock.skip(10);
+        assert!(gathering.gather((1, 0), &world, &mut character, &clock, &mut rng).is_ok());
+        assert_eq!(character.inventory().count_by_name("Fish"), 2);
+    }
+
+    #[test]
+    fn feeds_crafting() {
+        let mut gathering = Gathering::new().resource(Resource::herbs().difficulty(-100));
+        let mut world = shore();
+        world.set_position((2, 2));
+        let mut character = Character::new("Michael");
+        let clock = WorldClock::new();
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        gathering.gather((2, 2), &world, &mut character, &clock, &mut rng).unwrap();
+
+        let tea = Recipe::new(ItemGenerator::new().name("Herbal Tea").gen()).ingredient("Herb", 1);
+        assert!(tea.craft(&[], character.inventory_mut(), &mut rng).is_ok());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut gathering = Gathering::built_in();
+        let mut character = Character::new("Michael");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        gathering.resources[0].difficulty = -100;
+        gathering.gather((1, 0), &shore(), &mut character, &WorldClock::new(), &mut rng).unwrap();
+
+        assert_eq!(Gathering::from_json(&gathering.to_json()).unwrap(), gathering);
+    }
+}
@@ -0,0 +1,237 @@
+use bus::{GameEvent, Listener};
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeSet;
+use std::path::Path;
+use template::{Template, TemplateContext};
+
+/// A tip shown to the player the first time an event happens
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hint {
+    /// The unique id of the hint
+    pub id: String,
+    /// The kind of the event showing the hint, e.g. `ItemFound`, see `GameEvent::kind`
+    pub event: String,
+    /// The value the event has to carry, e.g. the name of an item (optional)
+    pub value: Option<String>,
+    /// The text shown to the player. `{value}` is replaced by the value of the event.
+    pub text: String,
+}
+
+impl Hint {
+    /// Creates a new instance of `Hint` shown for any event of the given kind
+    pub fn new(id: &str, event: &str, text: &str) -> Hint {
+        Hint {
+            id: id.to_owned(),
+            event: event.to_owned(),
+            value: None,
+            text: text.to_owned(),
+        }
+    }
+
+    /// A builder method for only showing the hint for events carrying the value
+    pub fn value(mut self, value: &str) -> Hint {
+        self.value = Some(value.to_owned());
+        self
+    }
+
+    /// Returns `true` if the hint is shown for the event
+    pub fn matches(&self, event: &GameEvent) -> bool {
+        self.event == event.kind() &&
+        self.value.as_ref().is_none_or(|value| *value == event.value())
+    }
+
+    /// Returns the text of the hint for the event
+    pub fn render(&self, event: &GameEvent) -> String {
+        Template::new(&self.text).render(&TemplateContext::new().value("value", event.value()))
+    }
+
+    /// Reads a hint from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Hint, DataError> {
+        let object = data::as_object(json, "hint")?;
+
+        Ok(Hint {
+            id: data::string(object, "id")?,
+            event: data::string(object, "event")?,
+            value: data::optional(object, "value", data::string)?,
+            text: data::string(object, "text")?,
+        })
+    }
+}
+
+impl ToJson for Hint {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.to_json());
+        object.insert("event".to_owned(), self.event.to_json());
+        if let Some(ref value) = self.value {
+            object.insert("value".to_owned(), value.to_json());
+        }
+        object.insert("text".to_owned(), self.text.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// Shows every hint once per save by listening to game events
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hints {
+    hints: Vec<Hint>,
+    seen: BTreeSet<String>,
+    enabled: bool,
+    pending: Vec<String>,
+}
+
+impl Hints {
+    /// Creates a new instance of `Hints` without any hints
+    pub fn new() -> Hints {
+        Hints {
+            hints: Vec::new(),
+            seen: BTreeSet::new(),
+            enabled: true,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the hints built into this crate, explaining the basics of the game
+    pub fn built_in() -> Hints {
+        let mut hints = Hints::new();
+        hints.add(Hint::new("first_item",
+                            "ItemFound",
+                            "You found {value} — use 'equip {value}'."));
+        hints.add(Hint::new("first_damage",
+                            "DamageTaken",
+                            "You are hurt. Potions and resting restore your health."));
+        hints.add(Hint::new("first_kill", "MonsterKilled", "Defeated monsters may drop loot."));
+        hints.add(Hint::new("first_level",
+                            "LevelReached",
+                            "Your attributes grow with your level."));
+        hints
+    }
+
+    /// Adds a hint, e.g. one defined by a campaign. A hint replaces the hint with the same id.
+    pub fn add(&mut self, hint: Hint) {
+        self.hints.retain(|known| known.id != hint.id);
+        self.hints.push(hint);
+    }
+
+    /// Adds the hints of a JSON file containing an array of hints
+    pub fn load_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DataError> {
+        for hint in data::as_array(&data::read_json(path)?, "hints")? {
+            self.add(Hint::from_json(hint)?);
+        }
+        Ok(())
+    }
+
+    /// Turns the hints on or off. Hints are not marked as seen while they are off.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the hint with the given id was shown in this save
+    pub fn is_seen(&self, id: &str) -> bool {
+        self.seen.contains(id)
+    }
+
+    /// Returns the texts of the hints shown since the last call
+    pub fn take_pending(&mut self) -> Vec<String> {
+        self.pending.drain(..).collect()
+    }
+
+    /// Reads the hints and which of them were seen from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Hints, DataError> {
+        let object = data::as_object(json, "hints")?;
+
+        Ok(Hints {
+            hints: data::as_array(data::field(object, "hints")?, "hints")?
+                .iter()
+                .map(Hint::from_json)
+                .collect::<Result<_, _>>()?,
+            seen: data::as_array(data::field(object, "seen")?, "seen")?
+                .iter()
+                .map(|id| data::parse_value(id, "seen"))
+                .collect::<Result<_, _>>()?,
+            enabled: data::optional(object, "enabled", data::boolean)?.unwrap_or(true),
+            pending: Vec::new(),
+        })
+    }
+}
+
+impl Default for Hints {
+    fn default() -> Hints {
+        Hints::built_in()
+    }
+}
+
+impl Listener for Hints {
+    fn notify(&mut self, event: &GameEvent) {
+        if !self.enabled {
+            return;
+        }
+
+        for hint in &self.hints {
+            if !self.seen.contains(&hint.id) && hint.matches(event) {
+                self.seen.insert(hint.id.clone());
+                self.pending.push(hint.render(event));
+            }
+        }
+    }
+}
+
+impl ToJson for Hints {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        let seen: Vec<String> = self.seen.iter().cloned().collect();
+        object.insert("hints".to_owned(), self.hints.to_json());
+        object.insert("seen".to_owned(), seen.to_json());
+        object.insert("enabled".to_owned(), self.enabled.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{self, GameEvent, Listener};
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn show_once() {
+        let mut hints = Hints::built_in();
+        bus::publish(&GameEvent::ItemFound("sword".to_owned()), &mut [&mut hints]);
+        bus::publish(&GameEvent::ItemFound("shield".to_owned()), &mut [&mut hints]);
+
+        assert_eq!(hints.take_pending(), vec!["You found sword — use 'equip sword'."]);
+        assert!(hints.is_seen("first_item"));
+        assert!(hints.take_pending().is_empty());
+    }
+
+    #[test]
+    fn campaign_hints() {
+        let mut hints = Hints::new();
+        hints.add(Hint::new("dragon", "MonsterKilled", "The village will hear of this.")
+            .value("dragon"));
+        hints.notify(&GameEvent::MonsterKilled("rat".to_owned()));
+        assert!(hints.take_pending().is_empty());
+
+        hints.set_enabled(false);
+        hints.notify(&GameEvent::MonsterKilled("dragon".to_owned()));
+        assert!(!hints.is_seen("dragon"));
+
+        hints.set_enabled(true);
+        hints.notify(&GameEvent::MonsterKilled("dragon".to_owned()));
+        assert_eq!(hints.take_pending(), vec!["The village will hear of this."]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut hints = Hints::built_in();
+        hints.notify(&GameEvent::LevelReached(2));
+        hints.take_pending();
+
+        let loaded = Hints::from_json(&hints.to_json()).unwrap();
+        assert_eq!(loaded, hints);
+        assert!(loaded.is_seen("first_level"));
+    }
+}
@@ -0,0 +1,204 @@
+use data::{self, DataError};
+use inventory::Inventory;
+use item::{Item, ItemId};
+use rustc_serialize::json::{Json, Object, ToJson};
+
+/// The amount of slots of a new hotbar, numbered `1` to `9` and `0` like the keys of a keyboard
+pub const DEFAULT_HOTBAR_SIZE: usize = 10;
+
+/// What a slot of the hotbar refers to
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HotbarEntry {
+    /// An item or stack of the inventory, referenced by its id
+    Item(ItemId),
+    /// A spell or another ability of the character, referenced by its name
+    Ability(String),
+}
+
+/// The result of using a slot of the hotbar
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Activation {
+    /// The item was taken out of the inventory to be used
+    Item(Item),
+    /// The ability with the given name should be used
+    Ability(String),
+}
+
+/// The reasons why a slot of the hotbar can not be used
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HotbarError {
+    /// There is no slot with the given index
+    OutOfRange,
+    /// Nothing is assigned to the slot
+    Empty,
+    /// The inventory does not contain the assigned item anymore
+    ItemMissing,
+}
+
+/// Quick-use slots for consumables and abilities
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hotbar {
+    slots: Vec<Option<HotbarEntry>>,
+}
+
+impl Hotbar {
+    /// Creates a new instance of `Hotbar` with the given amount of empty slots
+    pub fn new(size: usize) -> Hotbar {
+        Hotbar { slots: vec![None; size] }
+    }
+
+    /// Returns the amount of slots
+    pub fn size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the entry of the slot with the given index
+    pub fn get(&self, slot: usize) -> Option<&HotbarEntry> {
+        self.slots.get(slot).and_then(|entry| entry.as_ref())
+    }
+
+    /// Assigns the entry to the slot with the given index, replacing the previous entry. Returns
+    /// `false` if there is no such slot.
+    pub fn assign(&mut self, slot: usize, entry: HotbarEntry) -> bool {
+        match self.slots.get_mut(slot) {
+            Some(inner_slot) => {
+                *inner_slot = Some(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the entry of the slot with the given index and returns it
+    pub fn clear(&mut self, slot: usize) -> Option<HotbarEntry> {
+        self.slots.get_mut(slot).and_then(|entry| entry.take())
+    }
+
+    /// Uses the slot with the given index. Items are taken out of the inventory.
+    pub fn activate(&self,
+                    slot: usize,
+                    inventory: &mut Inventory)
+                    -> Result<Activation, HotbarError> {
+        match self.slots.get(slot) {
+            None => Err(HotbarError::OutOfRange),
+            Some(&None) => Err(HotbarError::Empty),
+            Some(&Some(HotbarEntry::Item(ref id))) => {
+                inventory.take_by_id(id).map(Activation::Item).ok_or(HotbarError::ItemMissing)
+            }
+            Some(&Some(HotbarEntry::Ability(ref name))) => Ok(Activation::Ability(name.clone())),
+        }
+    }
+
+    /// Returns the index of the slot a command of the player refers to. Commands are slot
+    /// numbers as shown to the player, e.g. `3` or `use 3`, where `0` is the tenth slot.
+    pub fn parse_command(&self, line: &str) -> Option<usize> {
+        let mut words = line.split_whitespace();
+        let number = match (words.next(), words.next(), words.next()) {
+            (Some("use"), Some(number), None) |
+            (Some(number), None, None) => number.parse::<usize>().ok()?,
+            _ => return None,
+        };
+
+        let slot = if number == 0 { 9 } else { number - 1 };
+        if slot < self.size() { Some(slot) } else { None }
+    }
+
+    /// Reads a hotbar from a JSON array as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Hotbar, DataError> {
+        let slots = data::as_array(json, "hotbar")?
+            .iter()
+            .map(|slot| match *slot {
+                Json::Null => Ok(None),
+                ref entry => {
+                    let object = data::as_object(entry, "hotbar")?;
+                    match object.get("ability") {
+                        Some(_) => Ok(Some(HotbarEntry::Ability(data::string(object, "ability")?))),
+                        None => Ok(Some(HotbarEntry::Item(data::parse(object, "item")?))),
+                    }
+                }
+            })
+            .collect::<Result<_, DataError>>()?;
+
+        Ok(Hotbar { slots })
+    }
+}
+
+impl Default for Hotbar {
+    fn default() -> Hotbar {
+        Hotbar::new(DEFAULT_HOTBAR_SIZE)
+    }
+}
+
+impl ToJson for Hotbar {
+    fn to_json(&self) -> Json {
+        let slots = self.slots
+            .iter()
+            .map(|slot| {
+                let (key, name) = match *slot {
+                    None => return Json::Null,
+                    Some(HotbarEntry::Item(ref id)) => ("item", id.as_str()),
+                    Some(HotbarEntry::Ability(ref name)) => ("ability", &name[..]),
+                };
+
+                let mut object = Object::new();
+                object.insert(key.to_owned(), name.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        Json::Array(slots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inventory::Inventory;
+    use item::{ItemId, ItemType};
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn activate() {
+        let mut inventory = Inventory::new(10);
+        let potion = ItemGenerator::new()
+            .name("Healing Potion")
+            .item_type(ItemType::ConsumablePotion)
+            .stack_size(5)
+            .gen();
+        inventory.add_item(potion.clone()).unwrap();
+
+        let mut hotbar = Hotbar::default();
+        assert!(hotbar.assign(0, HotbarEntry::Item(potion.id.clone())));
+        assert!(hotbar.assign(1, HotbarEntry::Ability("Fireball".to_owned())));
+        assert!(!hotbar.assign(10, HotbarEntry::Ability("Fireball".to_owned())));
+
+        assert_eq!(hotbar.activate(0, &mut inventory), Ok(Activation::Item(potion)));
+        assert_eq!(hotbar.activate(0, &mut inventory), Err(HotbarError::ItemMissing));
+        assert_eq!(hotbar.activate(1, &mut inventory),
+                   Ok(Activation::Ability("Fireball".to_owned())));
+        assert_eq!(hotbar.activate(2, &mut inventory), Err(HotbarError::Empty));
+        assert_eq!(hotbar.activate(12, &mut inventory), Err(HotbarError::OutOfRange));
+    }
+
+    #[test]
+    fn parse_command() {
+        let hotbar = Hotbar::new(DEFAULT_HOTBAR_SIZE);
+        assert_eq!(hotbar.parse_command("3"), Some(2));
+        assert_eq!(hotbar.parse_command("use 1"), Some(0));
+        assert_eq!(hotbar.parse_command("0"), Some(9));
+        assert_eq!(hotbar.parse_command("use sword"), None);
+        assert_eq!(Hotbar::new(3).parse_command("5"), None);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut hotbar = Hotbar::new(4);
+        let id = ItemId::random_with(&mut XorShiftRng::from_seed([1, 2, 3, 4]));
+        hotbar.assign(0, HotbarEntry::Item(id));
+        hotbar.assign(3, HotbarEntry::Ability("Heal".to_owned()));
+
+        assert_eq!(Hotbar::from_json(&hotbar.to_json()).unwrap(), hotbar);
+    }
+}
@@ -0,0 +1,136 @@
+use character::{Attribute, Character};
+use item::{Item, ItemRarity};
+use rand::Rng;
+use skill_check::{CheckOutcome, DIFFICULTY_MEDIUM};
+use types::{AttributeValue, Gold};
+
+/// The name of the scroll that identifies an item when read
+pub const IDENTIFY_SCROLL: &str = "Scroll of Identify";
+
+/// The difficulty of identifying an item by studying it
+pub const STUDY_DIFFICULTY: AttributeValue = DIFFICULTY_MEDIUM;
+
+/// The reasons why an item can not be identified
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IdentifyError {
+    /// The character does not carry an unidentified item with the given name
+    NotFound,
+    /// The character does not carry a `IDENTIFY_SCROLL`
+    NoScroll,
+    /// The character can not pay the price of the shop
+    NotEnoughGold,
+}
+
+/// Returns the price a shop asks for identifying the item, which grows with its rarity
+pub fn price(item: &Item) -> Gold {
+    match item.rarity {
+        ItemRarity::Common => 10,
+        ItemRarity::Uncommon => 25,
+        ItemRarity::Rare => 75,
+        ItemRarity::Epic => 200,
+        ItemRarity::Legendary => 500,
+    }
+}
+
+/// Identifies the carried item with the given name by reading a `IDENTIFY_SCROLL`, which is used
+/// up
+pub fn with_scroll(character: &mut Character, name: &str) -> Result<(), IdentifyError> {
+    find(character, name)?;
+
+    if character.inventory_mut().take_by_name(IDENTIFY_SCROLL).is_none() {
+        return Err(IdentifyError::NoScroll);
+    }
+
+    character.inventory_mut().identify(name);
+    Ok(())
+}
+
+/// Identifies the carried item with the given name at a shop, paying its `price`
+pub fn at_shop(character: &mut Character, name: &str) -> Result<Gold, IdentifyError> {
+    let price = price(&find(character, name)?);
+
+    if !character.inventory_mut().spend_gold(price) {
+        return Err(IdentifyError::NotEnoughGold);
+    }
+
+    character.inventory_mut().identify(name);
+    Ok(price)
+}
+
+/// Tries to identify the carried item with the given name using an `Attribute::Intelligence`
+/// skill check. A failed check can be repeated.
+pub fn by_study<R: Rng>(character: &mut Character,
+                        name: &str,
+                        rng: &mut R)
+                        -> Result<CheckOutcome, IdentifyError> {
+    find(character, name)?;
+
+    let outcome = character.skill_check(&Attribute::Intelligence, STUDY_DIFFICULTY, rng);
+    if outcome.is_success() {
+        character.inventory_mut().identify(name);
+    }
+
+    Ok(outcome)
+}
+
+fn find(character: &Character, name: &str) -> Result<Item, IdentifyError> {
+    match character.inventory().find_by_name(name) {
+        Some(item) if !item.identified => Ok(item.clone()),
+        _ => Err(IdentifyError::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use item::{ItemInfluence, ItemRarity, ItemType};
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn character() -> Character {
+        let mut character = Character::new("Gandalf");
+        let mut ring = ItemGenerator::new()
+            .name("Narya")
+            .item_type(ItemType::Usable)
+            .rarity(ItemRarity::Rare)
+            .influence(Some(ItemInfluence::new(Attribute::Wisdom, 30)))
+            .gen();
+        ring.identified = false;
+        character.inventory_mut().add_item(ring).unwrap();
+        character
+    }
+
+    #[test]
+    fn scroll() {
+        let mut character = character();
+        assert_eq!(with_scroll(&mut character, "Narya"), Err(IdentifyError::NoScroll));
+
+        let scroll = ItemGenerator::new().name(IDENTIFY_SCROLL).item_type(ItemType::Usable).gen();
+        character.inventory_mut().add_item(scroll).unwrap();
+        assert_eq!(with_scroll(&mut character, "Narya"), Ok(()));
+        assert!(character.inventory().find_by_name("Narya").unwrap().identified);
+        assert!(character.inventory().find_by_name(IDENTIFY_SCROLL).is_none());
+        assert_eq!(with_scroll(&mut character, "Narya"), Err(IdentifyError::NotFound));
+    }
+
+    #[test]
+    fn shop() {
+        let mut character = character();
+        character.inventory_mut().add_gold(100);
+
+        assert_eq!(at_shop(&mut character, "Narya"), Ok(75));
+        assert_eq!(character.inventory().gold(), 25);
+        assert_eq!(at_shop(&mut character, "Narya"), Err(IdentifyError::NotFound));
+    }
+
+    #[test]
+    fn study() {
+        let mut character = character();
+        character.update_attribute(&Attribute::Intelligence, 100);
+
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert_eq!(by_study(&mut character, "Narya", &mut rng), Ok(CheckOutcome::Success));
+        assert!(character.inventory().find_by_name("Narya").unwrap().identified);
+    }
+}
@@ -0,0 +1,140 @@
+use character::Character;
+use rand::Rng;
+use std::str::FromStr;
+use types::Health;
+
+/// The amount of turns it takes an untreated injury to heal
+pub const INJURY_DURATION: u64 = 200;
+
+/// The share of the maximum health a single hit has to deal to cause an injury
+pub const HEAVY_HIT_RATIO: f64 = 0.25;
+
+/// The share of attack damage lost per arm wound
+pub const ARM_WOUND_PENALTY: f64 = 0.25;
+
+/// The name of the consumable that treats arm wounds
+pub const BANDAGE: &str = "Bandage";
+
+/// The name of the consumable that treats leg wounds
+pub const SPLINT: &str = "Splint";
+
+/// The part of the body an injury is located at
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BodyPart {
+    /// An arm wound reduces the attack damage
+    Arm,
+    /// A leg wound makes every step take longer
+    Leg,
+}
+
+impl BodyPart {
+    /// Returns the name of the consumable that treats wounds at this body part
+    pub fn cure(&self) -> &'static str {
+        match *self {
+            BodyPart::Arm => BANDAGE,
+            BodyPart::Leg => SPLINT,
+        }
+    }
+}
+
+impl FromStr for BodyPart {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<BodyPart, ()> {
+        match s {
+            "Arm" => Ok(BodyPart::Arm),
+            "Leg" => Ok(BodyPart::Leg),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A wound at a specific part of the body
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Injury {
+    /// The injured part of the body
+    pub body_part: BodyPart,
+    /// The amount of turns until the injury is healed
+    pub turns_left: u64,
+}
+
+impl Injury {
+    /// Creates a new instance of `Injury` healing after `INJURY_DURATION` turns
+    pub fn new(body_part: BodyPart) -> Injury {
+        Injury {
+            body_part,
+            turns_left: INJURY_DURATION,
+        }
+    }
+}
+
+/// Returns `true` if the damage is high enough to cause an injury
+pub fn is_heavy_hit(damage: Health, max_health: Health) -> bool {
+    damage > 0 && (damage as f64) >= (max_health as f64) * HEAVY_HIT_RATIO
+}
+
+/// Deals damage to the character and injures a random part of its body if the hit was heavy.
+/// Returns the injured part of the body.
+pub fn inflict<R: Rng>(character: &mut Character, damage: Health, rng: &mut R) -> Option<BodyPart> {
+    let heavy = is_heavy_hit(damage, character.max_health());
+    character.take_damage(damage);
+
+    if !heavy || character.is_dead() {
+        return None;
+    }
+
+    let body_part = if rng.gen() { BodyPart::Arm } else { BodyPart::Leg };
+    character.injure(body_part);
+
+    Some(body_part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use time::{Tick, WorldClock};
+
+    #[test]
+    fn heavy_hits() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Frodo");
+
+        assert_eq!(inflict(&mut character, 2, &mut rng), None);
+        assert_eq!(inflict(&mut character, 10, &mut rng), Some(BodyPart::Arm));
+        assert_eq!(character.injuries().len(), 1);
+    }
+
+    #[test]
+    fn wound_effects() {
+        let mut character = Character::new("Frodo");
+        let attack_damage = character.attack_damage();
+
+        character.injure(BodyPart::Arm);
+        character.injure(BodyPart::Leg);
+
+        assert_eq!(character.attack_damage(), attack_damage * 3 / 4);
+        assert_eq!(character.movement_cost(), 2);
+    }
+
+    #[test]
+    fn healing() {
+        let mut character = Character::new("Frodo");
+        character.injure(BodyPart::Arm);
+        character.injure(BodyPart::Leg);
+
+        assert!(!character.treat(BodyPart::Arm));
+        let _ = character.inventory_mut().add_item(ItemGenerator::new().name(BANDAGE).gen());
+        assert!(character.treat(BodyPart::Arm));
+        assert_eq!(character.injuries(), &[Injury::new(BodyPart::Leg)]);
+
+        let mut clock = WorldClock::new();
+        for _ in 0..INJURY_DURATION {
+            clock.advance();
+            character.tick(&clock);
+        }
+        assert!(character.injuries().is_empty());
+    }
+}
@@ -1,15 +1,30 @@
-use item::Item;
+use data::{self, DataError};
+use item::{Item, ItemCategory, ItemId, ItemType};
+use rustc_serialize::json::{Json, Object, ToJson};
 use types::Gold;
 
+/// The reasons why an item can not be dropped, sold or traded
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InventoryError {
+    /// The inventory does not contain an item with the given name
+    NotFound,
+    /// The item is needed for a quest
+    QuestItem,
+    /// The receiving inventory is full
+    Full,
+}
+
 /// A single slot of the inventory
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct InventorySlot {
     item: Item,
     amount: usize,
+    /// The amount of items ever taken from the stack, which their ids are derived from
+    taken: u64,
 }
 
 /// An inventory holding items
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Inventory {
     contents: Vec<InventorySlot>,
     gold: Gold,
@@ -26,15 +41,47 @@ impl Inventory {
         }
     }
 
+    /// Returns the amount of slots of the inventory
+    pub fn capacity(&self) -> usize {
+        self.max_size
+    }
+
+    /// Changes the amount of slots of the inventory, e.g. when the character gets a bigger
+    /// backpack. Shrinking the inventory removes the stacks of the last slots, which are
+    /// returned one item each, so the caller can drop them to the ground.
+    pub fn resize(&mut self, capacity: usize) -> Vec<Item> {
+        self.max_size = capacity;
+
+        let mut overflow = Vec::new();
+        while self.contents.len() > capacity {
+            let index = self.contents.len() - 1;
+            while index < self.contents.len() {
+                overflow.push(self.take_at(index));
+            }
+        }
+
+        overflow
+    }
+
+    /// Adds an item to the inventory or, if the inventory is full, puts it onto the ground, e.g.
+    /// the items of the current room. Returns `false` if the item was dropped.
+    pub fn add_or_drop(&mut self, item: Item, ground: &mut Vec<Item>) -> bool {
+        match self.add_item(item) {
+            Ok(()) => true,
+            Err(item) => {
+                ground.push(*item);
+                false
+            }
+        }
+    }
+
     /// Adds an item to the inventory. If the inventory is full, the item won't be added to the
-    /// inventory and a `Err(Item)` is returned.
-    pub fn add_item(&mut self, new_item: Item) -> Result<(), Item> {
+    /// inventory and a `Err(Box<Item>)` is returned, so it is never lost.
+    pub fn add_item(&mut self, new_item: Item) -> Result<(), Box<Item>> {
         for slot in &mut self.contents {
-            if slot.item == new_item {
-                if slot.item.stack_size > slot.amount {
-                    slot.amount += 1;
-                    return Ok(());
-                }
+            if slot.item.stacks_with(&new_item) && slot.item.stack_size > slot.amount {
+                slot.amount += 1;
+                return Ok(());
             }
         }
 
@@ -42,25 +89,298 @@ impl Inventory {
             self.contents.push(InventorySlot {
                 item: new_item,
                 amount: 1,
+                taken: 0,
             });
         } else {
-            return Err(new_item);
+            return Err(Box::new(new_item));
         }
 
         Ok(())
     }
 
+    /// Returns the item with the given id
+    pub fn find_by_id(&self, id: &ItemId) -> Option<&Item> {
+        self.contents.iter().map(|slot| &slot.item).find(|item| &item.id == id)
+    }
+
+    /// Removes a single item of the stack with the given id from the inventory and returns it
+    pub fn take_by_id(&mut self, id: &ItemId) -> Option<Item> {
+        let index = self.contents.iter().position(|slot| &slot.item.id == id);
+        index.map(|index| self.take_at(index))
+    }
+
+    /// Returns the first item with the given name
+    pub fn find_by_name(&self, name: &str) -> Option<&Item> {
+        self.contents.iter().map(|slot| &slot.item).find(|item| item.name == name)
+    }
+
+    /// Returns the amount of items in the stack with the given id
+    pub fn count_by_id(&self, id: &ItemId) -> usize {
+        self.contents.iter().filter(|slot| &slot.item.id == id).map(|slot| slot.amount).sum()
+    }
+
+    /// Returns the amount of items with the given name
+    pub fn count_by_name(&self, name: &str) -> usize {
+        self.contents.iter().filter(|slot| slot.item.name == name).map(|slot| slot.amount).sum()
+    }
+
+    /// Returns all items, one per stack
+    pub fn items(&self) -> Vec<&Item> {
+        self.contents.iter().map(|slot| &slot.item).collect()
+    }
+
+    /// Removes a single item equal to the given one from the inventory and returns it
+    pub fn take(&mut self, item: &Item) -> Option<Item> {
+        let index = self.contents.iter().position(|slot| &slot.item == item);
+        index.map(|index| self.take_at(index))
+    }
+
+    /// Returns all items of the given category, e.g. to show only the weapons
+    pub fn items_in(&self, category: ItemCategory) -> Vec<&Item> {
+        self.contents
+            .iter()
+            .map(|slot| &slot.item)
+            .filter(|item| item.item_type.category() == category)
+            .collect()
+    }
+
+    /// Returns the stacks of items of the given category with their amounts
+    pub fn stacks_in(&self, category: ItemCategory) -> Vec<(&Item, usize)> {
+        self.contents
+            .iter()
+            .filter(|slot| slot.item.item_type.category() == category)
+            .map(|slot| (&slot.item, slot.amount))
+            .collect()
+    }
+
+    /// Returns `true` if the inventory contains an item of the given type
+    pub fn contains_type(&self, item_type: &ItemType) -> bool {
+        self.contents.iter().any(|slot| &slot.item.item_type == item_type)
+    }
+
+    /// Removes a single item of the given type from the inventory and returns it
+    pub fn take_by_type(&mut self, item_type: &ItemType) -> Option<Item> {
+        let index = self.contents.iter().position(|slot| &slot.item.item_type == item_type);
+        index.map(|index| self.take_at(index))
+    }
+
+    /// Removes a single item with the given name from the inventory and returns it
+    pub fn take_by_name(&mut self, name: &str) -> Option<Item> {
+        let index = self.contents.iter().position(|slot| slot.item.name == name);
+        index.map(|index| self.take_at(index))
+    }
+
+    /// Removes a single item with the given name to drop it on the ground. Quest items can not
+    /// be dropped.
+    pub fn drop_by_name(&mut self, name: &str) -> Result<Item, InventoryError> {
+        self.check_removable(name)?;
+        Ok(self.take_by_name(name).expect("Unable to find checked item"))
+    }
+
+    /// Sells a single item with the given name for the given price. Quest items can not be sold.
+    pub fn sell_by_name(&mut self, name: &str, price: Gold) -> Result<Item, InventoryError> {
+        let item = self.drop_by_name(name)?;
+        self.gold += price;
+        Ok(item)
+    }
+
+    /// Gives a single item with the given name to another inventory. Quest items can not be
+    /// traded.
+    pub fn trade_by_name(&mut self,
+                         name: &str,
+                         other: &mut Inventory)
+                         -> Result<(), InventoryError> {
+        let item = self.drop_by_name(name)?;
+
+        if let Err(item) = other.add_item(item) {
+            self.add_item(*item).expect("Unable to return traded item");
+            return Err(InventoryError::Full);
+        }
+
+        Ok(())
+    }
+
+    fn check_removable(&self, name: &str) -> Result<(), InventoryError> {
+        match self.find_by_name(name) {
+            None => Err(InventoryError::NotFound),
+            Some(item) if item.quest => Err(InventoryError::QuestItem),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn take_at(&mut self, index: usize) -> Item {
+        if self.contents[index].amount > 1 {
+            // The stack keeps its id, the item taken from it gets a new one
+            let slot = &mut self.contents[index];
+            slot.amount -= 1;
+            slot.taken += 1;
+            Item { id: slot.item.id.derived(slot.taken), ..slot.item.clone() }
+        } else {
+            self.contents.remove(index).item
+        }
+    }
+
+    /// Identifies all items with the given name. Returns `true` if any item was unidentified.
+    pub fn identify(&mut self, name: &str) -> bool {
+        let mut identified = false;
+        for slot in self.contents.iter_mut().filter(|slot| slot.item.name == name) {
+            identified |= !slot.item.identified;
+            slot.item.identify();
+        }
+
+        identified
+    }
+
+    /// Lists the items as shown to the player, one line each, e.g. `3x Unidentified potion`.
+    /// The names and influences of unidentified items stay hidden.
+    pub fn describe(&self) -> Vec<String> {
+        self.contents
+            .iter()
+            .map(|slot| if slot.amount > 1 {
+                format!("{}x {}", slot.amount, slot.item)
+            } else {
+                slot.item.to_string()
+            })
+            .collect()
+    }
+
+    /// Returns the gold carried
+    pub fn gold(&self) -> Gold {
+        self.gold
+    }
+
+    /// Adds gold
+    pub fn add_gold(&mut self, amount: Gold) {
+        self.gold += amount;
+    }
+
+    /// Removes the given amount of gold. Returns `false` and keeps the gold if there is not
+    /// enough.
+    pub fn spend_gold(&mut self, amount: Gold) -> bool {
+        if amount > self.gold {
+            return false;
+        }
+
+        self.gold -= amount;
+        true
+    }
+
     /// Returns `true` it the inventory is full
     pub fn is_full(&self) -> bool {
         self.contents.len() == self.max_size
     }
 }
 
+impl Inventory {
+    /// Reads an inventory from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Inventory, DataError> {
+        let object = data::as_object(json, "inventory")?;
+
+        let mut inventory = Inventory::new(data::unsigned(object, "capacity")? as usize);
+        inventory.gold = data::unsigned(object, "gold")? as Gold;
+        for slot in data::as_array(data::field(object, "items")?, "items")? {
+            let slot = data::as_object(slot, "items")?;
+            inventory.contents.push(InventorySlot {
+                item: Item::from_json(data::field(slot, "item")?)?,
+                amount: data::unsigned(slot, "amount")? as usize,
+                taken: data::optional(slot, "taken", data::unsigned)?.unwrap_or(0),
+            });
+        }
+
+        Ok(inventory)
+    }
+}
+
+impl ToJson for Inventory {
+    fn to_json(&self) -> Json {
+        let items: Vec<Json> = self.contents
+            .iter()
+            .map(|slot| {
+                let mut object = Object::new();
+                object.insert("item".to_owned(), slot.item.to_json());
+                object.insert("amount".to_owned(), slot.amount.to_json());
+                object.insert("taken".to_owned(), slot.taken.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let mut object = Object::new();
+        object.insert("capacity".to_owned(), self.max_size.to_json());
+        object.insert("gold".to_owned(), self.gold.to_json());
+        object.insert("items".to_owned(), Json::Array(items));
+
+        Json::Object(object)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use item_generator;
-    use item::ItemType;
+    use item::{ItemCategory, ItemType};
+
+    #[test]
+    fn resize() {
+        let mut inventory = Inventory::new(3);
+        let arrow = item_generator::ItemGenerator::new()
+            .item_type(ItemType::AmmoArrow)
+            .stack_size(10)
+            .gen();
+        let bread = item_generator::ItemGenerator::new()
+            .item_type(ItemType::ConsumableFood)
+            .stack_size(1)
+            .gen();
+        inventory.add_item(bread.clone()).unwrap();
+        inventory.add_item(arrow.clone()).unwrap();
+        inventory.add_item(arrow.clone()).unwrap();
+
+        let overflow = inventory.resize(1);
+        assert_eq!(inventory.capacity(), 1);
+        assert_eq!(overflow.len(), 2);
+        assert!(overflow.iter().all(|item| item.stacks_with(&arrow)));
+
+        let mut ground = Vec::new();
+        assert!(!inventory.add_or_drop(arrow, &mut ground));
+        assert_eq!(ground.len(), 1);
+        assert_eq!(inventory.resize(5), Vec::new());
+        assert!(inventory.add_or_drop(ground.remove(0), &mut ground));
+    }
+
+    #[test]
+    fn quest_items() {
+        let mut inventory = Inventory::new(10);
+        let ring = item_generator::ItemGenerator::new().name("The One Ring").quest(true).gen();
+        let bread = item_generator::ItemGenerator::new().name("Bread").gen();
+        inventory.add_item(ring).unwrap();
+        inventory.add_item(bread).unwrap();
+
+        assert_eq!(inventory.drop_by_name("The One Ring").map(|_| ()),
+                   Err(InventoryError::QuestItem));
+        assert_eq!(inventory.sell_by_name("Sword", 10).map(|_| ()),
+                   Err(InventoryError::NotFound));
+
+        let mut full = Inventory::new(0);
+        assert_eq!(inventory.trade_by_name("Bread", &mut full), Err(InventoryError::Full));
+        assert!(inventory.find_by_name("Bread").is_some());
+
+        assert!(inventory.sell_by_name("Bread", 3).is_ok());
+        assert_eq!(inventory.gold(), 3);
+        assert!(inventory.find_by_name("The One Ring").is_some());
+    }
+
+    #[test]
+    fn items_in_category() {
+        let mut inventory = Inventory::new(10);
+        let sword = item_generator::ItemGenerator::new().item_type(ItemType::WeaponSword).gen();
+        inventory.add_item(sword.clone()).unwrap();
+        inventory.add_item(item_generator::ItemGenerator::new()
+                .item_type(ItemType::ConsumableFood)
+                .gen())
+            .unwrap();
+
+        assert_eq!(inventory.items_in(ItemCategory::Weapon), vec![&sword]);
+        assert!(inventory.items_in(ItemCategory::Armor).is_empty());
+    }
 
     #[test]
     fn inventory_full() {
@@ -75,6 +395,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn take_by_type() {
+        let mut inv = Inventory::new(30);
+
+        let lockpick = item_generator::ItemGenerator::new()
+            .item_type(ItemType::Lockpick)
+            .stack_size(4)
+            .gen();
+        let _ = inv.add_item(lockpick.clone());
+        let _ = inv.add_item(lockpick.clone());
+
+        let taken = inv.take_by_type(&ItemType::Lockpick).unwrap();
+        assert!(taken.stacks_with(&lockpick));
+        assert_ne!(taken.id, lockpick.id);
+        assert_eq!(taken.id, lockpick.id.derived(1));
+        assert_eq!(inv.contents[0].amount, 1);
+
+        // The ids of the taken items do not repeat after a save
+        let mut loaded = Inventory::from_json(&inv.to_json()).unwrap();
+        loaded.add_item(lockpick.clone()).unwrap();
+        assert_eq!(loaded.take_by_type(&ItemType::Lockpick).unwrap().id, lockpick.id.derived(2));
+
+        assert_eq!(inv.take_by_type(&ItemType::Lockpick), Some(lockpick));
+        assert!(inv.contents.is_empty());
+        assert_eq!(inv.take_by_type(&ItemType::Lockpick), None);
+    }
+
+    #[test]
+    fn describe_hidden() {
+        let mut inv = Inventory::new(30);
+
+        let mut potion = item_generator::ItemGenerator::new()
+            .name("Elixir")
+            .item_type(ItemType::ConsumablePotion)
+            .stack_size(4)
+            .influence(None)
+            .gen();
+        potion.identified = false;
+        let _ = inv.add_item(potion.clone());
+        let _ = inv.add_item(potion);
+
+        assert_eq!(inv.describe(), vec!["2x Unidentified potion".to_owned()]);
+        assert!(inv.identify("Elixir"));
+        assert!(!inv.identify("Elixir"));
+        assert_eq!(inv.describe(), vec!["2x Elixir".to_owned()]);
+    }
+
+    #[test]
+    fn find_by_name() {
+        let mut inv = Inventory::new(30);
+
+        let key = item_generator::ItemGenerator::new().name("Rusty Key").gen();
+        let _ = inv.add_item(key.clone());
+
+        assert_eq!(inv.find_by_name("Rusty Key"), Some(&key));
+        assert_eq!(inv.find_by_name("Golden Key"), None);
+    }
+
     #[test]
     fn stackability() {
         let mut inv = Inventory::new(30);
@@ -93,4 +471,21 @@ mod tests {
 
         assert_eq!(inv.contents[1].amount, random_item_1.stack_size / 4);
     }
+
+    #[test]
+    fn json_round_trip() {
+        let mut inventory = Inventory::new(4);
+        let arrow = item_generator::ItemGenerator::new()
+            .item_type(ItemType::AmmoArrow)
+            .stack_size(10)
+            .gen();
+        inventory.add_item(arrow.clone()).unwrap();
+        inventory.add_item(arrow.clone()).unwrap();
+        inventory.add_gold(12);
+
+        let loaded = Inventory::from_json(&inventory.to_json()).unwrap();
+        assert_eq!(loaded.capacity(), 4);
+        assert_eq!(loaded.gold(), 12);
+        assert_eq!(loaded.count_by_name(&arrow.name), 2);
+    }
 }
@@ -1,10 +1,96 @@
-use character::Attribute;
-use rand::{Rand, Rng};
+use character::{Attribute, Character, EquipmentSlot};
+use data::{self, DataError};
+use dice::Dice;
+use rand::{Rand, Rng, SeedableRng, XorShiftRng};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use types::AttributeValue;
 
+/// The unique id of an item, a random UUID like `6f1c2a9e-3b4d-4e8f-9a0b-1c2d3e4f5a6b`. Items in
+/// the same stack of an inventory share the id of the stack.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct ItemId(String);
+
+impl ItemId {
+    /// Creates a new instance of `ItemId` using the given random number generator, so replays
+    /// create the same ids
+    pub fn random_with<R: Rng>(rng: &mut R) -> ItemId {
+        let mut bytes: [u8; 16] = rng.gen();
+        // The version (4, random) and the variant of the UUID
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        ItemId(format!("{}-{}-{}-{}-{}",
+                       hex[0..4].concat(),
+                       hex[4..6].concat(),
+                       hex[6..8].concat(),
+                       hex[8..10].concat(),
+                       hex[10..16].concat()))
+    }
+
+    /// Derives a new id from this one and the given number, e.g. for the items taken one by one
+    /// from a stack. The same id and number always derive the same id.
+    pub fn derived(&self, number: u64) -> ItemId {
+        let mut bytes = self.0.as_bytes().to_vec();
+        bytes.extend_from_slice(&number.to_le_bytes());
+        ItemId::hashed(fnv1a(&bytes))
+    }
+
+    /// Creates the id of an item read without one from its data, so loading the same data
+    /// always gives the same id
+    fn from_data(json: &Json) -> ItemId {
+        ItemId::hashed(fnv1a(json.to_string().as_bytes()))
+    }
+
+    fn hashed(hash: u64) -> ItemId {
+        // The constant words keep the seed from being all zeros
+        let seed = [hash as u32, (hash >> 32) as u32, 0x9e37_79b9, 0x7f4a_7c15];
+        ItemId::random_with(&mut XorShiftRng::from_seed(seed))
+    }
+
+    /// Returns the id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The 64 bit FNV-1a hash of the given bytes. Unlike the hashers of the standard library it is
+/// guaranteed to stay the same across Rust releases, so derived ids remain stable in saves.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ItemId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<ItemId, ()> {
+        let valid = s.len() == 36 &&
+                    s.char_indices().all(|(index, c)| match index {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+
+        if valid { Ok(ItemId(s.to_lowercase())) } else { Err(()) }
+    }
+}
+
 /// An item
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Item {
+    /// The unique id of the item, so quests, hotbars and saves can refer to this very item
+    pub id: ItemId,
     /// The name of the item
     pub name: String,
     /// The type of the item
@@ -15,26 +101,263 @@ pub struct Item {
     pub stack_size: usize,
     /// The rarity of the item
     pub rarity: ItemRarity,
+    /// `false` while the name and influence of a magical item are hidden from the player
+    pub identified: bool,
+    /// A cursed item can not be unequipped until its curse is lifted
+    pub cursed: bool,
+    /// A quest item can not be dropped, sold or traded
+    pub quest: bool,
+    /// The amount of gems that fit into the item
+    pub sockets: usize,
+    /// The gems inserted into the sockets of the item
+    pub gems: Vec<Item>,
+    /// The damage a weapon deals per attack on top of the influences, e.g. `1d8+2`
+    pub damage: Option<Dice>,
 }
 
 impl Item {
     /// Returns `true` if the item can be equipped
     pub fn can_be_equipped(&self) -> bool {
-        let equipable = vec![ItemType::ArmorHead,
-                             ItemType::ArmorChest,
-                             ItemType::ArmorLegs,
-                             ItemType::ArmorFeet,
-                             ItemType::WeaponSword,
-                             ItemType::WeaponWand,
-                             ItemType::WeaponHammer];
-
-        equipable.contains(&self.item_type)
+        self.item_type.is_equipable()
     }
 
     /// Returns `true` if the item can be stacked
     pub fn can_be_stacked(&self) -> bool {
         self.stack_size > 1
     }
+
+    /// Returns the influence of the item followed by the influences of its gems
+    pub fn influences(&self) -> Vec<&ItemInfluence> {
+        self.influence
+            .iter()
+            .chain(self.gems.iter().filter_map(|gem| gem.influence.as_ref()))
+            .collect()
+    }
+
+    /// Returns the influences the player knows of: none while the item is unidentified
+    pub fn known_influences(&self) -> Vec<&ItemInfluence> {
+        if self.identified {
+            self.influences()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns the attack damage the item adds when wielded by the character: the known
+    /// influences plus the average roll of the damage dice. `0` for everything except weapons.
+    pub fn damage_for(&self, character: &Character) -> AttributeValue {
+        if self.item_type.is_weapon() {
+            let dice = self.damage.map_or(0, |dice| (dice.min() + dice.max()) / 2);
+            character.weapon_damage(&self.known_influences()) + dice
+        } else {
+            0
+        }
+    }
+
+    /// Returns the defense the known influences of the item add for the character
+    pub fn defense_for(&self, character: &Character) -> AttributeValue {
+        let defense = character.get_attribute_value(&Attribute::Defense);
+        self.known_influences()
+            .iter()
+            .filter(|influence| influence.attribute == Attribute::Defense)
+            .map(|influence| influence.modifier(defense))
+            .sum()
+    }
+
+    /// Compares the item with another one, usually the one equipped, from the point of view of
+    /// the character. Hidden influences of unidentified items are not revealed.
+    pub fn compare_for(&self, character: &Character, other: &Item) -> ItemComparison {
+        let mut influences = HashMap::new();
+        for influence in self.known_influences() {
+            let amount = influence.modifier(character.get_attribute_value(&influence.attribute));
+            *influences.entry(influence.attribute.clone()).or_insert(0) += amount;
+        }
+        for influence in other.known_influences() {
+            let amount = influence.modifier(character.get_attribute_value(&influence.attribute));
+            *influences.entry(influence.attribute.clone()).or_insert(0) -= amount;
+        }
+        influences.retain(|_, amount| *amount != 0);
+
+        ItemComparison {
+            attack: self.damage_for(character) - other.damage_for(character),
+            defense: self.defense_for(character) - other.defense_for(character),
+            weight: self.item_type.weight() as i64 - other.item_type.weight() as i64,
+            influences,
+        }
+    }
+
+    /// Returns the amount of empty sockets
+    pub fn free_sockets(&self) -> usize {
+        self.sockets.saturating_sub(self.gems.len())
+    }
+
+    /// Inserts a gem into an empty socket. Returns the gem if it is no `ItemType::Gem` or all
+    /// sockets are taken.
+    pub fn insert_gem(&mut self, gem: Item) -> Result<(), Box<Item>> {
+        if gem.item_type != ItemType::Gem || self.free_sockets() == 0 {
+            return Err(Box::new(gem));
+        }
+
+        self.gems.push(gem);
+        Ok(())
+    }
+
+    /// Removes the gem at the given index and returns it
+    pub fn remove_gem(&mut self, index: usize) -> Option<Item> {
+        if index < self.gems.len() {
+            Some(self.gems.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the item equals the other one except for the id, so both fit into the
+    /// same stack
+    pub fn stacks_with(&self, other: &Item) -> bool {
+        Item { id: other.id.clone(), ..self.clone() } == *other
+    }
+
+    /// Returns a copy of the item with a new id created by the given random number generator,
+    /// e.g. to create items from a template
+    pub fn renewed<R: Rng>(&self, rng: &mut R) -> Item {
+        Item { id: ItemId::random_with(rng), ..self.clone() }
+    }
+
+    /// Returns the name shown to the player, e.g. `Unidentified sword`
+    pub fn display_name(&self) -> String {
+        if self.identified {
+            self.name.clone()
+        } else {
+            format!("Unidentified {}", self.item_type.noun())
+        }
+    }
+
+    /// Returns the influence shown to the player, which is hidden until the item is identified.
+    /// The influence applies either way.
+    pub fn known_influence(&self) -> Option<&ItemInfluence> {
+        if self.identified {
+            self.influence.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Reveals the name and influence of the item
+    pub fn identify(&mut self) {
+        self.identified = true;
+    }
+
+    /// Reads an item from a JSON object as written by `to_json`. Items are identified unless
+    /// `identified` is `false`.
+    pub fn from_json(json: &Json) -> Result<Item, DataError> {
+        let object = data::as_object(json, "item")?;
+
+        Ok(Item {
+            id: match data::optional(object, "id", data::parse)? {
+                Some(id) => id,
+                None => ItemId::from_data(json),
+            },
+            name: data::string(object, "name")?,
+            item_type: data::parse(object, "item_type")?,
+            influence: match object.get("influence") {
+                None | Some(&Json::Null) => None,
+                Some(influence) => Some(ItemInfluence::from_json(influence)?),
+            },
+            stack_size: data::unsigned(object, "stack_size")? as usize,
+            rarity: data::parse(object, "rarity")?,
+            identified: data::optional(object, "identified", data::boolean)?.unwrap_or(true),
+            cursed: data::optional(object, "cursed", data::boolean)?.unwrap_or(false),
+            quest: data::optional(object, "quest", data::boolean)?.unwrap_or(false),
+            sockets: data::optional(object, "sockets", data::unsigned)?.unwrap_or(0) as usize,
+            gems: match object.get("gems") {
+                None => Vec::new(),
+                Some(gems) => {
+                    data::as_array(gems, "gems")?
+                        .iter()
+                        .map(Item::from_json)
+                        .collect::<Result<_, _>>()?
+                }
+            },
+            damage: data::optional(object, "damage", data::parse)?,
+        })
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name())?;
+
+        if let Some(influence) = self.known_influence() {
+            write!(f, " ({})", influence)?;
+        }
+        if self.identified && self.cursed {
+            write!(f, " (cursed)")?;
+        }
+        if self.sockets > 0 {
+            write!(f, " [{}/{} gems]", self.gems.len(), self.sockets)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToJson for Item {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.as_str().to_json());
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("item_type".to_owned(), format!("{:?}", self.item_type).to_json());
+        if let Some(ref influence) = self.influence {
+            object.insert("influence".to_owned(), influence.to_json());
+        }
+        object.insert("stack_size".to_owned(), self.stack_size.to_json());
+        object.insert("rarity".to_owned(), format!("{:?}", self.rarity).to_json());
+        object.insert("identified".to_owned(), self.identified.to_json());
+        object.insert("cursed".to_owned(), self.cursed.to_json());
+        object.insert("quest".to_owned(), self.quest.to_json());
+        object.insert("sockets".to_owned(), self.sockets.to_json());
+        object.insert("gems".to_owned(), self.gems.to_json());
+        if let Some(ref damage) = self.damage {
+            object.insert("damage".to_owned(), damage.to_string().to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// The difference between two items for a character, e.g. to show `+4 damage over equipped`.
+/// Positive values are in favor of the compared item.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ItemComparison {
+    /// The difference in attack damage when wielding the item
+    pub attack: AttributeValue,
+    /// The difference in defense
+    pub defense: AttributeValue,
+    /// The difference in weight in pounds
+    pub weight: i64,
+    /// The differences of the known influences, without the attributes that stay the same
+    pub influences: HashMap<Attribute, AttributeValue>,
+}
+
+/// How the amount of an influence changes the attribute
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InfluenceKind {
+    /// The amount is added to the attribute
+    Flat,
+    /// The attribute grows by the amount in percent, e.g. `+10% Strength`
+    Percent,
+}
+
+impl FromStr for InfluenceKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<InfluenceKind, ()> {
+        match s {
+            "Flat" => Ok(InfluenceKind::Flat),
+            "Percent" => Ok(InfluenceKind::Percent),
+            _ => Err(()),
+        }
+    }
 }
 
 /// The influence an item can have on a certain attribute
@@ -44,18 +367,85 @@ pub struct ItemInfluence {
     pub attribute: Attribute,
     /// The amount about which the attribute is influenced
     pub amount: AttributeValue,
+    /// Whether the amount is a fixed value or a percentage
+    pub kind: InfluenceKind,
 }
 
 impl ItemInfluence {
-    /// Creates a new `ItemInfluence` object
+    /// Creates a new `ItemInfluence` object adding a fixed amount
     pub fn new(attribute: Attribute, amount: AttributeValue) -> ItemInfluence {
         ItemInfluence {
             attribute: attribute,
             amount: amount,
+            kind: InfluenceKind::Flat,
+        }
+    }
+
+    /// Creates a new `ItemInfluence` object changing the attribute by the amount in percent
+    pub fn percent(attribute: Attribute, amount: AttributeValue) -> ItemInfluence {
+        ItemInfluence { kind: InfluenceKind::Percent, ..ItemInfluence::new(attribute, amount) }
+    }
+
+    /// Returns the amount the influence adds to the given value of its attribute
+    pub fn modifier(&self, value: AttributeValue) -> AttributeValue {
+        match self.kind {
+            InfluenceKind::Flat => self.amount,
+            InfluenceKind::Percent => value * self.amount / 100,
+        }
+    }
+
+    /// Reads an influence from a JSON object like
+    /// `{"attribute": "Strength", "amount": 5, "kind": "Percent"}`. The kind defaults to `Flat`.
+    pub fn from_json(json: &Json) -> Result<ItemInfluence, DataError> {
+        let object = data::as_object(json, "influence")?;
+
+        Ok(ItemInfluence {
+            attribute: data::parse(object, "attribute")?,
+            amount: data::signed(object, "amount")? as AttributeValue,
+            kind: data::optional(object, "kind", data::parse)?.unwrap_or(InfluenceKind::Flat),
+        })
+    }
+}
+
+impl fmt::Display for ItemInfluence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            InfluenceKind::Flat => write!(f, "{:+} {:?}", self.amount, self.attribute),
+            InfluenceKind::Percent => write!(f, "{:+}% {:?}", self.amount, self.attribute),
         }
     }
 }
 
+impl ToJson for ItemInfluence {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("attribute".to_owned(), format!("{:?}", self.attribute).to_json());
+        object.insert("amount".to_owned(), self.amount.to_json());
+        object.insert("kind".to_owned(), format!("{:?}", self.kind).to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A group of item types, e.g. all kinds of armor
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ItemCategory {
+    /// Armor worn on the head, chest, legs or feet
+    Armor,
+    /// Melee and ranged weapons
+    Weapon,
+    /// Arrows and bolts shot by ranged weapons
+    Ammunition,
+    /// Potions, food and drinks
+    Consumable,
+    /// Lockpicks and other usable items
+    Tool,
+    /// Gems that can be inserted into sockets
+    Gem,
+    /// Everything else
+    Misc,
+}
+
 /// The type of an item
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ItemType {
@@ -72,6 +462,8 @@ pub enum ItemType {
     ConsumablePotion,
     /// Some kind of food
     ConsumableFood,
+    /// Some kind of drink, e.g. water
+    ConsumableDrink,
 
     /// Some kind of sword
     WeaponSword,
@@ -79,11 +471,26 @@ pub enum ItemType {
     WeaponWand,
     /// Some kind of hammer
     WeaponHammer,
+    /// A bow shooting arrows
+    WeaponBow,
+    /// A crossbow shooting bolts
+    WeaponCrossbow,
+    /// A weapon that is thrown, e.g. a throwing knife
+    WeaponThrown,
+
+    /// Arrows shot by a bow
+    AmmoArrow,
+    /// Bolts shot by a crossbow
+    AmmoBolt,
 
     /// A usable item
     Usable,
+    /// A tool used to pick locks
+    Lockpick,
     /// A useless prop
     Prop,
+    /// A gem that can be inserted into a socket of a weapon or armor to add its influence
+    Gem,
 }
 
 impl ItemType {
@@ -91,19 +498,11 @@ impl ItemType {
     pub fn attributes(&self) -> Vec<Attribute> {
         match *self {
             ItemType::ConsumableFood |
-            ItemType::ConsumablePotion => {
-                vec![Attribute::Charisma,
-                     Attribute::Constitution,
-                     Attribute::Defense,
-                     Attribute::Dexterity,
-                     Attribute::Intelligence,
-                     Attribute::Luck,
-                     Attribute::Perception,
-                     Attribute::Strength,
-                     Attribute::Willpower,
-                     Attribute::Wisdom]
-            }
-            ItemType::WeaponHammer | ItemType::WeaponSword | ItemType::WeaponWand => {
+            ItemType::ConsumableDrink |
+            ItemType::ConsumablePotion |
+            ItemType::Gem => Attribute::all(),
+            ItemType::WeaponHammer | ItemType::WeaponSword | ItemType::WeaponWand |
+            ItemType::WeaponBow | ItemType::WeaponCrossbow | ItemType::WeaponThrown => {
                 vec![Attribute::Dexterity, Attribute::Strength]
             }
             ItemType::ArmorHead | ItemType::ArmorChest | ItemType::ArmorLegs |
@@ -115,24 +514,165 @@ impl ItemType {
                      Attribute::Luck,
                      Attribute::Perception]
             }
-            ItemType::Usable | ItemType::Prop => vec![],
+            ItemType::AmmoArrow | ItemType::AmmoBolt | ItemType::Usable | ItemType::Lockpick |
+            ItemType::Prop => vec![],
+        }
+    }
+
+    /// Returns a lowercase noun describing items of this type, e.g. `sword`
+    pub fn noun(&self) -> &'static str {
+        match *self {
+            ItemType::ArmorHead => "helmet",
+            ItemType::ArmorChest => "armor",
+            ItemType::ArmorLegs => "leggings",
+            ItemType::ArmorFeet => "boots",
+            ItemType::ConsumablePotion => "potion",
+            ItemType::ConsumableFood => "food",
+            ItemType::ConsumableDrink => "drink",
+            ItemType::WeaponSword => "sword",
+            ItemType::WeaponWand => "wand",
+            ItemType::WeaponHammer => "hammer",
+            ItemType::WeaponBow => "bow",
+            ItemType::WeaponCrossbow => "crossbow",
+            ItemType::WeaponThrown => "throwing weapon",
+            ItemType::AmmoArrow => "arrow",
+            ItemType::AmmoBolt => "bolt",
+            ItemType::Usable => "item",
+            ItemType::Lockpick => "lockpick",
+            ItemType::Prop => "prop",
+            ItemType::Gem => "gem",
+        }
+    }
+
+    /// Returns the category of items of this type, e.g. to filter an inventory
+    pub fn category(&self) -> ItemCategory {
+        match *self {
+            ItemType::ArmorHead |
+            ItemType::ArmorChest |
+            ItemType::ArmorLegs |
+            ItemType::ArmorFeet => ItemCategory::Armor,
+            ItemType::WeaponSword |
+            ItemType::WeaponWand |
+            ItemType::WeaponHammer |
+            ItemType::WeaponBow |
+            ItemType::WeaponCrossbow |
+            ItemType::WeaponThrown => ItemCategory::Weapon,
+            ItemType::AmmoArrow | ItemType::AmmoBolt => ItemCategory::Ammunition,
+            ItemType::ConsumablePotion |
+            ItemType::ConsumableFood |
+            ItemType::ConsumableDrink => ItemCategory::Consumable,
+            ItemType::Usable | ItemType::Lockpick => ItemCategory::Tool,
+            ItemType::Gem => ItemCategory::Gem,
+            ItemType::Prop => ItemCategory::Misc,
+        }
+    }
+
+    /// Returns the slot items of this type are equipped in. Weapons go into the right hand by
+    /// default, but fit into both hands.
+    pub fn slot(&self) -> Option<EquipmentSlot> {
+        match *self {
+            ItemType::ArmorHead => Some(EquipmentSlot::Head),
+            ItemType::ArmorChest => Some(EquipmentSlot::Chest),
+            ItemType::ArmorLegs => Some(EquipmentSlot::Legs),
+            ItemType::ArmorFeet => Some(EquipmentSlot::Feet),
+            _ if self.is_weapon() => Some(EquipmentSlot::RightHand),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if items of this type can be equipped
+    pub fn is_equipable(&self) -> bool {
+        self.is_armor() || self.is_weapon()
+    }
+
+    /// Returns `true` if items of this type are worn as armor
+    pub fn is_armor(&self) -> bool {
+        self.category() == ItemCategory::Armor
+    }
+
+    /// Returns `true` if items of this type are wielded as weapons
+    pub fn is_weapon(&self) -> bool {
+        self.category() == ItemCategory::Weapon
+    }
+
+    /// Returns `true` if items of this type are used up when eaten or drunk
+    pub fn is_consumable(&self) -> bool {
+        self.category() == ItemCategory::Consumable
+    }
+
+    /// Returns the weight of a single item of this type in pounds
+    pub fn weight(&self) -> u32 {
+        match *self {
+            ItemType::ArmorChest => 20,
+            ItemType::WeaponHammer => 12,
+            ItemType::ArmorLegs => 8,
+            ItemType::WeaponCrossbow => 6,
+            ItemType::ArmorHead | ItemType::ArmorFeet | ItemType::WeaponSword => 4,
+            ItemType::WeaponBow | ItemType::Prop => 2,
+            ItemType::WeaponWand |
+            ItemType::WeaponThrown |
+            ItemType::ConsumablePotion |
+            ItemType::ConsumableFood |
+            ItemType::ConsumableDrink |
+            ItemType::Usable => 1,
+            ItemType::AmmoArrow | ItemType::AmmoBolt | ItemType::Lockpick | ItemType::Gem => 0,
         }
     }
 
     /// Returns `true` if the item created using this type should be stackable
     pub fn is_stackable(&self) -> bool {
-        let stackable_types = vec![ItemType::ConsumableFood, ItemType::ConsumablePotion];
+        let stackable_types = [ItemType::ConsumableFood,
+                               ItemType::ConsumableDrink,
+                               ItemType::ConsumablePotion,
+                               ItemType::Lockpick,
+                               ItemType::WeaponThrown,
+                               ItemType::AmmoArrow,
+                               ItemType::AmmoBolt];
 
         stackable_types.contains(self)
     }
 
+    /// The amount of fields a weapon of this type can reach. `0` for melee weapons and
+    /// everything else.
+    pub fn range(&self) -> u32 {
+        match *self {
+            ItemType::WeaponBow => 6,
+            ItemType::WeaponCrossbow => 8,
+            ItemType::WeaponThrown => 3,
+            _ => 0,
+        }
+    }
+
+    /// The damage dice of a plain weapon of this type. `None` for everything except weapons
+    pub fn damage(&self) -> Option<Dice> {
+        match *self {
+            ItemType::WeaponHammer => Some(Dice::new(1, 10)),
+            ItemType::WeaponSword | ItemType::WeaponCrossbow => Some(Dice::new(1, 8)),
+            ItemType::WeaponBow => Some(Dice::new(1, 6)),
+            ItemType::WeaponWand | ItemType::WeaponThrown => Some(Dice::new(1, 4)),
+            _ => None,
+        }
+    }
+
+    /// The type of ammunition a ranged weapon of this type uses up per shot. Thrown weapons use up
+    /// weapons of their own kind.
+    pub fn ammunition(&self) -> Option<ItemType> {
+        match *self {
+            ItemType::WeaponBow => Some(ItemType::AmmoArrow),
+            ItemType::WeaponCrossbow => Some(ItemType::AmmoBolt),
+            ItemType::WeaponThrown => Some(ItemType::WeaponThrown),
+            _ => None,
+        }
+    }
+
     /// A helper method to get an ItemType
     pub fn by_num(item_class_num: u32, item_type_num: u32) -> ItemType {
         match item_class_num {
             0...250 => {
                 match item_type_num {
-                    0...500 => ItemType::ConsumableFood,
-                    501...1000 => ItemType::ConsumablePotion,
+                    0...333 => ItemType::ConsumableFood,
+                    334...666 => ItemType::ConsumableDrink,
+                    667...1000 => ItemType::ConsumablePotion,
                     _ => ItemType::Prop,
                 }
             }
@@ -147,16 +687,23 @@ impl ItemType {
             }
             501...750 => {
                 match item_type_num {
-                    0...333 => ItemType::WeaponHammer,
-                    334...666 => ItemType::WeaponSword,
-                    667...1000 => ItemType::WeaponWand,
+                    0...200 => ItemType::WeaponHammer,
+                    201...400 => ItemType::WeaponSword,
+                    401...600 => ItemType::WeaponWand,
+                    601...733 => ItemType::WeaponBow,
+                    734...866 => ItemType::WeaponCrossbow,
+                    867...1000 => ItemType::WeaponThrown,
                     _ => ItemType::Prop,
                 }
             }
             751...1000 => {
                 match item_type_num {
-                    0...500 => ItemType::Usable,
-                    501...1000 => ItemType::Prop,
+                    0...300 => ItemType::Usable,
+                    301...400 => ItemType::Lockpick,
+                    401...450 => ItemType::AmmoArrow,
+                    451...500 => ItemType::AmmoBolt,
+                    501...600 => ItemType::Gem,
+                    601...1000 => ItemType::Prop,
                     _ => ItemType::Prop,
                 }
             }
@@ -165,6 +712,35 @@ impl ItemType {
     }
 }
 
+impl FromStr for ItemType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<ItemType, ()> {
+        match s {
+            "ArmorHead" => Ok(ItemType::ArmorHead),
+            "ArmorChest" => Ok(ItemType::ArmorChest),
+            "ArmorLegs" => Ok(ItemType::ArmorLegs),
+            "ArmorFeet" => Ok(ItemType::ArmorFeet),
+            "ConsumablePotion" => Ok(ItemType::ConsumablePotion),
+            "ConsumableFood" => Ok(ItemType::ConsumableFood),
+            "ConsumableDrink" => Ok(ItemType::ConsumableDrink),
+            "WeaponSword" => Ok(ItemType::WeaponSword),
+            "WeaponWand" => Ok(ItemType::WeaponWand),
+            "WeaponHammer" => Ok(ItemType::WeaponHammer),
+            "WeaponBow" => Ok(ItemType::WeaponBow),
+            "WeaponCrossbow" => Ok(ItemType::WeaponCrossbow),
+            "WeaponThrown" => Ok(ItemType::WeaponThrown),
+            "AmmoArrow" => Ok(ItemType::AmmoArrow),
+            "AmmoBolt" => Ok(ItemType::AmmoBolt),
+            "Usable" => Ok(ItemType::Usable),
+            "Lockpick" => Ok(ItemType::Lockpick),
+            "Prop" => Ok(ItemType::Prop),
+            "Gem" => Ok(ItemType::Gem),
+            _ => Err(()),
+        }
+    }
+}
+
 impl Rand for ItemType {
     fn rand<R: Rng>(rng: &mut R) -> ItemType {
         let item_class_num = rng.gen_range(0, 1000);
@@ -175,7 +751,7 @@ impl Rand for ItemType {
 }
 
 /// A type defining the rarity of an item
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ItemRarity {
     /// Items are found very often
     Common,
@@ -203,6 +779,21 @@ impl ItemRarity {
     }
 }
 
+impl FromStr for ItemRarity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<ItemRarity, ()> {
+        match s {
+            "Common" => Ok(ItemRarity::Common),
+            "Uncommon" => Ok(ItemRarity::Uncommon),
+            "Rare" => Ok(ItemRarity::Rare),
+            "Epic" => Ok(ItemRarity::Epic),
+            "Legendary" => Ok(ItemRarity::Legendary),
+            _ => Err(()),
+        }
+    }
+}
+
 impl Rand for ItemRarity {
     fn rand<R: Rng>(rng: &mut R) -> ItemRarity {
         let base = rng.gen_range(0, 1000);
@@ -253,6 +844,157 @@ mod tests {
         assert_eq!(ItemRarity::by_num(1000), ItemRarity::Legendary);
     }
 
+    #[test]
+    fn categories() {
+        assert_eq!(ItemType::ArmorLegs.category(), ItemCategory::Armor);
+        assert_eq!(ItemType::ArmorLegs.slot(), Some(EquipmentSlot::Legs));
+        assert_eq!(ItemType::WeaponBow.slot(), Some(EquipmentSlot::RightHand));
+        assert_eq!(ItemType::Gem.slot(), None);
+        assert!(ItemType::ConsumableDrink.is_consumable());
+        assert!(!ItemType::AmmoBolt.is_equipable());
+        assert!(ItemType::ArmorHead.is_armor() && !ItemType::ArmorHead.is_weapon());
+    }
+
+    #[test]
+    fn ranged_weapons() {
+        assert_eq!(ItemType::WeaponSword.range(), 0);
+        assert_eq!(ItemType::WeaponSword.ammunition(), None);
+        assert!(ItemType::WeaponBow.range() > 0);
+        assert_eq!(ItemType::WeaponBow.ammunition(), Some(ItemType::AmmoArrow));
+        assert_eq!(ItemType::WeaponThrown.ammunition(), Some(ItemType::WeaponThrown));
+        assert_eq!(ItemType::WeaponBow.damage(), Some(Dice::new(1, 6)));
+        assert_eq!(ItemType::AmmoArrow.damage(), None);
+        assert!(ItemType::AmmoBolt.is_stackable());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("WeaponSword".parse(), Ok(ItemType::WeaponSword));
+        assert_eq!("Lockpick".parse(), Ok(ItemType::Lockpick));
+        assert_eq!("Sword".parse::<ItemType>(), Err(()));
+
+        assert_eq!("Legendary".parse(), Ok(ItemRarity::Legendary));
+        assert_eq!("Mythic".parse::<ItemRarity>(), Err(()));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let item = item_generator::ItemGenerator::new().item_type(ItemType::ArmorChest).gen();
+        assert_eq!(Item::from_json(&item.to_json()).unwrap(), item);
+
+        let mut item = item;
+        item.influence = None;
+        assert_eq!(Item::from_json(&item.to_json()).unwrap(), item);
+    }
+
+    #[test]
+    fn unidentified() {
+        let mut item = item_generator::ItemGenerator::new()
+            .name("Ring Mail of Vuskia")
+            .item_type(ItemType::ArmorChest)
+            .influence(Some(ItemInfluence::new(Attribute::Defense, 12)))
+            .sockets(0)
+            .gen();
+        item.identified = false;
+
+        assert_eq!(item.to_string(), "Unidentified armor");
+        assert_eq!(item.known_influence(), None);
+        assert_eq!(Item::from_json(&item.to_json()).unwrap(), item);
+
+        item.identify();
+        assert_eq!(item.to_string(), "Ring Mail of Vuskia (+12 Defense)");
+    }
+
+    #[test]
+    fn sockets() {
+        let mut sword = item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponSword)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, 4)))
+            .sockets(1)
+            .gen();
+        let ruby = item_generator::ItemGenerator::new()
+            .item_type(ItemType::Gem)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, 2)))
+            .gen();
+        let rock = item_generator::ItemGenerator::new().item_type(ItemType::Prop).gen();
+
+        assert_eq!(sword.insert_gem(rock.clone()), Err(Box::new(rock)));
+        assert_eq!(sword.insert_gem(ruby.clone()), Ok(()));
+        assert_eq!(sword.insert_gem(ruby.clone()), Err(Box::new(ruby.clone())));
+        assert_eq!(sword.influences().iter().map(|influence| influence.amount).sum::<i64>(), 6);
+        assert_eq!(Item::from_json(&sword.to_json()).unwrap(), sword);
+
+        assert_eq!(sword.remove_gem(0), Some(ruby));
+        assert_eq!(sword.remove_gem(0), None);
+        assert_eq!(sword.free_sockets(), 1);
+    }
+
+    #[test]
+    fn compare() {
+        let character = Character::new("Aragorn");
+        let equipped = item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponSword)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, 2)))
+            .damage(Some(Dice::new(1, 8)))
+            .gen();
+        let hammer = item_generator::ItemGenerator::new()
+            .item_type(ItemType::WeaponHammer)
+            .influence(Some(ItemInfluence::new(Attribute::Defense, 6)))
+            .damage(Some(Dice::new(2, 6)))
+            .gen();
+
+        let comparison = hammer.compare_for(&character, &equipped);
+        assert_eq!(comparison.attack, 7);
+        assert_eq!(comparison.defense, 6);
+        assert_eq!(comparison.weight, 8);
+        assert_eq!(comparison.influences.get(&Attribute::Strength), Some(&-2));
+
+        let mut hidden = hammer.clone();
+        hidden.identified = false;
+        assert_eq!(hidden.compare_for(&character, &equipped).defense, 0);
+    }
+
+    #[test]
+    fn percent_influence() {
+        let influence = ItemInfluence::percent(Attribute::Strength, 10);
+        assert_eq!(influence.modifier(50), 5);
+        assert_eq!(influence.to_string(), "+10% Strength");
+        assert_eq!(ItemInfluence::new(Attribute::Strength, 10).modifier(50), 10);
+        assert_eq!(ItemInfluence::from_json(&influence.to_json()).unwrap(), influence);
+
+        let legacy = Json::from_str(r#"{"attribute": "Luck", "amount": -2}"#).unwrap();
+        assert_eq!(ItemInfluence::from_json(&legacy).unwrap().kind, InfluenceKind::Flat);
+    }
+
+    #[test]
+    fn item_id() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let id = ItemId::random_with(&mut rng);
+        assert_eq!(id.as_str().len(), 36);
+        assert_eq!(&id.as_str()[14..15], "4");
+        assert_eq!(id.as_str().parse(), Ok(id.clone()));
+        assert_ne!(ItemId::random_with(&mut rng), id);
+        assert_eq!("not-a-uuid".parse::<ItemId>(), Err(()));
+
+        assert_eq!(id.derived(1), id.derived(1));
+        assert_ne!(id.derived(1), id.derived(2));
+        assert_eq!(&id.derived(1).as_str()[14..15], "4");
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        let fixed: ItemId = "6f1c2a9e-3b4d-4e8f-9a0b-1c2d3e4f5a6b".parse().unwrap();
+        assert_eq!(fixed.derived(1).as_str(), "953efb4c-c65f-4b82-8a04-fb1053b36cc1");
+
+        let sword = item_generator::ItemGenerator::new().item_type(ItemType::WeaponSword).gen();
+        let copy = sword.renewed(&mut rng);
+        assert_ne!(copy, sword);
+        assert!(copy.stacks_with(&sword));
+
+        let json = Json::from_str(r#"{"name": "Sting", "item_type": "WeaponSword",
+                                      "stack_size": 1, "rarity": "Epic"}"#)
+            .unwrap();
+        assert_eq!(Item::from_json(&json).unwrap().id, Item::from_json(&json).unwrap().id);
+    }
+
     #[test]
     fn item_type() {
         for class_num in (0..1000) {
@@ -1,18 +1,274 @@
 use item::*;
 use character::Attribute;
+use data::{self, DataError};
+use dice::Dice;
+use format::Format;
 use rand::Rng;
 use rand;
 use names::{Generator, Name};
+use rustc_serialize::json::Json;
+use std::collections::HashSet;
+use std::path::Path;
 use types::AttributeValue;
+use world::two_dimensional::FieldType;
+
+/// How much every level above the first shifts the random rarity towards rarer items, out of
+/// `1000`
+const LEVEL_RARITY_BONUS: u32 = 10;
+
+/// How much stronger the influence of items gets with every level above the first
+const LEVEL_INFLUENCE_BONUS: f64 = 0.1;
+
+/// The built-in places weapons are from
+const SUFFIXES: &[&str] = &["Nashioce",
+                             "Gobloygro",
+                             "Vuskia",
+                             "Lawhos",
+                             "Shiyle",
+                             "Steiwana",
+                             "Ashington",
+                             "Ustistan",
+                             "Plez Chium",
+                             "Staej Slua",
+                             "Ospaewana",
+                             "Wespeugua",
+                             "Cuchein",
+                             "Keflya",
+                             "Speyle",
+                             "Swainia",
+                             "Eswijan",
+                             "Uswein",
+                             "Scok Slya",
+                             "Proz Drana",
+                             "Decruecia",
+                             "Vospoydan",
+                             "Xesneau",
+                             "Japlax",
+                             "Scuecia",
+                             "Dreina",
+                             "Uswela",
+                             "Usten",
+                             "Smen Snana",
+                             "Glan Gra",
+                             "Puswaenia",
+                             "Jepraoles",
+                             "Pasla",
+                             "Ewhium",
+                             "Floulia",
+                             "Plioso",
+                             "Aplurg",
+                             "Escines",
+                             "Groyt Thington",
+                             "Fleiw Flen"];
+
+/// The words random item names are made of
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NamePool {
+    /// The kinds of weapons, e.g. `Sword`
+    pub weapons: Vec<String>,
+    /// The words in front of weapon names, e.g. `Shiny`
+    pub prefixes: Vec<String>,
+    /// The places weapons are from, e.g. `Vuskia`
+    pub suffixes: Vec<String>,
+    /// The names of all other items. Random names are made up if empty.
+    pub items: Vec<String>,
+}
 
-/// A builder like generator for items. Missing fields are filled randomly
+impl Default for NamePool {
+    fn default() -> NamePool {
+        let strings = |words: &[&str]| -> Vec<String> {
+            words.iter().map(|word| word.to_string()).collect()
+        };
+
+        NamePool {
+            weapons: strings(&["Sword", "Boulder", "Wand", "Dagger", "Hammer", "Rifle", "Bow",
+                               "Crossbow", "Javelin"]),
+            prefixes: strings(&["Shiny", "Firey", "Wonderous", "Giant"]),
+            suffixes: strings(SUFFIXES),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl NamePool {
+    /// Reads a name pool from a JSON object like
+    /// `{"weapons": ["Axe"], "prefixes": ["Rusty"], "suffixes": ["Doom"], "items": ["Rope"]}`.
+    /// Missing lists are taken from the built-in pool. Fails if a list is empty, except `items`.
+    pub fn from_json(json: &Json) -> Result<NamePool, DataError> {
+        let object = data::as_object(json, "names")?;
+        let builtin = NamePool::default();
+
+        let list = |key: &str, default: Vec<String>| -> Result<Vec<String>, DataError> {
+            match object.get(key) {
+                None => Ok(default),
+                Some(list) => {
+                    data::as_array(list, key)?
+                        .iter()
+                        .map(|name| data::parse_value(name, key))
+                        .collect()
+                }
+            }
+        };
+
+        let names = NamePool {
+            weapons: list("weapons", builtin.weapons)?,
+            prefixes: list("prefixes", builtin.prefixes)?,
+            suffixes: list("suffixes", builtin.suffixes)?,
+            items: list("items", builtin.items)?,
+        };
+
+        for &(key, list) in &[("weapons", &names.weapons),
+                              ("prefixes", &names.prefixes),
+                              ("suffixes", &names.suffixes)] {
+            if list.is_empty() {
+                return Err(DataError::Invalid(key.to_owned()));
+            }
+        }
+
+        Ok(names)
+    }
+}
+
+/// A hand-made item, e.g. a unique weapon. Fields that are not set are filled randomly.
 #[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ItemTemplate {
+    /// The name of the item
+    pub name: String,
+    /// The type of the item
+    pub item_type: ItemType,
+    /// The rarity of the item. Random if not set
+    pub rarity: Option<ItemRarity>,
+    /// The influence of the item. Random if not set
+    pub influence: Option<ItemInfluence>,
+    /// The stack size of the item. Random if not set
+    pub stack_size: Option<usize>,
+}
+
+impl ItemTemplate {
+    /// Reads an item template from a JSON object like `{"name": "Sting", "item_type":
+    /// "WeaponSword", "rarity": "Epic", "influence": {"attribute": "Dexterity", "amount": 20}}`.
+    /// Fails if the item type can not have the influence or stack size.
+    pub fn from_json(json: &Json) -> Result<ItemTemplate, DataError> {
+        let object = data::as_object(json, "templates")?;
+
+        let template = ItemTemplate {
+            name: data::string(object, "name")?,
+            item_type: data::parse(object, "item_type")?,
+            rarity: data::optional(object, "rarity", data::parse)?,
+            influence: match object.get("influence") {
+                None | Some(&Json::Null) => None,
+                Some(influence) => Some(ItemInfluence::from_json(influence)?),
+            },
+            stack_size: data::optional(object, "stack_size", data::unsigned)?
+                .map(|stack_size| stack_size as usize),
+        };
+
+        if let Some(ref influence) = template.influence {
+            if !template.item_type.attributes().contains(&influence.attribute) {
+                return Err(DataError::Invalid("influence".to_owned()));
+            }
+        }
+        match template.stack_size {
+            Some(0) => return Err(DataError::Invalid("stack_size".to_owned())),
+            Some(stack_size) if stack_size > 1 && !template.item_type.is_stackable() => {
+                return Err(DataError::Invalid("stack_size".to_owned()));
+            }
+            _ => {}
+        }
+
+        Ok(template)
+    }
+
+    /// Returns a generator creating items from the template
+    pub fn generator(&self) -> ItemGenerator {
+        let mut generator = ItemGenerator::new().name(&self.name).item_type(self.item_type.clone());
+        if let Some(ref rarity) = self.rarity {
+            generator = generator.rarity(rarity.clone());
+        }
+        if self.influence.is_some() {
+            generator = generator.influence(self.influence.clone());
+        }
+        if let Some(stack_size) = self.stack_size {
+            generator = generator.stack_size(stack_size);
+        }
+
+        generator
+    }
+}
+
+/// Item templates and name pools loaded from a data file
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ItemData {
+    /// The hand-made items
+    pub templates: Vec<ItemTemplate>,
+    /// The words random item names are made of
+    pub names: NamePool,
+}
+
+impl ItemData {
+    /// Reads item data from a JSON object like `{"templates": [...], "names": {...}}`. Both keys
+    /// are optional. Fails if two templates have the same name.
+    pub fn from_json(json: &Json) -> Result<ItemData, DataError> {
+        let object = data::as_object(json, "items")?;
+
+        let templates: Vec<ItemTemplate> = match object.get("templates") {
+            None => Vec::new(),
+            Some(templates) => {
+                data::as_array(templates, "templates")?
+                    .iter()
+                    .map(ItemTemplate::from_json)
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        let mut seen = HashSet::new();
+        if !templates.iter().all(|template| seen.insert(&template.name)) {
+            return Err(DataError::Invalid("templates".to_owned()));
+        }
+
+        Ok(ItemData {
+            templates,
+            names: match object.get("names") {
+                None => NamePool::default(),
+                Some(names) => NamePool::from_json(names)?,
+            },
+        })
+    }
+
+    /// Loads item data from a file. The format is chosen by the extension, JSON by default.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<ItemData, DataError> {
+        let format = Format::from_path(&path).unwrap_or(Format::Json);
+        ItemData::from_json(&format.read_file(path)?)
+    }
+
+    /// Returns the template with the given name
+    pub fn template(&self, name: &str) -> Option<&ItemTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Returns a generator naming random items from the loaded name pool
+    pub fn generator(&self) -> ItemGenerator {
+        ItemGenerator::new().names(self.names.clone())
+    }
+}
+
+/// A builder like generator for items. Missing fields are filled randomly
+#[derive(Clone, PartialEq, Debug)]
 pub struct ItemGenerator {
     data_name: Option<String>,
     data_item_type: Option<ItemType>,
     data_influence: Option<Option<ItemInfluence>>,
     data_stack_size: Option<usize>,
     data_rarity: Option<ItemRarity>,
+    data_names: Option<NamePool>,
+    data_level: Option<u32>,
+    data_biome: Option<FieldType>,
+    data_type_weights: Vec<(ItemType, u32)>,
+    data_unidentified: f64,
+    data_cursed: f64,
+    data_sockets: Option<usize>,
+    data_quest: bool,
+    data_damage: Option<Option<Dice>>,
 }
 
 impl ItemGenerator {
@@ -38,6 +294,15 @@ impl ItemGenerator {
             data_influence: None,
             data_stack_size: None,
             data_rarity: None,
+            data_names: None,
+            data_level: None,
+            data_biome: None,
+            data_type_weights: Vec::new(),
+            data_unidentified: 0.0,
+            data_cursed: 0.0,
+            data_sockets: None,
+            data_quest: false,
+            data_damage: None,
         }
     }
 
@@ -71,33 +336,114 @@ impl ItemGenerator {
         self
     }
 
+    /// Sets the words random names are made of, instead of the built-in ones
+    pub fn names(mut self, names: NamePool) -> ItemGenerator {
+        self.data_names = Some(names);
+        self
+    }
+
+    /// Scales random rarities and influences to the level of the character finding the item, so
+    /// better items are found later in the game
+    pub fn for_level(mut self, level: u32) -> ItemGenerator {
+        self.data_level = Some(level);
+        self
+    }
+
+    /// Limits random item types to the ones fitting the field type the item is found on, e.g.
+    /// drinks near water. Custom field types do not limit the item types.
+    pub fn biome(mut self, field_type: FieldType) -> ItemGenerator {
+        self.data_biome = Some(field_type);
+        self
+    }
+
+    /// Adds a weight to the distribution of random item types. Once any weight is set, random
+    /// items are only of the weighted types, e.g. mostly potions and some food for an apothecary.
+    pub fn type_weight(mut self, item_type: ItemType, weight: u32) -> ItemGenerator {
+        self.data_type_weights.push((item_type, weight));
+        self
+    }
+
+    /// Sets the chance (`0.0` to `1.0`) that an item with an influence is unidentified. All items
+    /// are identified by default.
+    pub fn unidentified_chance(mut self, chance: f64) -> ItemGenerator {
+        self.data_unidentified = chance;
+        self
+    }
+
+    /// Sets the chance (`0.0` to `1.0`) that an equipable item with a random influence is cursed.
+    /// Cursed items have a negative influence, which stays hidden until they are equipped.
+    pub fn cursed_chance(mut self, chance: f64) -> ItemGenerator {
+        self.data_cursed = chance;
+        self
+    }
+
+    /// Sets the amount of sockets of the item. Equipable items get more sockets the rarer they
+    /// are by default.
+    pub fn sockets(mut self, sockets: usize) -> ItemGenerator {
+        self.data_sockets = Some(sockets);
+        self
+    }
+
+    /// Marks the item as a quest item that can not be dropped, sold or traded
+    pub fn quest(mut self, quest: bool) -> ItemGenerator {
+        self.data_quest = quest;
+        self
+    }
+
+    /// Sets the damage dice of the item. Weapons get the dice of their type plus a bonus for their
+    /// rarity by default.
+    pub fn damage(mut self, damage: Option<Dice>) -> ItemGenerator {
+        self.data_damage = Some(damage);
+        self
+    }
+
     /// Generates the item using the given data. Missing data will be filed randomly
     pub fn gen(&self) -> Item {
+        self.gen_with(&mut rand::thread_rng())
+    }
+
+    /// Generates the given amount of items, e.g. to fill a shop or a chest
+    pub fn gen_many<R: Rng>(&self, count: usize, rng: &mut R) -> Vec<Item> {
+        (0..count).map(|_| self.gen_with(rng)).collect()
+    }
+
+    /// Generates the item using the given random number generator
+    pub fn gen_with<R: Rng>(&self, rng: &mut R) -> Item {
         // The item type
         let item_type = if let Some(ref inner_item_type) = self.data_item_type {
             inner_item_type.clone()
+        } else if let Some(item_type) = weighted_item_type(&self.data_type_weights, rng) {
+            item_type
         } else {
-            random_item_type()
+            let biome_types = self.data_biome.as_ref().map_or_else(Vec::new, biome_item_types);
+            match rng.choose(&biome_types) {
+                Some(item_type) => item_type.clone(),
+                None => rng.gen::<ItemType>(),
+            }
         };
 
+        let level = self.data_level.unwrap_or(1).max(1);
+
         // The item rarity
         let rarity = if let Some(ref inner_rarity) = self.data_rarity {
             inner_rarity.clone()
         } else {
-            random_item_rarity()
+            random_item_rarity(level, rng)
         };
 
+        let mut cursed = false;
         let influence = if let Some(ref inner_influence) = self.data_influence {
             inner_influence.clone()
         } else {
-            let is_none = rand::thread_rng().gen::<bool>();
+            let is_none = rng.gen::<bool>();
             if is_none || item_type.attributes().is_empty() {
                 None
             } else {
-                Some(ItemInfluence {
-                    attribute: random_influence_attribute(&item_type),
-                    amount: random_influence_amount(&rarity),
-                })
+                let amount = random_influence_amount(&rarity, level, rng);
+                cursed = item_type.is_equipable() && rng.gen::<f64>() < self.data_cursed;
+
+                Some(ItemInfluence::new(random_influence_attribute(&item_type, rng),
+                                        if cursed { -amount.abs() } else { amount }))
             }
         };
 
@@ -105,38 +451,67 @@ impl ItemGenerator {
         let stack_size = if let Some(ref inner_stack_size) = self.data_stack_size {
             *inner_stack_size
         } else {
-            random_stack_size(&item_type)
+            random_stack_size(&item_type, rng)
+        };
+
+        let sockets = match self.data_sockets {
+            Some(sockets) => sockets,
+            None if item_type.is_equipable() => random_socket_count(&rarity, rng),
+            None => 0,
+        };
+
+        let damage = match self.data_damage {
+            Some(damage) => damage,
+            None => {
+                item_type.damage().map(|dice| dice.modifier(rarity_damage_bonus(&rarity)))
+            }
         };
 
+        // Only magical items can be unidentified
+        let identified = !cursed &&
+                         (influence.is_none() || rng.gen::<f64>() >= self.data_unidentified);
+
         // The name of the item
         let name = if let Some(ref inner_name) = self.data_name {
             inner_name.clone()
         } else {
-            random_item_name(&item_type)
+            match self.data_names {
+                Some(ref names) => random_item_name(&item_type, names, rng),
+                None => random_item_name(&item_type, &NamePool::default(), rng),
+            }
         };
 
         Item {
+            id: ItemId::random_with(rng),
             name: name,
             item_type: item_type,
             influence: influence,
             stack_size: stack_size,
             rarity: rarity,
+            identified,
+            cursed,
+            quest: self.data_quest,
+            sockets,
+            gems: Vec::new(),
+            damage,
         }
     }
 }
 
-fn random_influence_attribute(item_type: &ItemType) -> Attribute {
+fn random_influence_attribute<R: Rng>(item_type: &ItemType, rng: &mut R) -> Attribute {
     let mut attrbute_set = item_type.attributes();
     if attrbute_set.is_empty() {
         Attribute::Charisma
     } else {
-        let index = rand::thread_rng().gen_range(0, attrbute_set.len());
+        let index = rng.gen_range(0, attrbute_set.len());
         attrbute_set.remove(index)
     }
 }
 
-fn random_influence_amount(item_rarity: &ItemRarity) -> AttributeValue {
-    let mut rng = rand::thread_rng();
+fn random_influence_amount<R: Rng>(item_rarity: &ItemRarity,
+                                   level: u32,
+                                   rng: &mut R)
+                                   -> AttributeValue {
     let result = match *item_rarity {
         ItemRarity::Common => rng.gen_range(-1, 10),
         ItemRarity::Uncommon => rng.gen_range(1, 50),
@@ -145,98 +520,106 @@ fn random_influence_amount(item_rarity: &ItemRarity) -> AttributeValue {
         ItemRarity::Legendary => rng.gen_range(100, 500),
     };
 
-    if result == 0 { 1 } else { result }
+    let result = (result as f64 * (1.0 + (level - 1) as f64 * LEVEL_INFLUENCE_BONUS)).round();
+
+    if result == 0.0 { 1 } else { result as AttributeValue }
 }
 
-fn random_item_name(item_type: &ItemType) -> String {
+fn random_item_name<R: Rng>(item_type: &ItemType, names: &NamePool, rng: &mut R) -> String {
     match *item_type {
-        ItemType::WeaponSword | ItemType::WeaponHammer | ItemType::WeaponWand => {
-            random_weapon_name()
+        ItemType::WeaponSword | ItemType::WeaponHammer | ItemType::WeaponWand |
+        ItemType::WeaponBow | ItemType::WeaponCrossbow | ItemType::WeaponThrown => {
+            random_weapon_name(names, rng)
+        }
+        _ => {
+            match rng.choose(&names.items) {
+                Some(name) => name.clone(),
+                None => Generator::with_naming(Name::Plain).next().unwrap(),
+            }
         }
-        _ => Generator::with_naming(Name::Plain).next().unwrap(),
     }
 }
 
-fn random_weapon_name() -> String {
-    let mut weapon_names: Vec<String> = vec!["Sword", "Boulder", "Wand", "Dagger", "Hammer",
-                                             "Rifle"]
-        .into_iter()
-        .map(|string| String::from(string))
-        .collect();
-
-    let weapon_name = rand::thread_rng().gen_range(0, weapon_names.len());
-    let weapon_name = weapon_names.remove(weapon_name);
-
-    let mut weapon_prefixes: Vec<String> = vec!["Shiny", "Firey", "Wonderous", "Giant"]
-        .into_iter()
-        .map(|string| String::from(string))
-        .collect();
-
-    let weapon_prefix = rand::thread_rng().gen_range(0, weapon_prefixes.len());
-    let weapon_prefix = weapon_prefixes.remove(weapon_prefix);
-
-    let mut weapon_suffixes: Vec<String> = vec!["Nashioce",
-                                                "Gobloygro",
-                                                "Vuskia",
-                                                "Lawhos",
-                                                "Shiyle",
-                                                "Steiwana",
-                                                "Ashington",
-                                                "Ustistan",
-                                                "Plez Chium",
-                                                "Staej Slua",
-                                                "Ospaewana",
-                                                "Wespeugua",
-                                                "Cuchein",
-                                                "Keflya",
-                                                "Speyle",
-                                                "Swainia",
-                                                "Eswijan",
-                                                "Uswein",
-                                                "Scok Slya",
-                                                "Proz Drana",
-                                                "Decruecia",
-                                                "Vospoydan",
-                                                "Xesneau",
-                                                "Japlax",
-                                                "Scuecia",
-                                                "Dreina",
-                                                "Uswela",
-                                                "Usten",
-                                                "Smen Snana",
-                                                "Glan Gra",
-                                                "Puswaenia",
-                                                "Jepraoles",
-                                                "Pasla",
-                                                "Ewhium",
-                                                "Floulia",
-                                                "Plioso",
-                                                "Aplurg",
-                                                "Escines",
-                                                "Groyt Thington",
-                                                "Fleiw Flen"]
-        .into_iter()
-        .map(|string| String::from(string))
-        .collect();
-
-    let weapon_suffix = rand::thread_rng().gen_range(0, weapon_suffixes.len());
-    let weapon_suffix = weapon_suffixes.remove(weapon_suffix);
-
-    format!("{} {} of {}", weapon_prefix, weapon_name, weapon_suffix)
+fn random_weapon_name<R: Rng>(names: &NamePool, rng: &mut R) -> String {
+    format!("{} {} of {}",
+            rng.choose(&names.prefixes).unwrap(),
+            rng.choose(&names.weapons).unwrap(),
+            rng.choose(&names.suffixes).unwrap())
 }
 
-fn random_item_type() -> ItemType {
-    rand::thread_rng().gen::<ItemType>()
+fn rarity_damage_bonus(rarity: &ItemRarity) -> i64 {
+    match *rarity {
+        ItemRarity::Common => 0,
+        ItemRarity::Uncommon => 1,
+        ItemRarity::Rare => 2,
+        ItemRarity::Epic => 3,
+        ItemRarity::Legendary => 4,
+    }
 }
 
-fn random_item_rarity() -> ItemRarity {
-    rand::thread_rng().gen::<ItemRarity>()
+fn random_socket_count<R: Rng>(rarity: &ItemRarity, rng: &mut R) -> usize {
+    match *rarity {
+        ItemRarity::Common => 0,
+        ItemRarity::Uncommon => rng.gen_range(0, 2),
+        ItemRarity::Rare => rng.gen_range(1, 3),
+        ItemRarity::Epic => rng.gen_range(2, 4),
+        ItemRarity::Legendary => 3,
+    }
+}
+
+fn weighted_item_type<R: Rng>(weights: &[(ItemType, u32)], rng: &mut R) -> Option<ItemType> {
+    let total: u32 = weights.iter().map(|&(_, weight)| weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0, total);
+    for &(ref item_type, weight) in weights {
+        if roll < weight {
+            return Some(item_type.clone());
+        }
+        roll -= weight;
+    }
+
+    None
+}
+
+fn random_item_rarity<R: Rng>(level: u32, rng: &mut R) -> ItemRarity {
+    let base = rng.gen_range(0, 1000) + (level - 1) * LEVEL_RARITY_BONUS;
+
+    ItemRarity::by_num(base.min(1000))
 }
 
-fn random_stack_size(item_type: &ItemType) -> usize {
+fn biome_item_types(field_type: &FieldType) -> Vec<ItemType> {
+    match *field_type {
+        FieldType::Water | FieldType::SwampWater => {
+            vec![ItemType::ConsumableDrink, ItemType::ConsumablePotion, ItemType::WeaponThrown]
+        }
+        FieldType::Sand | FieldType::Quicksand => {
+            vec![ItemType::ConsumableDrink, ItemType::ArmorHead, ItemType::WeaponThrown]
+        }
+        FieldType::Grass | FieldType::Dirt | FieldType::Mud => {
+            vec![ItemType::ConsumableFood, ItemType::WeaponBow, ItemType::AmmoArrow]
+        }
+        FieldType::Wood | FieldType::WoodenFence => {
+            vec![ItemType::WeaponBow, ItemType::AmmoArrow, ItemType::WeaponCrossbow,
+                 ItemType::AmmoBolt]
+        }
+        FieldType::Stone | FieldType::StoneWall | FieldType::Hole => {
+            vec![ItemType::WeaponHammer, ItemType::ArmorChest, ItemType::ArmorLegs,
+                 ItemType::ArmorFeet, ItemType::WeaponSword]
+        }
+        FieldType::DoorClosed | FieldType::DoorOpen | FieldType::DoorLocked => {
+            vec![ItemType::Lockpick, ItemType::Usable, ItemType::WeaponWand]
+        }
+        FieldType::Custom(_) => Vec::new(),
+    }
+}
+
+fn random_stack_size<R: Rng>(item_type: &ItemType, rng: &mut R) -> usize {
     let mut base_sizes = vec![4, 16, 64];
     if item_type.is_stackable() {
-        let index = rand::thread_rng().gen_range(0, base_sizes.len());
+        let index = rng.gen_range(0, base_sizes.len());
         base_sizes.remove(index) as usize
     } else {
         1
@@ -248,8 +631,11 @@ mod tests {
     use super::*;
     use character::Attribute;
     use item::{ItemType, ItemRarity, ItemInfluence};
+    use data::DataError;
     use rand;
-    use rand::Rng;
+    use rand::{Rng, SeedableRng, XorShiftRng};
+    use rustc_serialize::json::Json;
+    use world::two_dimensional::FieldType;
 
     #[test]
     fn builder_item_type() {
@@ -290,6 +676,109 @@ mod tests {
         assert_eq!(rnd_item.stack_size, 45);
     }
 
+    #[test]
+    fn item_data() {
+        let json = Json::from_str(r#"{
+            "templates": [
+                {"name": "Sting", "item_type": "WeaponSword", "rarity": "Epic",
+                 "influence": {"attribute": "Dexterity", "amount": 20}},
+                {"name": "Lembas", "item_type": "ConsumableFood", "stack_size": 16}
+            ],
+            "names": {"weapons": ["Axe"], "prefixes": ["Rusty"], "suffixes": ["Moria"]}
+        }"#)
+            .unwrap();
+        let items = ItemData::from_json(&json).unwrap();
+
+        let sting = items.template("Sting").unwrap().generator().gen();
+        assert_eq!(sting.name, "Sting");
+        assert_eq!(sting.rarity, ItemRarity::Epic);
+        assert_eq!(sting.influence, Some(ItemInfluence::new(Attribute::Dexterity, 20)));
+        assert_eq!(items.template("Lembas").unwrap().generator().gen().stack_size, 16);
+
+        let axe = items.generator().item_type(ItemType::WeaponHammer).gen();
+        assert_eq!(axe.name, "Rusty Axe of Moria");
+    }
+
+    #[test]
+    fn invalid_item_data() {
+        let invalid = |text: &str| {
+            match ItemData::from_json(&Json::from_str(text).unwrap()) {
+                Err(DataError::Invalid(field)) => field,
+                _ => panic!("expected invalid item data"),
+            }
+        };
+
+        assert_eq!(invalid(r#"{"templates": [{"name": "Boots", "item_type": "ArmorFeet",
+                            "influence": {"attribute": "Strength", "amount": 1}}]}"#),
+                   "influence");
+        assert_eq!(invalid(r#"{"templates": [{"name": "Hat", "item_type": "ArmorHead",
+                            "stack_size": 4}]}"#),
+                   "stack_size");
+        assert_eq!(invalid(r#"{"templates": [{"name": "Hat", "item_type": "ArmorHead"},
+                            {"name": "Hat", "item_type": "Prop"}]}"#),
+                   "templates");
+        assert_eq!(invalid(r#"{"names": {"weapons": []}}"#), "weapons");
+    }
+
+    #[test]
+    fn builder_biome() {
+        for _ in 0..200 {
+            let item = ItemGenerator::new().biome(FieldType::Water).gen();
+            assert!(biome_item_types(&FieldType::Water).contains(&item.item_type));
+        }
+
+        let item = ItemGenerator::new()
+            .biome(FieldType::Water)
+            .item_type(ItemType::Prop)
+            .gen();
+        assert_eq!(item.item_type, ItemType::Prop);
+    }
+
+    #[test]
+    fn builder_for_level() {
+        let commons = |level| {
+            (0..2000)
+                .filter(|_| {
+                    ItemGenerator::new().for_level(level).gen().rarity == ItemRarity::Common
+                })
+                .count()
+        };
+        assert!(commons(50) < commons(1));
+
+        let influence = Some(ItemInfluence::new(Attribute::Strength, 7));
+        let item = ItemGenerator::new().for_level(30).influence(influence.clone()).gen();
+        assert_eq!(item.influence, influence);
+    }
+
+    #[test]
+    fn gen_many() {
+        let generator = ItemGenerator::new()
+            .type_weight(ItemType::ConsumablePotion, 3)
+            .type_weight(ItemType::ConsumableFood, 1)
+            .type_weight(ItemType::Prop, 0);
+        let items = generator.gen_many(400, &mut XorShiftRng::from_seed([1, 2, 3, 4]));
+
+        assert_eq!(items.len(), 400);
+        let potions = items.iter().filter(|item| item.item_type == ItemType::ConsumablePotion);
+        assert_eq!(potions.count(), 286);
+        assert!(items.iter().all(|item| item.item_type != ItemType::Prop));
+    }
+
+    #[test]
+    fn builder_cursed_chance() {
+        for _ in 0..50 {
+            let item = ItemGenerator::new()
+                .item_type(ItemType::ArmorChest)
+                .cursed_chance(1.0)
+                .gen();
+
+            if let Some(ref influence) = item.influence {
+                assert!(item.cursed && !item.identified);
+                assert!(influence.amount < 0);
+            }
+        }
+    }
+
     #[test]
     fn builder_rarity() {
         let rnd_item = ItemGenerator::new().rarity(ItemRarity::Rare).gen();
@@ -0,0 +1,193 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::str::FromStr;
+
+/// What a journal entry is about
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntryKind {
+    /// A quest was started, advanced or finished
+    Quest,
+    /// Something important was said
+    Dialogue,
+    /// A place, item or secret was found
+    Discovery,
+    /// A character died
+    Death,
+}
+
+impl EntryKind {
+    /// Returns the heading of the journal section listing entries of this kind
+    pub fn heading(&self) -> &'static str {
+        match *self {
+            EntryKind::Quest => "Quests",
+            EntryKind::Dialogue => "Dialogue",
+            EntryKind::Discovery => "Discoveries",
+            EntryKind::Death => "Deaths",
+        }
+    }
+}
+
+impl FromStr for EntryKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<EntryKind, ()> {
+        match s {
+            "Quest" => Ok(EntryKind::Quest),
+            "Dialogue" => Ok(EntryKind::Dialogue),
+            "Discovery" => Ok(EntryKind::Discovery),
+            "Death" => Ok(EntryKind::Death),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A single entry of the journal
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JournalEntry {
+    /// The turn of the world clock the entry was written at
+    pub turn: u64,
+    /// What the entry is about
+    pub kind: EntryKind,
+    /// The text of the entry
+    pub text: String,
+}
+
+impl JournalEntry {
+    /// Reads an entry from a JSON object like `{"turn": 12, "kind": "Quest", "text": "..."}`
+    pub fn from_json(json: &Json) -> Result<JournalEntry, DataError> {
+        let object = data::as_object(json, "journal")?;
+
+        Ok(JournalEntry {
+            turn: data::unsigned(object, "turn")?,
+            kind: data::parse(object, "kind")?,
+            text: data::string(object, "text")?,
+        })
+    }
+}
+
+impl ToJson for JournalEntry {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("turn".to_owned(), self.turn.to_json());
+        object.insert("kind".to_owned(), format!("{:?}", self.kind).to_json());
+        object.insert("text".to_owned(), self.text.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// The adventure log of the player, in the order the entries were written
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Creates a new instance of `Journal`
+    pub fn new() -> Journal {
+        Journal { entries: Vec::new() }
+    }
+
+    /// Writes a new entry at the given turn, e.g. `WorldClock::turn`
+    pub fn record(&mut self, turn: u64, kind: EntryKind, text: &str) {
+        self.entries.push(JournalEntry {
+            turn,
+            kind,
+            text: text.to_owned(),
+        });
+    }
+
+    /// Returns all entries
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Returns the entries of the given kind
+    pub fn of_kind(&self, kind: EntryKind) -> Vec<&JournalEntry> {
+        self.entries.iter().filter(|entry| entry.kind == kind).collect()
+    }
+
+    /// Returns the entries written at or after the given turn
+    pub fn since(&self, turn: u64) -> Vec<&JournalEntry> {
+        self.entries.iter().filter(|entry| entry.turn >= turn).collect()
+    }
+
+    /// Returns the last `count` entries, oldest first
+    pub fn latest(&self, count: usize) -> &[JournalEntry] {
+        &self.entries[self.entries.len().saturating_sub(count)..]
+    }
+
+    /// Lists the entries of the given kind under their heading, one line per entry
+    pub fn render_section(&self, kind: EntryKind) -> String {
+        let mut text = format!("{}\n", kind.heading());
+        for entry in self.of_kind(kind) {
+            text.push_str(&format!("- [Turn {}] {}\n", entry.turn, entry.text));
+        }
+
+        text
+    }
+
+    /// Lists all entries grouped by kind, leaving out kinds without entries
+    pub fn render(&self) -> String {
+        [EntryKind::Quest, EntryKind::Dialogue, EntryKind::Discovery, EntryKind::Death]
+            .iter()
+            .filter(|kind| self.entries.iter().any(|entry| entry.kind == **kind))
+            .map(|kind| self.render_section(*kind))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Reads the journal from a JSON array as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Journal, DataError> {
+        Ok(Journal {
+            entries: data::as_array(json, "journal")?
+                .iter()
+                .map(JournalEntry::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl ToJson for Journal {
+    fn to_json(&self) -> Json {
+        self.entries.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    fn journal() -> Journal {
+        let mut journal = Journal::new();
+        journal.record(3, EntryKind::Quest, "Asked to find the lost ring");
+        journal.record(40, EntryKind::Discovery, "Found a hidden cave");
+        journal.record(52, EntryKind::Quest, "Found the ring");
+        journal
+    }
+
+    #[test]
+    fn queries() {
+        let journal = journal();
+
+        assert_eq!(journal.of_kind(EntryKind::Quest).len(), 2);
+        assert_eq!(journal.since(40).len(), 2);
+        assert_eq!(journal.latest(1)[0].text, "Found the ring");
+        assert_eq!(journal.latest(10).len(), 3);
+        assert!(journal.of_kind(EntryKind::Death).is_empty());
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(journal().render(),
+                   "Quests\n- [Turn 3] Asked to find the lost ring\n- [Turn 52] Found the ring\n\
+                    \nDiscoveries\n- [Turn 40] Found a hidden cave\n");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let journal = journal();
+        assert_eq!(Journal::from_json(&journal.to_json()).unwrap(), journal);
+    }
+}
@@ -15,22 +15,134 @@ extern crate names;
 extern crate rand;
 extern crate rustc_serialize;
 
+/// Achievements unlocked by reaching goals during the game
+pub mod achievements;
+/// Shortcuts and macros the player defines for commands
+pub mod alias;
+/// The good–evil and lawful–chaotic alignment shifted by the choices of the player
+pub mod alignment;
 /// The behaviour of entities
 pub mod behaviour;
+/// A registry of monsters loaded from data files
+pub mod bestiary;
+/// Procedurally generated side quests posted at bounty boards
+pub mod bounty;
+/// Game events published to subsystems like achievements and statistics
+pub mod bus;
 /// The structures used to bulid a character
 pub mod character;
+/// Fights between the player and monsters
+pub mod combat;
+/// Poison, diseases and other damage over time
+pub mod condition;
+/// Non-player characters following and fighting for the party
+pub mod companion;
+/// Recipes turning ingredients into new items
+pub mod crafting;
+/// Loading game data from files
+pub mod data;
+/// Lifting the curses of cursed items
+pub mod curse;
+/// Scripted sequences played when the player reaches a tile or a quest advances
+pub mod cutscene;
+/// Resurrecting dead characters
+pub mod death;
+/// Difficulty settings scaling enemies, loot, experience and survival
+pub mod difficulty;
+/// Rolling dice using the standard notation
+pub mod dice;
+/// Dialogue trees with persuasion, intimidation and bribery
+pub mod dialogue;
+/// Adding and upgrading the influences of items at shops and crafting stations
+pub mod enchant;
 /// Everything regarding entities
 pub mod entity;
 /// The structure of events
 pub mod event;
+/// The reputation of the player with factions like towns and guilds
+pub mod faction;
+/// Falling down from heights
+pub mod fall;
+/// Text formats game data can be stored in
+pub mod format;
+/// Fishing, foraging and other resources gathered from the map
+pub mod gathering;
+/// One-time tips shown the first time something happens
+pub mod hint;
+/// Quick-use slots for consumables and abilities
+pub mod hotbar;
+/// Revealing the hidden names and influences of magical items
+pub mod identify;
+/// Wounds at specific parts of the body
+pub mod injury;
 /// The structure and mechanics of an inventory
 pub mod inventory;
 /// Generate random items
 pub mod item_generator;
 /// The structure of items
 pub mod item;
+/// A log of quests, dialogue, discoveries and deaths
+pub mod journal;
+/// Translations of the player-facing strings
+pub mod locale;
+/// Locks on doors, chests and other entities
+pub mod lock;
+/// Items dropped by monsters and found in containers
+pub mod loot;
+/// Games of chance played for gold at tavern gamblers
+pub mod minigame;
+/// Horses, boats and other mounts changing how the player moves
+pub mod mount;
+/// Notes the player pins to the map
+pub mod notes;
+/// A group of characters travelling together
+pub mod party;
+/// Plugins extending the game with monsters, field types, items and commands
+pub mod plugin;
+/// Shooting and throwing ranged weapons
+pub mod ranged;
+/// Recording sessions and replaying them deterministically
+pub mod replay;
+/// Named save slots in a directory and rotating autosaves
+pub mod saves;
+/// Game events triggering scripts of an embedded scripting language
+#[cfg(feature = "scripting")]
+pub mod scripting;
+/// Rendering characters as text for the terminal
+pub mod sheet;
+/// Shops whose stock and prices depend on reputation and quest flags
+pub mod shop;
+/// A shared mechanic for attribute based checks
+pub mod skill_check;
+/// Sound cues played for game events through sinks provided by front ends
+pub mod sound;
+/// Numbers collected over a whole save
+pub mod statistics;
+/// Sneaking and detection
+pub mod stealth;
+/// Monsters summoned to fight for the player for a limited time
+pub mod summon;
+/// Hunger, thirst and rest
+pub mod survival;
+/// Swimming through water and drowning
+pub mod swimming;
+/// Narrative text with placeholders, articles, plurals and pronouns
+pub mod template;
+/// The world clock and everything that changes over time
+pub mod time;
+/// Recording the input and output of a session to a file
+pub mod transcript;
+/// Treasure maps marking buried caches the player can dig up
+pub mod treasure;
+/// An interactive terminal client playing a 2d world
+#[cfg(feature = "tui")]
+pub mod tui;
 /// A module for global type consitency
 pub mod types;
+/// Bars, menus and color themes for the terminal
+pub mod ui;
+/// Hand-made items that exist only once per save
+pub mod unique;
 /// Structures for saving, loading and playing a game world
 pub mod world;
 /// The actual engine to run the game and handle the IO
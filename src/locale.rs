@@ -0,0 +1,235 @@
+use data::{self, DataError};
+use item::{Item, ItemType};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use std::path::Path;
+use template::{Template, TemplateContext};
+use world::two_dimensional::FieldType;
+
+/// The locale of the strings built into this crate
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The built-in English strings every catalog falls back to. Item nouns fall back to
+/// `ItemType::noun` instead.
+const ENGLISH: [(&str, &str); 49] =
+    [("item.unidentified", "Unidentified {noun}"),
+     ("combat.Hit", "{actor} hits {target} for {damage} damage."),
+     ("combat.Missed", "{actor} misses {target}."),
+     ("combat.Immune", "{target} is immune to the attack of {actor}."),
+     ("combat.PhaseChanged",
+      "{actor} hits {target} for {damage} damage. {target} enters phase {phase}!"),
+     ("combat.Healed", "{actor} heals {target} by {amount}."),
+     ("combat.Advanced", "{actor} advances."),
+     ("combat.Retreated", "{actor} falls back."),
+     ("combat.Fled", "{actor} flees."),
+     ("combat.FledHurt", "{actor} flees, taking {damage} damage on the way out."),
+     ("combat.Caught", "{actor} tries to flee but is caught."),
+     ("combat.Waited", "{actor} waits."),
+     ("combat.Vanished", "{actor} vanishes."),
+     ("combat.Taunted", "{actor} taunts the enemies."),
+     ("combat.Guarded", "{actor} raises a guard."),
+     ("combat.Helped", "{actor} helps {target}."),
+     ("combat.Strengthened", "{actor} strengthens {target}."),
+     ("field.Dirt", "dirt"),
+     ("field.Grass", "grass"),
+     ("field.Hole", "a hole"),
+     ("field.Mud", "mud"),
+     ("field.Quicksand", "quicksand"),
+     ("field.Sand", "sand"),
+     ("field.Stone", "stone"),
+     ("field.StoneWall", "a stone wall"),
+     ("field.SwampWater", "swamp water"),
+     ("field.Water", "water"),
+     ("field.Wood", "wooden planks"),
+     ("field.WoodenFence", "a wooden fence"),
+     ("field.DoorClosed", "a closed door"),
+     ("field.DoorOpen", "an open door"),
+     ("field.DoorLocked", "a locked door"),
+     ("gamble.gambler", "{name}: {games} (up to {max_wager} gold)"),
+     ("gamble.unknown_gambler", "Nobody called {name} is here"),
+     ("gamble.no_player", "There is nobody to play"),
+     ("gamble.dealt", "{name} deals a {card}. Higher or lower?"),
+     ("gamble.no_high_low", "{name} does not play high-low"),
+     ("gamble.usage", "Play dice, deal, higher or lower"),
+     ("gamble.no_wager", "How much do you wager?"),
+     ("gamble.NothingWagered", "You have to wager something"),
+     ("gamble.TooHigh", "{name} plays for at most {max_wager} gold"),
+     ("gamble.NotEnoughGold", "You can not pay the wager"),
+     ("gamble.UnknownGame", "{name} does not play {game}"),
+     ("gamble.NoCardDealt", "No card was dealt yet"),
+     ("gamble.Won", "You win {wager} gold!"),
+     ("gamble.Lost", "You lose {wager} gold."),
+     ("gamble.Draw", "It is a draw."),
+     ("gamble.dice", "You roll {ours}, the gambler rolls {theirs}."),
+     ("gamble.card", "The next card is {card}.")];
+
+/// The translations of the player-facing strings into one language. Strings are looked up by
+/// keys like `combat.Hit` or `field.Grass`; missing translations fall back to the built-in
+/// English strings.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Catalog {
+    locale: String,
+    strings: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Creates a new instance of `Catalog` without any translations
+    pub fn new(locale: &str) -> Catalog {
+        Catalog {
+            locale: locale.to_owned(),
+            strings: HashMap::new(),
+        }
+    }
+
+    /// Returns the catalog of the built-in English strings
+    pub fn english() -> Catalog {
+        Catalog::new(DEFAULT_LOCALE)
+    }
+
+    /// A builder method for translating the string with the given key
+    pub fn string(mut self, key: &str, text: &str) -> Catalog {
+        self.strings.insert(key.to_owned(), text.to_owned());
+        self
+    }
+
+    /// Returns the locale of the catalog, e.g. `de`
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Returns the string with the given key, falling back to English and then to the key
+    /// itself
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.lookup(key).unwrap_or(key)
+    }
+
+    /// Fills in the template with the given key
+    pub fn render(&self, key: &str, context: &TemplateContext) -> String {
+        Template::new(self.get(key)).render(context)
+    }
+
+    /// Returns the noun describing items of the type, using the key `item.<type>`, e.g.
+    /// `item.WeaponSword`
+    pub fn item_noun(&self, item_type: &ItemType) -> String {
+        let key = format!("item.{:?}", item_type);
+        self.lookup(&key).unwrap_or_else(|| item_type.noun()).to_owned()
+    }
+
+    /// Returns the name of the item shown to the player. Campaigns translate the names of their
+    /// items using the key `item.name.<name>`.
+    pub fn item_name(&self, item: &Item) -> String {
+        if item.identified {
+            let key = format!("item.name.{}", item.name);
+            self.lookup(&key).unwrap_or(&item.name).to_owned()
+        } else {
+            let noun = self.item_noun(&item.item_type);
+            self.render("item.unidentified", &TemplateContext::new().value("noun", noun))
+        }
+    }
+
+    /// Returns the description of fields of the type, using the key `field.<type>`. Custom
+    /// field types fall back to their id.
+    pub fn field_name(&self, field_type: &FieldType) -> String {
+        let key = format!("field.{}", field_type);
+        match (self.lookup(&key), field_type) {
+            (Some(text), _) => text.to_owned(),
+            (None, FieldType::Custom(id)) => id.clone(),
+            (None, field_type) => field_type.to_string(),
+        }
+    }
+
+    /// Reads a catalog from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Catalog, DataError> {
+        let object = data::as_object(json, "catalog")?;
+        let mut catalog = Catalog::new(&data::string(object, "locale")?);
+
+        for (key, text) in data::as_object(data::field(object, "strings")?, "strings")? {
+            let text = text.as_string().ok_or_else(|| DataError::Invalid(key.clone()))?;
+            catalog.strings.insert(key.clone(), text.to_owned());
+        }
+
+        Ok(catalog)
+    }
+
+    /// Reads a catalog from a JSON file, e.g. a translation shipped with a campaign
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Catalog, DataError> {
+        Catalog::from_json(&data::read_json(path)?)
+    }
+
+    fn lookup(&self, key: &str) -> Option<&str> {
+        match self.strings.get(key) {
+            Some(text) => Some(text),
+            None => ENGLISH.iter().find(|&&(english, _)| english == key).map(|&(_, text)| text),
+        }
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Catalog {
+        Catalog::english()
+    }
+}
+
+impl ToJson for Catalog {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("locale".to_owned(), self.locale.to_json());
+        object.insert("strings".to_owned(), self.strings.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rustc_serialize::json::ToJson;
+    use template::TemplateContext;
+    use world::two_dimensional::FieldType;
+
+    fn german() -> Catalog {
+        Catalog::new("de")
+            .string("combat.Missed", "{actor} verfehlt {target}.")
+            .string("item.WeaponSword", "Schwert")
+            .string("item.unidentified", "Unbekanntes {noun}")
+            .string("item.name.Anduril", "Andúril")
+            .string("field.Grass", "Gras")
+    }
+
+    #[test]
+    fn fallback() {
+        let catalog = german();
+        let context = TemplateContext::new().value("actor", "Held").value("target", "Ork");
+
+        assert_eq!(catalog.render("combat.Missed", &context), "Held verfehlt Ork.");
+        assert_eq!(catalog.render("combat.Waited", &context), "Held waits.");
+        assert_eq!(catalog.get("unknown.key"), "unknown.key");
+        assert_eq!(catalog.field_name(&FieldType::Grass), "Gras");
+        assert_eq!(catalog.field_name(&FieldType::Water), "water");
+        assert_eq!(catalog.field_name(&FieldType::Custom("lava".to_owned())), "lava");
+    }
+
+    #[test]
+    fn item_names() {
+        let catalog = german();
+        let mut sword = ItemGenerator::new()
+            .name("Anduril")
+            .item_type(ItemType::WeaponSword)
+            .gen();
+        sword.identified = false;
+
+        assert_eq!(catalog.item_name(&sword), "Unbekanntes Schwert");
+        assert_eq!(Catalog::english().item_name(&sword), sword.display_name());
+        sword.identify();
+        assert_eq!(catalog.item_name(&sword), "Andúril");
+        assert_eq!(catalog.item_noun(&ItemType::WeaponBow), "bow");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let catalog = german();
+        assert_eq!(Catalog::from_json(&catalog.to_json()).unwrap(), catalog);
+    }
+}
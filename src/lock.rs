@@ -0,0 +1,189 @@
+use character::{Attribute, Character};
+use data::{self, DataError};
+use item::{Item, ItemId, ItemType};
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use skill_check::CheckOutcome;
+use types::AttributeValue;
+
+/// The reasons why picking a lock can fail before the attempt is even made
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PickError {
+    /// The lock is not locked
+    NotLocked,
+    /// The lock is jammed and can only be opened using its key
+    Jammed,
+    /// The character does not carry a lockpick
+    NoLockpick,
+}
+
+/// A lock on a door, chest or any other entity
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lock {
+    /// The difficulty of picking the lock
+    pub difficulty: AttributeValue,
+    /// The id of the key item that opens the lock (optional)
+    pub key: Option<ItemId>,
+    locked: bool,
+    jammed: bool,
+}
+
+impl Lock {
+    /// Creates a new, locked instance of `Lock`
+    pub fn new(difficulty: AttributeValue) -> Lock {
+        Lock {
+            difficulty,
+            key: None,
+            locked: true,
+            jammed: false,
+        }
+    }
+
+    /// A builder method for setting the id of the key that opens the lock
+    pub fn key(mut self, key: &ItemId) -> Lock {
+        self.key = Some(key.clone());
+        self
+    }
+
+    /// Returns `true` if the lock is locked
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Returns `true` if a failed attempt jammed the lock
+    pub fn is_jammed(&self) -> bool {
+        self.jammed
+    }
+
+    /// Locks the lock again
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Unlocks the lock if the given item is its key. Returns `true` if the lock is open
+    /// afterwards.
+    pub fn unlock_with(&mut self, item: &Item) -> bool {
+        if let Some(ref key) = self.key {
+            if &item.id == key {
+                self.locked = false;
+                self.jammed = false;
+            }
+        }
+
+        !self.locked
+    }
+
+    /// Unlocks the lock if the character carries its key. Returns `true` if the lock is open
+    /// afterwards.
+    pub fn unlock_with_inventory(&mut self, character: &Character) -> bool {
+        let key = match self.key {
+            Some(ref key) => character.inventory().find_by_id(key).cloned(),
+            None => None,
+        };
+
+        match key {
+            Some(key) => self.unlock_with(&key),
+            None => !self.locked,
+        }
+    }
+
+    /// Tries to pick the lock using a `Attribute::Dexterity` skill check. Every attempt uses up
+    /// one of the character's lockpicks, a critical failure jams the lock.
+    pub fn pick<R: Rng>(&mut self,
+                        character: &mut Character,
+                        rng: &mut R)
+                        -> Result<CheckOutcome, PickError> {
+        if !self.locked {
+            return Err(PickError::NotLocked);
+        }
+
+        if self.jammed {
+            return Err(PickError::Jammed);
+        }
+
+        if character.inventory_mut().take_by_type(&ItemType::Lockpick).is_none() {
+            return Err(PickError::NoLockpick);
+        }
+
+        let outcome = character.skill_check(&Attribute::Dexterity, self.difficulty, rng);
+
+        match outcome {
+            CheckOutcome::CriticalSuccess | CheckOutcome::Success => self.locked = false,
+            CheckOutcome::CriticalFailure => self.jammed = true,
+            CheckOutcome::Failure => (),
+        }
+
+        Ok(outcome)
+    }
+
+    /// Reads a lock from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Lock, DataError> {
+        let object = data::as_object(json, "lock")?;
+
+        Ok(Lock {
+            difficulty: data::signed(object, "difficulty")?,
+            key: data::optional(object, "key", data::parse)?,
+            locked: data::boolean(object, "locked")?,
+            jammed: data::optional(object, "jammed", data::boolean)?.unwrap_or(false),
+        })
+    }
+}
+
+impl ToJson for Lock {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("difficulty".to_owned(), self.difficulty.to_json());
+        if let Some(ref key) = self.key {
+            object.insert("key".to_owned(), key.as_str().to_json());
+        }
+        object.insert("locked".to_owned(), self.locked.to_json());
+        object.insert("jammed".to_owned(), self.jammed.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn unlock_with_key() {
+        let key = ItemGenerator::new().name("Rusty Key").gen();
+        let mut lock = Lock::new(15).key(&key.id);
+
+        let wrong_key = ItemGenerator::new().name("Golden Key").gen();
+        assert!(!lock.unlock_with(&wrong_key));
+        assert!(!lock.unlock_with(&ItemGenerator::new().name("Rusty Key").gen()));
+        assert!(lock.is_locked());
+
+        let mut character = Character::new("Locksmith");
+        let _ = character.inventory_mut().add_item(key);
+
+        assert!(lock.unlock_with_inventory(&character));
+        assert!(!lock.is_locked());
+    }
+
+    #[test]
+    fn pick_lock() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut lock = Lock::new(1);
+        let mut character = Character::new("Locksmith");
+        character.update_attribute(&Attribute::Dexterity, 1000);
+
+        assert_eq!(lock.pick(&mut character, &mut rng), Err(PickError::NoLockpick));
+
+        let lockpick = ItemGenerator::new().item_type(ItemType::Lockpick).stack_size(64).gen();
+        for _ in 0..64 {
+            let _ = character.inventory_mut().add_item(lockpick.clone());
+        }
+
+        assert!(lock.pick(&mut character, &mut rng).unwrap().is_success());
+        assert_eq!(lock.pick(&mut character, &mut rng), Err(PickError::NotLocked));
+    }
+}
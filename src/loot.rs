@@ -0,0 +1,242 @@
+use data::{self, DataError};
+use item::{Item, ItemRarity, ItemType};
+use item_generator::ItemGenerator;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeSet;
+use unique::UniqueItems;
+
+/// A single possible drop of a loot table
+#[derive(Clone, PartialEq, Debug)]
+pub struct LootEntry {
+    /// The type of the dropped item
+    pub item_type: ItemType,
+    /// The chance (`0.0` to `1.0`) that the item is dropped
+    pub chance: f64,
+    /// The rarity of the dropped item. Random if not set
+    pub rarity: Option<ItemRarity>,
+}
+
+impl LootEntry {
+    /// Creates a new instance of `LootEntry`
+    pub fn new(item_type: ItemType, chance: f64) -> LootEntry {
+        LootEntry {
+            item_type,
+            chance,
+            rarity: None,
+        }
+    }
+
+    /// A builder method for setting the rarity of the dropped item
+    pub fn rarity(mut self, rarity: ItemRarity) -> LootEntry {
+        self.rarity = Some(rarity);
+        self
+    }
+
+    /// Generates the dropped item
+    pub fn gen(&self) -> Item {
+        let mut generator = ItemGenerator::new().item_type(self.item_type.clone());
+        if let Some(ref rarity) = self.rarity {
+            generator = generator.rarity(rarity.clone());
+        }
+
+        generator.gen()
+    }
+
+    /// Reads a loot entry from a JSON object like
+    /// `{"item_type": "WeaponSword", "chance": 0.1, "rarity": "Rare"}`
+    pub fn from_json(json: &Json) -> Result<LootEntry, DataError> {
+        let object = data::as_object(json, "loot")?;
+
+        Ok(LootEntry {
+            item_type: data::parse(object, "item_type")?,
+            chance: data::float(object, "chance")?,
+            rarity: data::optional(object, "rarity", data::parse)?,
+        })
+    }
+}
+
+impl ToJson for LootEntry {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("item_type".to_owned(), format!("{:?}", self.item_type).to_json());
+        object.insert("chance".to_owned(), self.chance.to_json());
+        if let Some(ref rarity) = self.rarity {
+            object.insert("rarity".to_owned(), format!("{:?}", rarity).to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// A possible drop of a unique item, see `UniqueItems`
+#[derive(Clone, PartialEq, Debug)]
+pub struct UniqueDrop {
+    /// The id of the unique item
+    pub id: String,
+    /// The chance (`0.0` to `1.0`) that the item is dropped, if it was not found before
+    pub chance: f64,
+}
+
+/// A list of items that may be dropped, e.g. by a defeated monster
+#[derive(Clone, PartialEq, Debug)]
+pub struct LootTable {
+    /// All possible drops
+    pub entries: Vec<LootEntry>,
+    /// All possible drops of unique items
+    pub uniques: Vec<UniqueDrop>,
+}
+
+impl Default for LootTable {
+    fn default() -> LootTable {
+        LootTable::new()
+    }
+}
+
+impl LootTable {
+    /// Creates a new, empty instance of `LootTable`
+    pub fn new() -> LootTable {
+        LootTable {
+            entries: Vec::new(),
+            uniques: Vec::new(),
+        }
+    }
+
+    /// A builder method for adding a possible drop
+    pub fn entry(mut self, entry: LootEntry) -> LootTable {
+        self.entries.push(entry);
+        self
+    }
+
+    /// A builder method for adding a possible drop of the unique item with the given id
+    pub fn unique(mut self, id: &str, chance: f64) -> LootTable {
+        self.uniques.push(UniqueDrop {
+            id: id.to_owned(),
+            chance,
+        });
+        self
+    }
+
+    /// Reads a loot table from a JSON array of loot entries. Entries like
+    /// `{"unique": "sting", "chance": 0.05}` drop unique items.
+    pub fn from_json(json: &Json) -> Result<LootTable, DataError> {
+        let mut table = LootTable::new();
+
+        for entry in data::as_array(json, "loot")? {
+            let object = data::as_object(entry, "loot")?;
+            if object.contains_key("unique") {
+                table = table.unique(&data::string(object, "unique")?,
+                                     data::float(object, "chance")?);
+            } else {
+                table.entries.push(LootEntry::from_json(entry)?);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Rolls every entry of the table like `roll`, including the unique items not found in this
+    /// save yet. Dropped unique items are added to `found`.
+    pub fn roll_with_uniques<R: Rng>(&self,
+                                     rng: &mut R,
+                                     uniques: &UniqueItems,
+                                     found: &mut BTreeSet<String>)
+                                     -> Vec<Item> {
+        let mut items = self.roll(rng);
+
+        for drop in &self.uniques {
+            if !found.contains(&drop.id) && rng.gen::<f64>() < drop.chance {
+                items.extend(uniques.draw(&drop.id, found));
+            }
+        }
+
+        items
+    }
+
+    /// Rolls every entry of the table and generates the dropped items
+    pub fn roll<R: Rng>(&self, rng: &mut R) -> Vec<Item> {
+        self.entries
+            .iter()
+            .filter(|entry| rng.gen::<f64>() < entry.chance)
+            .map(|entry| entry.gen())
+            .collect()
+    }
+
+    /// Generates every item of the table, regardless of its chance
+    pub fn roll_all(&self) -> Vec<Item> {
+        self.entries.iter().map(|entry| entry.gen()).collect()
+    }
+}
+
+impl ToJson for LootTable {
+    fn to_json(&self) -> Json {
+        let mut entries: Vec<Json> = self.entries.iter().map(ToJson::to_json).collect();
+        entries.extend(self.uniques.iter().map(|drop| {
+            let mut object = Object::new();
+            object.insert("unique".to_owned(), drop.id.to_json());
+            object.insert("chance".to_owned(), drop.chance.to_json());
+            Json::Object(object)
+        }));
+
+        Json::Array(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::{ItemRarity, ItemType};
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::Json;
+    use std::collections::BTreeSet;
+    use unique::{UniqueItem, UniqueItems};
+
+    #[test]
+    fn roll() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let table = LootTable::new()
+            .entry(LootEntry::new(ItemType::WeaponSword, 1.0).rarity(ItemRarity::Epic))
+            .entry(LootEntry::new(ItemType::ArmorHead, 0.0))
+            .entry(LootEntry::new(ItemType::ConsumablePotion, 0.5))
+            .entry(LootEntry::new(ItemType::WeaponWand, 0.5));
+
+        let items = table.roll(&mut rng);
+
+        assert_eq!(items.iter().map(|item| item.item_type.clone()).collect::<Vec<_>>(),
+                   vec![ItemType::WeaponSword, ItemType::ConsumablePotion, ItemType::WeaponWand]);
+        assert_eq!(items[0].rarity, ItemRarity::Epic);
+    }
+
+    #[test]
+    fn roll_uniques() {
+        let mut uniques = UniqueItems::new();
+        uniques.add(UniqueItem::new("sting", ItemGenerator::new().name("Sting").gen())).unwrap();
+        let table = LootTable::new().unique("sting", 1.0).unique("unknown", 1.0);
+
+        let mut found = BTreeSet::new();
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let items = table.roll_with_uniques(&mut rng, &uniques, &mut found);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Sting");
+        assert!(table.roll_with_uniques(&mut rng, &uniques, &mut found).is_empty());
+    }
+
+    #[test]
+    fn from_json() {
+        let json = Json::from_str(r#"[{"item_type": "ConsumablePotion", "chance": 0.5},
+                                      {"item_type": "WeaponWand", "chance": 0.1,
+                                       "rarity": "Rare"},
+                                      {"unique": "sting", "chance": 0.01}]"#)
+            .unwrap();
+
+        let table = LootTable::from_json(&json).unwrap();
+
+        assert_eq!(table,
+                   LootTable::new()
+                       .entry(LootEntry::new(ItemType::ConsumablePotion, 0.5))
+                       .entry(LootEntry::new(ItemType::WeaponWand, 0.1).rarity(ItemRarity::Rare))
+                       .unique("sting", 0.01));
+    }
+}
@@ -0,0 +1,502 @@
+use bus::GameEvent;
+use character::{Attribute, Character};
+use data::{self, DataError};
+use dice::Dice;
+use locale::Catalog;
+use plugin::Content;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use template::TemplateContext;
+use types::{AttributeValue, Gold};
+
+/// The chance in percent per point of `Attribute::Luck` that a lost round is played again
+pub const LUCK_REROLL_CHANCE: AttributeValue = 2;
+
+/// The highest chance in percent that a lost round is played again
+pub const MAX_REROLL_CHANCE: AttributeValue = 50;
+
+/// The highest card of a deck, the king
+pub const HIGHEST_CARD: u32 = 13;
+
+/// The reasons why a wager is not accepted
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WagerError {
+    /// The wager is zero
+    NothingWagered,
+    /// The gambler does not accept that much
+    TooHigh,
+    /// The character can not pay the wager
+    NotEnoughGold,
+    /// The gambler does not play the game
+    UnknownGame,
+    /// A high-low wager was placed before a card was dealt
+    NoCardDealt,
+}
+
+/// The games of chance played in taverns
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Game {
+    /// Both players roll two dice, the higher sum wins
+    Dice,
+    /// A card is dealt and the player guesses whether the next one is higher or lower
+    HighLow,
+}
+
+impl FromStr for Game {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Game, ()> {
+        match s {
+            "Dice" => Ok(Game::Dice),
+            "HighLow" => Ok(Game::HighLow),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The guess of the player in a game of high-low
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Guess {
+    /// The next card is higher than the dealt one
+    Higher,
+    /// The next card is lower than the dealt one
+    Lower,
+}
+
+/// How a round ended for the player
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// The player wins as much as they wagered
+    Won,
+    /// The player loses the wager
+    Lost,
+    /// Nobody wins, the player keeps the wager
+    Draw,
+}
+
+/// What was rolled or dealt in a round
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Draw {
+    /// The sums of the dice rolled by the player and by the gambler
+    Dice(AttributeValue, AttributeValue),
+    /// The next card in a game of high-low
+    Card(u32),
+}
+
+/// A played round and what happened in it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Round {
+    /// How the round ended
+    pub outcome: Outcome,
+    /// What was rolled or dealt
+    pub draw: Draw,
+}
+
+impl Round {
+    /// Describes what happened using the keys `gamble.dice` and `gamble.card` of the catalog,
+    /// e.g. "You roll 9, the gambler rolls 7."
+    pub fn describe(&self, catalog: &Catalog) -> String {
+        match self.draw {
+            Draw::Dice(ours, theirs) => {
+                let context = TemplateContext::new().value("ours", ours).value("theirs", theirs);
+                catalog.render("gamble.dice", &context)
+            }
+            Draw::Card(card) => {
+                catalog.render("gamble.card", &TemplateContext::new().value("card", card))
+            }
+        }
+    }
+}
+
+/// Returns the chance in percent that a lost round is played again for a character with the
+/// given luck
+pub fn reroll_chance(luck: AttributeValue) -> AttributeValue {
+    (luck * LUCK_REROLL_CHANCE).clamp(0, MAX_REROLL_CHANCE)
+}
+
+/// Plays a round of dice. A lucky character may roll again after losing.
+pub fn play_dice<R: Rng>(luck: AttributeValue, rng: &mut R) -> Round {
+    let dice = Dice::new(2, 6);
+    let theirs = dice.roll(rng);
+    let mut ours = dice.roll(rng);
+    if ours < theirs && rng.gen_range(0, 100) < reroll_chance(luck) {
+        ours = dice.roll(rng);
+    }
+
+    Round {
+        outcome: if ours > theirs {
+            Outcome::Won
+        } else if ours < theirs {
+            Outcome::Lost
+        } else {
+            Outcome::Draw
+        },
+        draw: Draw::Dice(ours, theirs),
+    }
+}
+
+/// Deals a card between ace (`1`) and king (`HIGHEST_CARD`)
+pub fn deal<R: Rng>(rng: &mut R) -> u32 {
+    rng.gen_range(1, HIGHEST_CARD + 1)
+}
+
+/// Plays a round of high-low on the dealt card. A lucky character may have the next card dealt
+/// again after losing.
+pub fn play_high_low<R: Rng>(dealt: u32, guess: Guess, luck: AttributeValue, rng: &mut R) -> Round {
+    let outcome = |next: u32| if next == dealt {
+        Outcome::Draw
+    } else if (next > dealt) == (guess == Guess::Higher) {
+        Outcome::Won
+    } else {
+        Outcome::Lost
+    };
+
+    let mut next = deal(rng);
+    if outcome(next) == Outcome::Lost && rng.gen_range(0, 100) < reroll_chance(luck) {
+        next = deal(rng);
+    }
+
+    Round {
+        outcome: outcome(next),
+        draw: Draw::Card(next),
+    }
+}
+
+/// A tavern NPC playing games of chance for gold
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Gambler {
+    /// The name of the gambler
+    pub name: String,
+    /// The games the gambler plays
+    pub games: Vec<Game>,
+    /// The highest wager the gambler accepts
+    pub max_wager: Gold,
+}
+
+impl Gambler {
+    /// Creates a new instance of `Gambler` who plays no game yet
+    pub fn new(name: &str, max_wager: Gold) -> Gambler {
+        Gambler {
+            name: name.to_owned(),
+            games: Vec::new(),
+            max_wager,
+        }
+    }
+
+    /// A builder method for adding a game the gambler plays
+    pub fn game(mut self, game: Game) -> Gambler {
+        self.games.push(game);
+        self
+    }
+
+    /// Returns `true` if the gambler plays the game
+    pub fn plays(&self, game: Game) -> bool {
+        self.games.contains(&game)
+    }
+
+    /// Deals a card for a game of high-low, which the next wager on high-low is placed on. The
+    /// card is kept on the table of the gambler until then.
+    pub fn deal<R: Rng>(&self, table: &mut Gambling, rng: &mut R) -> Result<u32, WagerError> {
+        if !self.plays(Game::HighLow) {
+            return Err(WagerError::UnknownGame);
+        }
+
+        let card = deal(rng);
+        table.dealt.insert(self.name.clone(), card);
+        Ok(card)
+    }
+
+    /// Plays a round of the game with the wager, paying the winnings to or taking the wager from
+    /// the inventory of the character. High-low needs a card `deal`t before and a guess.
+    pub fn play<R: Rng>(&self,
+                        table: &mut Gambling,
+                        game: Game,
+                        guess: Option<Guess>,
+                        wager: Gold,
+                        character: &mut Character,
+                        rng: &mut R)
+                        -> Result<Round, WagerError> {
+        if !self.plays(game) {
+            return Err(WagerError::UnknownGame);
+        }
+        if wager == 0 {
+            return Err(WagerError::NothingWagered);
+        }
+        if wager > self.max_wager {
+            return Err(WagerError::TooHigh);
+        }
+        if character.inventory().gold() < wager {
+            return Err(WagerError::NotEnoughGold);
+        }
+
+        let luck = character.get_attribute_value(&Attribute::Luck);
+        let round = match (game, guess, table.dealt(&self.name)) {
+            (Game::Dice, _, _) => play_dice(luck, rng),
+            (Game::HighLow, Some(guess), Some(dealt)) => {
+                table.dealt.remove(&self.name);
+                play_high_low(dealt, guess, luck, rng)
+            }
+            (Game::HighLow, _, _) => return Err(WagerError::NoCardDealt),
+        };
+
+        match round.outcome {
+            Outcome::Won => character.inventory_mut().add_gold(wager),
+            Outcome::Lost => {
+                character.inventory_mut().spend_gold(wager);
+            }
+            Outcome::Draw => {}
+        }
+        Ok(round)
+    }
+}
+
+/// The cards the gamblers dealt for high-low, waiting for a wager
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Gambling {
+    dealt: BTreeMap<String, u32>,
+}
+
+impl Gambling {
+    /// Creates a new instance of `Gambling` without dealt cards
+    pub fn new() -> Gambling {
+        Gambling::default()
+    }
+
+    /// Returns the card the gambler with the given name dealt (optional)
+    pub fn dealt(&self, gambler: &str) -> Option<u32> {
+        self.dealt.get(gambler).cloned()
+    }
+
+    /// Reads the dealt cards from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Gambling, DataError> {
+        let object = data::as_object(json, "gambling")?;
+
+        let dealt = object.iter()
+            .map(|(gambler, card)| {
+                card.as_u64()
+                    .map(|card| (gambler.clone(), card as u32))
+                    .ok_or_else(|| DataError::Invalid(gambler.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Gambling { dealt })
+    }
+}
+
+impl ToJson for Gambling {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        for (gambler, card) in &self.dealt {
+            object.insert(gambler.clone(), card.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// Makes the gamblers of a tavern playable through the `gamble` command, acting on the
+/// `player` of the state and keeping the dealt cards in its `gambling`:
+///
+/// - `gamble` lists the gamblers and their games
+/// - `gamble <name> dice <wager>` plays a round of dice
+/// - `gamble <name> deal` deals a card for high-low
+/// - `gamble <name> higher|lower <wager>` wagers on the next card
+///
+/// The replies are taken from the `gamble.*` keys of the catalog. Won gold is published as
+/// `GameEvent::GoldEarned`.
+pub fn register(content: &mut Content, gamblers: Vec<Gambler>, catalog: Catalog) {
+    content.register_command("gamble", move |context, arguments, rng| {
+        let (name, arguments) = match arguments.split_first() {
+            None => {
+                return gamblers.iter()
+                    .map(|gambler| {
+                        let games: Vec<_> =
+                            gambler.games.iter().map(|game| format!("{:?}", game)).collect();
+                        let values = TemplateContext::new()
+                            .value("name", &gambler.name)
+                            .value("games", games.join(", "))
+                            .value("max_wager", gambler.max_wager);
+                        catalog.render("gamble.gambler", &values)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            }
+            Some((name, arguments)) => (name, arguments),
+        };
+
+        let gambler = match gamblers.iter().find(|gambler| gambler.name == *name) {
+            Some(gambler) => gambler,
+            None => {
+                let values = TemplateContext::new().value("name", name);
+                return catalog.render("gamble.unknown_gambler", &values);
+            }
+        };
+        let character = match context.player {
+            Some(ref mut character) => character,
+            None => return catalog.get("gamble.no_player").to_owned(),
+        };
+
+        let (game, guess) = match arguments.first() {
+            Some(&"deal") => {
+                return match gambler.deal(&mut context.gambling, rng) {
+                    Ok(card) => {
+                        let values = TemplateContext::new()
+                            .value("name", &gambler.name)
+                            .value("card", card);
+                        catalog.render("gamble.dealt", &values)
+                    }
+                    Err(_) => {
+                        let values = TemplateContext::new().value("name", &gambler.name);
+                        catalog.render("gamble.no_high_low", &values)
+                    }
+                };
+            }
+            Some(&"dice") => (Game::Dice, None),
+            Some(&"higher") => (Game::HighLow, Some(Guess::Higher)),
+            Some(&"lower") => (Game::HighLow, Some(Guess::Lower)),
+            _ => return catalog.get("gamble.usage").to_owned(),
+        };
+        let wager = match arguments.get(1).and_then(|wager| wager.parse().ok()) {
+            Some(wager) => wager,
+            None => return catalog.get("gamble.no_wager").to_owned(),
+        };
+
+        let round = match gambler.play(&mut context.gambling, game, guess, wager, character, rng) {
+            Ok(round) => round,
+            Err(error) => {
+                let values = TemplateContext::new()
+                    .value("name", &gambler.name)
+                    .value("game", format!("{:?}", game))
+                    .value("max_wager", gambler.max_wager);
+                return catalog.render(&format!("gamble.{:?}", error), &values);
+            }
+        };
+
+        let values = TemplateContext::new().value("wager", wager);
+        let result = catalog.render(&format!("gamble.{:?}", round.outcome), &values);
+        if round.outcome == Outcome::Won {
+            context.publish(GameEvent::GoldEarned(wager));
+        }
+        format!("{} {}", round.describe(&catalog), result)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use engine::EngineContext;
+    use locale::Catalog;
+    use plugin::Content;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn luck() {
+        assert_eq!(reroll_chance(0), 0);
+        assert_eq!(reroll_chance(10), 20);
+        assert_eq!(reroll_chance(-5), 0);
+        assert_eq!(reroll_chance(1000), MAX_REROLL_CHANCE);
+    }
+
+    #[test]
+    fn wager() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut table = Gambling::new();
+        let gambler = Gambler::new("Old Tom", 50).game(Game::Dice);
+        let mut character = Character::new("Michael");
+        character.inventory_mut().add_gold(100);
+
+        assert_eq!(gambler.play(&mut table, Game::Dice, None, 80, &mut character, &mut rng),
+                   Err(WagerError::TooHigh));
+        assert_eq!(gambler.play(&mut table, Game::HighLow, None, 10, &mut character, &mut rng),
+                   Err(WagerError::UnknownGame));
+
+        let round = gambler.play(&mut table, Game::Dice, None, 10, &mut character, &mut rng)
+            .unwrap();
+        let gold = match round.outcome {
+            Outcome::Won => 110,
+            Outcome::Lost => 90,
+            Outcome::Draw => 100,
+        };
+        assert_eq!(character.inventory().gold(), gold);
+    }
+
+    #[test]
+    fn high_low() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut table = Gambling::new();
+        let gambler = Gambler::new("Mara", 20).game(Game::HighLow);
+        let mut character = Character::new("Michael");
+        character.update_attribute(&Attribute::Luck, 1000);
+        character.inventory_mut().add_gold(10);
+
+        let higher = Some(Guess::Higher);
+        assert_eq!(gambler.play(&mut table, Game::HighLow, higher, 5, &mut character, &mut rng),
+                   Err(WagerError::NoCardDealt));
+
+        let card = gambler.deal(&mut table, &mut rng).unwrap();
+        assert!((1..=HIGHEST_CARD).contains(&card));
+        assert_eq!(table.dealt("Mara"), Some(card));
+        assert_eq!(Gambling::from_json(&table.to_json()).unwrap(), table);
+
+        let guess = Some(if card > HIGHEST_CARD / 2 { Guess::Lower } else { Guess::Higher });
+        let round = gambler.play(&mut table, Game::HighLow, guess, 5, &mut character, &mut rng)
+            .unwrap();
+        assert_eq!(table.dealt("Mara"), None);
+        assert_eq!(round.describe(&Catalog::english()),
+                   format!("The next card is {}.", match round.draw {
+                       Draw::Card(card) => card,
+                       Draw::Dice(..) => unreachable!(),
+                   }));
+    }
+
+    #[test]
+    fn command() {
+        let mut content = Content::new();
+        register(&mut content,
+                 vec![Gambler::new("Tom", 50).game(Game::Dice)],
+                 Catalog::english());
+        let mut context = EngineContext::new();
+
+        assert_eq!(content.execute(&mut context, "gamble").unwrap(),
+                   "Tom: Dice (up to 50 gold)");
+        assert_eq!(content.execute(&mut context, "gamble Tom dice 10").unwrap(),
+                   "There is nobody to play");
+
+        context.player = Some(Character::new("Michael"));
+        assert_eq!(content.execute(&mut context, "gamble Tom dice 10").unwrap(),
+                   "You can not pay the wager");
+        context.player.as_mut().unwrap().inventory_mut().add_gold(10);
+        let output = content.execute(&mut context, "gamble Tom dice 10").unwrap();
+        assert!(output.starts_with("You roll"));
+    }
+
+    #[test]
+    fn dealt_card_is_saved() {
+        let mut content = Content::new();
+        register(&mut content,
+                 vec![Gambler::new("Mara", 20).game(Game::HighLow)],
+                 Catalog::new("de").string("gamble.no_player", "Niemand spielt"));
+        let mut context = EngineContext::new();
+
+        assert_eq!(content.execute(&mut context, "gamble Mara deal").unwrap(),
+                   "Niemand spielt");
+        context.player = Some(Character::new("Michael"));
+        let dealt = content.execute(&mut context, "gamble Mara deal").unwrap();
+        let card = context.gambling.dealt("Mara").unwrap();
+        assert_eq!(dealt, format!("Mara deals a {}. Higher or lower?", card));
+
+        let loaded = EngineContext::from_json(&context.to_json()).unwrap();
+        assert_eq!(loaded.gambling.dealt("Mara"), Some(card));
+
+        // The replayed commands deal the same card
+        let mut replayed = EngineContext::new();
+        replayed.player = Some(Character::new("Michael"));
+        context.replay.play(&mut replayed, |context, line, rng| {
+            content.run(context, line, rng);
+        });
+        assert_eq!(replayed.gambling.dealt("Mara"), Some(card));
+    }
+}
@@ -0,0 +1,268 @@
+use data::{self, DataError};
+use inventory::Inventory;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::str::FromStr;
+use world::World;
+use world::two_dimensional::{FieldType, World2d};
+
+/// The kinds of mounts and vehicles
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MountKind {
+    /// Rides over land and gallops over grass, but can not enter water
+    Horse,
+    /// Sails over water, but can not enter land
+    Boat,
+}
+
+impl MountKind {
+    /// Returns `true` if the mount can enter a field of the given type
+    pub fn can_enter(&self, field_type: &FieldType) -> bool {
+        match *self {
+            MountKind::Horse => !field_type.is_water(),
+            MountKind::Boat => field_type.is_water(),
+        }
+    }
+
+    /// Returns the amount of fields crossed with a single step starting on a field of the given
+    /// type
+    pub fn stride(&self, field_type: &FieldType) -> u32 {
+        match (*self, field_type) {
+            (MountKind::Horse, &FieldType::Grass) => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the amount of inventory slots the mount carries
+    pub fn capacity(&self) -> usize {
+        match *self {
+            MountKind::Horse => 10,
+            MountKind::Boat => 20,
+        }
+    }
+}
+
+impl FromStr for MountKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<MountKind, ()> {
+        match s {
+            "Horse" => Ok(MountKind::Horse),
+            "Boat" => Ok(MountKind::Boat),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A horse, boat or other mount owned by the player, carrying its own inventory
+#[derive(Clone, Debug)]
+pub struct Mount {
+    /// The name of the mount, e.g. `Shadowfax`
+    pub name: String,
+    /// The kind of the mount
+    pub kind: MountKind,
+    /// The items carried by the mount
+    pub saddlebags: Inventory,
+}
+
+impl Mount {
+    /// Creates a new instance of `Mount` with empty saddlebags
+    pub fn new(name: &str, kind: MountKind) -> Mount {
+        Mount {
+            name: name.to_owned(),
+            kind,
+            saddlebags: Inventory::new(kind.capacity()),
+        }
+    }
+
+    /// Reads a mount from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Mount, DataError> {
+        let object = data::as_object(json, "mount")?;
+
+        Ok(Mount {
+            name: data::string(object, "name")?,
+            kind: data::parse(object, "kind")?,
+            saddlebags: Inventory::from_json(data::field(object, "saddlebags")?)?,
+        })
+    }
+}
+
+impl ToJson for Mount {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("kind".to_owned(), format!("{:?}", self.kind).to_json());
+        object.insert("saddlebags".to_owned(), self.saddlebags.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// The mounts owned by the player and the one they ride
+#[derive(Clone, Debug, Default)]
+pub struct Stable {
+    mounts: Vec<Mount>,
+    riding: Option<usize>,
+}
+
+impl Stable {
+    /// Creates a new instance of `Stable` without mounts
+    pub fn new() -> Stable {
+        Stable::default()
+    }
+
+    /// Adds a mount to the mounts of the player, e.g. after buying it
+    pub fn add(&mut self, mount: Mount) {
+        self.mounts.push(mount);
+    }
+
+    /// Returns all owned mounts
+    pub fn all(&self) -> &[Mount] {
+        &self.mounts
+    }
+
+    /// Returns the mount ridden by the player (optional)
+    pub fn riding(&self) -> Option<&Mount> {
+        self.riding.map(|index| &self.mounts[index])
+    }
+
+    /// Returns the mount ridden by the player as mutable (optional), e.g. to use its saddlebags
+    pub fn riding_mut(&mut self) -> Option<&mut Mount> {
+        match self.riding {
+            Some(index) => Some(&mut self.mounts[index]),
+            None => None,
+        }
+    }
+
+    /// Mounts the owned mount with the given name. Returns `false` if there is none.
+    pub fn mount(&mut self, name: &str) -> bool {
+        match self.mounts.iter().position(|mount| mount.name == name) {
+            Some(index) => {
+                self.riding = Some(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Dismounts the ridden mount and returns its name
+    pub fn dismount(&mut self) -> Option<String> {
+        self.riding.take().map(|index| self.mounts[index].name.clone())
+    }
+
+    /// Moves the player one step in the direction, crossing as many fields as the ridden mount's
+    /// stride allows on the field they start on. Without a mount, every passable field can be
+    /// entered; a mount only enters the fields it `can_enter`. Returns the amount of fields
+    /// crossed.
+    pub fn step(&self, world: &mut World2d, movement: (i64, i64)) -> u32 {
+        let start = &world.get_field(world.get_position()).field_type;
+        let stride = self.riding().map_or(1, |mount| mount.kind.stride(start));
+
+        let mut crossed = 0;
+        while crossed < stride {
+            let (x, y) = world.get_position();
+            let target = ((x as i64 + movement.0) as usize, (y as i64 + movement.1) as usize);
+            let allowed = world.is_passable(target) &&
+                          self.riding().is_none_or(|mount| {
+                mount.kind.can_enter(&world.get_field(target).field_type)
+            });
+            if !allowed {
+                break;
+            }
+
+            world.move_to(movement);
+            crossed += 1;
+        }
+
+        crossed
+    }
+
+    /// Reads the mounts from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Stable, DataError> {
+        let object = data::as_object(json, "mounts")?;
+
+        let mounts = data::as_array(data::field(object, "mounts")?, "mounts")?
+            .iter()
+            .map(Mount::from_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        let riding = data::optional(object, "riding", data::unsigned)?.map(|index| index as usize);
+        if riding.is_some_and(|index| index >= mounts.len()) {
+            return Err(DataError::Invalid("riding".to_owned()));
+        }
+
+        Ok(Stable {
+            mounts,
+            riding,
+        })
+    }
+}
+
+impl ToJson for Stable {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("mounts".to_owned(), self.mounts.to_json());
+        if let Some(riding) = self.riding {
+            object.insert("riding".to_owned(), riding.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item_generator::ItemGenerator;
+    use rustc_serialize::json::ToJson;
+    use world::{Teleport, World};
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn coast() -> World2d {
+        let mut world = World2d::new("Coast", (6, 1));
+        for x in 3..6 {
+            world.set_field(Field::new(FieldType::Water), (x, 0));
+        }
+        world
+    }
+
+    #[test]
+    fn horse() {
+        let mut world = coast();
+        let mut stable = Stable::new();
+        stable.add(Mount::new("Shadowfax", MountKind::Horse));
+
+        assert!(stable.mount("Shadowfax"));
+        assert_eq!(stable.step(&mut world, (1, 0)), 2);
+        assert_eq!(world.get_position(), (2, 0));
+        assert_eq!(stable.step(&mut world, (1, 0)), 0);
+
+        assert_eq!(stable.dismount(), Some("Shadowfax".to_owned()));
+        assert_eq!(stable.step(&mut world, (1, 0)), 1);
+    }
+
+    #[test]
+    fn boat() {
+        let mut world = coast();
+        world.set_position((3, 0));
+        let mut stable = Stable::new();
+        stable.add(Mount::new("Black Pearl", MountKind::Boat));
+        stable.mount("Black Pearl");
+
+        assert_eq!(stable.step(&mut world, (1, 0)), 1);
+        assert_eq!(stable.step(&mut world, (-1, 0)), 1);
+        assert_eq!(stable.step(&mut world, (-1, 0)), 0);
+        assert_eq!(stable.riding().unwrap().saddlebags.capacity(), 20);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut stable = Stable::new();
+        let mut horse = Mount::new("Shadowfax", MountKind::Horse);
+        horse.saddlebags.add_item(ItemGenerator::new().name("Apple").gen()).unwrap();
+        stable.add(horse);
+        stable.mount("Shadowfax");
+
+        let loaded = Stable::from_json(&stable.to_json()).unwrap();
+        assert_eq!(loaded.riding().unwrap().name, "Shadowfax");
+        assert_eq!(loaded.riding().unwrap().saddlebags.count_by_name("Apple"), 1);
+    }
+}
@@ -0,0 +1,159 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+
+/// A note pinned by the player to a position, e.g. "merchant here" or "trap!"
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Note {
+    /// The id of the world the note belongs to
+    pub world: String,
+    /// The position the note is pinned to
+    pub position: (usize, usize),
+    /// The text written by the player
+    pub text: String,
+}
+
+impl Note {
+    /// Creates a new instance of `Note`
+    pub fn new(world: &str, position: (usize, usize), text: &str) -> Note {
+        Note {
+            world: world.to_owned(),
+            position,
+            text: text.to_owned(),
+        }
+    }
+
+    /// Reads a note from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Note, DataError> {
+        let object = data::as_object(json, "notes")?;
+
+        Ok(Note::new(&data::string(object, "world")?,
+                     data::position(object, "position")?,
+                     &data::string(object, "text")?))
+    }
+}
+
+impl ToJson for Note {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("world".to_owned(), self.world.to_json());
+        object.insert("position".to_owned(), self.position.to_json());
+        object.insert("text".to_owned(), self.text.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// All notes the player pinned to the map, in the order they were written
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Notes {
+    notes: Vec<Note>,
+}
+
+impl Notes {
+    /// Creates a new instance of `Notes`
+    pub fn new() -> Notes {
+        Notes { notes: Vec::new() }
+    }
+
+    /// Pins a note to the given position of a world
+    pub fn pin(&mut self, world: &str, position: (usize, usize), text: &str) {
+        self.notes.push(Note::new(world, position, text));
+    }
+
+    /// Removes all notes at the given position of a world. Returns the amount of removed notes.
+    pub fn remove(&mut self, world: &str, position: (usize, usize)) -> usize {
+        let count = self.notes.len();
+        self.notes.retain(|note| note.world != world || note.position != position);
+
+        count - self.notes.len()
+    }
+
+    /// Returns the notes at the given position of a world
+    pub fn at(&self, world: &str, position: (usize, usize)) -> Vec<&Note> {
+        self.notes.iter().filter(|note| note.world == world && note.position == position).collect()
+    }
+
+    /// Returns the notes of a world. Used by renderers to mark the annotated fields.
+    pub fn in_world(&self, world: &str) -> Vec<&Note> {
+        self.notes.iter().filter(|note| note.world == world).collect()
+    }
+
+    /// Returns all notes
+    pub fn all(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Returns `true` if there are no notes
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Lists all notes as the "Notes" section of a journal, one line per note
+    pub fn journal_section(&self) -> String {
+        let mut text = "Notes\n".to_owned();
+        for note in &self.notes {
+            text.push_str(&format!("- {} ({}, {}): {}\n",
+                                   note.world,
+                                   note.position.0,
+                                   note.position.1,
+                                   note.text));
+        }
+
+        text
+    }
+
+    /// Reads the notes from a JSON array as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Notes, DataError> {
+        Ok(Notes {
+            notes: data::as_array(json, "notes")?
+                .iter()
+                .map(Note::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl ToJson for Notes {
+    fn to_json(&self) -> Json {
+        self.notes.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    fn notes() -> Notes {
+        let mut notes = Notes::new();
+        notes.pin("village", (3, 4), "merchant here");
+        notes.pin("cave", (1, 1), "trap!");
+        notes.pin("cave", (1, 1), "really, a trap");
+        notes
+    }
+
+    #[test]
+    fn pin_and_remove() {
+        let mut notes = notes();
+
+        assert_eq!(notes.at("cave", (1, 1)).len(), 2);
+        assert_eq!(notes.at("village", (1, 1)), Vec::<&Note>::new());
+        assert_eq!(notes.in_world("village")[0].text, "merchant here");
+
+        assert_eq!(notes.remove("cave", (1, 1)), 2);
+        assert_eq!(notes.all().len(), 1);
+    }
+
+    #[test]
+    fn journal_section() {
+        assert_eq!(notes().journal_section(),
+                   "Notes\n- village (3, 4): merchant here\n- cave (1, 1): trap!\n- cave (1, 1): \
+                    really, a trap\n");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let notes = notes();
+        assert_eq!(Notes::from_json(&notes.to_json()).unwrap(), notes);
+    }
+}
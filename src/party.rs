@@ -0,0 +1,324 @@
+use character::Character;
+use combat::{Battle, Combatant, Side};
+use companion::Companion;
+use event::Event;
+use types::{Experience, Gold};
+
+/// The amount of party members fighting in the front row. Everyone behind them starts the battle
+/// one step away from the front line.
+pub const FRONT_ROW_SIZE: usize = 2;
+
+/// A group of characters travelling together and sharing their gold
+pub struct Party {
+    members: Vec<Character>,
+    companions: Vec<Companion>,
+    active: usize,
+    max_size: usize,
+    /// The gold shared by all members
+    pub gold: Gold,
+}
+
+impl Party {
+    /// Creates a new party led by the given character
+    pub fn new(leader: Character, max_size: usize) -> Party {
+        assert!(max_size > 0);
+        Party {
+            members: vec![leader],
+            companions: Vec::new(),
+            active: 0,
+            max_size,
+            gold: 0,
+        }
+    }
+
+    /// Returns all members in formation order
+    pub fn members(&self) -> &[Character] {
+        &self.members
+    }
+
+    /// Returns the member with the given index as mutable
+    pub fn member_mut(&mut self, index: usize) -> Option<&mut Character> {
+        self.members.get_mut(index)
+    }
+
+    /// Adds a member to the end of the formation. If the party is full, the character is returned
+    /// as `Err(Box<Character>)`.
+    pub fn add_member(&mut self, character: Character) -> Result<(), Box<Character>> {
+        if self.is_full() {
+            return Err(Box::new(character));
+        }
+
+        self.members.push(character);
+        Ok(())
+    }
+
+    /// Removes the member with the given index. The last member can not be removed.
+    pub fn remove_member(&mut self, index: usize) -> Option<Character> {
+        if index >= self.members.len() || self.members.len() == 1 {
+            return None;
+        }
+
+        let member = self.members.remove(index);
+
+        if self.active > index || self.active == self.members.len() {
+            self.active -= 1;
+        }
+
+        Some(member)
+    }
+
+    /// Swaps the formation positions of two members
+    pub fn swap(&mut self, first: usize, second: usize) {
+        self.members.swap(first, second);
+
+        if self.active == first {
+            self.active = second;
+        } else if self.active == second {
+            self.active = first;
+        }
+    }
+
+    /// Makes the member with the given index the active character. Returns `false` if there is
+    /// no such member.
+    pub fn set_active(&mut self, index: usize) -> bool {
+        if index < self.members.len() {
+            self.active = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the index of the active character
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Returns the active character
+    pub fn active(&self) -> &Character {
+        &self.members[self.active]
+    }
+
+    /// Returns the active character as mutable
+    pub fn active_mut(&mut self) -> &mut Character {
+        &mut self.members[self.active]
+    }
+
+    /// Returns all companions following the party
+    pub fn companions(&self) -> &[Companion] {
+        &self.companions
+    }
+
+    /// Returns all companions following the party as mutable
+    pub fn companions_mut(&mut self) -> &mut [Companion] {
+        &mut self.companions
+    }
+
+    /// Asks the companion to join the party. If the party is full or the companion refuses, the
+    /// companion is returned boxed together with its answer.
+    pub fn recruit(&mut self, mut companion: Companion) -> Result<Event, Box<(Companion, Event)>> {
+        if self.is_full() {
+            return Err(Box::new((companion, Event::Nothing)));
+        }
+
+        let answer = companion.talk(Event::Recruit);
+        if !companion.is_recruited() {
+            return Err(Box::new((companion, answer)));
+        }
+
+        self.companions.push(companion);
+        Ok(answer)
+    }
+
+    /// Asks the companion with the given index to leave the party
+    pub fn dismiss(&mut self, index: usize) -> Option<(Companion, Event)> {
+        if index >= self.companions.len() {
+            return None;
+        }
+
+        let mut companion = self.companions.remove(index);
+        let answer = companion.talk(Event::Dismiss);
+
+        Some((companion, answer))
+    }
+
+    /// Returns `true` if the party is full. Companions take up a place in the party as well.
+    pub fn is_full(&self) -> bool {
+        self.members.len() + self.companions.len() >= self.max_size
+    }
+
+    /// Returns `true` if all members are dead, which means the game is over
+    pub fn is_defeated(&self) -> bool {
+        self.members.iter().all(|member| member.is_dead())
+    }
+
+    /// Spends the given amount of gold. Returns `false` if the party can not afford it.
+    pub fn spend_gold(&mut self, amount: Gold) -> bool {
+        if self.gold < amount {
+            return false;
+        }
+
+        self.gold -= amount;
+        true
+    }
+
+    /// Splits the experience evenly among all members still standing. The remainder goes to the
+    /// active character.
+    pub fn split_experience(&mut self, experience: Experience) {
+        let standing: Vec<usize> = (0..self.members.len())
+            .filter(|&index| !self.members[index].is_dead())
+            .collect();
+
+        if standing.is_empty() {
+            return;
+        }
+
+        let share = experience / standing.len() as Experience;
+        let remainder = experience % standing.len() as Experience;
+
+        for &index in &standing {
+            self.members[index].gain_experience(share);
+        }
+
+        let receiver = if standing.contains(&self.active) {
+            self.active
+        } else {
+            standing[0]
+        };
+        self.members[receiver].gain_experience(remainder);
+    }
+
+    /// Adds all members to the battle in formation order, which is also their order of initiative.
+    /// Companions join last, controlled by their own AI. Returns the indices of the members and
+    /// companions in the battle.
+    pub fn join_battle(&self, battle: &mut Battle) -> Vec<usize> {
+        let members = self.members
+            .iter()
+            .map(|member| Combatant::from_character(member, Side::Player));
+        let companions = self.companions.iter().map(|companion| companion.combatant());
+
+        members.chain(companions)
+            .enumerate()
+            .map(|(position, combatant)| {
+                let distance = if position < FRONT_ROW_SIZE { 0 } else { 1 };
+                battle.add_combatant(combatant.distance(distance))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use combat::{AggressiveMelee, Battle};
+    use companion::Companion;
+    use event::Event;
+
+    fn party() -> Party {
+        let mut party = Party::new(Character::new("Frodo"), 4);
+        party.add_member(Character::new("Sam")).unwrap();
+        party.add_member(Character::new("Merry")).unwrap();
+        party
+    }
+
+    #[test]
+    fn members() {
+        let mut party = party();
+        party.add_member(Character::new("Pippin")).unwrap();
+
+        assert!(party.is_full());
+        assert!(party.add_member(Character::new("Gandalf")).is_err());
+
+        assert_eq!(party.remove_member(3).unwrap().name(), "Pippin");
+        assert_eq!(party.members().len(), 3);
+    }
+
+    #[test]
+    fn active_character() {
+        let mut party = party();
+        assert_eq!(party.active().name(), "Frodo");
+
+        assert!(party.set_active(2));
+        assert!(!party.set_active(5));
+        assert_eq!(party.active().name(), "Merry");
+
+        party.swap(0, 2);
+        assert_eq!(party.active_index(), 0);
+        assert_eq!(party.active().name(), "Merry");
+
+        party.remove_member(0);
+        assert_eq!(party.active().name(), "Sam");
+
+        let mut lonely = Party::new(Character::new("Gollum"), 1);
+        assert!(lonely.remove_member(0).is_none());
+    }
+
+    #[test]
+    fn shared_gold() {
+        let mut party = party();
+        party.gold = 50;
+
+        assert!(party.spend_gold(30));
+        assert!(!party.spend_gold(30));
+        assert_eq!(party.gold, 20);
+    }
+
+    #[test]
+    fn split_experience() {
+        let mut party = party();
+        party.set_active(1);
+
+        party.split_experience(100);
+
+        assert_eq!(party.members()[0].experience(), 33);
+        assert_eq!(party.members()[1].experience(), 34);
+        assert_eq!(party.members()[2].experience(), 33);
+    }
+
+    #[test]
+    fn defeated() {
+        let mut party = party();
+
+        for index in 0..2 {
+            party.member_mut(index).unwrap().take_damage(100);
+        }
+        assert!(!party.is_defeated());
+
+        party.member_mut(2).unwrap().take_damage(100);
+        assert!(party.is_defeated());
+    }
+
+    #[test]
+    fn formation() {
+        let party = party();
+        let mut battle = Battle::new();
+
+        let indices = party.join_battle(&mut battle);
+
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert_eq!(battle.combatant(0).name, "Frodo");
+        assert_eq!(battle.combatant(1).distance, 0);
+        assert_eq!(battle.combatant(2).distance, 1);
+    }
+
+    #[test]
+    fn companions() {
+        let mut party = party();
+        let dog = Companion::new(Character::new("Dogmeat"), AggressiveMelee);
+
+        assert_eq!(party.recruit(dog.clone()).ok(),
+                   Some(Event::Tell("Lead the way!".to_owned())));
+        assert!(party.is_full());
+        assert!(party.recruit(dog).is_err());
+
+        let mut battle = Battle::new();
+        assert_eq!(party.join_battle(&mut battle).len(), 4);
+        assert!(battle.combatant(3).get_ai().is_some());
+
+        let (dog, answer) = party.dismiss(0).unwrap();
+        assert_eq!(answer, Event::Tell("Farewell.".to_owned()));
+        assert!(!dog.is_recruited());
+        assert!(party.companions().is_empty());
+    }
+}
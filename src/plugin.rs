@@ -0,0 +1,448 @@
+use alias::AliasError;
+use bestiary::Bestiary;
+use data::DataError;
+use engine::EngineContext;
+use hotbar::{Activation, HotbarError};
+use inventory::InventoryError;
+use item::Item;
+use rand::{Rng, XorShiftRng};
+use std::collections::HashMap;
+use unique::UniqueItems;
+use world::terrain::FieldRegistry;
+use world::two_dimensional::{PickUpError, World2d};
+
+/// Executes a command typed by the player. Receives the state, the words following the name of
+/// the command and the random number generator of the command, and returns the text shown to the
+/// player. Everything random has to use that generator, so replaying the command leads to the
+/// same result.
+pub type CommandHandler = Box<dyn Fn(&mut EngineContext, &[&str], &mut XorShiftRng) -> String>;
+
+/// A crate extending the game with its own content
+pub trait ContentPlugin {
+    /// The unique name of the plugin
+    fn name(&self) -> &str;
+
+    /// Adds the content of the plugin to the registry
+    fn register(&self, content: &mut Content) -> Result<(), DataError>;
+}
+
+/// All content known to the game: monsters, field types, items and commands, whether built-in
+/// or added by plugins
+pub struct Content {
+    /// The monsters and AI behaviors
+    pub bestiary: Bestiary,
+    /// The custom field types, see `prepare_world`
+    pub field_types: FieldRegistry,
+    /// The hand-made items that exist only once per save
+    pub uniques: UniqueItems,
+    item_templates: HashMap<String, Item>,
+    commands: HashMap<String, CommandHandler>,
+    plugins: Vec<String>,
+}
+
+impl Default for Content {
+    fn default() -> Content {
+        Content::new()
+    }
+}
+
+impl Content {
+    /// Creates a new instance of `Content` knowing only the built-in content: the commands
+    /// `alias`, which lists the aliases of the player or defines one like `alias n go north`,
+    /// `unalias`, which removes an alias, `mount`, which lists the mounts of the player or rides
+    /// one like `mount Shadowfax`, `dismount`, `drop`, which drops an item of the player onto the
+    /// field they stand on like `drop Rusty Sword`, `take`, which picks an item up from there, and
+    /// `use`, which uses a slot of the hotbar of the player by its number like `use 3`
+    pub fn new() -> Content {
+        let mut content = Content {
+            bestiary: Bestiary::new(),
+            field_types: FieldRegistry::new(),
+            uniques: UniqueItems::new(),
+            item_templates: HashMap::new(),
+            commands: HashMap::new(),
+            plugins: Vec::new(),
+        };
+
+        content.register_command("alias", |context, arguments, _| match arguments.split_first() {
+            None => {
+                context.aliases
+                    .iter()
+                    .iter()
+                    .map(|&(name, expansion)| format!("{} = {}", name, expansion))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Some((name, expansion)) => {
+                match context.aliases.define(name, &expansion.join(" ")) {
+                    Ok(()) => format!("{} = {}", name, expansion.join(" ")),
+                    Err(AliasError::InvalidName) => format!("Invalid alias name: {}", name),
+                    Err(AliasError::EmptyExpansion) => format!("Nothing to alias {} to", name),
+                }
+            }
+        });
+        content.register_command("unalias", |context, arguments, _| {
+            let removed = arguments.iter().filter(|name| context.aliases.remove(name).is_some());
+            format!("Removed {} aliases", removed.count())
+        });
+        content.register_command("mount", |context, arguments, _| {
+            if arguments.is_empty() {
+                return context.mounts
+                    .all()
+                    .iter()
+                    .map(|mount| format!("{} ({:?})", mount.name, mount.kind))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            }
+
+            let name = arguments.join(" ");
+            if context.mounts.mount(&name) {
+                format!("You mount {}", name)
+            } else {
+                format!("You have no mount called {}", name)
+            }
+        });
+        content.register_command("dismount", |context, _, _| match context.mounts.dismount() {
+            Some(name) => format!("You dismount {}", name),
+            None => "You are not riding".to_owned(),
+        });
+        content.register_command("drop", |context, arguments, _| {
+            let (character, world) = match (context.player.as_mut(), context.world.as_mut()) {
+                (Some(character), Some(world)) => (character, world),
+                _ => return "There is nowhere to drop anything".to_owned(),
+            };
+
+            let name = arguments.join(" ");
+            match character.inventory_mut().drop_by_name(&name) {
+                Ok(item) => {
+                    world.drop_item(item);
+                    format!("You drop {}", name)
+                }
+                Err(InventoryError::QuestItem) => format!("You can not drop {}", name),
+                Err(_) => format!("You have no {}", name),
+            }
+        });
+        content.register_command("take", |context, arguments, _| {
+            let (character, world) = match (context.player.as_mut(), context.world.as_mut()) {
+                (Some(character), Some(world)) => (character, world),
+                _ => return "There is nothing to take".to_owned(),
+            };
+
+            let name = arguments.join(" ");
+            match world.pick_up(&name, character.inventory_mut()) {
+                Ok(()) => format!("You take {}", name),
+                Err(PickUpError::NotHere) => format!("There is no {} here", name),
+                Err(PickUpError::InventoryFull) => format!("There is no room for {}", name),
+            }
+        });
+        content.register_command("use", |context, arguments, _| {
+            let character = match context.player {
+                Some(ref mut character) => character,
+                None => return "There is no one to use anything".to_owned(),
+            };
+
+            let number = arguments.join(" ");
+            let line = format!("use {}", number);
+            let slot = match character.hotbar().parse_command(&line) {
+                Some(slot) => slot,
+                None => return format!("There is no hotbar slot {}", number),
+            };
+
+            match character.use_hotbar(slot) {
+                Ok(Activation::Item(item)) => format!("You use {}", item.name),
+                Ok(Activation::Ability(name)) => format!("You use {}", name),
+                Err(HotbarError::ItemMissing) => format!("The item of slot {} is gone", number),
+                Err(_) => format!("Hotbar slot {} is empty", number),
+            }
+        });
+
+        content
+    }
+
+    /// Lets the plugin register its content. Fails if a plugin with the same name was installed
+    /// before.
+    pub fn install(&mut self, plugin: &dyn ContentPlugin) -> Result<(), DataError> {
+        if self.plugins.iter().any(|name| name == plugin.name()) {
+            return Err(DataError::Invalid("plugin".to_owned()));
+        }
+
+        plugin.register(self)?;
+        self.plugins.push(plugin.name().to_owned());
+        Ok(())
+    }
+
+    /// Returns the names of all installed plugins in the order they were installed
+    pub fn plugins(&self) -> &[String] {
+        &self.plugins
+    }
+
+    /// Adds an item template. Items are created as copies of the template, using its name.
+    pub fn add_item_template(&mut self, template: Item) {
+        self.item_templates.insert(template.name.clone(), template);
+    }
+
+    /// Makes the custom field types registered by plugins available to the world, e.g. right
+    /// after creating or loading it. Field types the world defines itself take precedence.
+    pub fn prepare_world(&self, world: &mut World2d) {
+        world.field_types.merge(&self.field_types);
+    }
+
+    /// Creates an item from the template with the given name, with a new id created by the rng
+    pub fn item<R: Rng>(&self, name: &str, rng: &mut R) -> Option<Item> {
+        self.item_templates.get(name).map(|item| item.renewed(rng))
+    }
+
+    /// Makes a command available under the given name, replacing a command with the same name
+    pub fn register_command<F>(&mut self, name: &str, handler: F)
+        where F: Fn(&mut EngineContext, &[&str], &mut XorShiftRng) -> String + 'static
+    {
+        self.commands.insert(name.to_owned(), Box::new(handler));
+    }
+
+    /// Returns `true` if a command with the given name is known
+    pub fn has_command(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    /// Executes a line typed by the player, e.g. `pray at altar`, after expanding the aliases
+    /// of the player. The outputs of the commands of a macro are joined by newlines. Lines
+    /// defining an alias are not expanded, so macros can be defined. Returns `None` without
+    /// executing anything if a command is not known. Executed lines are recorded to the replay of
+    /// the state, which makes them a turn that can be replayed and undone. The line and the
+    /// output are written to the transcript of the state, if one is recorded.
+    pub fn execute(&self, context: &mut EngineContext, line: &str) -> Option<String> {
+        let turn = context.replay.commands().len();
+        if let Some(ref mut transcript) = context.transcript {
+            transcript.input(turn, line).ok();
+        }
+
+        let calls = self.calls(context, line)?;
+        let mut rng = context.replay.record(line);
+        let output = Content::call(context, &calls, &mut rng);
+
+        if let Some(ref mut transcript) = context.transcript {
+            transcript.output(turn, &output).ok();
+        }
+        Some(output)
+    }
+
+    /// Executes a line like `execute` with the given random number generator, but neither
+    /// records nor transcribes it, e.g. as step of `Replay::play` or `EngineContext::undo`
+    pub fn run(&self,
+               context: &mut EngineContext,
+               line: &str,
+               rng: &mut XorShiftRng)
+               -> Option<String> {
+        let calls = self.calls(context, line)?;
+        Some(Content::call(context, &calls, rng))
+    }
+
+    /// Returns the handlers of the commands the line expands to and their words, or `None` if a
+    /// command is not known
+    fn calls(&self, context: &EngineContext, line: &str) -> Option<Vec<(&CommandHandler, String)>> {
+        let commands = match line.split_whitespace().next() {
+            Some("alias") => vec![line.to_owned()],
+            _ => context.aliases.expand(line),
+        };
+
+        commands.into_iter()
+            .map(|command| {
+                let name = command.split_whitespace().next().unwrap_or("");
+                self.commands.get(name).map(|handler| (handler, command))
+            })
+            .collect()
+    }
+
+    /// Calls the handlers and joins their outputs by newlines
+    fn call(context: &mut EngineContext,
+            calls: &[(&CommandHandler, String)],
+            rng: &mut XorShiftRng)
+            -> String {
+        calls.iter()
+            .map(|&(handler, ref command)| {
+                let words: Vec<&str> = command.split_whitespace().skip(1).collect();
+                handler(context, &words, rng)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use data::DataError;
+    use engine::EngineContext;
+    use hotbar::HotbarEntry;
+    use item_generator::ItemGenerator;
+    use item::ItemType;
+    use mount::{Mount, MountKind};
+    use rand::SeedableRng;
+    use rustc_serialize::json::ToJson;
+    use std::{env, fs};
+    use world::terrain::FieldDefinition;
+    use world::two_dimensional::{FieldType, World2d};
+
+    struct Swamp;
+
+    impl ContentPlugin for Swamp {
+        fn name(&self) -> &str {
+            "swamp"
+        }
+
+        fn register(&self, content: &mut Content) -> Result<(), DataError> {
+            content.field_types.register("bog", FieldDefinition::new("Bog", '~').movement_cost(3));
+
+            let mut boots = ItemGenerator::new().item_type(ItemType::ArmorFeet).gen();
+            boots.name = "Waders".to_owned();
+            content.add_item_template(boots);
+
+            content.register_command("croak",
+                                     |_, arguments, _| format!("Ribbit {}", arguments.join(" ")));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn install_plugin() {
+        let mut content = Content::new();
+        content.install(&Swamp).unwrap();
+
+        assert_eq!(content.plugins(), &["swamp".to_owned()]);
+        let mut world = World2d::new("Marsh", (2, 2));
+        content.prepare_world(&mut world);
+        assert_eq!(world.field_types.movement_cost(&FieldType::Custom("bog".to_owned())), 3);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        assert_eq!(content.item("Waders", &mut rng).map(|item| item.item_type),
+                   Some(ItemType::ArmorFeet));
+        assert!(content.has_command("croak"));
+
+        let mut context = EngineContext::new();
+        assert_eq!(content.execute(&mut context, "croak twice"), Some("Ribbit twice".to_owned()));
+        assert_eq!(content.execute(&mut context, "jump"), None);
+    }
+
+    #[test]
+    fn aliases() {
+        let mut content = Content::new();
+        content.install(&Swamp).unwrap();
+        let mut context = EngineContext::new();
+
+        assert_eq!(content.execute(&mut context, "alias c croak loudly"),
+                   Some("c = croak loudly".to_owned()));
+        assert_eq!(content.execute(&mut context, "alias cc c; c twice"),
+                   Some("cc = c; c twice".to_owned()));
+        assert_eq!(content.execute(&mut context, "cc"),
+                   Some("Ribbit loudly\nRibbit loudly twice".to_owned()));
+        assert_eq!(content.execute(&mut context, "c; jump"), None);
+
+        content.execute(&mut context, "unalias c");
+        assert_eq!(content.execute(&mut context, "cc"), None);
+    }
+
+    #[test]
+    fn mounts() {
+        let content = Content::new();
+        let mut context = EngineContext::new();
+        context.mounts.add(Mount::new("Shadowfax", MountKind::Horse));
+
+        assert_eq!(content.execute(&mut context, "mount"), Some("Shadowfax (Horse)".to_owned()));
+        assert_eq!(content.execute(&mut context, "mount Roach"),
+                   Some("You have no mount called Roach".to_owned()));
+        assert_eq!(content.execute(&mut context, "mount Shadowfax"),
+                   Some("You mount Shadowfax".to_owned()));
+        assert_eq!(context.mounts.riding().map(|mount| &mount.name[..]), Some("Shadowfax"));
+        assert_eq!(content.execute(&mut context, "dismount"),
+                   Some("You dismount Shadowfax".to_owned()));
+    }
+
+    #[test]
+    fn ground_items() {
+        let content = Content::new();
+        let mut context = EngineContext::new();
+        assert_eq!(content.execute(&mut context, "take Sting"),
+                   Some("There is nothing to take".to_owned()));
+
+        let mut character = Character::new("Bilbo");
+        character.inventory_mut().add_item(ItemGenerator::new().name("Sting").gen()).unwrap();
+        context.player = Some(character);
+        context.world = Some(World2d::new("Shire", (3, 3)).starting_point((1, 1)));
+
+        assert_eq!(content.execute(&mut context, "drop Sting"), Some("You drop Sting".to_owned()));
+        assert_eq!(content.execute(&mut context, "drop Sting"),
+                   Some("You have no Sting".to_owned()));
+
+        let mut context = EngineContext::from_json(&context.to_json()).unwrap();
+        assert_eq!(context.world.as_ref().unwrap().items_at((1, 1)).len(), 1);
+        assert_eq!(content.execute(&mut context, "take Sting"), Some("You take Sting".to_owned()));
+        assert_eq!(content.execute(&mut context, "take Sting"),
+                   Some("There is no Sting here".to_owned()));
+        assert!(context.player.unwrap().inventory().find_by_name("Sting").is_some());
+    }
+
+    #[test]
+    fn hotbar() {
+        let content = Content::new();
+        let mut context = EngineContext::new();
+        let mut character = Character::new("Frodo");
+        let phial = ItemGenerator::new().name("Phial").gen();
+        character.inventory_mut().add_item(phial.clone()).unwrap();
+        character.hotbar_mut().assign(2, HotbarEntry::Item(phial.id));
+        character.hotbar_mut().assign(0, HotbarEntry::Ability("Invisibility".to_owned()));
+        context.player = Some(character);
+
+        assert_eq!(content.execute(&mut context, "use 3"), Some("You use Phial".to_owned()));
+        assert_eq!(content.execute(&mut context, "use 3"),
+                   Some("The item of slot 3 is gone".to_owned()));
+        assert_eq!(content.execute(&mut context, "use 1"),
+                   Some("You use Invisibility".to_owned()));
+        assert_eq!(content.execute(&mut context, "use 2"),
+                   Some("Hotbar slot 2 is empty".to_owned()));
+        assert_eq!(content.execute(&mut context, "use 11"),
+                   Some("There is no hotbar slot 11".to_owned()));
+    }
+
+    #[test]
+    fn transcript() {
+        let path = env::temp_dir().join("txtrpg_plugin_transcript.txt");
+        let mut content = Content::new();
+        content.install(&Swamp).unwrap();
+        let mut context = EngineContext::new();
+        context.start_transcript(&path).unwrap();
+
+        content.execute(&mut context, "croak twice");
+        content.execute(&mut context, "jump");
+        assert_eq!(fs::read_to_string(&path).unwrap(),
+                   "[Turn 0] > croak twice\n[Turn 0] Ribbit twice\n[Turn 1] > jump\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replay() {
+        let mut content = Content::new();
+        content.install(&Swamp).unwrap();
+        let mut context = EngineContext::new();
+
+        content.execute(&mut context, "croak twice");
+        content.execute(&mut context, "jump");
+        content.execute(&mut context, "alias c croak");
+        assert_eq!(context.replay.commands(), &["croak twice", "alias c croak"]);
+
+        let mut replayed = EngineContext::new();
+        context.replay.play(&mut replayed, |context, line, rng| {
+            content.run(context, line, rng);
+        });
+        assert_eq!(replayed.aliases.get("c"), Some("croak"));
+    }
+
+    #[test]
+    fn duplicate_plugin() {
+        let mut content = Content::new();
+        content.install(&Swamp).unwrap();
+
+        match content.install(&Swamp) {
+            Err(DataError::Invalid(field)) => assert_eq!(field, "plugin"),
+            _ => panic!("expected a duplicate plugin"),
+        }
+    }
+}
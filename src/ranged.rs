@@ -0,0 +1,133 @@
+use character::Character;
+use rand::Rng;
+use types::AttributeValue;
+use world::two_dimensional::World2d;
+
+/// The reasons why a character can not shoot at a position
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShotError {
+    /// The character has no ranged weapon equipped
+    NoRangedWeapon,
+    /// The target is further away than the weapon reaches
+    OutOfRange,
+    /// The target lies outside of the world
+    OutsideWorld,
+    /// Something blocks the line of sight to the target
+    NoLineOfSight,
+    /// The character does not carry the ammunition the weapon needs
+    NoAmmunition,
+}
+
+/// Checks whether the character standing at `from` can shoot at `target` without using up any
+/// ammunition
+pub fn can_shoot(character: &Character,
+                 world: &World2d,
+                 from: (usize, usize),
+                 target: (usize, usize))
+                 -> Result<(), ShotError> {
+    let weapon = match character.ranged_weapon() {
+        Some(weapon) => weapon,
+        None => return Err(ShotError::NoRangedWeapon),
+    };
+
+    if World2d::distance(from, target) > weapon.item_type.range() as usize {
+        return Err(ShotError::OutOfRange);
+    }
+
+    if world.get(target.0, target.1).is_none() {
+        return Err(ShotError::OutsideWorld);
+    }
+
+    if !world.line_of_sight(from, target) {
+        return Err(ShotError::NoLineOfSight);
+    }
+
+    match weapon.item_type.ammunition() {
+        Some(ammunition) if !character.inventory().contains_type(&ammunition) => {
+            Err(ShotError::NoAmmunition)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Lets the character standing at `from` shoot at `target`. One piece of ammunition is taken
+/// from the inventory and the damage of the shot, rolled with the rng, is returned.
+pub fn shoot<R: Rng>(character: &mut Character,
+                     world: &World2d,
+                     from: (usize, usize),
+                     target: (usize, usize),
+                     rng: &mut R)
+                     -> Result<AttributeValue, ShotError> {
+    can_shoot(character, world, from, target)?;
+
+    let ammunition = character.ranged_weapon().and_then(|weapon| weapon.item_type.ammunition());
+    if let Some(ammunition) = ammunition {
+        character.inventory_mut().take_by_type(&ammunition);
+    }
+
+    Ok(character.roll_attack_damage(rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn archer(arrows: usize) -> Character {
+        let mut character = Character::new("Legolas");
+        character.set_weapon_slot_right(Some(ItemGenerator::new()
+            .item_type(ItemType::WeaponBow)
+            .gen()));
+
+        if arrows > 0 {
+            let quiver = ItemGenerator::new()
+                .item_type(ItemType::AmmoArrow)
+                .stack_size(arrows)
+                .gen();
+            for _ in 0..arrows {
+                let _ = character.inventory_mut().add_item(quiver.clone());
+            }
+        }
+
+        character
+    }
+
+    #[test]
+    fn shoot_uses_ammunition() {
+        let world = World2d::new("Rohan", (10, 10));
+        let mut character = archer(2);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let expected = character.roll_attack_damage(&mut XorShiftRng::from_seed([1, 2, 3, 4]));
+        assert_eq!(shoot(&mut character, &world, (0, 0), (5, 5), &mut rng), Ok(expected));
+        assert!(shoot(&mut character, &world, (0, 0), (5, 5), &mut rng).is_ok());
+        assert_eq!(shoot(&mut character, &world, (0, 0), (5, 5), &mut rng),
+                   Err(ShotError::NoAmmunition));
+    }
+
+    #[test]
+    fn range_and_line_of_sight() {
+        let mut world = World2d::new("Helm's Deep", (10, 10));
+        world.set_field(Field::new(FieldType::StoneWall), (3, 0));
+        let mut character = archer(5);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        assert_eq!(can_shoot(&character, &world, (0, 0), (9, 9)), Err(ShotError::OutOfRange));
+        assert_eq!(can_shoot(&character, &world, (9, 9), (15, 15)),
+                   Err(ShotError::OutsideWorld));
+        assert_eq!(can_shoot(&character, &world, (0, 0), (5, 0)),
+                   Err(ShotError::NoLineOfSight));
+        assert_eq!(can_shoot(&Character::new("Gimli"), &world, (0, 0), (1, 0)),
+                   Err(ShotError::NoRangedWeapon));
+
+        assert!(shoot(&mut character, &world, (0, 0), (5, 0), &mut rng).is_err());
+        for _ in 0..5 {
+            assert!(shoot(&mut character, &world, (0, 0), (0, 5), &mut rng).is_ok());
+        }
+        assert!(!character.inventory().contains_type(&ItemType::AmmoArrow));
+    }
+}
@@ -0,0 +1,166 @@
+use data::{self, DataError};
+use engine::EngineContext;
+use format::Format;
+use rand::{self, Rng, SeedableRng, XorShiftRng};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::path::Path;
+
+/// Mixed into the seed of every command, so the generator is never seeded with zeros only
+const SEED_SALT: u32 = 0x9e37_79b9;
+
+/// The commands of a session and the seed of its randomness. Every command gets its own random
+/// number generator derived from the seed, so replaying the commands on a new state leads to the
+/// same game, e.g. to reproduce a bug.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Replay {
+    seed: u32,
+    commands: Vec<String>,
+}
+
+impl Replay {
+    /// Creates a new instance of `Replay` with the given seed
+    pub fn new(seed: u32) -> Replay {
+        Replay {
+            seed,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Creates a new instance of `Replay` with a random seed
+    pub fn random() -> Replay {
+        Replay::new(rand::thread_rng().gen())
+    }
+
+    /// Returns the seed of the session
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Returns all recorded commands in the order they were given
+    pub fn commands(&self) -> &[String] {
+        &self.commands
+    }
+
+    /// Records a command and returns the random number generator the command has to use for
+    /// everything random it does
+    pub fn record(&mut self, command: &str) -> XorShiftRng {
+        self.commands.push(command.to_owned());
+        self.rng(self.commands.len() - 1)
+    }
+
+    /// Returns the random number generator of the command with the given index
+    pub fn rng(&self, index: usize) -> XorShiftRng {
+        XorShiftRng::from_seed([self.seed, index as u32, SEED_SALT, SEED_SALT])
+    }
+
+    /// Returns a copy of the replay without the last `commands` commands
+    pub fn truncated(&self, commands: usize) -> Replay {
+        let length = self.commands.len().saturating_sub(commands);
+
+        Replay {
+            seed: self.seed,
+            commands: self.commands[..length].to_vec(),
+        }
+    }
+
+    /// Plays all recorded commands on the given state, which should be the state the session
+    /// started with. The state records the commands again while they are played.
+    pub fn play<F>(&self, context: &mut EngineContext, mut step: F)
+        where F: FnMut(&mut EngineContext, &str, &mut XorShiftRng)
+    {
+        context.replay = Replay::new(self.seed);
+
+        for command in &self.commands {
+            let mut rng = context.replay.record(command);
+            step(context, command, &mut rng);
+        }
+    }
+
+    /// Reads a replay from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Replay, DataError> {
+        let object = data::as_object(json, "replay")?;
+
+        Ok(Replay {
+            seed: data::unsigned(object, "seed")? as u32,
+            commands: data::as_array(data::field(object, "commands")?, "commands")?
+                .iter()
+                .map(|command| data::parse_value(command, "commands"))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Reads a replay from a file in the given format, e.g. one attached to a bug report
+    pub fn read_file<P: AsRef<Path>>(path: P, format: Format) -> Result<Replay, DataError> {
+        Replay::from_json(&format.read_file(path)?)
+    }
+
+    /// Writes the replay into a file in the given format
+    pub fn write_file<P: AsRef<Path>>(&self, path: P, format: Format) -> Result<(), DataError> {
+        format.write_file(path, &self.to_json())
+    }
+}
+
+impl ToJson for Replay {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("seed".to_owned(), self.seed.to_json());
+        object.insert("commands".to_owned(), self.commands.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::GameEvent;
+    use engine::EngineContext;
+    use format::Format;
+    use rand::{Rng, XorShiftRng};
+    use std::env;
+
+    fn step(context: &mut EngineContext, command: &str, rng: &mut XorShiftRng) {
+        if command == "loot" {
+            context.publish(GameEvent::GoldEarned(rng.gen_range(1, 100)));
+        }
+    }
+
+    #[test]
+    fn deterministic_playback() {
+        let mut context = EngineContext::new();
+        context.replay = Replay::new(42);
+        for command in &["loot", "north", "loot", "loot"] {
+            let mut rng = context.replay.record(command);
+            step(&mut context, command, &mut rng);
+        }
+
+        let mut replayed = EngineContext::new();
+        context.replay.play(&mut replayed, step);
+
+        assert_eq!(replayed.statistics, context.statistics);
+        assert_eq!(replayed.replay, context.replay);
+        assert_ne!(Replay::new(43).rng(0).next_u32(), context.replay.rng(0).next_u32());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut replay = Replay::new(3);
+        replay.record("north");
+        replay.record("east");
+
+        assert_eq!(replay.truncated(1).commands(), &["north".to_owned()]);
+        assert!(replay.truncated(5).commands().is_empty());
+        assert_eq!(replay.truncated(0), replay);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let path = env::temp_dir().join("txtrpg_replay.json");
+        let mut replay = Replay::new(7);
+        replay.record("look");
+        replay.record("take sword");
+
+        replay.write_file(&path, Format::Json).unwrap();
+        assert_eq!(Replay::read_file(&path, Format::Json).unwrap(), replay);
+    }
+}
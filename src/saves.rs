@@ -0,0 +1,617 @@
+use bus::{GameEvent, Listener};
+use data::{self, DataError};
+use engine::EngineContext;
+use format::{self, Format};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The part of the file name separating the metadata of a slot from its state
+const METADATA_SUFFIX: &str = "meta";
+
+/// The extension appended to the path of a save for the backup of the previous save
+pub const BACKUP_EXTENSION: &str = "bak";
+
+/// The first line of an obfuscated save, followed by the obfuscated text in hexadecimal
+const OBFUSCATED_HEADER: &str = "txtrpg obfuscated save";
+
+/// The key obfuscated saves are XORed with
+#[cfg(feature = "obfuscation")]
+const OBFUSCATION_KEY: &[u8] = b"txtrpg-rs save";
+
+/// The prefix of the slots written by `Autosave`, followed by a running number
+pub const AUTOSAVE_PREFIX: &str = "autosave_";
+
+/// The details of a save shown in a load menu
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SaveMetadata {
+    /// The name of the slot
+    pub slot: String,
+    /// The name of the player character
+    pub character: String,
+    /// The level of the player character
+    pub level: u32,
+    /// The seconds played in total
+    pub playtime: u64,
+    /// The seconds since the Unix epoch at which the game was saved
+    pub timestamp: u64,
+}
+
+impl SaveMetadata {
+    /// Creates a new instance of `SaveMetadata` saved right now. The slot is set when saving.
+    pub fn new(character: &str, level: u32, playtime: u64) -> SaveMetadata {
+        SaveMetadata {
+            slot: String::new(),
+            character: character.to_owned(),
+            level,
+            playtime,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Reads the metadata from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<SaveMetadata, DataError> {
+        let object = data::as_object(json, "metadata")?;
+
+        Ok(SaveMetadata {
+            slot: data::string(object, "slot")?,
+            character: data::string(object, "character")?,
+            level: data::unsigned(object, "level")? as u32,
+            playtime: data::unsigned(object, "playtime")?,
+            timestamp: data::unsigned(object, "timestamp")?,
+        })
+    }
+}
+
+impl ToJson for SaveMetadata {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("slot".to_owned(), self.slot.to_json());
+        object.insert("character".to_owned(), self.character.to_json());
+        object.insert("level".to_owned(), self.level.to_json());
+        object.insert("playtime".to_owned(), self.playtime.to_json());
+        object.insert("timestamp".to_owned(), self.timestamp.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A directory of save slots. Every slot consists of the saved state and a small metadata file,
+/// so listing the slots does not read whole saves.
+pub struct SaveManager {
+    directory: PathBuf,
+    format: Format,
+}
+
+impl SaveManager {
+    /// Creates a new instance of `SaveManager` storing the slots in the given directory
+    pub fn new<P: AsRef<Path>>(directory: P, format: Format) -> SaveManager {
+        SaveManager {
+            directory: directory.as_ref().to_path_buf(),
+            format,
+        }
+    }
+
+    /// Returns the name of the slot with the given number
+    pub fn numbered(number: u32) -> String {
+        format!("slot{}", number)
+    }
+
+    /// Saves the state into the given slot, replacing an earlier save. Once a permadeath game is
+    /// over, the slot is locked or deleted as the state demands.
+    pub fn save(&self,
+                slot: &str,
+                context: &EngineContext,
+                metadata: &SaveMetadata)
+                -> Result<(), DataError> {
+        check_slot(slot)?;
+        fs::create_dir_all(&self.directory)?;
+
+        context.save_file(self.state_path(slot), self.format)?;
+        if context.game_over && context.delete_on_death {
+            return self.delete(slot);
+        }
+
+        let mut metadata = metadata.clone();
+        metadata.slot = slot.to_owned();
+        self.format.write_file(self.metadata_path(slot), &metadata.to_json())
+    }
+
+    /// Loads the state saved in the given slot. Fails with `DataError::Locked` if the game in the
+    /// slot is over.
+    pub fn load(&self, slot: &str) -> Result<EngineContext, DataError> {
+        check_slot(slot)?;
+
+        EngineContext::load_file(self.state_path(slot), self.format)
+    }
+
+    /// Returns the metadata of the given slot
+    pub fn metadata(&self, slot: &str) -> Result<SaveMetadata, DataError> {
+        check_slot(slot)?;
+
+        SaveMetadata::from_json(&self.format.read_file(self.metadata_path(slot))?)
+    }
+
+    /// Returns the metadata of all slots, the most recent save first
+    pub fn list(&self) -> Result<Vec<SaveMetadata>, DataError> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let suffix = format!(".{}.{}", METADATA_SUFFIX, self.format.extension());
+        let mut saves = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            if name.ends_with(&suffix) {
+                saves.push(self.metadata(&name[..name.len() - suffix.len()])?);
+            }
+        }
+
+        saves.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.slot.cmp(&b.slot)));
+        Ok(saves)
+    }
+
+    /// Returns `true` if something is saved in the given slot
+    pub fn exists(&self, slot: &str) -> bool {
+        check_slot(slot).is_ok() && self.state_path(slot).exists()
+    }
+
+    /// Deletes the given slot. Deleting an empty slot does nothing.
+    pub fn delete(&self, slot: &str) -> Result<(), DataError> {
+        check_slot(slot)?;
+
+        let state_path = self.state_path(slot);
+        for path in &[backup_path(&state_path), state_path, self.metadata_path(slot)] {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn state_path(&self, slot: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}", slot, self.format.extension()))
+    }
+
+    fn metadata_path(&self, slot: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}.{}", slot, METADATA_SUFFIX, self.format.extension()))
+    }
+}
+
+/// Saves the game automatically every few turns and when the player enters another world,
+/// keeping only the most recent autosaves. Entering a world is noticed by listening to
+/// `GameEvent::WorldEntered`.
+pub struct Autosave {
+    interval: Option<u64>,
+    on_world_change: bool,
+    keep: usize,
+    last_turn: u64,
+    pending: bool,
+}
+
+impl Autosave {
+    /// Creates a new instance of `Autosave` keeping the given amount of autosaves. It saves on
+    /// every world change, but not after a number of turns.
+    ///
+    /// # Panics
+    /// Panics if `keep` is `0`
+    pub fn new(keep: usize) -> Autosave {
+        assert!(keep > 0);
+        Autosave {
+            interval: None,
+            on_world_change: true,
+            keep,
+            last_turn: 0,
+            pending: false,
+        }
+    }
+
+    /// A builder method for saving every time the given amount of turns passed
+    ///
+    /// # Panics
+    /// Panics if `turns` is `0`
+    pub fn every(mut self, turns: u64) -> Autosave {
+        assert!(turns > 0);
+        self.interval = Some(turns);
+        self
+    }
+
+    /// A builder method for setting whether to save when the player enters another world
+    pub fn on_world_change(mut self, on_world_change: bool) -> Autosave {
+        self.on_world_change = on_world_change;
+        self
+    }
+
+    /// Returns `true` if an autosave is due at the given turn
+    pub fn is_due(&self, turn: u64) -> bool {
+        self.pending ||
+        self.interval.is_some_and(|interval| turn >= self.last_turn + interval)
+    }
+
+    /// Saves the game if an autosave is due at the given turn. Returns the slot written to.
+    pub fn update(&mut self,
+                  turn: u64,
+                  saves: &SaveManager,
+                  context: &EngineContext,
+                  metadata: &SaveMetadata)
+                  -> Result<Option<String>, DataError> {
+        if !self.is_due(turn) {
+            return Ok(None);
+        }
+
+        self.last_turn = turn;
+        self.pending = false;
+        self.save(saves, context, metadata).map(Some)
+    }
+
+    /// Saves the game into a new autosave slot and deletes the oldest autosaves beyond the ones
+    /// to keep. Returns the slot written to.
+    pub fn save(&self,
+                saves: &SaveManager,
+                context: &EngineContext,
+                metadata: &SaveMetadata)
+                -> Result<String, DataError> {
+        let mut numbers = Autosave::numbers(saves)?;
+        let slot = format!("{}{}", AUTOSAVE_PREFIX, numbers.last().map_or(0, |last| last + 1));
+        saves.save(&slot, context, metadata)?;
+
+        numbers.reverse();
+        for number in numbers.iter().skip(self.keep - 1) {
+            saves.delete(&format!("{}{}", AUTOSAVE_PREFIX, number))?;
+        }
+
+        Ok(slot)
+    }
+
+    /// Returns the metadata of all autosaves, the most recent first
+    pub fn list(saves: &SaveManager) -> Result<Vec<SaveMetadata>, DataError> {
+        Ok(saves.list()?
+            .into_iter()
+            .filter(|metadata| metadata.slot.starts_with(AUTOSAVE_PREFIX))
+            .collect())
+    }
+
+    /// Returns the running numbers of all autosaves in ascending order
+    fn numbers(saves: &SaveManager) -> Result<Vec<u64>, DataError> {
+        let mut numbers: Vec<u64> = Autosave::list(saves)?
+            .iter()
+            .filter_map(|metadata| metadata.slot[AUTOSAVE_PREFIX.len()..].parse().ok())
+            .collect();
+        numbers.sort();
+
+        Ok(numbers)
+    }
+}
+
+impl Listener for Autosave {
+    fn notify(&mut self, event: &GameEvent) {
+        if let GameEvent::WorldEntered(_) = *event {
+            self.pending = self.pending || self.on_world_change;
+        }
+    }
+}
+
+/// Returns the path of the backup kept for the save at the given path
+pub fn backup_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut backup = path.as_ref().as_os_str().to_owned();
+    backup.push(".");
+    backup.push(BACKUP_EXTENSION);
+
+    PathBuf::from(backup)
+}
+
+/// Writes a saved state together with its checksum. The file is replaced atomically and the
+/// previous save is kept as backup, if it was intact. With the `obfuscation` feature, the whole
+/// file is obfuscated, so it cannot be edited by hand.
+///
+/// The state is saved as the format reads it back, e.g. without the `null` values TOML cannot
+/// express, so the checksum matches when loading.
+pub fn write_save<P: AsRef<Path>>(path: P, format: Format, state: &Json) -> Result<(), DataError> {
+    let path = path.as_ref();
+    if read_save(path, format).is_ok() {
+        fs::copy(path, backup_path(path))?;
+    }
+
+    let state = format.parse(&format.write(state)?)?;
+    let mut object = Object::new();
+    object.insert("checksum".to_owned(), checksum(&state).to_json());
+    object.insert("state".to_owned(), state);
+
+    format::write_atomic(path, &obfuscate(format.write(&Json::Object(object))?))
+}
+
+/// Reads a saved state written by `write_save` and verifies its checksum. Fails with
+/// `DataError::Corrupt` if the state was changed after saving. Saves without a checksum are read
+/// as they are. Obfuscated saves fail with `DataError::Obfuscated` unless the `obfuscation`
+/// feature is enabled.
+pub fn read_save<P: AsRef<Path>>(path: P, format: Format) -> Result<Json, DataError> {
+    let path = path.as_ref();
+    let json = format.parse(&deobfuscate(fs::read_to_string(path)?, path)?)?;
+
+    let (checksum, state) = match json.as_object() {
+        Some(object) if object.contains_key("checksum") => {
+            (data::string(object, "checksum")?, data::field(object, "state")?)
+        }
+        _ => return Ok(json),
+    };
+
+    if checksum != self::checksum(state) {
+        return Err(DataError::Corrupt(path.display().to_string()));
+    }
+
+    Ok(state.clone())
+}
+
+/// Returns the checksum of a saved state as hex string
+fn checksum(state: &Json) -> String {
+    format!("{:016x}", data::checksum(state.to_string().as_bytes()))
+}
+
+/// XORs the bytes with the obfuscation key
+#[cfg(feature = "obfuscation")]
+fn xor(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().zip(OBFUSCATION_KEY.iter().cycle()).map(|(byte, key)| byte ^ key).collect()
+}
+
+/// Turns the text of a save into an obfuscated save
+#[cfg(feature = "obfuscation")]
+fn obfuscate(text: String) -> String {
+    let hex: String = xor(text.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("{}\n{}\n", OBFUSCATED_HEADER, hex)
+}
+
+#[cfg(not(feature = "obfuscation"))]
+fn obfuscate(text: String) -> String {
+    text
+}
+
+/// Returns the text of an obfuscated save. Other saves are returned as they are.
+#[cfg(feature = "obfuscation")]
+fn deobfuscate(text: String, path: &Path) -> Result<String, DataError> {
+    if !text.starts_with(OBFUSCATED_HEADER) {
+        return Ok(text);
+    }
+
+    let corrupt = || DataError::Corrupt(path.display().to_string());
+    let bytes = text[OBFUSCATED_HEADER.len()..]
+        .trim()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            String::from_utf8(pair.to_vec())
+                .ok()
+                .and_then(|pair| u8::from_str_radix(&pair, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(&corrupt)?;
+
+    String::from_utf8(xor(&bytes)).map_err(|_| corrupt())
+}
+
+#[cfg(not(feature = "obfuscation"))]
+fn deobfuscate(text: String, path: &Path) -> Result<String, DataError> {
+    if text.starts_with(OBFUSCATED_HEADER) {
+        Err(DataError::Obfuscated(path.display().to_string()))
+    } else {
+        Ok(text)
+    }
+}
+
+/// Makes sure the slot name can be used as a file name
+fn check_slot(slot: &str) -> Result<(), DataError> {
+    let valid = !slot.is_empty() &&
+                slot.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(DataError::Invalid("slot".to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{GameEvent, Listener};
+    use character::Character;
+    use data::DataError;
+    use engine::EngineContext;
+    use format::Format;
+    use injury::BodyPart;
+    use item_generator::ItemGenerator;
+    use std::env;
+    use std::fs;
+    use world::Teleport;
+    use world::two_dimensional::World2d;
+
+    fn manager(name: &str) -> SaveManager {
+        let directory = env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&directory);
+
+        SaveManager::new(directory, Format::Json)
+    }
+
+    #[test]
+    fn slots() {
+        let saves = manager("txtrpg_save_slots");
+        assert_eq!(saves.list().unwrap(), Vec::new());
+
+        let mut context = EngineContext::new();
+        context.notes.pin("town", (1, 2), "inn");
+        let mut older = SaveMetadata::new("Aragorn", 3, 600);
+        older.timestamp -= 100;
+
+        saves.save(&SaveManager::numbered(1), &context, &older).unwrap();
+        saves.save("quicksave", &EngineContext::new(), &SaveMetadata::new("Aragorn", 4, 900))
+            .unwrap();
+
+        let list = saves.list().unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].slot, "quicksave");
+        assert_eq!(list[1].level, 3);
+        assert_eq!(saves.load("slot1").unwrap().notes, context.notes);
+
+        saves.delete("slot1").unwrap();
+        assert!(!saves.exists("slot1"));
+        assert_eq!(saves.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn player_and_world() {
+        let saves = manager("txtrpg_save_player_and_world");
+        let mut player = Character::new("Aragorn");
+        player.inventory_mut().add_gold(12);
+        player.injure(BodyPart::Leg);
+        let mut world = World2d::new("Bree", (4, 4));
+        world.set_position((2, 3));
+        world.drop_item(ItemGenerator::new().name("Pipe").gen());
+
+        let mut context = EngineContext::new();
+        context.player = Some(player);
+        context.world = Some(world);
+        saves.save("slot", &context, &SaveMetadata::new("Aragorn", 1, 0)).unwrap();
+
+        let loaded = saves.load("slot").unwrap();
+        assert_eq!(loaded.player.to_json(), context.player.to_json());
+        assert_eq!(loaded.world.to_json(), context.world.to_json());
+        let world = loaded.world.unwrap();
+        assert_eq!(world.items_at((2, 3))[0].name, "Pipe");
+        assert_eq!(loaded.player.unwrap().inventory().gold(), 12);
+    }
+
+    #[test]
+    fn autosave_rotation() {
+        let saves = manager("txtrpg_autosave_rotation");
+        let context = EngineContext::new();
+        let metadata = SaveMetadata::new("Gimli", 5, 0);
+        let mut autosave = Autosave::new(2).every(10);
+
+        assert_eq!(autosave.update(5, &saves, &context, &metadata).unwrap(), None);
+        assert_eq!(autosave.update(10, &saves, &context, &metadata).unwrap(),
+                   Some("autosave_0".to_owned()));
+        assert_eq!(autosave.update(15, &saves, &context, &metadata).unwrap(), None);
+
+        autosave.notify(&GameEvent::WorldEntered("mines".to_owned()));
+        assert_eq!(autosave.update(16, &saves, &context, &metadata).unwrap(),
+                   Some("autosave_1".to_owned()));
+        assert_eq!(autosave.update(26, &saves, &context, &metadata).unwrap(),
+                   Some("autosave_2".to_owned()));
+
+        assert!(!saves.exists("autosave_0"));
+        assert!(saves.exists("autosave_1"));
+        assert_eq!(Autosave::list(&saves).unwrap().len(), 2);
+    }
+
+    fn round_trip(format: Format) {
+        let path = env::temp_dir().join(format!("txtrpg_round_trip.{}", format.extension()));
+        let _ = fs::remove_file(&path);
+        let mut context = EngineContext::new();
+        context.notes.pin("bree", (4, 2), "prancing pony");
+        context.undo_limit = 3;
+
+        context.save_file(&path, format).unwrap();
+        let loaded = EngineContext::load_file(&path, format).unwrap();
+        assert_eq!(loaded.notes, context.notes);
+        assert_eq!(loaded.undo_limit, 3);
+        assert_eq!(loaded.progress, None);
+    }
+
+    #[test]
+    fn ron_round_trip() {
+        round_trip(Format::Ron);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        round_trip(Format::Toml);
+    }
+
+    #[test]
+    #[cfg(not(feature = "obfuscation"))]
+    fn corrupt_save() {
+        let saves = manager("txtrpg_corrupt_save");
+        let mut context = EngineContext::new();
+        let metadata = SaveMetadata::new("Frodo", 1, 0);
+
+        saves.save("slot", &context, &metadata).unwrap();
+        context.notes.pin("shire", (0, 0), "home");
+        saves.save("slot", &context, &metadata).unwrap();
+
+        let path = saves.state_path("slot");
+        let text = fs::read_to_string(&path).unwrap().replace("home", "h0me");
+        fs::write(&path, text).unwrap();
+
+        match saves.load("slot") {
+            Err(DataError::Corrupt(file)) => assert_eq!(file, path.display().to_string()),
+            _ => panic!("expected a corrupt save"),
+        }
+        let backup = EngineContext::load_file(backup_path(&path), Format::Json).unwrap();
+        assert!(backup.notes.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "obfuscation")]
+    fn obfuscated_save() {
+        let saves = manager("txtrpg_obfuscated_save");
+        let mut context = EngineContext::new();
+        context.notes.pin("shire", (0, 0), "home");
+        saves.save("slot", &context, &SaveMetadata::new("Frodo", 1, 0)).unwrap();
+
+        let path = saves.state_path("slot");
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with(OBFUSCATED_HEADER));
+        assert!(!text.contains("home"));
+        assert_eq!(saves.load("slot").unwrap().notes.at("shire", (0, 0)).len(), 1);
+
+        let text = deobfuscate(text, &path).unwrap().replace("home", "h0me");
+        fs::write(&path, obfuscate(text)).unwrap();
+        match saves.load("slot") {
+            Err(DataError::Corrupt(_)) => {}
+            _ => panic!("expected a corrupt save"),
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "obfuscation"))]
+    fn obfuscated_save_without_feature() {
+        let path = env::temp_dir().join("txtrpg_obfuscated_save_without_feature.json");
+        fs::write(&path, format!("{}\n00\n", OBFUSCATED_HEADER)).unwrap();
+
+        match read_save(&path, Format::Json) {
+            Err(DataError::Obfuscated(file)) => assert_eq!(file, path.display().to_string()),
+            _ => panic!("expected an obfuscated save"),
+        }
+    }
+
+    #[test]
+    fn invalid_slot() {
+        let saves = manager("txtrpg_invalid_slot");
+
+        match saves.load("../escape") {
+            Err(DataError::Invalid(field)) => assert_eq!(field, "slot"),
+            _ => panic!("expected an invalid slot"),
+        }
+    }
+
+    #[test]
+    fn permadeath() {
+        let saves = manager("txtrpg_permadeath_slots");
+
+        let mut context = EngineContext::new();
+        context.permadeath = true;
+        context.delete_on_death = true;
+        saves.save("hardcore", &context, &SaveMetadata::new("Boromir", 9, 60)).unwrap();
+        assert!(saves.exists("hardcore"));
+
+        context.publish(GameEvent::CharacterDied);
+        saves.save("hardcore", &context, &SaveMetadata::new("Boromir", 9, 70)).unwrap();
+        assert!(!saves.exists("hardcore"));
+        assert!(saves.list().unwrap().is_empty());
+    }
+}
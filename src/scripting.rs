@@ -0,0 +1,190 @@
+use bus::{GameEvent, Listener};
+use character::Character;
+use rustc_serialize::json::{Json, Object, ToJson};
+use world::World;
+use world::two_dimensional::World2d;
+
+/// An embedded scripting language, e.g. a binding to Lua or rhai. This crate does not bundle an
+/// interpreter; the binding exposes the variables of the scope to the script and writes changed
+/// variables back.
+pub trait ScriptEngine {
+    /// Runs the script with the given variables. Fails with a message of the interpreter.
+    fn run(&mut self, source: &str, scope: &mut ScriptScope) -> Result<(), String>;
+}
+
+/// The part of the game state a script can read and change
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ScriptScope {
+    variables: Object,
+}
+
+impl ScriptScope {
+    /// Creates a new, empty instance of `ScriptScope`
+    pub fn new() -> ScriptScope {
+        ScriptScope::default()
+    }
+
+    /// A builder method for exposing the character as `character`
+    pub fn character(mut self, character: &Character) -> ScriptScope {
+        let mut object = Object::new();
+        object.insert("name".to_owned(), character.name().to_json());
+        object.insert("health".to_owned(), character.health().to_json());
+        object.insert("max_health".to_owned(), character.max_health().to_json());
+        object.insert("level".to_owned(), character.level().to_json());
+        object.insert("experience".to_owned(), character.experience().to_json());
+
+        self.set("character", Json::Object(object));
+        self
+    }
+
+    /// A builder method for exposing the world the player is in as `level`
+    pub fn level(mut self, world: &World2d) -> ScriptScope {
+        let (x, y) = world.get_position();
+        let mut object = Object::new();
+        object.insert("name".to_owned(), world.name.to_json());
+        object.insert("position".to_owned(), vec![x, y].to_json());
+
+        self.set("level", Json::Object(object));
+        self
+    }
+
+    /// Returns the variable with the given name
+    pub fn get(&self, name: &str) -> Option<&Json> {
+        self.variables.get(name)
+    }
+
+    /// Sets the variable with the given name
+    pub fn set(&mut self, name: &str, value: Json) {
+        self.variables.insert(name.to_owned(), value);
+    }
+
+    /// Returns all variables
+    pub fn variables(&self) -> &Object {
+        &self.variables
+    }
+}
+
+/// Scripts run when certain game events are published, e.g. quest logic of a campaign
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Scripts {
+    handlers: Vec<(String, String)>,
+    pending: Vec<(String, GameEvent)>,
+}
+
+impl Scripts {
+    /// Creates a new instance of `Scripts` without any handlers
+    pub fn new() -> Scripts {
+        Scripts::default()
+    }
+
+    /// Runs the script whenever an event of the given kind, e.g. `MonsterKilled`, is published
+    pub fn on(&mut self, event: &str, source: &str) {
+        self.handlers.push((event.to_owned(), source.to_owned()));
+    }
+
+    /// Returns `true` if events are waiting for their scripts
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Runs the scripts of all events published since the last call. The event is exposed as
+    /// `event`. Returns the amount of scripts that were run.
+    pub fn run_pending(&mut self,
+                       engine: &mut dyn ScriptEngine,
+                       scope: &mut ScriptScope)
+                       -> Result<usize, String> {
+        let pending: Vec<(String, GameEvent)> = self.pending.drain(..).collect();
+
+        for (source, event) in &pending {
+            scope.set("event", event_json(event));
+            engine.run(source, scope)?;
+        }
+
+        Ok(pending.len())
+    }
+}
+
+impl Listener for Scripts {
+    fn notify(&mut self, event: &GameEvent) {
+        let kind = event.kind();
+
+        for (handled, source) in &self.handlers {
+            if *handled == kind {
+                self.pending.push((source.clone(), event.clone()));
+            }
+        }
+    }
+}
+
+/// Returns the event as an object like `{"type": "GoldEarned", "value": 20}`
+fn event_json(event: &GameEvent) -> Json {
+    let value = match *event {
+        GameEvent::MonsterKilled(ref name) |
+        GameEvent::ItemCrafted(ref name) |
+        GameEvent::ItemFound(ref name) |
+        GameEvent::CampaignStarted(ref name) |
+        GameEvent::CampaignFinished(ref name) |
+        GameEvent::WorldEntered(ref name) => name.to_json(),
+        GameEvent::DamageDealt(damage) |
+        GameEvent::DamageTaken(damage) => damage.to_json(),
+        GameEvent::GoldEarned(gold) => gold.to_json(),
+        GameEvent::LevelReached(level) => level.to_json(),
+        GameEvent::CharacterDied | GameEvent::Walked | GameEvent::DoorOpened => Json::Null,
+    };
+
+    let mut object = Object::new();
+    object.insert("type".to_owned(), event.kind().to_json());
+    object.insert("value".to_owned(), value);
+
+    Json::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{GameEvent, Listener};
+    use character::Character;
+    use rustc_serialize::json::{Json, ToJson};
+
+    /// Understands a single statement: `set <variable> <value of the event>`
+    struct SetEngine;
+
+    impl ScriptEngine for SetEngine {
+        fn run(&mut self, source: &str, scope: &mut ScriptScope) -> Result<(), String> {
+            let mut words = source.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some("set"), Some(name)) => {
+                    let value = scope.get("event")
+                        .and_then(|event| event.find("value"))
+                        .cloned()
+                        .unwrap_or(Json::Null);
+                    scope.set(name, value);
+                    Ok(())
+                }
+                _ => Err(format!("unknown statement `{}`", source)),
+            }
+        }
+    }
+
+    #[test]
+    fn run_on_events() {
+        let mut scripts = Scripts::new();
+        scripts.on("MonsterKilled", "set last_kill");
+        scripts.on("Walked", "jump");
+
+        scripts.notify(&GameEvent::GoldEarned(5));
+        scripts.notify(&GameEvent::MonsterKilled("dragon".to_owned()));
+        assert!(scripts.has_pending());
+
+        let mut scope = ScriptScope::new().character(&Character::new("Siegfried"));
+        assert_eq!(scripts.run_pending(&mut SetEngine, &mut scope), Ok(1));
+        assert_eq!(scope.get("last_kill"), Some(&"dragon".to_json()));
+        assert_eq!(scope.get("character").and_then(|character| character.find("name")),
+                   Some(&"Siegfried".to_json()));
+
+        scripts.notify(&GameEvent::Walked);
+        assert_eq!(scripts.run_pending(&mut SetEngine, &mut scope),
+                   Err("unknown statement `jump`".to_owned()));
+        assert!(!scripts.has_pending());
+    }
+}
@@ -0,0 +1,229 @@
+use character::{Attribute, Character, EquipmentSlot};
+use ui::Bar;
+
+/// The characters and widths a character sheet is drawn with
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SheetTheme {
+    /// The width of the column holding the labels
+    pub label_width: usize,
+    /// The width of the lines under the headings
+    pub width: usize,
+    /// The character the lines under the headings are drawn with
+    pub rule: char,
+    /// The bar showing the health
+    pub health_bar: Bar,
+    /// The bar showing the stamina
+    pub stamina_bar: Bar,
+    /// The bar showing the experience towards the next level
+    pub experience_bar: Bar,
+}
+
+impl SheetTheme {
+    /// Returns a theme using only ASCII characters and no colors, for terminals without unicode
+    /// or color support
+    pub fn plain() -> SheetTheme {
+        let plain = |bar: Bar| bar.chars('#', '.').color(None);
+        let theme = SheetTheme::default();
+
+        SheetTheme {
+            rule: '-',
+            health_bar: plain(theme.health_bar.clone()),
+            stamina_bar: plain(theme.stamina_bar.clone()),
+            experience_bar: plain(theme.experience_bar.clone()),
+            ..theme
+        }
+    }
+}
+
+impl Default for SheetTheme {
+    fn default() -> SheetTheme {
+        SheetTheme {
+            label_width: 14,
+            width: 40,
+            rule: '─',
+            health_bar: Bar::health().width(20),
+            stamina_bar: Bar::stamina().width(20),
+            experience_bar: Bar::experience().width(20),
+        }
+    }
+}
+
+/// Renders characters as aligned text for the terminal
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CharacterSheet {
+    theme: SheetTheme,
+}
+
+impl CharacterSheet {
+    /// Creates a new instance of `CharacterSheet` using the default theme
+    pub fn new() -> CharacterSheet {
+        CharacterSheet::default()
+    }
+
+    /// A builder method for setting the theme
+    pub fn theme(mut self, theme: SheetTheme) -> CharacterSheet {
+        self.theme = theme;
+        self
+    }
+
+    /// Renders the stats, attributes, equipment and active effects of the character
+    pub fn render(&self, character: &Character) -> String {
+        let mut sheet = String::new();
+
+        self.heading(&mut sheet, character.name());
+        self.line(&mut sheet, "Level", &character.level().to_string());
+        self.line(&mut sheet, "Experience", &self.experience_bar(character));
+        self.line(&mut sheet,
+                  "Health",
+                  &self.theme
+                      .health_bar
+                      .render(character.health() as u64, character.max_health() as u64));
+        self.line(&mut sheet,
+                  "Stamina",
+                  &self.theme
+                      .stamina_bar
+                      .render(character.stamina() as u64, character.max_stamina() as u64));
+        self.line(&mut sheet, "Attack", &character.attack_damage().to_string());
+        self.line(&mut sheet,
+                  "Defense",
+                  &character.equipped_attribute_value(&Attribute::Defense).to_string());
+
+        sheet.push('\n');
+        self.heading(&mut sheet, "Attributes");
+        for attribute in Attribute::all() {
+            let value = character.get_attribute_value(&attribute);
+            let equipped = character.equipped_attribute_value(&attribute);
+            let text = match equipped - value {
+                0 => value.to_string(),
+                bonus => format!("{} ({:+})", equipped, bonus),
+            };
+            self.line(&mut sheet, &format!("{:?}", attribute), &text);
+        }
+
+        sheet.push('\n');
+        self.heading(&mut sheet, "Equipment");
+        for &(slot, label) in &SLOTS {
+            let text = character.equipped(slot).map_or("-".to_owned(), |item| item.to_string());
+            self.line(&mut sheet, label, &text);
+        }
+
+        sheet.push('\n');
+        self.heading(&mut sheet, "Effects");
+        let effects = self.effects(character);
+        if effects.is_empty() {
+            sheet.push_str("none\n");
+        }
+        for (label, text) in effects {
+            self.line(&mut sheet, &label, &text);
+        }
+
+        sheet
+    }
+
+    /// Renders the experience gained since the last level up towards the next level, e.g.
+    /// `[█████░░░░░] 150/300 XP`
+    pub fn experience_bar(&self, character: &Character) -> String {
+        let level = character.level();
+        let start = Character::experience_for_level(level);
+        let end = Character::experience_for_level(level + 1);
+
+        self.theme.experience_bar.render(character.experience() - start, end - start)
+    }
+
+    fn effects(&self, character: &Character) -> Vec<(String, String)> {
+        let mut effects: Vec<(String, String)> = character.conditions()
+            .iter()
+            .map(|condition| {
+                (format!("{:?}", condition.kind), format!("{} turns", condition.turns_left))
+            })
+            .collect();
+
+        effects.extend(character.injuries().iter().map(|injury| {
+            (format!("{:?} wound", injury.body_part), format!("{} turns", injury.turns_left))
+        }));
+
+        if character.is_sneaking() {
+            effects.push(("Sneaking".to_owned(), String::new()));
+        }
+
+        effects
+    }
+
+    fn heading(&self, sheet: &mut String, title: &str) {
+        sheet.push_str(title);
+        sheet.push('\n');
+        sheet.extend((0..self.theme.width).map(|_| self.theme.rule));
+        sheet.push('\n');
+    }
+
+    fn line(&self, sheet: &mut String, label: &str, text: &str) {
+        let line = format!("{:<width$}{}", label, text, width = self.theme.label_width);
+        sheet.push_str(line.trim_end());
+        sheet.push('\n');
+    }
+}
+
+const SLOTS: [(EquipmentSlot, &str); 6] = [(EquipmentSlot::Head, "Head"),
+                                           (EquipmentSlot::Chest, "Chest"),
+                                           (EquipmentSlot::Legs, "Legs"),
+                                           (EquipmentSlot::Feet, "Feet"),
+                                           (EquipmentSlot::LeftHand, "Left hand"),
+                                           (EquipmentSlot::RightHand, "Right hand")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use condition::Condition;
+    use injury::{BodyPart, Injury};
+    use item::{ItemInfluence, ItemType};
+    use item_generator::ItemGenerator;
+
+    #[test]
+    fn render() {
+        let mut character = Character::new("Michael");
+        let sword = ItemGenerator::new()
+            .name("Anduril")
+            .item_type(ItemType::WeaponSword)
+            .influence(Some(ItemInfluence::new(Attribute::Strength, 5)))
+            .sockets(0)
+            .gen();
+        character.set_weapon_slot_right(Some(sword));
+
+        let sheet = CharacterSheet::new().render(&character);
+        assert!(sheet.starts_with("Michael\n"));
+        assert!(sheet.contains("\nLevel         1\n"));
+        assert!(sheet.contains("\nStrength      25 (+5)\n"));
+        assert!(sheet.contains("\nHead          -\n"));
+        assert!(sheet.contains("\nRight hand    Anduril (+5 Strength)\n"));
+        assert!(sheet.ends_with(&format!("\nEffects\n{}\nnone\n", "─".repeat(40))));
+    }
+
+    #[test]
+    fn effects() {
+        let mut character = Character::new("Michael");
+        character.afflict(Condition::poison());
+        character.injure(BodyPart::Leg);
+        character.set_sneaking(true);
+        let sheet = CharacterSheet::new().render(&character);
+
+        let turns = Injury::new(BodyPart::Leg).turns_left;
+        assert!(sheet.contains("\nPoison        20 turns\n"));
+        assert!(sheet.contains(&format!("\nLeg wound     {} turns\n", turns)));
+        assert!(sheet.ends_with("\nSneaking\n"));
+    }
+
+    #[test]
+    fn experience_bar() {
+        let mut character = Character::new("Michael");
+        character.gain_experience(250);
+        let plain = SheetTheme::plain();
+        let theme = SheetTheme {
+            experience_bar: plain.experience_bar.clone().width(10),
+            ..plain
+        };
+
+        let sheet = CharacterSheet::new().theme(theme);
+        assert_eq!(sheet.experience_bar(&character), "[#####.....] 150/300 XP");
+    }
+}
@@ -0,0 +1,361 @@
+use data::{self, DataError};
+use faction::{Reputation, Standing};
+use inventory::Inventory;
+use item::Item;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::path::Path;
+use time::{Tick, WorldClock};
+use types::Gold;
+use world::progress::CampaignProgress;
+
+/// The default amount of turns between two restocks of a shop
+pub const DEFAULT_RESTOCK_INTERVAL: u64 = 1440;
+
+/// The reasons why an item can not be bought
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShopError {
+    /// The faction of the shop is hostile towards the player
+    Refused,
+    /// There is no offer with the given index
+    Unknown,
+    /// The offer is sold out until the next restock
+    SoldOut,
+    /// The character can not pay the price
+    NotEnoughGold,
+    /// The inventory of the character is full
+    InventoryFull,
+}
+
+/// Returns the multiplier for the prices of a shop whose faction treats the player the given way
+pub fn price_factor(standing: Standing) -> f64 {
+    match standing {
+        Standing::Hostile | Standing::Unfriendly => 1.25,
+        Standing::Neutral => 1.0,
+        Standing::Friendly => 0.9,
+        Standing::Allied => 0.75,
+    }
+}
+
+/// An item a shop sells
+#[derive(Clone, PartialEq, Debug)]
+pub struct StockEntry {
+    /// The item sold
+    pub item: Item,
+    /// The price before the reputation is taken into account
+    pub price: Gold,
+    /// How many of the item the shop has after restocking
+    pub amount: u32,
+    /// The standing with the faction of the shop needed to see the item
+    pub standing: Standing,
+    /// The quest flag needed to see the item (optional)
+    pub flag: Option<String>,
+}
+
+impl StockEntry {
+    /// Creates a new instance of `StockEntry` offered to everyone who is not hostile
+    pub fn new(item: Item, price: Gold, amount: u32) -> StockEntry {
+        StockEntry {
+            item,
+            price,
+            amount,
+            standing: Standing::Hostile,
+            flag: None,
+        }
+    }
+
+    /// A builder method for only offering the item at the standing or better
+    pub fn standing(mut self, standing: Standing) -> StockEntry {
+        self.standing = standing;
+        self
+    }
+
+    /// A builder method for only offering the item once the quest flag is set
+    pub fn flag(mut self, flag: &str) -> StockEntry {
+        self.flag = Some(flag.to_owned());
+        self
+    }
+
+    /// Reads an entry from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<StockEntry, DataError> {
+        let object = data::as_object(json, "stock")?;
+
+        Ok(StockEntry {
+            item: Item::from_json(data::field(object, "item")?)?,
+            price: data::unsigned(object, "price")? as Gold,
+            amount: data::unsigned(object, "amount")? as u32,
+            standing: data::optional(object, "standing", data::parse)?
+                .unwrap_or(Standing::Hostile),
+            flag: data::optional(object, "flag", data::string)?,
+        })
+    }
+}
+
+impl ToJson for StockEntry {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("item".to_owned(), self.item.to_json());
+        object.insert("price".to_owned(), self.price.to_json());
+        object.insert("amount".to_owned(), self.amount.to_json());
+        object.insert("standing".to_owned(), format!("{:?}", self.standing).to_json());
+        if let Some(ref flag) = self.flag {
+            object.insert("flag".to_owned(), flag.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// An item offered to the player with the price they pay
+#[derive(Clone, PartialEq, Debug)]
+pub struct Offer<'a> {
+    /// The item offered
+    pub item: &'a Item,
+    /// The price the player pays
+    pub price: Gold,
+    /// How many of the item are left
+    pub available: u32,
+}
+
+/// A shop whose stock and prices depend on the reputation with its faction and on the quest
+/// flags. Sold items come back when the shop restocks, which it does as time passes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Shop {
+    /// The name of the shop
+    pub name: String,
+    /// The faction the shop belongs to (optional)
+    pub faction: Option<String>,
+    /// The items the shop sells
+    pub entries: Vec<StockEntry>,
+    /// The amount of turns between two restocks
+    pub restock_interval: u64,
+    stock: Vec<u32>,
+    restocked_at: u64,
+}
+
+impl Shop {
+    /// Creates a new instance of `Shop` without any items
+    pub fn new(name: &str) -> Shop {
+        Shop {
+            name: name.to_owned(),
+            faction: None,
+            entries: Vec::new(),
+            restock_interval: DEFAULT_RESTOCK_INTERVAL,
+            stock: Vec::new(),
+            restocked_at: 0,
+        }
+    }
+
+    /// A builder method for setting the faction of the shop
+    pub fn faction(mut self, faction: &str) -> Shop {
+        self.faction = Some(faction.to_owned());
+        self
+    }
+
+    /// A builder method for adding an item to the stock
+    pub fn entry(mut self, entry: StockEntry) -> Shop {
+        self.stock.push(entry.amount);
+        self.entries.push(entry);
+        self
+    }
+
+    /// A builder method for setting the amount of turns between two restocks
+    pub fn restock_interval(mut self, turns: u64) -> Shop {
+        self.restock_interval = turns;
+        self
+    }
+
+    /// Returns how the faction of the shop treats the player. Shops without faction are
+    /// neutral.
+    pub fn standing(&self, reputation: &Reputation) -> Standing {
+        self.faction.as_ref().map_or(Standing::Neutral, |faction| reputation.standing(faction))
+    }
+
+    /// Returns the items offered to the player, cheaper for allies and more expensive for
+    /// unfriendly players. Hostile players are refused.
+    pub fn offers(&self,
+                  reputation: &Reputation,
+                  progress: &CampaignProgress)
+                  -> Result<Vec<Offer<'_>>, ShopError> {
+        let factor = price_factor(self.standing(reputation));
+
+        Ok(self.offered(reputation, progress)?
+            .into_iter()
+            .map(|index| {
+                Offer {
+                    item: &self.entries[index].item,
+                    price: (self.entries[index].price as f64 * factor).round() as Gold,
+                    available: self.stock[index],
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the indices of the entries offered to the player
+    fn offered(&self,
+               reputation: &Reputation,
+               progress: &CampaignProgress)
+               -> Result<Vec<usize>, ShopError> {
+        let standing = self.standing(reputation);
+        if standing == Standing::Hostile {
+            return Err(ShopError::Refused);
+        }
+
+        Ok((0..self.entries.len())
+            .filter(|&index| {
+                let entry = &self.entries[index];
+                standing >= entry.standing &&
+                entry.flag.as_ref().is_none_or(|flag| progress.has_flag(flag))
+            })
+            .collect())
+    }
+
+    /// Buys the offer with the given index among the `offers`, paying from the inventory. The
+    /// bought item gets a new id created by the rng.
+    pub fn buy<R: Rng>(&mut self,
+                       index: usize,
+                       reputation: &Reputation,
+                       progress: &CampaignProgress,
+                       inventory: &mut Inventory,
+                       rng: &mut R)
+                       -> Result<Gold, ShopError> {
+        let entry = *self.offered(reputation, progress)?.get(index).ok_or(ShopError::Unknown)?;
+        let price = self.offers(reputation, progress)?[index].price;
+
+        if self.stock[entry] == 0 {
+            return Err(ShopError::SoldOut);
+        }
+        if inventory.is_full() {
+            return Err(ShopError::InventoryFull);
+        }
+        if !inventory.spend_gold(price) {
+            return Err(ShopError::NotEnoughGold);
+        }
+
+        self.stock[entry] -= 1;
+        inventory.add_item(self.entries[entry].item.renewed(rng))
+            .expect("Unable to add bought item");
+        Ok(price)
+    }
+
+    /// Refills the stock of every item
+    pub fn restock(&mut self) {
+        self.stock = self.entries.iter().map(|entry| entry.amount).collect();
+    }
+
+    /// Reads a shop from a JSON object as written by `to_json`. `faction` and `restock_interval`
+    /// are optional; the shop starts fully stocked.
+    pub fn from_json(json: &Json) -> Result<Shop, DataError> {
+        let object = data::as_object(json, "shop")?;
+
+        let mut shop = Shop::new(&data::string(object, "name")?);
+        shop.faction = data::optional(object, "faction", data::string)?;
+        shop.restock_interval = data::optional(object, "restock_interval", data::unsigned)?
+            .unwrap_or(DEFAULT_RESTOCK_INTERVAL);
+        for entry in data::as_array(data::field(object, "entries")?, "entries")? {
+            shop = shop.entry(StockEntry::from_json(entry)?);
+        }
+
+        Ok(shop)
+    }
+
+    /// Reads a shop from a JSON file
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Shop, DataError> {
+        Shop::from_json(&data::read_json(path)?)
+    }
+}
+
+impl Tick for Shop {
+    fn tick(&mut self, clock: &WorldClock) {
+        if clock.turn() >= self.restocked_at + self.restock_interval {
+            self.restock();
+            self.restocked_at = clock.turn();
+        }
+    }
+}
+
+impl ToJson for Shop {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("name".to_owned(), self.name.to_json());
+        if let Some(ref faction) = self.faction {
+            object.insert("faction".to_owned(), faction.to_json());
+        }
+        object.insert("entries".to_owned(), self.entries.to_json());
+        object.insert("restock_interval".to_owned(), self.restock_interval.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use faction::{Reputation, Standing};
+    use inventory::Inventory;
+    use item_generator::ItemGenerator;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::ToJson;
+    use time::WorldClock;
+    use world::progress::CampaignProgress;
+
+    fn smithy() -> Shop {
+        Shop::new("Smithy")
+            .faction("Riverwood")
+            .restock_interval(10)
+            .entry(StockEntry::new(ItemGenerator::new().name("Dagger").gen(), 20, 1))
+            .entry(StockEntry::new(ItemGenerator::new().name("Longsword").gen(), 100, 1)
+                .standing(Standing::Allied))
+            .entry(StockEntry::new(ItemGenerator::new().name("Dragonbane").gen(), 500, 1)
+                .flag("dragon_slain"))
+    }
+
+    #[test]
+    fn reputation() {
+        let shop = smithy();
+        let mut progress = CampaignProgress::new("village");
+        let mut reputation = Reputation::new();
+
+        let offers = shop.offers(&reputation, &progress).unwrap();
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].price, 20);
+
+        reputation.change("Riverwood", 80);
+        progress.set_flag("dragon_slain");
+        let offers = shop.offers(&reputation, &progress).unwrap();
+        assert_eq!(offers.len(), 3);
+        assert_eq!(offers[1].price, 75);
+
+        reputation.change("Riverwood", -200);
+        assert_eq!(shop.offers(&reputation, &progress), Err(ShopError::Refused));
+    }
+
+    #[test]
+    fn buy_and_restock() {
+        let mut shop = smithy();
+        let progress = CampaignProgress::new("village");
+        let reputation = Reputation::new();
+        let mut inventory = Inventory::new(5);
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        assert_eq!(shop.buy(0, &reputation, &progress, &mut inventory, &mut rng),
+                   Err(ShopError::NotEnoughGold));
+        inventory.add_gold(30);
+        assert_eq!(shop.buy(0, &reputation, &progress, &mut inventory, &mut rng), Ok(20));
+        assert_eq!(inventory.count_by_name("Dagger"), 1);
+        assert_eq!(shop.buy(0, &reputation, &progress, &mut inventory, &mut rng),
+                   Err(ShopError::SoldOut));
+
+        let mut clock = WorldClock::new();
+        for _ in 0..10 {
+            clock.advance_with(&mut [&mut shop]);
+        }
+        assert_eq!(shop.offers(&reputation, &progress).unwrap()[0].available, 1);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let shop = smithy();
+        assert_eq!(Shop::from_json(&shop.to_json()).unwrap(), shop);
+    }
+}
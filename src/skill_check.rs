@@ -0,0 +1,123 @@
+use dice::Dice;
+use rand::Rng;
+use types::AttributeValue;
+
+/// A check most characters pass without trouble
+pub const DIFFICULTY_EASY: AttributeValue = 10;
+/// A check a trained character passes most of the time
+pub const DIFFICULTY_MEDIUM: AttributeValue = 15;
+/// A check only skilled characters pass regularly
+pub const DIFFICULTY_HARD: AttributeValue = 20;
+/// A check that is almost impossible to pass
+pub const DIFFICULTY_HEROIC: AttributeValue = 25;
+
+/// The amount of attribute points that grant a `+1` on the roll
+const ATTRIBUTE_POINTS_PER_BONUS: AttributeValue = 5;
+
+/// The amount of luck points that widen the critical ranges by one
+const LUCK_POINTS_PER_CRITICAL: AttributeValue = 10;
+
+/// The maximum amount the critical ranges can be widened by luck
+const MAX_CRITICAL_WIDENING: AttributeValue = 4;
+
+/// The outcome of a skill check
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CheckOutcome {
+    /// The check succeeded exceptionally well
+    CriticalSuccess,
+    /// The check succeeded
+    Success,
+    /// The check failed
+    Failure,
+    /// The check failed horribly
+    CriticalFailure,
+}
+
+impl CheckOutcome {
+    /// Returns `true` if the check succeeded
+    pub fn is_success(&self) -> bool {
+        *self == CheckOutcome::CriticalSuccess || *self == CheckOutcome::Success
+    }
+
+    /// Returns `true` if the outcome is critical
+    pub fn is_critical(&self) -> bool {
+        *self == CheckOutcome::CriticalSuccess || *self == CheckOutcome::CriticalFailure
+    }
+}
+
+/// Resolves a check using the given natural `d20` roll. Good luck widens the range of critical
+/// successes, bad luck widens the range of critical failures.
+pub fn resolve(roll: AttributeValue,
+               attribute_value: AttributeValue,
+               luck: AttributeValue,
+               difficulty: AttributeValue)
+               -> CheckOutcome {
+    let widening = luck / LUCK_POINTS_PER_CRITICAL;
+    let widening = widening.clamp(-MAX_CRITICAL_WIDENING, MAX_CRITICAL_WIDENING);
+
+    let critical_success = 20 - if widening > 0 { widening } else { 0 };
+    let critical_failure = 1 + if widening < 0 { -widening } else { 0 };
+
+    if roll >= critical_success {
+        return CheckOutcome::CriticalSuccess;
+    }
+
+    if roll <= critical_failure {
+        return CheckOutcome::CriticalFailure;
+    }
+
+    if roll + attribute_value / ATTRIBUTE_POINTS_PER_BONUS >= difficulty {
+        CheckOutcome::Success
+    } else {
+        CheckOutcome::Failure
+    }
+}
+
+/// Rolls a `d20` and resolves a check against the given difficulty
+pub fn roll<R: Rng>(attribute_value: AttributeValue,
+                    luck: AttributeValue,
+                    difficulty: AttributeValue,
+                    rng: &mut R)
+                    -> CheckOutcome {
+    let natural = Dice::new(1, 20).roll(rng);
+
+    resolve(natural, attribute_value, luck, difficulty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_rolls() {
+        assert_eq!(resolve(20, 0, 0, 100), CheckOutcome::CriticalSuccess);
+        assert_eq!(resolve(1, 100, 0, 0), CheckOutcome::CriticalFailure);
+    }
+
+    #[test]
+    fn attribute_bonus() {
+        assert_eq!(resolve(10, 0, 0, DIFFICULTY_MEDIUM), CheckOutcome::Failure);
+        assert_eq!(resolve(10, 25, 0, DIFFICULTY_MEDIUM), CheckOutcome::Success);
+    }
+
+    #[test]
+    fn luck() {
+        assert_eq!(resolve(17, 0, 30, DIFFICULTY_HEROIC),
+                   CheckOutcome::CriticalSuccess);
+        assert_eq!(resolve(17, 0, 0, DIFFICULTY_HEROIC), CheckOutcome::Failure);
+
+        assert_eq!(resolve(3, 100, -20, DIFFICULTY_EASY),
+                   CheckOutcome::CriticalFailure);
+        assert_eq!(resolve(3, 100, 0, DIFFICULTY_EASY), CheckOutcome::Success);
+
+        assert_eq!(resolve(15, 0, 1000, DIFFICULTY_HEROIC), CheckOutcome::Failure);
+    }
+
+    #[test]
+    fn outcome_predicates() {
+        assert!(CheckOutcome::CriticalSuccess.is_success());
+        assert!(CheckOutcome::CriticalSuccess.is_critical());
+        assert!(!CheckOutcome::Failure.is_success());
+        assert!(!CheckOutcome::Success.is_critical());
+    }
+}
@@ -0,0 +1,164 @@
+use bus::{GameEvent, Listener};
+use std::io::Write;
+
+/// A sound effect describing what happened, leaving the actual sound to the front end
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Cue {
+    /// The player hit a monster
+    Hit,
+    /// The player was hit
+    Hurt,
+    /// The player killed a monster
+    Kill,
+    /// The player character died
+    Death,
+    /// The player character reached a new level
+    LevelUp,
+    /// The player opened a door
+    DoorOpen,
+    /// The player earned gold
+    Coins,
+    /// The player crafted an item
+    Craft,
+    /// The player found an item
+    Pickup,
+    /// The player walked onto the next field
+    Footstep,
+    /// The player started or finished a campaign
+    Fanfare,
+    /// The player entered another world
+    Travel,
+}
+
+impl Cue {
+    /// Returns the cue played for the game event
+    pub fn for_event(event: &GameEvent) -> Cue {
+        match *event {
+            GameEvent::DamageDealt(_) => Cue::Hit,
+            GameEvent::DamageTaken(_) => Cue::Hurt,
+            GameEvent::MonsterKilled(_) => Cue::Kill,
+            GameEvent::CharacterDied => Cue::Death,
+            GameEvent::LevelReached(_) => Cue::LevelUp,
+            GameEvent::DoorOpened => Cue::DoorOpen,
+            GameEvent::GoldEarned(_) => Cue::Coins,
+            GameEvent::ItemCrafted(_) => Cue::Craft,
+            GameEvent::ItemFound(_) => Cue::Pickup,
+            GameEvent::Walked => Cue::Footstep,
+            GameEvent::CampaignStarted(_) |
+            GameEvent::CampaignFinished(_) => Cue::Fanfare,
+            GameEvent::WorldEntered(_) => Cue::Travel,
+        }
+    }
+}
+
+/// Something that plays sound cues, e.g. a binding to an audio library or the terminal bell.
+/// This crate does not bundle any audio library.
+pub trait SoundSink {
+    /// Called once for every cue that is not muted
+    fn play(&mut self, cue: Cue);
+}
+
+/// Plays the cues of the game events through a sound sink. Listens on the event bus, see
+/// `bus::publish`.
+pub struct Sounds {
+    sink: Box<dyn SoundSink>,
+    muted: Vec<Cue>,
+}
+
+impl Sounds {
+    /// Creates a new instance of `Sounds` playing all cues through the sink
+    pub fn new(sink: Box<dyn SoundSink>) -> Sounds {
+        Sounds {
+            sink,
+            muted: Vec::new(),
+        }
+    }
+
+    /// A builder method for muting a cue, e.g. the footsteps
+    pub fn mute(mut self, cue: Cue) -> Sounds {
+        if !self.muted.contains(&cue) {
+            self.muted.push(cue);
+        }
+        self
+    }
+
+    /// Returns whether the cue is muted
+    pub fn is_muted(&self, cue: Cue) -> bool {
+        self.muted.contains(&cue)
+    }
+}
+
+impl Listener for Sounds {
+    fn notify(&mut self, event: &GameEvent) {
+        let cue = Cue::for_event(event);
+        if !self.is_muted(cue) {
+            self.sink.play(cue);
+        }
+    }
+}
+
+/// A sound sink ringing the terminal bell for the important cues: getting hurt, dying and
+/// leveling up
+pub struct Bell<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Bell<W> {
+    /// Creates a new instance of `Bell` writing the bell character to the output
+    pub fn new(out: W) -> Bell<W> {
+        Bell { out }
+    }
+}
+
+impl<W: Write> SoundSink for Bell<W> {
+    fn play(&mut self, cue: Cue) {
+        if let Cue::Hurt | Cue::Death | Cue::LevelUp = cue {
+            // A bell that can not be rung is not worth interrupting the game for
+            let _ = self.out.write_all(b"\x07").and_then(|()| self.out.flush());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{self, GameEvent};
+    use std::cell::RefCell;
+    use std::io;
+    use std::rc::Rc;
+
+    struct Recorder {
+        cues: Rc<RefCell<Vec<Cue>>>,
+    }
+
+    impl SoundSink for Recorder {
+        fn play(&mut self, cue: Cue) {
+            self.cues.borrow_mut().push(cue);
+        }
+    }
+
+    #[test]
+    fn cues() {
+        let cues = Rc::new(RefCell::new(Vec::new()));
+        let mut sounds = Sounds::new(Box::new(Recorder { cues: cues.clone() }))
+            .mute(Cue::Footstep);
+
+        bus::publish(&GameEvent::DamageDealt(4), &mut [&mut sounds]);
+        bus::publish(&GameEvent::Walked, &mut [&mut sounds]);
+        bus::publish(&GameEvent::LevelReached(2), &mut [&mut sounds]);
+        bus::publish(&GameEvent::DoorOpened, &mut [&mut sounds]);
+
+        assert_eq!(*cues.borrow(), vec![Cue::Hit, Cue::LevelUp, Cue::DoorOpen]);
+    }
+
+    #[test]
+    fn bell() {
+        let mut out = io::Cursor::new(Vec::new());
+        {
+            let mut bell = Bell::new(&mut out);
+            bell.play(Cue::Footstep);
+            bell.play(Cue::Hurt);
+        }
+        assert_eq!(out.into_inner(), b"\x07");
+    }
+}
@@ -0,0 +1,121 @@
+use bus::{GameEvent, Listener};
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+
+/// Numbers collected over a whole save, e.g. for an end screen
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Statistics {
+    /// The damage the player dealt
+    pub damage_dealt: u64,
+    /// The damage the player took
+    pub damage_taken: u64,
+    /// The amount of fields the player walked
+    pub tiles_walked: u64,
+    /// The gold the player earned
+    pub gold_earned: u64,
+    /// The amount of items the player crafted
+    pub items_crafted: u64,
+    /// The amount of monsters the player killed
+    pub monsters_killed: u64,
+    /// How often the player died
+    pub deaths: u64,
+}
+
+impl Statistics {
+    /// Creates a new instance of `Statistics` with all numbers at `0`
+    pub fn new() -> Statistics {
+        Statistics::default()
+    }
+
+    /// Lists all numbers, one line each
+    pub fn summary(&self) -> String {
+        format!("Monsters killed: {}\nDamage dealt: {}\nDamage taken: {}\nTiles walked: {}\nGold \
+                 earned: {}\nItems crafted: {}\nDeaths: {}\n",
+                self.monsters_killed,
+                self.damage_dealt,
+                self.damage_taken,
+                self.tiles_walked,
+                self.gold_earned,
+                self.items_crafted,
+                self.deaths)
+    }
+
+    /// Reads the statistics from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Statistics, DataError> {
+        let object = data::as_object(json, "statistics")?;
+
+        Ok(Statistics {
+            damage_dealt: data::unsigned(object, "damage_dealt")?,
+            damage_taken: data::unsigned(object, "damage_taken")?,
+            tiles_walked: data::unsigned(object, "tiles_walked")?,
+            gold_earned: data::unsigned(object, "gold_earned")?,
+            items_crafted: data::unsigned(object, "items_crafted")?,
+            monsters_killed: data::unsigned(object, "monsters_killed")?,
+            deaths: data::unsigned(object, "deaths")?,
+        })
+    }
+}
+
+impl Listener for Statistics {
+    fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::MonsterKilled(_) => self.monsters_killed += 1,
+            GameEvent::CharacterDied => self.deaths += 1,
+            GameEvent::DamageDealt(damage) => self.damage_dealt += damage as u64,
+            GameEvent::DamageTaken(damage) => self.damage_taken += damage as u64,
+            GameEvent::Walked => self.tiles_walked += 1,
+            GameEvent::GoldEarned(gold) => self.gold_earned += gold as u64,
+            GameEvent::ItemCrafted(_) => self.items_crafted += 1,
+            _ => {}
+        }
+    }
+}
+
+impl ToJson for Statistics {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("damage_dealt".to_owned(), self.damage_dealt.to_json());
+        object.insert("damage_taken".to_owned(), self.damage_taken.to_json());
+        object.insert("tiles_walked".to_owned(), self.tiles_walked.to_json());
+        object.insert("gold_earned".to_owned(), self.gold_earned.to_json());
+        object.insert("items_crafted".to_owned(), self.items_crafted.to_json());
+        object.insert("monsters_killed".to_owned(), self.monsters_killed.to_json());
+        object.insert("deaths".to_owned(), self.deaths.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bus::{GameEvent, Listener};
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn count_events() {
+        let mut statistics = Statistics::new();
+        statistics.notify(&GameEvent::DamageDealt(7));
+        statistics.notify(&GameEvent::DamageDealt(3));
+        statistics.notify(&GameEvent::Walked);
+        statistics.notify(&GameEvent::GoldEarned(25));
+        statistics.notify(&GameEvent::ItemCrafted("Rope".to_owned()));
+        statistics.notify(&GameEvent::CampaignStarted("Quest".to_owned()));
+
+        assert_eq!(statistics.damage_dealt, 10);
+        assert_eq!(statistics.tiles_walked, 1);
+        assert_eq!(statistics.gold_earned, 25);
+        assert_eq!(statistics.items_crafted, 1);
+        assert_eq!(statistics.deaths, 0);
+        assert!(statistics.summary().contains("Damage dealt: 10\n"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut statistics = Statistics::new();
+        statistics.notify(&GameEvent::MonsterKilled("bat".to_owned()));
+        statistics.notify(&GameEvent::CharacterDied);
+
+        assert_eq!(Statistics::from_json(&statistics.to_json()).unwrap(), statistics);
+    }
+}
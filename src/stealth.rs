@@ -0,0 +1,114 @@
+use character::{Attribute, Character};
+use dice::Dice;
+use rand::Rng;
+use time::LightLevel;
+use types::AttributeValue;
+use world::two_dimensional::FieldType;
+
+/// The factor by which the damage of an attack from an undetected character is multiplied
+pub const SNEAK_ATTACK_MULTIPLIER: AttributeValue = 2;
+
+/// The amount of attribute points that grant a `+1` on a stealth or perception roll
+const ATTRIBUTE_POINTS_PER_BONUS: AttributeValue = 5;
+
+/// The bonus on the stealth roll granted by the light level
+fn light_modifier(light_level: LightLevel) -> AttributeValue {
+    match light_level {
+        LightLevel::Night => 4,
+        LightLevel::Dawn | LightLevel::Dusk => 2,
+        LightLevel::Day => 0,
+    }
+}
+
+/// The bonus (or malus) on the stealth roll granted by the ground the character is sneaking on
+fn terrain_modifier(field_type: &FieldType) -> AttributeValue {
+    match *field_type {
+        FieldType::Grass | FieldType::Dirt | FieldType::Sand => 0,
+        FieldType::Stone | FieldType::StoneWall => 1,
+        FieldType::Wood | FieldType::WoodenFence | FieldType::DoorClosed | FieldType::DoorOpen |
+        FieldType::DoorLocked => -1,
+        FieldType::Mud | FieldType::Hole => -2,
+        FieldType::Quicksand | FieldType::SwampWater | FieldType::Water => -4,
+        FieldType::Custom(_) => 0,
+    }
+}
+
+/// Rolls whether an observer with the given perception detects the character. Characters that
+/// are not sneaking are always detected.
+pub fn detects<R: Rng>(perception: AttributeValue,
+                       character: &Character,
+                       light_level: LightLevel,
+                       field_type: &FieldType,
+                       rng: &mut R)
+                       -> bool {
+    if !character.is_sneaking() {
+        return true;
+    }
+
+    let d20 = Dice::new(1, 20);
+
+    let stealth = d20.roll(rng) +
+                  character.get_attribute_value(&Attribute::Dexterity) /
+                  ATTRIBUTE_POINTS_PER_BONUS + light_modifier(light_level) +
+                  terrain_modifier(field_type);
+    let perception = d20.roll(rng) + perception / ATTRIBUTE_POINTS_PER_BONUS;
+
+    perception >= stealth
+}
+
+/// Returns the attack damage of the character against an observer that did or did not detect
+/// the character
+pub fn attack_damage(character: &Character, detected: bool) -> AttributeValue {
+    if character.is_sneaking() && !detected {
+        character.attack_damage() * SNEAK_ATTACK_MULTIPLIER
+    } else {
+        character.attack_damage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use rand::{SeedableRng, XorShiftRng};
+    use time::LightLevel;
+    use world::two_dimensional::FieldType;
+
+    #[test]
+    fn not_sneaking() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let character = Character::new("Thief");
+
+        assert!(detects(0, &character, LightLevel::Night, &FieldType::Grass, &mut rng));
+        assert_eq!(attack_damage(&character, false), character.attack_damage());
+    }
+
+    #[test]
+    fn sneaking() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Thief");
+        character.set_sneaking(true);
+
+        character.update_attribute(&Attribute::Dexterity, 1000);
+        assert!(!detects(10, &character, LightLevel::Day, &FieldType::Water, &mut rng));
+
+        character.update_attribute(&Attribute::Dexterity, 0);
+        assert!(detects(1000, &character, LightLevel::Night, &FieldType::Grass, &mut rng));
+
+        character.update_attribute(&Attribute::Dexterity, 10);
+        let rolls: Vec<bool> = (0..6)
+            .map(|_| detects(10, &character, LightLevel::Dusk, &FieldType::Stone, &mut rng))
+            .collect();
+        assert_eq!(rolls, vec![true, false, false, true, false, false]);
+    }
+
+    #[test]
+    fn sneak_attack() {
+        let mut character = Character::new("Thief");
+        character.set_sneaking(true);
+
+        assert_eq!(attack_damage(&character, false),
+                   character.attack_damage() * SNEAK_ATTACK_MULTIPLIER);
+        assert_eq!(attack_damage(&character, true), character.attack_damage());
+    }
+}
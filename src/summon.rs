@@ -0,0 +1,214 @@
+use bestiary::Bestiary;
+use combat::{Battle, Combatant, Side};
+use inventory::Inventory;
+use item::{Item, ItemType};
+use item_generator::ItemGenerator;
+use world::World;
+use world::entities::{EntityId, MapEntity};
+use world::two_dimensional::World2d;
+
+/// A way to call a monster of the bestiary to fight for the player for a limited time, e.g. a
+/// scroll or a spell
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Summon {
+    /// The id of the monster in the bestiary
+    pub monster: String,
+    /// The rounds of a battle or the turns on the map the ally stays
+    pub lifespan: u32,
+}
+
+impl Summon {
+    /// Creates a new instance of `Summon`
+    pub fn new(monster: &str, lifespan: u32) -> Summon {
+        Summon {
+            monster: monster.to_owned(),
+            lifespan,
+        }
+    }
+
+    /// Returns the name of the scroll item, e.g. `Scroll of Summoning: wolf`
+    pub fn scroll_name(&self) -> String {
+        format!("Scroll of Summoning: {}", self.monster)
+    }
+
+    /// Generates the summon as a scroll the player can find
+    pub fn scroll(&self) -> Item {
+        ItemGenerator::new()
+            .name(&self.scroll_name())
+            .item_type(ItemType::Usable)
+            .influence(None)
+            .gen()
+    }
+
+    /// Returns `true` if the item is the scroll of this summon
+    pub fn is_scroll(&self, item: &Item) -> bool {
+        item.name == self.scroll_name()
+    }
+
+    /// Spawns the monster next to the summoner, fighting on its side with the AI of the monster
+    /// until its lifespan runs out. Returns its index, or `None` if the bestiary does not know
+    /// the monster.
+    pub fn in_battle(&self,
+                     bestiary: &Bestiary,
+                     battle: &mut Battle,
+                     summoner: usize)
+                     -> Option<usize> {
+        bestiary.spawn(&self.monster)
+            .map(|combatant| battle.summon(summoner, combatant.lifespan(self.lifespan)))
+    }
+
+    /// Spawns the monster onto the map of the world next to the player, following them until
+    /// its lifespan runs out. Returns its id in the registry of entities, or `None` if the
+    /// bestiary does not know the monster.
+    pub fn on_map(&self, bestiary: &Bestiary, world: &mut World2d) -> Option<EntityId> {
+        bestiary.spawn(&self.monster).map(|mut combatant| {
+            combatant.side = Side::Player;
+            let ally = Ally {
+                combatant: combatant.lifespan(self.lifespan),
+                turns_left: self.lifespan as u64,
+            };
+            world.entities.add(world.get_position(), MapEntity::Ally(Box::new(ally)))
+        })
+    }
+
+    /// Reads the scroll of this summon from the inventory, which uses it up, and spawns the
+    /// monster onto the map like `on_map`. Returns `None` without using the scroll if the
+    /// inventory holds none or the bestiary does not know the monster.
+    pub fn read_scroll(&self,
+                       inventory: &mut Inventory,
+                       bestiary: &Bestiary,
+                       world: &mut World2d)
+                       -> Option<EntityId> {
+        inventory.find_by_name(&self.scroll_name())?;
+
+        let id = self.on_map(bestiary, world)?;
+        inventory.take_by_name(&self.scroll_name());
+        Some(id)
+    }
+}
+
+/// A summoned monster on the map, registered as an entity of the world. Allies follow the
+/// player and vanish once their turns ran out, see `EntityRegistry`.
+#[derive(Clone)]
+pub struct Ally {
+    /// The combatant joining the battles of the player
+    pub combatant: Combatant,
+    /// The turns left before the ally vanishes
+    pub turns_left: u64,
+}
+
+/// Lets the allies on the map of the world join a battle on the side of the player. Returns
+/// their ids and their indices in the battle, which `leave_battle` needs.
+pub fn join_battle(world: &World2d, battle: &mut Battle) -> Vec<(EntityId, usize)> {
+    world.entities
+        .iter()
+        .into_iter()
+        .filter_map(|(id, placed)| match placed.entity {
+            MapEntity::Ally(ref ally) => Some((id, battle.add_combatant(ally.combatant.clone()))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Writes the health and the remaining lifespan of the allies that joined the battle back to
+/// the map. Allies that died or vanished during the battle are removed.
+pub fn leave_battle(world: &mut World2d, battle: &Battle, joined: &[(EntityId, usize)]) {
+    for &(id, index) in joined {
+        let combatant = battle.combatant(index);
+        if !combatant.is_alive() || combatant.has_vanished() {
+            world.entities.remove(id);
+            continue;
+        }
+
+        if let Some(&mut MapEntity::Ally(ref mut ally)) =
+            world.entities.get_mut(id).map(|placed| &mut placed.entity) {
+            ally.combatant.health = combatant.health;
+            ally.combatant.lifespan = combatant.lifespan;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bestiary::Bestiary;
+    use combat::{Action, Battle, Combatant, Side};
+    use inventory::Inventory;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::Json;
+    use time::{Tick, WorldClock};
+    use world::{Teleport, World};
+    use world::entities::MapEntity;
+    use world::two_dimensional::World2d;
+
+    fn bestiary() -> Bestiary {
+        let mut bestiary = Bestiary::new();
+        let wolf = r#"[{"id": "wolf", "name": "Wolf", "health": 8, "attack_damage": 3,
+                        "ai": "aggressive"}]"#;
+        bestiary.load_json(&Json::from_str(wolf).unwrap()).unwrap();
+        bestiary
+    }
+
+    #[test]
+    fn in_battle() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut battle = Battle::new();
+        battle.add_combatant(Combatant::new("Mage", Side::Player, 10));
+        battle.add_combatant(Combatant::new("Troll", Side::Enemy, 100));
+
+        let summon = Summon::new("wolf", 2);
+        assert_eq!(Summon::new("bear", 2).in_battle(&bestiary(), &mut battle, 0), None);
+        let wolf = summon.in_battle(&bestiary(), &mut battle, 0).unwrap();
+        assert_eq!(battle.combatant(wolf).side, Side::Player);
+        assert_eq!(battle.decide(wolf), Some(Action::Attack(1)));
+
+        battle.run_ai_turns(&mut rng);
+        battle.run_ai_turns(&mut rng);
+        assert!(!battle.combatant(wolf).is_fighting());
+        assert!(summon.is_scroll(&summon.scroll()));
+    }
+
+    #[test]
+    fn on_map() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut world = World2d::new("Forest", (5, 5)).starting_point((1, 1));
+        world.set_position((1, 1));
+        let wolf = Summon::new("wolf", 2).on_map(&bestiary(), &mut world).unwrap();
+
+        world.move_to((1, 0));
+        assert_eq!(world.entities.get(wolf).unwrap().position, (1, 1));
+
+        let mut battle = Battle::new();
+        let joined = join_battle(&world, &mut battle);
+        assert_eq!(joined, vec![(wolf, 0)]);
+        assert_eq!(battle.combatant(0).side, Side::Player);
+        battle.add_combatant(Combatant::new("Bear", Side::Enemy, 50));
+        battle.execute(1, Action::Attack(0), &mut rng).unwrap();
+        leave_battle(&mut world, &battle, &joined);
+        match world.entities.get(wolf).unwrap().entity {
+            MapEntity::Ally(ref ally) => assert_eq!(ally.combatant.health, 7),
+            _ => panic!("The wolf is not an ally"),
+        }
+
+        let clock = WorldClock::new();
+        world.entities.tick(&clock);
+        assert_eq!(world.entities.len(), 1);
+        world.entities.tick(&clock);
+        assert!(world.entities.is_empty());
+    }
+
+    #[test]
+    fn read_scroll() {
+        let summon = Summon::new("wolf", 5);
+        let mut world = World2d::new("Forest", (5, 5));
+        let mut inventory = Inventory::new(5);
+        assert_eq!(summon.read_scroll(&mut inventory, &bestiary(), &mut world), None);
+
+        inventory.add_item(summon.scroll()).unwrap();
+        assert_eq!(Summon::new("bear", 5).read_scroll(&mut inventory, &bestiary(), &mut world),
+                   None);
+        assert!(summon.read_scroll(&mut inventory, &bestiary(), &mut world).is_some());
+        assert_eq!(inventory.count_by_name(&summon.scroll_name()), 0);
+        assert_eq!(world.entities.len(), 1);
+    }
+}
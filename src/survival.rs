@@ -0,0 +1,235 @@
+use character::{Attribute, Character};
+use difficulty::{Difficulty, Modifiers};
+use time::{Tick, WorldClock};
+use types::AttributeValue;
+use world::two_dimensional::{FieldType, World2d};
+
+/// The highest value a need can reach
+pub const NEED_MAX: u32 = 100;
+
+/// The value from which on a neglected need lowers an attribute
+pub const NEGLECT_THRESHOLD: u32 = 75;
+
+/// The amount an attribute is lowered by a neglected need
+pub const NEGLECT_PENALTY: AttributeValue = 5;
+
+/// The amount of turns after which hunger grows by one
+const HUNGER_INTERVAL: u64 = 10;
+
+/// The amount of turns after which thirst grows by one
+const THIRST_INTERVAL: u64 = 6;
+
+/// The amount of turns after which fatigue grows by one
+const FATIGUE_INTERVAL: u64 = 12;
+
+/// The amount of hunger a single piece of food stills
+const FOOD_NOURISHMENT: u32 = 40;
+
+/// The amount of thirst a single drink quenches
+const DRINK_REFRESHMENT: u32 = 50;
+
+/// The amount of hours a character sleeps when camping
+pub const CAMPING_HOURS: u64 = 8;
+
+/// The hunger, thirst and fatigue of a character, each ranging from `0` to `NEED_MAX`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Needs {
+    /// The hunger of the character
+    pub hunger: u32,
+    /// The thirst of the character
+    pub thirst: u32,
+    /// The fatigue of the character
+    pub fatigue: u32,
+}
+
+impl Default for Needs {
+    fn default() -> Needs {
+        Needs::new()
+    }
+}
+
+impl Needs {
+    /// Creates a new instance of `Needs` with all needs satisfied
+    pub fn new() -> Needs {
+        Needs {
+            hunger: 0,
+            thirst: 0,
+            fatigue: 0,
+        }
+    }
+
+    /// Stills hunger by eating a single piece of food
+    pub fn eat(&mut self) {
+        self.hunger = self.hunger.saturating_sub(FOOD_NOURISHMENT);
+    }
+
+    /// Quenches thirst by drinking once
+    pub fn drink(&mut self) {
+        self.thirst = self.thirst.saturating_sub(DRINK_REFRESHMENT);
+    }
+
+    /// Removes all fatigue
+    pub fn rest(&mut self) {
+        self.fatigue = 0;
+    }
+
+    /// Returns the penalty for the given attribute caused by neglected needs. Hunger weakens
+    /// `Attribute::Strength`, thirst `Attribute::Perception` and fatigue `Attribute::Dexterity`.
+    pub fn penalty(&self, attribute: &Attribute) -> AttributeValue {
+        let need = match *attribute {
+            Attribute::Strength => self.hunger,
+            Attribute::Perception => self.thirst,
+            Attribute::Dexterity => self.fatigue,
+            _ => return 0,
+        };
+
+        if need >= NEGLECT_THRESHOLD {
+            NEGLECT_PENALTY
+        } else {
+            0
+        }
+    }
+
+    /// Returns `true` if any need reached `NEED_MAX`
+    pub fn is_critical(&self) -> bool {
+        self.hunger >= NEED_MAX || self.thirst >= NEED_MAX || self.fatigue >= NEED_MAX
+    }
+}
+
+impl Needs {
+    /// Lets the needs grow for the current turn, as fast as the difficulty demands
+    pub fn grow(&mut self, clock: &WorldClock, modifiers: &Modifiers) {
+        let grow = |need: u32, interval: u64| {
+            if clock.turn().is_multiple_of(modifiers.need_interval(interval)) && need < NEED_MAX {
+                need + 1
+            } else {
+                need
+            }
+        };
+
+        self.hunger = grow(self.hunger, HUNGER_INTERVAL);
+        self.thirst = grow(self.thirst, THIRST_INTERVAL);
+        self.fatigue = grow(self.fatigue, FATIGUE_INTERVAL);
+    }
+}
+
+impl Tick for Needs {
+    fn tick(&mut self, clock: &WorldClock) {
+        self.grow(clock, &Difficulty::Normal.modifiers());
+    }
+}
+
+/// Returns `true` if characters can safely camp on a field of this type
+pub fn is_safe_to_camp(field_type: &FieldType) -> bool {
+    matches!(*field_type,
+             FieldType::Dirt | FieldType::Grass | FieldType::Sand | FieldType::Stone |
+             FieldType::Wood)
+}
+
+/// Lets the character camp at the given position and sleep for `CAMPING_HOURS`, which removes
+/// all fatigue. Returns `false` if the field is not safe to camp on.
+pub fn camp(character: &mut Character,
+            world: &World2d,
+            position: (usize, usize),
+            clock: &mut WorldClock)
+            -> bool {
+    if !is_safe_to_camp(&world.get_field(position).field_type) {
+        return false;
+    }
+
+    let turns = CAMPING_HOURS * clock.turns_per_hour();
+    for _ in 0..turns {
+        clock.advance();
+        character.tick(clock);
+    }
+
+    if let Some(needs) = character.needs_mut() {
+        needs.rest();
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Attribute, Character};
+    use difficulty::Difficulty;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use time::{Tick, WorldClock};
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    #[test]
+    fn neglected_needs() {
+        let mut character = Character::new("Robinson");
+        let strength = character.get_attribute_value(&Attribute::Strength);
+
+        let mut clock = WorldClock::new();
+        clock.advance();
+        character.tick(&clock);
+        assert!(character.needs().is_none());
+
+        character.set_survival(true);
+        character.needs_mut().unwrap().hunger = NEGLECT_THRESHOLD;
+        assert_eq!(character.get_attribute_value(&Attribute::Strength),
+                   strength - NEGLECT_PENALTY);
+
+        assert!(!character.eat());
+        let _ = character.inventory_mut()
+            .add_item(ItemGenerator::new().item_type(ItemType::ConsumableFood).gen());
+        assert!(character.eat());
+        assert_eq!(character.get_attribute_value(&Attribute::Strength), strength);
+    }
+
+    #[test]
+    fn needs_grow() {
+        let mut needs = Needs::new();
+        let mut clock = WorldClock::new();
+
+        for _ in 0..60 {
+            clock.advance();
+            needs.tick(&clock);
+        }
+
+        assert_eq!(needs,
+                   Needs {
+                       hunger: 6,
+                       thirst: 10,
+                       fatigue: 5,
+                   });
+    }
+
+    #[test]
+    fn difficulty_pressure() {
+        let mut needs = Needs::new();
+        let mut clock = WorldClock::new();
+        let modifiers = Difficulty::Nightmare.modifiers();
+
+        for _ in 0..60 {
+            clock.advance();
+            needs.grow(&clock, &modifiers);
+        }
+
+        assert_eq!(needs.hunger, 12);
+        assert_eq!(needs.thirst, 20);
+    }
+
+    #[test]
+    fn camping() {
+        let mut world = World2d::new("Island", (5, 5));
+        world.set_field(Field::new(FieldType::Quicksand), (1, 1));
+
+        let mut character = Character::new("Robinson");
+        character.set_survival(true);
+        character.needs_mut().unwrap().fatigue = 90;
+        let mut clock = WorldClock::new();
+
+        assert!(!camp(&mut character, &world, (1, 1), &mut clock));
+        assert_eq!(clock.turn(), 0);
+
+        assert!(camp(&mut character, &world, (0, 0), &mut clock));
+        assert_eq!(clock.hour(), CAMPING_HOURS);
+        assert_eq!(character.needs().unwrap().fatigue, 0);
+    }
+}
@@ -0,0 +1,94 @@
+use character::{Attribute, Character};
+use rand::Rng;
+use skill_check::{DIFFICULTY_EASY, DIFFICULTY_MEDIUM};
+use types::{AttributeValue, Health};
+use world::two_dimensional::FieldType;
+
+/// The stamina used up by a successful stroke
+pub const STAMINA_PER_STROKE: u32 = 3;
+
+/// The stamina used up by a failed stroke
+pub const STAMINA_PER_STRUGGLE: u32 = 6;
+
+/// The damage an exhausted swimmer takes per stroke
+pub const DROWNING_DAMAGE: Health = 5;
+
+/// The result of swimming across a single field
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stroke {
+    /// The field is not water, so nobody had to swim
+    Dry,
+    /// The character swam across the field
+    Swam,
+    /// The character made it across, but used up a lot of stamina
+    Struggled,
+    /// The character is exhausted and took drowning damage
+    Drowning(Health),
+}
+
+/// Returns the difficulty of swimming across a field of the given type
+pub fn difficulty(field_type: &FieldType) -> AttributeValue {
+    match *field_type {
+        FieldType::SwampWater => DIFFICULTY_MEDIUM,
+        _ => DIFFICULTY_EASY,
+    }
+}
+
+/// Lets the character swim across a field of the given type. Every stroke takes an
+/// `Attribute::Constitution` check and uses up stamina. Exhausted characters start drowning.
+pub fn swim<R: Rng>(character: &mut Character, field_type: &FieldType, rng: &mut R) -> Stroke {
+    if !field_type.is_water() {
+        return Stroke::Dry;
+    }
+
+    if character.stamina() == 0 {
+        character.take_damage(DROWNING_DAMAGE);
+        return Stroke::Drowning(DROWNING_DAMAGE);
+    }
+
+    let outcome = character.skill_check(&Attribute::Constitution, difficulty(field_type), rng);
+    if outcome.is_success() {
+        character.drain_stamina(STAMINA_PER_STROKE);
+        Stroke::Swam
+    } else {
+        character.drain_stamina(STAMINA_PER_STRUGGLE);
+        Stroke::Struggled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use rand::{SeedableRng, XorShiftRng};
+    use world::two_dimensional::FieldType;
+
+    #[test]
+    fn dry_land() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Michael Phelps");
+
+        assert_eq!(swim(&mut character, &FieldType::Grass, &mut rng), Stroke::Dry);
+        assert_eq!(character.stamina(), character.max_stamina());
+    }
+
+    #[test]
+    fn drowning() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut character = Character::new("Michael Phelps");
+        let health = character.health();
+
+        for _ in 0..character.max_stamina() / STAMINA_PER_STROKE {
+            assert!(swim(&mut character, &FieldType::Water, &mut rng) !=
+                    Stroke::Drowning(DROWNING_DAMAGE));
+            if character.stamina() == 0 {
+                break;
+            }
+        }
+        assert_eq!(character.stamina(), 0);
+
+        assert_eq!(swim(&mut character, &FieldType::SwampWater, &mut rng),
+                   Stroke::Drowning(DROWNING_DAMAGE));
+        assert_eq!(character.health(), health - DROWNING_DAMAGE);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The pronouns used to refer to a character or monster
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Pronouns {
+    /// he, him, his, himself
+    He,
+    /// she, her, her, herself
+    She,
+    /// they, them, their, themself
+    #[default]
+    They,
+    /// it, it, its, itself
+    It,
+}
+
+impl Pronouns {
+    /// Returns the pronoun used as the subject, e.g. "she"
+    pub fn subject(&self) -> &'static str {
+        match *self {
+            Pronouns::He => "he",
+            Pronouns::She => "she",
+            Pronouns::They => "they",
+            Pronouns::It => "it",
+        }
+    }
+
+    /// Returns the pronoun used as the object, e.g. "her"
+    pub fn object(&self) -> &'static str {
+        match *self {
+            Pronouns::He => "him",
+            Pronouns::She => "her",
+            Pronouns::They => "them",
+            Pronouns::It => "it",
+        }
+    }
+
+    /// Returns the possessive pronoun, e.g. "her"
+    pub fn possessive(&self) -> &'static str {
+        match *self {
+            Pronouns::He => "his",
+            Pronouns::She => "her",
+            Pronouns::They => "their",
+            Pronouns::It => "its",
+        }
+    }
+
+    /// Returns the reflexive pronoun, e.g. "herself"
+    pub fn reflexive(&self) -> &'static str {
+        match *self {
+            Pronouns::He => "himself",
+            Pronouns::She => "herself",
+            Pronouns::They => "themself",
+            Pronouns::It => "itself",
+        }
+    }
+}
+
+
+impl FromStr for Pronouns {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Pronouns, ()> {
+        match s {
+            "He" => Ok(Pronouns::He),
+            "She" => Ok(Pronouns::She),
+            "They" => Ok(Pronouns::They),
+            "It" => Ok(Pronouns::It),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Puts "a" or "an" in front of the word, depending on its first letter
+pub fn article(word: &str) -> String {
+    match word.chars().next().map(|letter| letter.to_ascii_lowercase()) {
+        Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => format!("an {}", word),
+        _ => format!("a {}", word),
+    }
+}
+
+/// Returns the plural of the noun if the count is not `1`
+pub fn plural(noun: &str, count: u64) -> String {
+    if count == 1 {
+        return noun.to_owned();
+    }
+
+    let irregular = IRREGULAR_PLURALS.iter().find(|&&(singular, _)| noun.ends_with(singular));
+    if let Some(&(singular, plural)) = irregular {
+        return format!("{}{}", &noun[..noun.len() - singular.len()], plural);
+    }
+
+    let mut chars = noun.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some('y'), Some(letter)) if !"aeiou".contains(letter) => {
+            format!("{}ies", &noun[..noun.len() - 1])
+        }
+        (Some('s'), _) | (Some('x'), _) | (Some('z'), _) | (Some('h'), Some('c')) |
+        (Some('h'), Some('s')) => format!("{}es", noun),
+        _ => format!("{}s", noun),
+    }
+}
+
+/// Nouns, or endings of nouns, that are not pluralized by adding "s" or "es"
+const IRREGULAR_PLURALS: [(&str, &str); 9] = [("dwarf", "dwarves"),
+                                               ("child", "children"),
+                                               ("foot", "feet"),
+                                               ("tooth", "teeth"),
+                                               ("mouse", "mice"),
+                                               ("wolf", "wolves"),
+                                               ("elf", "elves"),
+                                               ("thief", "thieves"),
+                                               ("knife", "knives")];
+
+/// The values of the placeholders of a template
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TemplateContext {
+    values: HashMap<String, String>,
+}
+
+impl TemplateContext {
+    /// Creates a new, empty instance of `TemplateContext`
+    pub fn new() -> TemplateContext {
+        TemplateContext::default()
+    }
+
+    /// A builder method for setting the value of a placeholder
+    pub fn value<T: fmt::Display>(mut self, key: &str, value: T) -> TemplateContext {
+        self.values.insert(key.to_owned(), value.to_string());
+        self
+    }
+
+    /// A builder method for adding a character or monster. Besides its name as `{key}`, the
+    /// template can refer to it as `{key.they}`, `{key.them}`, `{key.their}` and
+    /// `{key.themself}`.
+    pub fn person(self, key: &str, name: &str, pronouns: Pronouns) -> TemplateContext {
+        self.value(key, name)
+            .value(&format!("{}.they", key), pronouns.subject())
+            .value(&format!("{}.them", key), pronouns.object())
+            .value(&format!("{}.their", key), pronouns.possessive())
+            .value(&format!("{}.themself", key), pronouns.reflexive())
+    }
+
+    /// Returns the value of the placeholder
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|value| &value[..])
+    }
+}
+
+/// A text with placeholders, e.g. `"{attacker} slashes {target} for {damage} damage!"`.
+///
+/// A placeholder may pass its value through filters separated by `|`:
+///
+/// * `a` puts "a" or "an" in front of the value
+/// * `plural:count` pluralizes the value unless the placeholder `count` is `1`
+/// * `capitalize` makes the first letter uppercase
+///
+/// Placeholders without a value are kept as they are, so missing values are easy to spot.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Template {
+    text: String,
+}
+
+impl Template {
+    /// Creates a new instance of `Template`
+    pub fn new(text: &str) -> Template {
+        Template { text: text.to_owned() }
+    }
+
+    /// Fills in the placeholders with the values of the context
+    pub fn render(&self, context: &TemplateContext) -> String {
+        let mut rendered = String::new();
+        let mut rest = &self.text[..];
+
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+
+            rendered.push_str(&rest[..start]);
+            match Template::placeholder(&rest[start + 1..end], context) {
+                Some(value) => rendered.push_str(&value),
+                None => rendered.push_str(&rest[start..end + 1]),
+            }
+            rest = &rest[end + 1..];
+        }
+
+        rendered.push_str(rest);
+        rendered
+    }
+
+    fn placeholder(placeholder: &str, context: &TemplateContext) -> Option<String> {
+        let mut parts = placeholder.split('|').map(str::trim);
+        let mut value = context.get(parts.next()?)?.to_owned();
+
+        for filter in parts {
+            value = match filter.find(':') {
+                None if filter == "a" => article(&value),
+                None if filter == "capitalize" => capitalize(&value),
+                Some(colon) if &filter[..colon] == "plural" => {
+                    let count = context.get(&filter[colon + 1..])?.parse().ok()?;
+                    plural(&value, count)
+                }
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let template = Template::new("{attacker} slashes {target} for {damage} damage!");
+        let context = TemplateContext::new()
+            .value("attacker", "Hero")
+            .value("target", "Goblin")
+            .value("damage", 7);
+
+        assert_eq!(template.render(&context), "Hero slashes Goblin for 7 damage!");
+        assert_eq!(Template::new("{missing} and {").render(&context), "{missing} and {");
+    }
+
+    #[test]
+    fn filters() {
+        let context = TemplateContext::new()
+            .value("item", "apple")
+            .value("count", 3)
+            .person("target", "Aragorn", Pronouns::He);
+
+        assert_eq!(Template::new("{item|a|capitalize}").render(&context), "An apple");
+        assert_eq!(Template::new("{count} {item|plural:count}").render(&context), "3 apples");
+        assert_eq!(Template::new("{target} raises {target.their} sword.").render(&context),
+                   "Aragorn raises his sword.");
+    }
+
+    #[test]
+    fn grammar() {
+        assert_eq!(article("orc"), "an orc");
+        assert_eq!(article("Sword"), "a Sword");
+        assert_eq!(plural("potion", 1), "potion");
+        assert_eq!(plural("berry", 2), "berries");
+        assert_eq!(plural("torch", 2), "torches");
+        assert_eq!(plural("werewolf", 2), "werewolves");
+        assert_eq!(plural("key", 0), "keys");
+    }
+}
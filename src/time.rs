@@ -0,0 +1,266 @@
+use std::mem;
+
+/// The amount of hours that make up one in-game day
+pub const HOURS_PER_DAY: u64 = 24;
+
+/// The default amount of turns that make up one in-game day
+pub const DEFAULT_DAY_LENGTH: u64 = 240;
+
+/// The view radius of a character in broad daylight
+pub const DAYLIGHT_VIEW_RADIUS: usize = 8;
+
+/// The amount of light in the world, depending on the time of day
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LightLevel {
+    /// From midnight until dawn
+    Night,
+    /// The early morning hours
+    Dawn,
+    /// Broad daylight
+    Day,
+    /// The evening hours
+    Dusk,
+}
+
+impl LightLevel {
+    /// A helper method to get the light level at the given hour of the day
+    pub fn by_hour(hour: u64) -> LightLevel {
+        match hour {
+            5 | 6 => LightLevel::Dawn,
+            7..=18 => LightLevel::Day,
+            19 | 20 => LightLevel::Dusk,
+            _ => LightLevel::Night,
+        }
+    }
+
+    /// Returns the brightness from `0.0` (pitch black) to `1.0` (broad daylight)
+    pub fn brightness(&self) -> f64 {
+        match *self {
+            LightLevel::Night => 0.25,
+            LightLevel::Dawn | LightLevel::Dusk => 0.6,
+            LightLevel::Day => 1.0,
+        }
+    }
+
+    /// Returns `true` if it is dark enough for nocturnal monsters to appear
+    pub fn is_dark(&self) -> bool {
+        *self == LightLevel::Night
+    }
+
+    /// Returns the view radius of a character under this light level
+    pub fn view_radius(&self) -> usize {
+        ((DAYLIGHT_VIEW_RADIUS as f64) * self.brightness()).ceil() as usize
+    }
+
+    /// Darkens the given RGB color according to the brightness. Used by renderers
+    pub fn shade(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        let brightness = self.brightness();
+        let (r, g, b) = color;
+
+        (((r as f64) * brightness) as u8,
+         ((g as f64) * brightness) as u8,
+         ((b as f64) * brightness) as u8)
+    }
+}
+
+/// Something that changes as time passes, e.g. status effects, schedules or shop stock
+pub trait Tick {
+    /// Called once for every turn that passes
+    fn tick(&mut self, clock: &WorldClock);
+}
+
+/// A function called every time a turn passes
+type Hook = Box<dyn FnMut(&WorldClock)>;
+
+/// The clock of the game world counting turns, hours and days
+pub struct WorldClock {
+    turn: u64,
+    day_length: u64,
+    hooks: Vec<Hook>,
+}
+
+impl Default for WorldClock {
+    fn default() -> WorldClock {
+        WorldClock::new()
+    }
+}
+
+impl WorldClock {
+    /// Creates a new instance of `WorldClock` starting at the first turn of the first day
+    pub fn new() -> WorldClock {
+        WorldClock {
+            turn: 0,
+            day_length: DEFAULT_DAY_LENGTH,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// A builder method for setting the amount of turns a day lasts.
+    ///
+    /// # Panics
+    ///
+    /// **Panics** if the day length is not a non-zero multiple of `HOURS_PER_DAY`
+    pub fn day_length(mut self, day_length: u64) -> WorldClock {
+        assert!(day_length > 0 && day_length.is_multiple_of(HOURS_PER_DAY));
+        self.day_length = day_length;
+        self
+    }
+
+    /// A builder method for starting the clock at the given day and hour.
+    ///
+    /// # Panics
+    ///
+    /// **Panics** if the hour is not less than `HOURS_PER_DAY`
+    pub fn starting_at(mut self, day: u64, hour: u64) -> WorldClock {
+        assert!(hour < HOURS_PER_DAY);
+        self.turn = day * self.day_length + hour * self.turns_per_hour();
+        self
+    }
+
+    /// Adds a hook that is called every time a turn passes
+    pub fn add_hook<T>(&mut self, hook: T)
+        where T: FnMut(&WorldClock) + 'static
+    {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// Returns the amount of turns that passed since the clock was started
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// Returns the amount of turns that make up one hour
+    pub fn turns_per_hour(&self) -> u64 {
+        self.day_length / HOURS_PER_DAY
+    }
+
+    /// Returns the current hour of the day (`0` to `HOURS_PER_DAY - 1`)
+    pub fn hour(&self) -> u64 {
+        (self.turn % self.day_length) / self.turns_per_hour()
+    }
+
+    /// Returns the current day, starting with day `0`
+    pub fn day(&self) -> u64 {
+        self.turn / self.day_length
+    }
+
+    /// Returns the current light level
+    pub fn light_level(&self) -> LightLevel {
+        LightLevel::by_hour(self.hour())
+    }
+
+    /// Advances the clock by one turn and calls all registered hooks
+    pub fn advance(&mut self) {
+        self.turn += 1;
+
+        let mut hooks = mem::take(&mut self.hooks);
+        for hook in &mut hooks {
+            hook(self);
+        }
+        self.hooks = hooks;
+    }
+
+    /// Advances the clock by one turn and ticks all given subjects
+    pub fn advance_with(&mut self, subjects: &mut [&mut dyn Tick]) {
+        self.advance();
+
+        for subject in subjects.iter_mut() {
+            subject.tick(self);
+        }
+    }
+
+    /// Advances the clock by the given amount of turns
+    pub fn skip(&mut self, turns: u64) {
+        for _ in 0..turns {
+            self.advance();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::cell::Cell;
+
+    struct Counter {
+        ticks: u64,
+    }
+
+    impl Tick for Counter {
+        fn tick(&mut self, _: &WorldClock) {
+            self.ticks += 1;
+        }
+    }
+
+    #[test]
+    fn hours_and_days() {
+        let mut clock = WorldClock::new();
+
+        assert_eq!(clock.hour(), 0);
+        assert_eq!(clock.day(), 0);
+
+        clock.skip(clock.turns_per_hour() * 25);
+
+        assert_eq!(clock.hour(), 1);
+        assert_eq!(clock.day(), 1);
+    }
+
+    #[test]
+    fn starting_at() {
+        let clock = WorldClock::new().starting_at(3, 12);
+
+        assert_eq!(clock.day(), 3);
+        assert_eq!(clock.hour(), 12);
+    }
+
+    #[test]
+    fn custom_day_length() {
+        let mut clock = WorldClock::new().day_length(48);
+
+        clock.skip(50);
+
+        assert_eq!(clock.day(), 1);
+        assert_eq!(clock.hour(), 1);
+    }
+
+    #[test]
+    fn day_night_cycle() {
+        let mut clock = WorldClock::new();
+        assert_eq!(clock.light_level(), LightLevel::Night);
+
+        clock = clock.starting_at(0, 12);
+        assert_eq!(clock.light_level(), LightLevel::Day);
+        assert_eq!(clock.light_level().view_radius(), DAYLIGHT_VIEW_RADIUS);
+
+        clock = clock.starting_at(0, 23);
+        assert!(clock.light_level().is_dark());
+        assert!(clock.light_level().view_radius() < DAYLIGHT_VIEW_RADIUS);
+        assert_eq!(clock.light_level().shade((200, 100, 0)), (50, 25, 0));
+    }
+
+    #[test]
+    fn hooks() {
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+
+        let mut clock = WorldClock::new();
+        clock.add_hook(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        clock.skip(5);
+
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn advance_with() {
+        let mut counter = Counter { ticks: 0 };
+        let mut clock = WorldClock::new();
+
+        clock.advance_with(&mut [&mut counter]);
+        clock.advance_with(&mut [&mut counter]);
+
+        assert_eq!(counter.ticks, 2);
+        assert_eq!(clock.turn(), 2);
+    }
+}
@@ -0,0 +1,68 @@
+use data::DataError;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A file the input of the player and the text shown to them are written to while playing, e.g.
+/// to share a playthrough or to find out why a campaign behaves unexpectedly. Every line is
+/// stamped with the turn it belongs to:
+///
+/// ```text
+/// [Turn 3] > go north
+/// [Turn 3] You walk north.
+/// ```
+pub struct Transcript {
+    path: PathBuf,
+    file: File,
+}
+
+impl Transcript {
+    /// Creates the transcript file, replacing an existing file
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Transcript, DataError> {
+        Ok(Transcript {
+            path: path.as_ref().to_path_buf(),
+            file: File::create(path)?,
+        })
+    }
+
+    /// Returns the path of the transcript file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes a line typed by the player
+    pub fn input(&mut self, turn: usize, line: &str) -> Result<(), DataError> {
+        writeln!(self.file, "[Turn {}] > {}", turn, line.trim())?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the text shown to the player, stamping each of its lines
+    pub fn output(&mut self, turn: usize, text: &str) -> Result<(), DataError> {
+        for line in text.lines() {
+            writeln!(self.file, "[Turn {}] {}", turn, line)?;
+        }
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn record() {
+        let path = env::temp_dir().join("txtrpg_transcript.txt");
+        let mut transcript = Transcript::create(&path).unwrap();
+        transcript.input(3, "go north").unwrap();
+        transcript.output(3, "You walk north.\nA goblin appears!").unwrap();
+
+        assert_eq!(transcript.path(), path.as_path());
+        assert_eq!(fs::read_to_string(&path).unwrap(),
+                   "[Turn 3] > go north\n[Turn 3] You walk north.\n[Turn 3] A goblin appears!\n");
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+use character::{Attribute, Character};
+use data::{self, DataError};
+use entity::Entity;
+use item::{Item, ItemType};
+use item_generator::ItemGenerator;
+use loot::LootTable;
+use notes::Notes;
+use rand::Rng;
+use rustc_serialize::json::Json;
+use skill_check::{CheckOutcome, DIFFICULTY_MEDIUM};
+use types::AttributeValue;
+use world::World;
+use world::progress::CampaignProgress;
+use world::two_dimensional::World2d;
+
+/// The name of the container holding the treasure once it is dug up
+pub const CACHE_NAME: &str = "Buried cache";
+
+/// The reasons why digging for a treasure fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DigError {
+    /// The player is not standing on the marked spot
+    WrongSpot,
+    /// The treasure was dug up already
+    AlreadyFound,
+    /// The Perception check failed, the player may try again
+    Failed(CheckOutcome),
+}
+
+/// A map marking the spot where a treasure is buried. Reading the map pins the spot to the
+/// notes of the player; digging there with a successful Perception check unearths a cache
+/// holding the loot of the map.
+#[derive(Clone, Debug)]
+pub struct TreasureMap {
+    /// The unique id of the treasure
+    pub id: String,
+    /// The id of the world the treasure is buried in
+    pub world: String,
+    /// The position of the treasure
+    pub position: (usize, usize),
+    /// The items buried
+    pub loot: LootTable,
+    /// The difficulty of the Perception check for finding the treasure
+    pub difficulty: AttributeValue,
+}
+
+impl TreasureMap {
+    /// Creates a new instance of `TreasureMap`
+    pub fn new(id: &str, world: &str, position: (usize, usize), loot: LootTable) -> TreasureMap {
+        TreasureMap {
+            id: id.to_owned(),
+            world: world.to_owned(),
+            position,
+            loot,
+            difficulty: DIFFICULTY_MEDIUM,
+        }
+    }
+
+    /// A builder method for setting the difficulty of finding the treasure
+    pub fn difficulty(mut self, difficulty: AttributeValue) -> TreasureMap {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Returns the name of the map item, e.g. `Treasure map: pirate_gold`
+    pub fn item_name(&self) -> String {
+        format!("Treasure map: {}", self.id)
+    }
+
+    /// Generates the map as an item the player can find
+    pub fn item(&self) -> Item {
+        ItemGenerator::new()
+            .name(&self.item_name())
+            .item_type(ItemType::Usable)
+            .influence(None)
+            .gen()
+    }
+
+    /// Returns `true` if the item is this map
+    pub fn is_map(&self, item: &Item) -> bool {
+        item.name == self.item_name()
+    }
+
+    /// Reads the map, marking the spot in the notes of the player
+    pub fn reveal(&self, notes: &mut Notes) {
+        notes.pin(&self.world, self.position, "X marks the spot");
+    }
+
+    /// Returns the quest flag set once the treasure was found, e.g. `treasure.pirate_gold`
+    pub fn found_flag(&self) -> String {
+        format!("treasure.{}", self.id)
+    }
+
+    /// Digs for the treasure at the position of the player. On success, a cache holding the loot
+    /// is placed on the spot and the treasure is marked as found in the progress.
+    pub fn dig<R: Rng>(&self,
+                       world: &mut World2d,
+                       progress: &mut CampaignProgress,
+                       character: &Character,
+                       rng: &mut R)
+                       -> Result<(), DigError> {
+        if progress.active != self.world || world.get_position() != self.position {
+            return Err(DigError::WrongSpot);
+        }
+        if progress.has_flag(&self.found_flag()) {
+            return Err(DigError::AlreadyFound);
+        }
+
+        let outcome = character.skill_check(&Attribute::Perception, self.difficulty, rng);
+        if !outcome.is_success() {
+            return Err(DigError::Failed(outcome));
+        }
+
+        let cache = Entity::new(CACHE_NAME).container(self.loot.roll(rng));
+        world.spawn_container(self.position, cache);
+        progress.set_flag(&self.found_flag());
+        Ok(())
+    }
+
+    /// Reads a map from a JSON object like
+    /// `{"id": "pirate_gold", "world": "island", "position": [3, 4], "loot": [...]}` with an
+    /// optional `difficulty`
+    pub fn from_json(json: &Json) -> Result<TreasureMap, DataError> {
+        let object = data::as_object(json, "treasure")?;
+
+        Ok(TreasureMap::new(&data::string(object, "id")?,
+                            &data::string(object, "world")?,
+                            data::position(object, "position")?,
+                            LootTable::from_json(data::field(object, "loot")?)?)
+            .difficulty(data::optional(object, "difficulty", data::signed)?
+                .unwrap_or(DIFFICULTY_MEDIUM)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item::ItemType;
+    use loot::{LootEntry, LootTable};
+    use notes::Notes;
+    use rand::{SeedableRng, XorShiftRng};
+    use world::Teleport;
+    use world::progress::CampaignProgress;
+    use world::two_dimensional::World2d;
+
+    fn map() -> TreasureMap {
+        TreasureMap::new("pirate_gold",
+                         "island",
+                         (2, 1),
+                         LootTable::new().entry(LootEntry::new(ItemType::Gem, 1.0)))
+            .difficulty(-100)
+    }
+
+    #[test]
+    fn reveal() {
+        let map = map();
+        let mut notes = Notes::new();
+        map.reveal(&mut notes);
+
+        assert!(map.is_map(&map.item()));
+        assert_eq!(notes.at("island", (2, 1)).len(), 1);
+    }
+
+    #[test]
+    fn dig() {
+        let map = map();
+        let mut world = World2d::new("Island", (5, 5));
+        let mut progress = CampaignProgress::new("island");
+        let character = Character::new("Michael");
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        assert_eq!(map.dig(&mut world, &mut progress, &character, &mut rng),
+                   Err(DigError::WrongSpot));
+
+        world.set_position((2, 1));
+        assert_eq!(map.dig(&mut world, &mut progress, &character, &mut rng), Ok(()));
+        assert_eq!(world.get_field((2, 1)).entity.as_ref().unwrap().name(), CACHE_NAME);
+        assert_eq!(map.dig(&mut world, &mut progress, &character, &mut rng),
+                   Err(DigError::AlreadyFound));
+    }
+}
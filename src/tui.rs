@@ -0,0 +1,261 @@
+use character::Character;
+use locale::Catalog;
+use ui::{self, Bar};
+use world::World;
+use world::two_dimensional::World2d;
+
+/// The character the player is drawn with on the map
+pub const PLAYER_GLYPH: char = '@';
+
+/// The amount of lines of the log pane
+pub const LOG_LINES: usize = 5;
+
+/// How many fields the surroundings are described in the accessible mode
+pub const DESCRIPTION_RADIUS: usize = 10;
+
+/// A key pressed by the player
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Key {
+    /// The up arrow
+    Up,
+    /// The down arrow
+    Down,
+    /// The left arrow
+    Left,
+    /// The right arrow
+    Right,
+    /// The escape key
+    Escape,
+    /// Any other key producing a character
+    Char(char),
+}
+
+/// A terminal the client draws to and reads keys from. This crate does not bundle a terminal
+/// library; a binding to e.g. crossterm switches the terminal to raw mode, draws the lines of
+/// the screen from the top left corner and translates key events.
+pub trait Terminal {
+    /// Returns the amount of columns and rows of the terminal
+    fn size(&self) -> (usize, usize);
+
+    /// Replaces the content of the terminal with the given lines
+    fn draw(&mut self, lines: &[String]) -> Result<(), String>;
+
+    /// Waits for the next key the player presses
+    fn read_key(&mut self) -> Result<Key, String>;
+}
+
+/// An interactive client showing the map around the player, a status bar and a log pane, moved
+/// using the arrow keys, `wasd` or `hjkl`. `g` picks up the items on the field of the player,
+/// `q` or escape quits.
+pub struct Client {
+    world: World2d,
+    character: Character,
+    log: Vec<String>,
+    health_bar: Bar,
+    accessible: bool,
+}
+
+impl Client {
+    /// Creates a new instance of `Client` for the character in the world
+    pub fn new(world: World2d, character: Character) -> Client {
+        Client {
+            world,
+            character,
+            log: Vec::new(),
+            health_bar: Bar::health(),
+            accessible: false,
+        }
+    }
+
+    /// A builder method for setting the bar the health is shown with
+    pub fn health_bar(mut self, bar: Bar) -> Client {
+        self.health_bar = bar;
+        self
+    }
+
+    /// A builder method for switching to the mode for screen readers, which describes the
+    /// surroundings of the player in words instead of drawing the map and shows values as text
+    /// instead of bars
+    pub fn accessible(mut self, accessible: bool) -> Client {
+        self.accessible = accessible;
+        self
+    }
+
+    /// Returns the world being played
+    pub fn world(&self) -> &World2d {
+        &self.world
+    }
+
+    /// Returns the character of the player
+    pub fn character(&self) -> &Character {
+        &self.character
+    }
+
+    /// Returns the messages of the log pane, the latest last
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    /// Adds a message to the log pane
+    pub fn message(&mut self, message: &str) {
+        self.log.push(message.to_owned());
+    }
+
+    /// Handles a key pressed by the player. Returns `false` if the player quits.
+    pub fn handle(&mut self, key: Key) -> bool {
+        let movement = match key {
+            Key::Escape | Key::Char('q') => return false,
+            Key::Char('g') => {
+                self.pick_up();
+                return true;
+            }
+            Key::Up | Key::Char('w') | Key::Char('k') => (0, -1),
+            Key::Down | Key::Char('s') | Key::Char('j') => (0, 1),
+            Key::Left | Key::Char('a') | Key::Char('h') => (-1, 0),
+            Key::Right | Key::Char('d') | Key::Char('l') => (1, 0),
+            Key::Char(_) => return true,
+        };
+
+        let (x, y) = self.world.get_position();
+        let target = ((x as i64 + movement.0) as usize, (y as i64 + movement.1) as usize);
+        if self.world.is_passable(target) {
+            self.world.move_to(movement);
+        } else {
+            self.message("Something blocks the way.");
+        }
+
+        true
+    }
+
+    /// Renders the screen: the map filling the space above the status bar and the log pane
+    pub fn render(&self, size: (usize, usize)) -> Vec<String> {
+        let (columns, rows) = size;
+        let map_rows = rows.saturating_sub(LOG_LINES + 1);
+
+        let (health, max_health) =
+            (self.character.health() as u64, self.character.max_health() as u64);
+        let (mut lines, health) = if self.accessible {
+            let mut lines =
+                ui::describe_surroundings(&self.world, DESCRIPTION_RADIUS, &Catalog::english());
+            lines.truncate(map_rows);
+            (lines, ui::describe_value("Health", health, max_health))
+        } else {
+            (self.map_view(columns, map_rows), self.health_bar.render(health, max_health))
+        };
+        lines.push(format!("{} | Level {} | {}",
+                           self.character.name(),
+                           self.character.level(),
+                           health));
+
+        let start = self.log.len().saturating_sub(LOG_LINES);
+        lines.extend(self.log[start..].iter().cloned());
+        while lines.len() < rows {
+            lines.push(String::new());
+        }
+
+        lines
+    }
+
+    /// Draws the screen and handles keys until the player quits or the world is finished
+    pub fn run<T: Terminal>(&mut self, terminal: &mut T) -> Result<(), String> {
+        loop {
+            terminal.draw(&self.render(terminal.size()))?;
+            if self.world.is_finished() || !self.handle(terminal.read_key()?) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn pick_up(&mut self) {
+        let position = self.world.get_position();
+        let names: Vec<String> =
+            self.world.items_at(position).iter().map(|item| item.name.clone()).collect();
+
+        if names.is_empty() {
+            self.message("There is nothing here.");
+        }
+
+        for name in names {
+            let message = match self.world.pick_up(&name, self.character.inventory_mut()) {
+                Ok(()) => format!("You pick up {}.", name),
+                Err(_) => format!("There is no room for {}.", name),
+            };
+            self.message(&message);
+        }
+    }
+
+    /// Renders the part of the map around the player that fits into the given size
+    fn map_view(&self, columns: usize, rows: usize) -> Vec<String> {
+        let (width, height) = self.world.size();
+        let (x, y) = self.world.get_position();
+        let left = x.saturating_sub(columns / 2).min(width.saturating_sub(columns));
+        let top = y.saturating_sub(rows / 2).min(height.saturating_sub(rows));
+
+        (top..(top + rows).min(height))
+            .map(|row| {
+                (left..(left + columns).min(width))
+                    .map(|column| if (column, row) == (x, y) {
+                        PLAYER_GLYPH
+                    } else {
+                        self.world.glyph_at((column, row))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item_generator::ItemGenerator;
+    use ui::Bar;
+    use world::World;
+    use world::entities::MapEntity;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn client() -> Client {
+        let mut world = World2d::new("Meadow", (20, 20)).starting_point((0, 0));
+        world.set_field(Field::new(FieldType::StoneWall), (0, 1));
+        world.entities.add((1, 0), MapEntity::Item(ItemGenerator::new().name("Athelas").gen()));
+
+        Client::new(world, Character::new("Michael")).health_bar(Bar::health().color(None))
+    }
+
+    #[test]
+    fn movement() {
+        let mut client = client();
+        assert!(client.handle(Key::Down));
+        assert_eq!(client.world().get_position(), (0, 0));
+        assert_eq!(client.log(), &["Something blocks the way.".to_owned()]);
+
+        assert!(client.handle(Key::Char('l')));
+        assert!(client.handle(Key::Char('g')));
+        assert_eq!(client.character().inventory().count_by_name("Athelas"), 1);
+        assert!(!client.handle(Key::Char('q')));
+    }
+
+    #[test]
+    fn render() {
+        let client = client();
+        let screen = client.render((10, 10));
+
+        assert_eq!(screen.len(), 10);
+        assert_eq!(screen[0], "@*,,,,,,,,");
+        assert_eq!(screen[1].chars().next(), Some('#'));
+        assert!(screen[4].starts_with("Michael | Level 1 | ["));
+    }
+
+    #[test]
+    fn accessible() {
+        let client = client().accessible(true);
+        let screen = client.render((40, 10));
+
+        assert_eq!(screen[0], "You stand on grass.");
+        assert!(screen.contains(&"Athelas to the east.".to_owned()));
+        assert!(screen.contains(&"A stone wall to the south.".to_owned()));
+        assert!(screen.contains(&"Michael | Level 1 | Health 30 of 30".to_owned()));
+        assert!(screen.iter().all(|line| !line.contains('\x1b')));
+    }
+}
@@ -6,3 +6,6 @@ pub type Health = usize;
 
 /// The type for gold. Used as a currency.
 pub type Gold = usize;
+
+/// The type for experience points.
+pub type Experience = u64;
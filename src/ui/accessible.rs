@@ -0,0 +1,108 @@
+use locale::Catalog;
+use world::World;
+use world::rooms::Direction;
+use world::two_dimensional::World2d;
+
+/// Describes the surroundings of the player as text for screen readers instead of a map of
+/// glyphs, e.g. `A stone wall 3 tiles north` or `Water to the east`. Looking into every
+/// direction, the nearest field within the radius that differs from the field the player stands
+/// on is named, along with the items and entities on it.
+pub fn describe_surroundings(world: &World2d, radius: usize, catalog: &Catalog) -> Vec<String> {
+    let position = world.get_position();
+    let here = world.get_field(position);
+    let mut lines = vec![format!("You stand on {}.", catalog.field_name(&here.field_type))];
+
+    for item in world.items_at(position) {
+        lines.push(format!("{} lies here.", capitalize(&catalog.item_name(item))));
+    }
+
+    for &(direction, step) in &DIRECTIONS {
+        let name = format!("{:?}", direction).to_lowercase();
+
+        for distance in 1..radius + 1 {
+            let x = position.0 as i64 + step.0 * distance as i64;
+            let y = position.1 as i64 + step.1 * distance as i64;
+            let field = match world.get(x as usize, y as usize) {
+                Some(field) if x >= 0 && y >= 0 => field,
+                _ => {
+                    lines.push(format!("The edge of the map {}.", at(distance, &name)));
+                    break;
+                }
+            };
+
+            let mut things: Vec<String> = world.items_at((x as usize, y as usize))
+                .into_iter()
+                .map(|item| catalog.item_name(item))
+                .collect();
+            things.extend(field.entity.iter().map(|entity| entity.name().to_owned()));
+            if field.field_type != here.field_type {
+                things.insert(0, catalog.field_name(&field.field_type));
+            }
+
+            if !things.is_empty() {
+                let things = capitalize(&things.join(" and "));
+                lines.push(format!("{} {}.", things, at(distance, &name)));
+                break;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Describes a value without relying on a bar or color, e.g. `Health 24 of 30`
+pub fn describe_value(label: &str, value: u64, max: u64) -> String {
+    format!("{} {} of {}", label, value, max)
+}
+
+/// The directions looked into and the step towards them
+const DIRECTIONS: [(Direction, (i64, i64)); 4] = [(Direction::North, (0, -1)),
+                                                  (Direction::East, (1, 0)),
+                                                  (Direction::South, (0, 1)),
+                                                  (Direction::West, (-1, 0))];
+
+fn at(distance: usize, direction: &str) -> String {
+    match distance {
+        1 => format!("to the {}", direction),
+        distance => format!("{} tiles {}", distance, direction),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item_generator::ItemGenerator;
+    use locale::Catalog;
+    use world::entities::MapEntity;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    #[test]
+    fn surroundings() {
+        let mut world = World2d::new("Meadow", (5, 5)).starting_point((1, 4));
+        world.set_field(Field::new(FieldType::StoneWall), (1, 1));
+        world.set_field(Field::new(FieldType::Water), (2, 4));
+        world.drop_item(ItemGenerator::new().name("Sting").gen());
+        world.entities.add((0, 4), MapEntity::Item(ItemGenerator::new().name("Athelas").gen()));
+
+        assert_eq!(describe_surroundings(&world, 5, &Catalog::english()),
+                   vec!["You stand on grass.",
+                        "Sting lies here.",
+                        "A stone wall 3 tiles north.",
+                        "Water to the east.",
+                        "The edge of the map to the south.",
+                        "Athelas to the west."]);
+    }
+
+    #[test]
+    fn value() {
+        assert_eq!(describe_value("Health", 24, 30), "Health 24 of 30");
+    }
+}
@@ -0,0 +1,202 @@
+use std::cmp;
+use std::str::FromStr;
+
+/// The colors of a terminal, drawn using ANSI escape codes
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    /// A dark color, invisible on most dark terminals
+    Black,
+    /// Used for health
+    Red,
+    /// Used for stamina
+    Green,
+    /// Used for experience
+    Yellow,
+    /// Used for mana
+    Blue,
+    /// A purple color
+    Magenta,
+    /// A light blue color
+    Cyan,
+    /// The default color of most terminals
+    White,
+}
+
+impl Color {
+    /// Returns the ANSI code of the foreground color
+    pub fn code(&self) -> u8 {
+        match *self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+
+    /// Returns the color closest to the given RGB color
+    pub fn from_rgb(rgb: (u8, u8, u8)) -> Color {
+        let distance = |color: &&(Color, (u8, u8, u8))| {
+            let (r, g, b) = color.1;
+            let square = |a: u8, b: u8| (a as i32 - b as i32) * (a as i32 - b as i32);
+            square(r, rgb.0) + square(g, rgb.1) + square(b, rgb.2)
+        };
+
+        PALETTE.iter().min_by_key(distance).unwrap().0
+    }
+
+    /// Wraps the text in the escape codes drawing it in this color
+    pub fn paint(&self, text: &str) -> String {
+        format!("\x1b[{}m{}\x1b[0m", self.code(), text)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Color, ()> {
+        match s {
+            "Black" => Ok(Color::Black),
+            "Red" => Ok(Color::Red),
+            "Green" => Ok(Color::Green),
+            "Yellow" => Ok(Color::Yellow),
+            "Blue" => Ok(Color::Blue),
+            "Magenta" => Ok(Color::Magenta),
+            "Cyan" => Ok(Color::Cyan),
+            "White" => Ok(Color::White),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The RGB values of the colors in the VGA palette, which most terminals stay close to
+const PALETTE: [(Color, (u8, u8, u8)); 8] = [(Color::Black, (0, 0, 0)),
+                                             (Color::Red, (170, 0, 0)),
+                                             (Color::Green, (0, 170, 0)),
+                                             (Color::Yellow, (170, 85, 0)),
+                                             (Color::Blue, (0, 0, 170)),
+                                             (Color::Magenta, (170, 0, 170)),
+                                             (Color::Cyan, (0, 170, 170)),
+                                             (Color::White, (170, 170, 170))];
+
+/// A bar showing how full a value is, e.g. `[████░░░░] 24/30 HP`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bar {
+    width: usize,
+    filled: char,
+    empty: char,
+    unit: String,
+    color: Option<Color>,
+}
+
+impl Bar {
+    /// Creates a new instance of `Bar` with the given amount of characters, without a unit or
+    /// color
+    pub fn new(width: usize) -> Bar {
+        Bar {
+            width,
+            filled: '█',
+            empty: '░',
+            unit: String::new(),
+            color: None,
+        }
+    }
+
+    /// A red bar for health
+    pub fn health() -> Bar {
+        Bar::new(10).unit("HP").color(Some(Color::Red))
+    }
+
+    /// A blue bar for mana
+    pub fn mana() -> Bar {
+        Bar::new(10).unit("MP").color(Some(Color::Blue))
+    }
+
+    /// A green bar for stamina
+    pub fn stamina() -> Bar {
+        Bar::new(10).unit("SP").color(Some(Color::Green))
+    }
+
+    /// A yellow bar for the experience towards the next level
+    pub fn experience() -> Bar {
+        Bar::new(10).unit("XP").color(Some(Color::Yellow))
+    }
+
+    /// A builder method for setting the amount of characters
+    pub fn width(mut self, width: usize) -> Bar {
+        self.width = width;
+        self
+    }
+
+    /// A builder method for setting the characters of the full and the empty part
+    pub fn chars(mut self, filled: char, empty: char) -> Bar {
+        self.filled = filled;
+        self.empty = empty;
+        self
+    }
+
+    /// A builder method for setting the unit shown after the numbers
+    pub fn unit(mut self, unit: &str) -> Bar {
+        self.unit = unit.to_owned();
+        self
+    }
+
+    /// A builder method for setting the color of the full part. `None` draws no escape codes.
+    pub fn color(mut self, color: Option<Color>) -> Bar {
+        self.color = color;
+        self
+    }
+
+    /// Renders the bar for the given value. Values above `max` show a full bar.
+    pub fn render(&self, value: u64, max: u64) -> String {
+        let filled = match max {
+            0 => 0,
+            max => (cmp::min(value, max) * self.width as u64 / max) as usize,
+        };
+
+        let full: String = (0..filled).map(|_| self.filled).collect();
+        let empty: String = (filled..self.width).map(|_| self.empty).collect();
+        let full = match self.color {
+            Some(color) if !full.is_empty() => color.paint(&full),
+            _ => full,
+        };
+
+        let bar = format!("[{}{}] {}/{}", full, empty, value, max);
+        if self.unit.is_empty() {
+            bar
+        } else {
+            format!("{} {}", bar, self.unit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let bar = Bar::new(8).unit("HP");
+        assert_eq!(bar.render(24, 30), "[██████░░] 24/30 HP");
+        assert_eq!(bar.render(40, 30), "[████████] 40/30 HP");
+        assert_eq!(Bar::new(4).chars('#', '-').render(1, 0), "[----] 1/0");
+    }
+
+    #[test]
+    fn color() {
+        let bar = Bar::new(4).chars('#', '-').color(Some(Color::Red));
+        assert_eq!(bar.render(2, 4), "[\x1b[31m##\x1b[0m--] 2/4");
+        assert_eq!(bar.render(0, 4), "[----] 0/4");
+    }
+
+    #[test]
+    fn from_rgb() {
+        assert_eq!(Color::from_rgb((60, 160, 60)), Color::Green);
+        assert_eq!(Color::from_rgb((20, 20, 20)), Color::Black);
+        assert_eq!(Color::from_rgb((120, 80, 40)), Color::Yellow);
+        assert_eq!(Color::from_rgb((128, 128, 128)), Color::White);
+    }
+}
@@ -0,0 +1,225 @@
+/// How the options of a menu are selected
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Selector {
+    /// The options are numbered `1` to `9`
+    Numbers,
+    /// The options are lettered `a` to `z`
+    Letters,
+}
+
+impl Selector {
+    /// Returns the most options a single page can hold
+    pub fn max_page_size(&self) -> usize {
+        match *self {
+            Selector::Numbers => 9,
+            Selector::Letters => 26,
+        }
+    }
+
+    /// Returns the key selecting the option at the given position of a page
+    pub fn key(&self, position: usize) -> String {
+        match *self {
+            Selector::Numbers => (position + 1).to_string(),
+            Selector::Letters => ((b'a' + position as u8) as char).to_string(),
+        }
+    }
+
+    /// Returns the position on a page the key selects
+    pub fn position(&self, key: &str) -> Option<usize> {
+        let position = match *self {
+            Selector::Numbers => key.parse::<usize>().ok()?.checked_sub(1)?,
+            Selector::Letters => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(letter), None) if letter.is_ascii_lowercase() => {
+                        (letter as u8 - b'a') as usize
+                    }
+                    _ => return None,
+                }
+            }
+        };
+
+        if position < self.max_page_size() { Some(position) } else { None }
+    }
+}
+
+/// What the input of the player did to a menu
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuInput {
+    /// The option with the given index was chosen
+    Selected(usize),
+    /// The menu turned to another page
+    PageChanged,
+    /// The input does not refer to an option or page
+    Invalid,
+}
+
+/// A titled list of options the player chooses from, e.g. the answers of a dialogue, the wares
+/// of a shop or the items of an inventory. Long menus are split into pages, turned using `<` and
+/// `>`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Menu<T> {
+    title: String,
+    options: Vec<(String, T)>,
+    selector: Selector,
+    page_size: usize,
+    page: usize,
+}
+
+impl<T> Menu<T> {
+    /// Creates a new instance of `Menu` without options, numbering up to 9 options per page
+    pub fn new(title: &str) -> Menu<T> {
+        Menu {
+            title: title.to_owned(),
+            options: Vec::new(),
+            selector: Selector::Numbers,
+            page_size: Selector::Numbers.max_page_size(),
+            page: 0,
+        }
+    }
+
+    /// A builder method for adding an option with the given label and value
+    pub fn option(mut self, label: &str, value: T) -> Menu<T> {
+        self.options.push((label.to_owned(), value));
+        self
+    }
+
+    /// A builder method for setting the selector. The page size is shrunk to fit the selector.
+    pub fn selector(mut self, selector: Selector) -> Menu<T> {
+        let page_size = self.page_size;
+        self.selector = selector;
+        self.page_size(page_size)
+    }
+
+    /// A builder method for setting the amount of options per page, at least 1 and at most as
+    /// many as the selector allows
+    pub fn page_size(mut self, page_size: usize) -> Menu<T> {
+        self.page_size = page_size.max(1).min(self.selector.max_page_size());
+        self
+    }
+
+    /// Returns the value of the option with the given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.options.get(index).map(|(_, value)| value)
+    }
+
+    /// Returns the amount of options
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+
+    /// Returns `true` if the menu has no options
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    /// Returns the index of the current page
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Returns the amount of pages, which is at least 1
+    pub fn pages(&self) -> usize {
+        self.options.len().div_ceil(self.page_size).max(1)
+    }
+
+    /// Turns to the next page. Returns `false` on the last page.
+    pub fn next_page(&mut self) -> bool {
+        if self.page + 1 < self.pages() {
+            self.page += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Turns to the previous page. Returns `false` on the first page.
+    pub fn previous_page(&mut self) -> bool {
+        if self.page > 0 {
+            self.page -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Handles a line typed by the player: a key of an option on the current page, `<` or `>`
+    pub fn input(&mut self, line: &str) -> MenuInput {
+        match line.trim() {
+            "<" if self.previous_page() => MenuInput::PageChanged,
+            ">" if self.next_page() => MenuInput::PageChanged,
+            key => {
+                let index = self.selector
+                    .position(key)
+                    .filter(|&position| position < self.page_size)
+                    .map(|position| self.page * self.page_size + position);
+
+                match index {
+                    Some(index) if index < self.options.len() => MenuInput::Selected(index),
+                    _ => MenuInput::Invalid,
+                }
+            }
+        }
+    }
+
+    /// Renders the title and the options of the current page, one per line
+    pub fn render(&self) -> String {
+        let mut menu = self.title.clone();
+        menu.push('\n');
+
+        let start = self.page * self.page_size;
+        let options = self.options.iter().skip(start).take(self.page_size);
+        for (position, (label, _)) in options.enumerate() {
+            menu.push_str(&format!("{}) {}\n", self.selector.key(position), label));
+        }
+
+        if self.pages() > 1 {
+            menu.push_str(&format!("Page {}/{} (< previous, > next)\n",
+                                   self.page + 1,
+                                   self.pages()));
+        }
+
+        menu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shop() -> Menu<u32> {
+        Menu::new("Shop")
+            .option("Sword", 120)
+            .option("Shield", 80)
+            .option("Bow", 100)
+    }
+
+    #[test]
+    fn select() {
+        let mut menu = shop();
+        assert_eq!(menu.input("2"), MenuInput::Selected(1));
+        assert_eq!(menu.get(1), Some(&80));
+        assert_eq!(menu.input("4"), MenuInput::Invalid);
+        assert_eq!(menu.input("b"), MenuInput::Invalid);
+        assert_eq!(menu.input(">"), MenuInput::Invalid);
+
+        let mut menu = shop().selector(Selector::Letters);
+        assert_eq!(menu.input("c"), MenuInput::Selected(2));
+        assert_eq!(menu.render(), "Shop\na) Sword\nb) Shield\nc) Bow\n");
+    }
+
+    #[test]
+    fn pagination() {
+        let mut menu = shop().page_size(2);
+        assert_eq!(menu.pages(), 2);
+        assert_eq!(menu.render(),
+                   "Shop\n1) Sword\n2) Shield\nPage 1/2 (< previous, > next)\n");
+
+        assert_eq!(menu.input(">"), MenuInput::PageChanged);
+        assert_eq!(menu.render(), "Shop\n1) Bow\nPage 2/2 (< previous, > next)\n");
+        assert_eq!(menu.input("1"), MenuInput::Selected(2));
+        assert_eq!(menu.input("2"), MenuInput::Invalid);
+        assert_eq!(menu.input("<"), MenuInput::PageChanged);
+        assert_eq!(menu.page(), 0);
+    }
+}
@@ -0,0 +1,9 @@
+pub use self::accessible::*;
+pub use self::bar::*;
+pub use self::menu::*;
+pub use self::theme::*;
+
+mod accessible;
+mod bar;
+mod menu;
+mod theme;
@@ -0,0 +1,336 @@
+use data::{self, DataError};
+use item::ItemRarity;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use super::Color;
+use world::two_dimensional::FieldType;
+
+/// How a piece of text is drawn
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Style {
+    /// The color of the text, `None` keeps the color of the terminal
+    pub color: Option<Color>,
+    /// Whether the text is bold
+    pub bold: bool,
+}
+
+impl Style {
+    /// Creates a new instance of `Style` that is not bold
+    pub fn new(color: Option<Color>) -> Style {
+        Style {
+            color,
+            bold: false,
+        }
+    }
+
+    /// A builder method making the style bold
+    pub fn bold(mut self) -> Style {
+        self.bold = true;
+        self
+    }
+
+    /// Wraps the text in the escape codes drawing it in this style
+    pub fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_owned());
+        }
+        if let Some(color) = self.color {
+            codes.push(color.code().to_string());
+        }
+
+        if codes.is_empty() {
+            text.to_owned()
+        } else {
+            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+        }
+    }
+
+    /// Reads a style from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<Style, DataError> {
+        let object = data::as_object(json, "style")?;
+
+        Ok(Style {
+            color: data::optional(object, "color", data::parse)?,
+            bold: data::optional(object, "bold", data::boolean)?.unwrap_or(false),
+        })
+    }
+}
+
+impl ToJson for Style {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        if let Some(color) = self.color {
+            object.insert("color".to_owned(), format!("{:?}", color).to_json());
+        }
+        object.insert("bold".to_owned(), self.bold.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A part of the game a theme assigns a style to
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Element {
+    /// Fields of the given type on the map
+    Field(FieldType),
+    /// The names of items of the given rarity
+    Rarity(ItemRarity),
+    /// Damage numbers
+    Damage,
+    /// Healing numbers
+    Healing,
+    /// Borders, rules and other lines around the content
+    Border,
+    /// Titles and headings
+    Title,
+    /// The highlighted option of a menu
+    Selection,
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Element::Field(ref field_type) => write!(f, "Field:{}", field_type),
+            Element::Rarity(ref rarity) => write!(f, "Rarity:{:?}", rarity),
+            ref element => write!(f, "{:?}", element),
+        }
+    }
+}
+
+impl FromStr for Element {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Element, ()> {
+        match s {
+            "Damage" => Ok(Element::Damage),
+            "Healing" => Ok(Element::Healing),
+            "Border" => Ok(Element::Border),
+            "Title" => Ok(Element::Title),
+            "Selection" => Ok(Element::Selection),
+            _ if s.starts_with("Field:") => s["Field:".len()..].parse().map(Element::Field),
+            _ if s.starts_with("Rarity:") => s["Rarity:".len()..].parse().map(Element::Rarity),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Maps the elements of the game to styles. Elements without a style are drawn plain.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// The name of the theme
+    pub name: String,
+    styles: HashMap<Element, Style>,
+}
+
+impl Theme {
+    /// Creates a new instance of `Theme` without any styles
+    pub fn new(name: &str) -> Theme {
+        Theme {
+            name: name.to_owned(),
+            styles: HashMap::new(),
+        }
+    }
+
+    /// A theme for terminals with a dark background. Fields use the colors closest to
+    /// `FieldType::color`.
+    pub fn dark() -> Theme {
+        let theme = Theme::new("dark")
+            .style(Element::Rarity(ItemRarity::Common), Style::new(Some(Color::White)))
+            .style(Element::Rarity(ItemRarity::Uncommon), Style::new(Some(Color::Green)))
+            .style(Element::Rarity(ItemRarity::Rare), Style::new(Some(Color::Blue)))
+            .style(Element::Rarity(ItemRarity::Epic), Style::new(Some(Color::Magenta)))
+            .style(Element::Rarity(ItemRarity::Legendary),
+                   Style::new(Some(Color::Yellow)).bold())
+            .style(Element::Damage, Style::new(Some(Color::Red)).bold())
+            .style(Element::Healing, Style::new(Some(Color::Green)))
+            .style(Element::Border, Style::new(Some(Color::White)))
+            .style(Element::Title, Style::new(Some(Color::Cyan)).bold())
+            .style(Element::Selection, Style::new(Some(Color::Yellow)));
+
+        FIELD_TYPES.iter().fold(theme, |theme, field_type| {
+            theme.style(Element::Field(field_type.clone()),
+                        Style::new(Some(Color::from_rgb(field_type.color()))))
+        })
+    }
+
+    /// A theme for terminals with a light background, avoiding white and yellow text
+    pub fn light() -> Theme {
+        let mut theme = Theme::dark()
+            .style(Element::Rarity(ItemRarity::Common), Style::new(None))
+            .style(Element::Rarity(ItemRarity::Legendary), Style::new(Some(Color::Red)).bold())
+            .style(Element::Border, Style::new(None))
+            .style(Element::Title, Style::new(Some(Color::Blue)).bold())
+            .style(Element::Selection, Style::new(Some(Color::Magenta)));
+
+        theme.name = "light".to_owned();
+        theme.replace(Color::White, Color::Black);
+        theme.replace(Color::Yellow, Color::Red);
+        theme
+    }
+
+    /// A theme for a dark background that never tells elements apart by red and green alone
+    pub fn colorblind() -> Theme {
+        let mut theme = Theme::dark();
+
+        theme.name = "colorblind".to_owned();
+        theme.replace(Color::Red, Color::Yellow);
+        theme.replace(Color::Green, Color::Cyan);
+        theme.styles.insert(Element::Damage, Style::new(Some(Color::Magenta)).bold());
+        theme
+    }
+
+    /// Returns the built-in theme with the given name
+    pub fn built_in(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "colorblind" => Some(Theme::colorblind()),
+            _ => None,
+        }
+    }
+
+    /// A builder method for setting the style of an element
+    pub fn style(mut self, element: Element, style: Style) -> Theme {
+        self.styles.insert(element, style);
+        self
+    }
+
+    /// Returns the style of the element
+    pub fn get(&self, element: &Element) -> Style {
+        self.styles.get(element).cloned().unwrap_or_default()
+    }
+
+    /// Wraps the text in the escape codes drawing it in the style of the element
+    pub fn paint(&self, element: &Element, text: &str) -> String {
+        self.get(element).paint(text)
+    }
+
+    /// Reads a theme from a JSON object as written by `to_json`. The optional `base` names a
+    /// built-in theme whose styles are used for the elements the object does not list.
+    pub fn from_json(json: &Json) -> Result<Theme, DataError> {
+        let object = data::as_object(json, "theme")?;
+        let name = data::string(object, "name")?;
+
+        let mut theme = match data::optional(object, "base", data::string)? {
+            Some(base) => {
+                Theme::built_in(&base)
+                    .ok_or_else(|| DataError::Invalid(format!("unknown base theme {}", base)))?
+            }
+            None => Theme::new(&name),
+        };
+        theme.name = name;
+
+        if let Some(styles) = object.get("styles") {
+            for (element, style) in data::as_object(styles, "styles")? {
+                let element = element.parse()
+                    .map_err(|_| DataError::Invalid(format!("unknown element {}", element)))?;
+                theme.styles.insert(element, Style::from_json(style)?);
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Reads a user-defined theme from a JSON file
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Theme, DataError> {
+        Theme::from_json(&data::read_json(path)?)
+    }
+
+    fn replace(&mut self, from: Color, to: Color) {
+        for style in self.styles.values_mut() {
+            if style.color == Some(from) {
+                style.color = Some(to);
+            }
+        }
+    }
+}
+
+impl ToJson for Theme {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        let mut styles = Object::new();
+
+        for (element, style) in &self.styles {
+            styles.insert(element.to_string(), style.to_json());
+        }
+
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("styles".to_owned(), Json::Object(styles));
+
+        Json::Object(object)
+    }
+}
+
+/// The field types built into the crate, which the built-in themes assign styles to
+const FIELD_TYPES: [FieldType; 15] = [FieldType::Dirt,
+                                      FieldType::Grass,
+                                      FieldType::Hole,
+                                      FieldType::Mud,
+                                      FieldType::Quicksand,
+                                      FieldType::Sand,
+                                      FieldType::Stone,
+                                      FieldType::StoneWall,
+                                      FieldType::SwampWater,
+                                      FieldType::Water,
+                                      FieldType::Wood,
+                                      FieldType::WoodenFence,
+                                      FieldType::DoorClosed,
+                                      FieldType::DoorOpen,
+                                      FieldType::DoorLocked];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::ItemRarity;
+    use rustc_serialize::json::{Json, ToJson};
+    use ui::Color;
+    use world::two_dimensional::FieldType;
+
+    #[test]
+    fn paint() {
+        let theme = Theme::dark();
+        assert_eq!(theme.paint(&Element::Damage, "7"), "\x1b[1;31m7\x1b[0m");
+        assert_eq!(theme.get(&Element::Field(FieldType::Water)).color, Some(Color::Cyan));
+        assert_eq!(Theme::new("plain").paint(&Element::Damage, "7"), "7");
+    }
+
+    #[test]
+    fn built_in_themes() {
+        let light = Theme::built_in("light").unwrap();
+        assert_eq!(light.get(&Element::Rarity(ItemRarity::Common)).color, None);
+        assert_eq!(light.get(&Element::Field(FieldType::Stone)).color, Some(Color::Black));
+
+        let colorblind = Theme::colorblind();
+        assert_eq!(colorblind.get(&Element::Healing).color, Some(Color::Cyan));
+        assert!(colorblind.styles.values().all(|style| {
+            style.color != Some(Color::Red) && style.color != Some(Color::Green)
+        }));
+    }
+
+    #[test]
+    fn user_defined_theme() {
+        let json = Json::from_str(r#"{
+            "name": "mine",
+            "base": "dark",
+            "styles": {
+                "Damage": {"color": "Magenta"},
+                "Field:custom:lava": {"color": "Red", "bold": true}
+            }
+        }"#)
+            .unwrap();
+
+        let theme = Theme::from_json(&json).unwrap();
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.get(&Element::Damage), Style::new(Some(Color::Magenta)));
+        assert_eq!(theme.get(&Element::Field(FieldType::Custom("lava".to_owned()))),
+                   Style::new(Some(Color::Red)).bold());
+        assert_eq!(theme.get(&Element::Title), Theme::dark().get(&Element::Title));
+
+        assert_eq!(Theme::from_json(&theme.to_json()).unwrap(), theme);
+    }
+}
@@ -0,0 +1,159 @@
+use data::{self, DataError};
+use format::Format;
+use item::Item;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A hand-made item with fixed stats that exists only once per save, e.g. a legendary sword
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UniqueItem {
+    /// The unique id of the item
+    pub id: String,
+    /// The item itself
+    pub item: Item,
+    /// The story of the item shown to the player
+    pub lore: String,
+}
+
+impl UniqueItem {
+    /// Creates a new instance of `UniqueItem`
+    pub fn new(id: &str, item: Item) -> UniqueItem {
+        UniqueItem {
+            id: id.to_owned(),
+            item,
+            lore: String::new(),
+        }
+    }
+
+    /// A builder method for setting the lore of the item
+    pub fn lore(mut self, lore: &str) -> UniqueItem {
+        self.lore = lore.to_owned();
+        self
+    }
+
+    /// Reads a unique item from a JSON object like
+    /// `{"id": "sting", "item": {...}, "lore": "Glows blue when orcs are near"}`
+    pub fn from_json(json: &Json) -> Result<UniqueItem, DataError> {
+        let object = data::as_object(json, "uniques")?;
+
+        Ok(UniqueItem::new(&data::string(object, "id")?,
+                           Item::from_json(data::field(object, "item")?)?)
+            .lore(&data::optional(object, "lore", data::string)?.unwrap_or_default()))
+    }
+}
+
+impl ToJson for UniqueItem {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("id".to_owned(), self.id.to_json());
+        object.insert("item".to_owned(), self.item.to_json());
+        object.insert("lore".to_owned(), self.lore.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// A registry of all unique items of a campaign
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct UniqueItems {
+    items: BTreeMap<String, UniqueItem>,
+}
+
+impl UniqueItems {
+    /// Creates a new, empty instance of `UniqueItems`
+    pub fn new() -> UniqueItems {
+        UniqueItems::default()
+    }
+
+    /// Adds a unique item. Fails if an item with the same id was added before.
+    pub fn add(&mut self, unique: UniqueItem) -> Result<(), DataError> {
+        if self.items.contains_key(&unique.id) {
+            return Err(DataError::Invalid("id".to_owned()));
+        }
+
+        self.items.insert(unique.id.clone(), unique);
+        Ok(())
+    }
+
+    /// Returns the unique item with the given id
+    pub fn get(&self, id: &str) -> Option<&UniqueItem> {
+        self.items.get(id)
+    }
+
+    /// Returns the unique item with the given id unless it was found in this save before, and
+    /// marks it as found
+    pub fn draw(&self, id: &str, found: &mut BTreeSet<String>) -> Option<Item> {
+        let unique = self.items.get(id)?;
+        if !found.insert(id.to_owned()) {
+            return None;
+        }
+
+        Some(unique.item.clone())
+    }
+
+    /// Loads all unique items from a JSON object like `{"uniques": [...]}`. A bare array of
+    /// unique items is read as well.
+    pub fn load_json(&mut self, json: &Json) -> Result<(), DataError> {
+        let uniques = match *json {
+            Json::Array(_) => json,
+            _ => data::field(data::as_object(json, "uniques")?, "uniques")?,
+        };
+
+        for unique in data::as_array(uniques, "uniques")? {
+            self.add(UniqueItem::from_json(unique)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads all unique items from a file. The format is chosen by the extension, JSON by
+    /// default.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DataError> {
+        let format = Format::from_path(&path).unwrap_or(Format::Json);
+        self.load_json(&format.read_file(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::ItemType;
+    use item_generator::ItemGenerator;
+    use rustc_serialize::json::ToJson;
+    use std::collections::BTreeSet;
+    use std::env;
+
+    #[test]
+    fn draw_once() {
+        let mut uniques = UniqueItems::new();
+        let sword = ItemGenerator::new().name("Glamdring").item_type(ItemType::WeaponSword).gen();
+        uniques.add(UniqueItem::new("glamdring", sword.clone()).lore("The Foe-hammer")).unwrap();
+        assert!(uniques.add(UniqueItem::new("glamdring", sword.clone())).is_err());
+
+        let mut found = BTreeSet::new();
+        assert_eq!(uniques.draw("glamdring", &mut found), Some(sword));
+        assert_eq!(uniques.draw("glamdring", &mut found), None);
+        assert_eq!(uniques.draw("orcrist", &mut found), None);
+        assert_eq!(uniques.get("glamdring").unwrap().lore, "The Foe-hammer");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let unique = UniqueItem::new("lembas", ItemGenerator::new().name("Lembas").gen())
+            .lore("Elvish waybread");
+
+        let mut uniques = UniqueItems::new();
+        uniques.load_json(&vec![unique.to_json()].to_json()).unwrap();
+        assert_eq!(uniques.get("lembas"), Some(&unique));
+
+        let path = env::temp_dir().join("txtrpg_uniques.ron");
+        let mut object = Object::new();
+        object.insert("uniques".to_owned(), vec![unique.to_json()].to_json());
+        Format::Ron.write_file(&path, &Json::Object(object)).unwrap();
+
+        let mut loaded = UniqueItems::new();
+        loaded.load_file(&path).unwrap();
+        assert_eq!(loaded.get("lembas"), Some(&unique));
+    }
+}
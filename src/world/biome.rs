@@ -0,0 +1,325 @@
+use crafting::Station;
+use loot::LootTable;
+use std::collections::HashMap;
+use super::chunked::{Chunk, ChunkSource, CHUNK_SIZE};
+use super::spawn::EncounterTable;
+use super::two_dimensional::{Field, FieldType, World2d};
+
+/// The height of the highest possible field
+const HEIGHT_SCALE: f64 = 10.0;
+
+/// The amount of noise layers added up, each with half the size of the previous one
+const OCTAVES: u32 = 4;
+
+/// A region of an overworld with its own terrain
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Biome {
+    /// Open grassland
+    Plains,
+    /// Dry, sandy land
+    Desert,
+    /// Low land filled with water
+    Lake,
+    /// High, rocky land
+    Mountains,
+}
+
+impl Biome {
+    /// Returns the field type the biome consists of
+    pub fn field_type(&self) -> FieldType {
+        match *self {
+            Biome::Plains => FieldType::Grass,
+            Biome::Desert => FieldType::Sand,
+            Biome::Lake => FieldType::Water,
+            Biome::Mountains => FieldType::Stone,
+        }
+    }
+}
+
+/// Creates coherent overworlds from two noise maps. The elevation separates lakes, land and
+/// mountains, the moisture separates deserts from plains.
+pub struct BiomeGenerator {
+    /// The seed of the noise maps. The same seed always creates the same terrain
+    pub seed: u32,
+    /// The amount of fields a single noise value spans. Larger values create larger regions
+    pub scale: f64,
+    /// The elevation (`0.0` to `1.0`) below which there is water
+    pub water_level: f64,
+    /// The elevation (`0.0` to `1.0`) above which there are mountains
+    pub mountain_level: f64,
+    /// The moisture (`0.0` to `1.0`) below which there is desert
+    pub dry_level: f64,
+    encounter_tables: HashMap<Biome, EncounterTable>,
+    loot_tables: HashMap<Biome, LootTable>,
+    stations: Vec<(Biome, Station)>,
+}
+
+impl BiomeGenerator {
+    /// Creates a new instance of `BiomeGenerator`
+    pub fn new(seed: u32) -> BiomeGenerator {
+        BiomeGenerator {
+            seed,
+            scale: 16.0,
+            water_level: 0.35,
+            mountain_level: 0.7,
+            dry_level: 0.35,
+            encounter_tables: HashMap::new(),
+            loot_tables: HashMap::new(),
+            stations: Vec::new(),
+        }
+    }
+
+    /// A builder method for setting the size of the regions
+    pub fn scale(mut self, scale: f64) -> BiomeGenerator {
+        self.scale = scale;
+        self
+    }
+
+    /// A builder method for binding random encounters to a biome
+    pub fn encounter_table(mut self, biome: Biome, table: EncounterTable) -> BiomeGenerator {
+        self.encounter_tables.insert(biome, table);
+        self
+    }
+
+    /// A builder method for binding the items that can be found to a biome
+    pub fn loot_table(mut self, biome: Biome, table: LootTable) -> BiomeGenerator {
+        self.loot_tables.insert(biome, table);
+        self
+    }
+
+    /// A builder method for placing a crafting station, e.g. a campfire, in the given biome of
+    /// every generated world. The field is chosen by the seed; nothing is placed if the world
+    /// has no field of the biome.
+    pub fn station(mut self, biome: Biome, station: Station) -> BiomeGenerator {
+        self.stations.push((biome, station));
+        self
+    }
+
+    /// Returns the elevation (`0.0` to `1.0`) at the given position
+    pub fn elevation(&self, x: i64, y: i64) -> f64 {
+        fractal_noise(self.seed, x as f64 / self.scale, y as f64 / self.scale)
+    }
+
+    /// Returns the moisture (`0.0` to `1.0`) at the given position
+    pub fn moisture(&self, x: i64, y: i64) -> f64 {
+        fractal_noise(self.seed.wrapping_add(1), x as f64 / self.scale, y as f64 / self.scale)
+    }
+
+    /// Returns the biome at the given position
+    pub fn biome(&self, x: i64, y: i64) -> Biome {
+        let elevation = self.elevation(x, y);
+
+        if elevation < self.water_level {
+            Biome::Lake
+        } else if elevation > self.mountain_level {
+            Biome::Mountains
+        } else if self.moisture(x, y) < self.dry_level {
+            Biome::Desert
+        } else {
+            Biome::Plains
+        }
+    }
+
+    /// Returns the field at the given position, with its height following the elevation
+    pub fn field(&self, x: i64, y: i64) -> Field {
+        let height = (self.elevation(x, y) * HEIGHT_SCALE) as i32;
+
+        Field::new(self.biome(x, y).field_type()).height(height)
+    }
+
+    /// Generates a world of the given size with the encounters and loot of all biomes
+    pub fn generate(&self, name: &str, size: (usize, usize)) -> World2d {
+        let mut world = World2d::new(name, size);
+
+        for x in 0..size.0 {
+            for y in 0..size.1 {
+                world.set_field(self.field(x as i64, y as i64), (x, y));
+            }
+        }
+
+        for (biome, table) in &self.encounter_tables {
+            world = world.encounter_table(biome.field_type(), table.clone());
+        }
+
+        for (biome, table) in &self.loot_tables {
+            world = world.loot_table(biome.field_type(), table.clone());
+        }
+
+        for (index, &(biome, station)) in self.stations.iter().enumerate() {
+            let seed = self.seed.wrapping_add(2 + index as u32);
+            let position = (0..size.0)
+                .flat_map(|x| (0..size.1).map(move |y| (x, y)))
+                .filter(|&position| {
+                    let field = world.get_field(position);
+                    field.field_type == biome.field_type() && field.station.is_none()
+                })
+                .max_by(|&(ax, ay), &(bx, by)| {
+                    grid_value(seed, ax as i64, ay as i64)
+                        .partial_cmp(&grid_value(seed, bx as i64, by as i64))
+                        .expect("Grid values are never NaN")
+                });
+            if let Some(position) = position {
+                let field = world.get_field(position).clone().station(station);
+                world.set_field(field, position);
+            }
+        }
+
+        world
+    }
+}
+
+impl ChunkSource for BiomeGenerator {
+    fn load_chunk(&mut self, coords: (i64, i64)) -> Chunk {
+        let size = CHUNK_SIZE as i64;
+        let mut chunk = Chunk::new(Field::new(FieldType::Grass));
+
+        for x in 0..CHUNK_SIZE {
+            for y in 0..CHUNK_SIZE {
+                chunk.set(x, y, self.field(coords.0 * size + x as i64, coords.1 * size + y as i64));
+            }
+        }
+
+        chunk
+    }
+}
+
+/// Returns a pseudo-random value (`0.0` to `1.0`) for the given grid point
+fn grid_value(seed: u32, x: i64, y: i64) -> f64 {
+    let mut hash = (seed as u64) ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^
+                   (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+    hash ^= hash >> 33;
+
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Smoothly interpolates the grid values around the given point
+fn value_noise(seed: u32, x: f64, y: f64) -> f64 {
+    let (cell_x, cell_y) = (x.floor(), y.floor());
+    let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+    let (weight_x, weight_y) = (smooth(x - cell_x), smooth(y - cell_y));
+    let (cell_x, cell_y) = (cell_x as i64, cell_y as i64);
+
+    let top = grid_value(seed, cell_x, cell_y) * (1.0 - weight_x) +
+              grid_value(seed, cell_x + 1, cell_y) * weight_x;
+    let bottom = grid_value(seed, cell_x, cell_y + 1) * (1.0 - weight_x) +
+                 grid_value(seed, cell_x + 1, cell_y + 1) * weight_x;
+
+    top * (1.0 - weight_y) + bottom * weight_y
+}
+
+/// Adds up several layers of value noise, each with half the size and influence of the previous
+/// one. The result stays between `0.0` and `1.0`.
+fn fractal_noise(seed: u32, x: f64, y: f64) -> f64 {
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut max = 0.0;
+
+    for octave in 0..OCTAVES {
+        total += value_noise(seed.wrapping_add(octave * 7919), x * frequency, y * frequency) *
+                 amplitude;
+        max += amplitude;
+        amplitude /= 2.0;
+        frequency *= 2.0;
+    }
+
+    total / max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item::ItemType;
+    use loot::{LootEntry, LootTable};
+    use world::chunked::{ChunkSource, CHUNK_SIZE};
+    use world::spawn::EncounterTable;
+    use world::two_dimensional::{FieldType, World2d};
+
+    #[test]
+    fn deterministic() {
+        let generator = BiomeGenerator::new(42);
+        let world = generator.generate("Overworld", (20, 20));
+        let again = BiomeGenerator::new(42).generate("Overworld", (20, 20));
+
+        for x in 0..20 {
+            for y in 0..20 {
+                assert_eq!(world.get_field((x, y)).field_type, again.get_field((x, y)).field_type);
+            }
+        }
+
+        for &(x, y) in &[(0, 0), (-500, 13), (7, 123_456)] {
+            let elevation = generator.elevation(x, y);
+            assert!((0.0..=1.0).contains(&elevation));
+        }
+    }
+
+    #[test]
+    fn coherent_regions() {
+        let generator = BiomeGenerator::new(7);
+        let mut same = 0;
+
+        for x in 0..50 {
+            for y in 0..50 {
+                if generator.biome(x, y) == generator.biome(x + 1, y) {
+                    same += 1;
+                }
+            }
+        }
+
+        assert!(same > 2000);
+    }
+
+    #[test]
+    fn bindings() {
+        let world = BiomeGenerator::new(1)
+            .encounter_table(Biome::Desert, EncounterTable::new(0.1).encounter("scorpion", 1, 1))
+            .loot_table(Biome::Lake,
+                        LootTable::new().entry(LootEntry::new(ItemType::ConsumableDrink, 1.0)))
+            .generate("Dunes", (5, 5));
+
+        assert!(world.encounter_tables.contains_key(&FieldType::Sand));
+        assert!(!world.encounter_tables.contains_key(&FieldType::Grass));
+        assert!(world.loot_tables.contains_key(&FieldType::Water));
+    }
+
+    #[test]
+    fn stations() {
+        let generator = BiomeGenerator::new(3)
+            .station(Biome::Plains, Station::Campfire)
+            .station(Biome::Mountains, Station::Forge);
+        let placed = |world: &World2d| -> Vec<_> {
+            (0..30)
+                .flat_map(|x| (0..30).map(move |y| (x, y)))
+                .filter_map(|position| {
+                    world.get_field(position).station.map(|station| (position, station))
+                })
+                .collect()
+        };
+        let world = generator.generate("Overworld", (30, 30));
+        let stations = placed(&world);
+
+        assert!(!stations.is_empty() && stations.len() <= 2);
+        for &(position, station) in &stations {
+            let biome = if station == Station::Campfire {
+                Biome::Plains
+            } else {
+                Biome::Mountains
+            };
+            assert_eq!(world.get_field(position).field_type, biome.field_type());
+        }
+        assert_eq!(placed(&generator.generate("Overworld", (30, 30))), stations);
+    }
+
+    #[test]
+    fn seamless_chunks() {
+        let mut generator = BiomeGenerator::new(3);
+        let chunk = generator.load_chunk((-1, 2));
+        let size = CHUNK_SIZE as i64;
+
+        assert_eq!(chunk.get(5, 9).field_type, generator.field(5 - size, 2 * size + 9).field_type);
+    }
+}
@@ -1,10 +1,52 @@
-use super::World;
+use character::Character;
+use data::DataError;
+use inventory::Inventory;
+use locale::Catalog;
+use super::{Teleport, World};
+use super::manifest::CampaignManifest;
+use super::portal::Portal;
+use super::progress::CampaignProgress;
+use super::streaming::LevelSource;
+use super::waypoint::{self, TravelError, Waypoint};
+use time::{WorldClock, DEFAULT_DAY_LENGTH, HOURS_PER_DAY};
 
-/// A collection of worlds. Usually used to create larger adventures
+/// A way from one world of a campaign to another
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Successor {
+    /// The id of the following world
+    pub id: String,
+    /// The quest flags that have to be set before the way opens
+    pub required_flags: Vec<String>,
+}
+
+/// A world of a campaign together with the worlds that may follow it
+pub struct CampaignNode<T: World> {
+    /// The unique id of the world within the campaign
+    pub id: String,
+    /// The world itself, `None` while it is not loaded from the level source of the campaign
+    pub world: Option<T>,
+    /// The worlds that may follow this one
+    pub successors: Vec<Successor>,
+    /// `true` if the player may fast-travel from and to this world
+    pub fast_travel: bool,
+}
+
+/// A collection of worlds connected to a graph. Usually used to create larger adventures with
+/// branching paths and optional side areas
 pub struct Campaign<T: World> {
     /// The title of the campaign
     pub title: String,
-    worlds: Vec<T>,
+    /// `true` if characters have to eat, drink and sleep in this campaign
+    pub survival: bool,
+    /// The author, version and other details of a shared campaign (optional)
+    pub manifest: Option<CampaignManifest>,
+    day_length: u64,
+    translations: Vec<Catalog>,
+    nodes: Vec<CampaignNode<T>>,
+    portals: Vec<Portal<T::Position>>,
+    waypoints: Vec<Waypoint<T::Position>>,
+    progress: CampaignProgress,
+    source: Option<Box<dyn LevelSource<T>>>,
 }
 
 impl<T: World> Campaign<T> {
@@ -12,12 +54,522 @@ impl<T: World> Campaign<T> {
     pub fn new(title: &str) -> Campaign<T> {
         Campaign {
             title: title.to_owned(),
-            worlds: Vec::new(),
+            day_length: DEFAULT_DAY_LENGTH,
+            survival: false,
+            manifest: None,
+            translations: Vec::new(),
+            nodes: Vec::new(),
+            portals: Vec::new(),
+            waypoints: Vec::new(),
+            progress: CampaignProgress::new(""),
+            source: None,
         }
     }
 
-    /// Adds a world to the campaign
+    /// A builder method for setting the amount of turns a day lasts.
+    ///
+    /// # Panics
+    ///
+    /// **Panics** if the day length is not a non-zero multiple of `HOURS_PER_DAY`
+    pub fn day_length(mut self, day_length: u64) -> Campaign<T> {
+        assert!(day_length > 0 && day_length.is_multiple_of(HOURS_PER_DAY));
+        self.day_length = day_length;
+        self
+    }
+
+    /// Returns the amount of turns a day lasts in this campaign
+    pub fn get_day_length(&self) -> u64 {
+        self.day_length
+    }
+
+    /// A builder method for turning the survival needs on or off
+    pub fn survival(mut self, survival: bool) -> Campaign<T> {
+        self.survival = survival;
+        self
+    }
+
+    /// A builder method for describing the campaign
+    pub fn manifest(mut self, manifest: CampaignManifest) -> Campaign<T> {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// A builder method for shipping a translation of the strings of the campaign
+    pub fn translation(mut self, catalog: Catalog) -> Campaign<T> {
+        self.translations.push(catalog);
+        self
+    }
+
+    /// Returns all translations shipped with the campaign
+    pub fn translations(&self) -> &[Catalog] {
+        &self.translations
+    }
+
+    /// Returns the translation into the given locale, or the built-in English strings if the
+    /// campaign does not ship one
+    pub fn catalog(&self, locale: &str) -> Catalog {
+        self.translations
+            .iter()
+            .find(|catalog| catalog.locale() == locale)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// A builder method for loading worlds on demand from the given source. Only the world the
+    /// player is in stays loaded, all others are evicted when leaving them.
+    pub fn stream_from<S: LevelSource<T> + 'static>(mut self, source: S) -> Campaign<T> {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Prepares a character for playing this campaign, e.g. by turning its survival needs on
+    pub fn prepare(&self, character: &mut Character) {
+        character.set_survival(self.survival);
+    }
+
+    /// Creates a new world clock running at the day length of the campaign
+    pub fn clock(&self) -> WorldClock {
+        WorldClock::new().day_length(self.day_length)
+    }
+
+    /// Adds a world to the campaign. Its id is its index and it follows the previously added
+    /// world, which keeps simple campaigns linear.
     pub fn add_world(&mut self, world: T) {
-        self.worlds.push(world);
+        let id = self.nodes.len().to_string();
+        let previous = self.nodes.last().map(|node| node.id.clone());
+
+        self.add_level(&id, world);
+
+        if let Some(previous) = previous {
+            self.link(&previous, &id, &[]);
+        }
+    }
+
+    /// Adds a world with the given id to the campaign without linking it to any other world
+    ///
+    /// # Panics
+    /// Panics if the id is already taken
+    pub fn add_level(&mut self, id: &str, world: T) {
+        self.add_node(id, Some(world));
+    }
+
+    /// Adds a world with the given id, which is loaded from the level source once the player
+    /// enters it
+    ///
+    /// # Panics
+    /// Panics if the id is already taken
+    pub fn add_streamed_level(&mut self, id: &str) {
+        self.add_node(id, None);
+    }
+
+    fn add_node(&mut self, id: &str, world: Option<T>) {
+        assert!(self.index_of(id).is_none());
+
+        if self.nodes.is_empty() {
+            self.progress.active = id.to_owned();
+        }
+
+        self.nodes.push(CampaignNode {
+            id: id.to_owned(),
+            world,
+            successors: Vec::new(),
+            fast_travel: true,
+        });
+    }
+
+    /// Lets the world with the id `to` follow the world with the id `from`, once all of the
+    /// given quest flags are set
+    ///
+    /// # Panics
+    /// Panics if one of the worlds does not exist
+    pub fn link(&mut self, from: &str, to: &str, required_flags: &[&str]) {
+        assert!(self.index_of(to).is_some());
+        let from = self.index_of(from).expect("Unable to find world");
+
+        self.nodes[from].successors.push(Successor {
+            id: to.to_owned(),
+            required_flags: required_flags.iter().map(|flag| (*flag).to_owned()).collect(),
+        });
+    }
+
+    /// Allows or forbids fast travel from and to the world with the given id, e.g. inside a
+    /// dungeon the player has to fight their way out of. Fast travel is allowed by default.
+    ///
+    /// # Panics
+    /// Panics if the world does not exist
+    pub fn set_fast_travel(&mut self, id: &str, allowed: bool) {
+        let index = self.index_of(id).expect("Unable to find world");
+        self.nodes[index].fast_travel = allowed;
+    }
+
+    /// Returns the index of the world with the given id
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+
+    /// Returns the world with the given index together with its id and successors
+    pub fn node(&self, index: usize) -> Option<&CampaignNode<T>> {
+        self.nodes.get(index)
+    }
+
+    /// Returns the amount of worlds in the campaign
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the campaign contains no worlds
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the index of the world the player is currently in
+    pub fn current_index(&self) -> usize {
+        self.index_of(&self.progress.active).unwrap_or(0)
+    }
+
+    /// Returns the world the player is currently in, if it is loaded
+    pub fn current_world(&self) -> Option<&T> {
+        let current = self.current_index();
+        self.nodes.get(current).and_then(|node| node.world.as_ref())
+    }
+
+    /// Returns the world the player is currently in as mutable, if it is loaded
+    pub fn current_world_mut(&mut self) -> Option<&mut T> {
+        let current = self.current_index();
+        self.nodes.get_mut(current).and_then(|node| node.world.as_mut())
+    }
+
+    /// Returns `true` if the world with the given id is in memory
+    pub fn is_loaded(&self, id: &str) -> bool {
+        self.index_of(id).is_some_and(|index| self.nodes[index].world.is_some())
+    }
+
+    /// Loads the world with the given id from the level source, unless it is loaded already
+    ///
+    /// # Panics
+    /// Panics if the world does not exist
+    pub fn load(&mut self, id: &str) -> Result<(), DataError> {
+        let index = self.index_of(id).expect("Unable to find world");
+        if self.nodes[index].world.is_some() {
+            return Ok(());
+        }
+
+        let world = match self.source {
+            Some(ref source) => source.load_level(id)?,
+            None => return Err(DataError::Missing(id.to_owned())),
+        };
+        self.nodes[index].world = Some(world);
+        Ok(())
+    }
+
+    /// Loads the world the player is currently in, e.g. after restoring a saved game
+    pub fn load_active(&mut self) -> Result<(), DataError> {
+        let active = self.progress.active.clone();
+        self.load(&active)
+    }
+
+    /// Drops all worlds except the one the player is in. Does nothing without a level source, as
+    /// the worlds could not be loaded again.
+    pub fn evict_unused(&mut self) {
+        if self.source.is_none() {
+            return;
+        }
+
+        let current = self.current_index();
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            if index != current {
+                node.world = None;
+            }
+        }
+    }
+
+    /// Returns the progress of the player
+    pub fn progress(&self) -> &CampaignProgress {
+        &self.progress
+    }
+
+    /// Returns a copy of the progress that can be restored later
+    pub fn checkpoint(&self) -> CampaignProgress {
+        self.progress.clone()
+    }
+
+    /// Restores the progress of a checkpoint or a saved game
+    pub fn restore(&mut self, progress: CampaignProgress) {
+        self.progress = progress;
+    }
+
+    /// Sets a quest flag, which may open the way to further worlds
+    pub fn set_flag(&mut self, flag: &str) {
+        self.progress.set_flag(flag);
+    }
+
+    /// Returns `true` if the given quest flag is set
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.progress.has_flag(flag)
+    }
+
+    /// Returns the ids of all worlds that can follow the current one with the quest flags set so
+    /// far
+    pub fn available_successors(&self) -> Vec<&str> {
+        let node = match self.nodes.get(self.current_index()) {
+            Some(node) => node,
+            None => return Vec::new(),
+        };
+
+        node.successors
+            .iter()
+            .filter(|successor| {
+                successor.required_flags.iter().all(|flag| self.progress.has_flag(flag))
+            })
+            .map(|successor| &successor.id[..])
+            .collect()
+    }
+
+    /// Marks the current world as completed and moves on to the world with the given id.
+    /// Returns `false` if the current world is not finished yet, the world is not an available
+    /// successor or it could not be loaded.
+    pub fn advance(&mut self, id: &str) -> bool {
+        let finished = self.current_world().is_some_and(|world| world.is_finished());
+        if !finished || !self.available_successors().contains(&id) || self.load(id).is_err() {
+            return false;
+        }
+
+        let completed = self.progress.active.clone();
+        self.progress.completed.insert(completed);
+        self.progress.active = id.to_owned();
+        self.evict_unused();
+        true
+    }
+
+    /// Adds a portal linking two worlds of the campaign
+    ///
+    /// # Panics
+    /// Panics if one of the linked worlds does not exist
+    pub fn add_portal(&mut self, portal: Portal<T::Position>) {
+        assert!(portal.from_world < self.nodes.len() && portal.to_world < self.nodes.len());
+        self.portals.push(portal);
+    }
+
+    /// Returns all portals of the campaign
+    pub fn portals(&self) -> &[Portal<T::Position>] {
+        &self.portals
+    }
+
+    /// Returns the portal at the given position of the given world
+    pub fn portal_at(&self, world: usize, position: &T::Position) -> Option<&Portal<T::Position>> {
+        self.portals
+            .iter()
+            .find(|portal| portal.from_world == world && &portal.from == position)
+    }
+
+    /// Adds a waypoint the player can fast-travel to once discovered
+    ///
+    /// # Panics
+    /// Panics if the world of the waypoint does not exist or the name is already taken
+    pub fn add_waypoint(&mut self, waypoint: Waypoint<T::Position>) {
+        assert!(waypoint.world < self.nodes.len());
+        assert!(self.waypoints.iter().all(|other| other.name != waypoint.name));
+        self.waypoints.push(waypoint);
+    }
+
+    /// Returns all waypoints of the campaign
+    pub fn waypoints(&self) -> &[Waypoint<T::Position>] {
+        &self.waypoints
+    }
+
+    /// Returns the waypoints discovered so far that can be travelled to
+    pub fn travel_destinations(&self) -> Vec<&Waypoint<T::Position>> {
+        self.waypoints
+            .iter()
+            .filter(|waypoint| {
+                self.progress.discovered_waypoints.contains(&waypoint.name) &&
+                self.nodes[waypoint.world].fast_travel
+            })
+            .collect()
+    }
+
+    /// Discovers the waypoint at the player's position. Returns its name if it was not
+    /// discovered before.
+    pub fn discover_waypoint(&mut self) -> Option<String> {
+        let name = self.waypoint_here()?.name.clone();
+        if self.progress.discovered_waypoints.insert(name.clone()) {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    fn waypoint_here(&self) -> Option<&Waypoint<T::Position>> {
+        let current = self.current_index();
+        let position = self.current_world()?.get_position();
+        self.waypoints
+            .iter()
+            .find(|waypoint| waypoint.world == current && waypoint.position == position)
+    }
+}
+
+impl<T: Teleport> Campaign<T> {
+    /// Takes the portal at the player's position to its destination and remembers it as
+    /// discovered. Returns `false` if there is no portal or its destination could not be loaded.
+    pub fn use_portal(&mut self) -> bool {
+        let current = self.current_index();
+        let destination = match self.current_world() {
+            Some(world) => {
+                let position = world.get_position();
+                self.portals
+                    .iter()
+                    .position(|portal| portal.from_world == current && portal.from == position)
+            }
+            None => None,
+        };
+
+        match destination {
+            Some(index) => {
+                let (world, position) = (self.portals[index].to_world,
+                                         self.portals[index].to.clone());
+                let id = self.nodes[world].id.clone();
+                if self.load(&id).is_err() {
+                    return false;
+                }
+
+                self.progress.discovered_portals.insert(index);
+                self.progress.active = id;
+                self.evict_unused();
+                if let Some(ref mut world) = self.nodes[world].world {
+                    world.set_position(position);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Travels from the discovered waypoint the player stands on to the discovered waypoint with
+    /// the given name
+    pub fn fast_travel(&mut self, name: &str) -> Result<(), TravelError> {
+        let here = self.waypoint_here()
+            .is_some_and(|waypoint| self.progress.discovered_waypoints.contains(&waypoint.name));
+        if !here {
+            return Err(TravelError::NotAtWaypoint);
+        }
+
+        self.travel_to(name)
+    }
+
+    /// Reads a scroll of teleportation from the inventory to travel to the discovered waypoint
+    /// with the given name from anywhere. The scroll is only used up if the travel succeeds.
+    pub fn read_scroll(&mut self,
+                       name: &str,
+                       inventory: &mut Inventory)
+                       -> Result<(), TravelError> {
+        if inventory.count_by_name(waypoint::SCROLL_NAME) == 0 {
+            return Err(TravelError::NoScroll);
+        }
+
+        self.travel_to(name)?;
+        inventory.take_by_name(waypoint::SCROLL_NAME);
+        Ok(())
+    }
+
+    fn travel_to(&mut self, name: &str) -> Result<(), TravelError> {
+        let index = self.waypoints
+            .iter()
+            .position(|waypoint| waypoint.name == name)
+            .ok_or(TravelError::Unknown)?;
+        if !self.progress.discovered_waypoints.contains(name) {
+            return Err(TravelError::Undiscovered);
+        }
+
+        let (world, position) = (self.waypoints[index].world,
+                                 self.waypoints[index].position.clone());
+        if !self.nodes[self.current_index()].fast_travel || !self.nodes[world].fast_travel {
+            return Err(TravelError::Forbidden);
+        }
+
+        let id = self.nodes[world].id.clone();
+        self.load(&id).map_err(|_| TravelError::NotLoaded)?;
+        self.progress.active = id;
+        self.evict_unused();
+        if let Some(ref mut world) = self.nodes[world].world {
+            world.set_position(position);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locale::{Catalog, DEFAULT_LOCALE};
+    use world::Teleport;
+    use world::two_dimensional::World2d;
+
+    fn world(name: &str) -> World2d {
+        World2d::new(name, (5, 5)).end_point((4, 4))
+    }
+
+    #[test]
+    fn linear() {
+        let mut campaign = Campaign::new("Straight Ahead");
+        campaign.add_world(world("First"));
+        campaign.add_world(world("Second"));
+
+        assert_eq!(campaign.available_successors(), vec!["1"]);
+        assert!(!campaign.advance("1"));
+
+        campaign.current_world_mut().unwrap().set_position((4, 4));
+        assert!(campaign.advance("1"));
+        assert_eq!(campaign.current_world().unwrap().name, "Second");
+    }
+
+    #[test]
+    fn branching() {
+        let mut campaign = Campaign::new("Crossroads");
+        campaign.add_level("village", world("Village"));
+        campaign.add_level("forest", world("Forest"));
+        campaign.add_level("crypt", world("Crypt"));
+        campaign.link("village", "forest", &[]);
+        campaign.link("village", "crypt", &["found_crypt_key"]);
+
+        campaign.current_world_mut().unwrap().set_position((4, 4));
+        assert_eq!(campaign.available_successors(), vec!["forest"]);
+        assert!(!campaign.advance("crypt"));
+
+        campaign.set_flag("found_crypt_key");
+        assert_eq!(campaign.available_successors(), vec!["forest", "crypt"]);
+        assert!(campaign.advance("crypt"));
+        assert_eq!(campaign.current_index(), 2);
+        assert!(campaign.progress().is_completed("village"));
+    }
+
+    #[test]
+    fn checkpoint() {
+        let mut campaign = Campaign::new("Save Scumming");
+        campaign.add_world(world("First"));
+        campaign.add_world(world("Second"));
+        let checkpoint = campaign.checkpoint();
+
+        campaign.current_world_mut().unwrap().set_position((4, 4));
+        campaign.set_flag("lever_pulled");
+        assert!(campaign.advance("1"));
+
+        campaign.restore(checkpoint);
+        assert_eq!(campaign.current_index(), 0);
+        assert!(!campaign.has_flag("lever_pulled"));
+    }
+
+    #[test]
+    fn translations() {
+        let campaign: Campaign<World2d> = Campaign::new("Abroad")
+            .translation(Catalog::new("de").string("field.Grass", "Gras"));
+
+        assert_eq!(campaign.catalog("de").get("field.Grass"), "Gras");
+        assert_eq!(campaign.catalog("fr").locale(), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_day_length() {
+        let _: Campaign<World2d> = Campaign::new("Endless Night").day_length(25);
     }
 }
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use super::{Teleport, World};
+use super::two_dimensional::{Field, FieldType};
+
+/// The width and height of a chunk in fields
+pub const CHUNK_SIZE: usize = 32;
+
+/// A square section of a chunked world, stored row by row
+#[derive(Clone)]
+pub struct Chunk {
+    fields: Vec<Field>,
+}
+
+impl Chunk {
+    /// Creates a new instance of `Chunk` filled with the given field
+    pub fn new(field: Field) -> Chunk {
+        Chunk { fields: vec![field; CHUNK_SIZE * CHUNK_SIZE] }
+    }
+
+    /// Returns the field at the given position inside of the chunk
+    pub fn get(&self, x: usize, y: usize) -> &Field {
+        &self.fields[y * CHUNK_SIZE + x]
+    }
+
+    /// Sets the field at the given position inside of the chunk
+    pub fn set(&mut self, x: usize, y: usize, field: Field) {
+        self.fields[y * CHUNK_SIZE + x] = field;
+    }
+}
+
+/// Generates or loads the chunks of a chunked world
+pub trait ChunkSource {
+    /// Returns the chunk at the given chunk coordinates
+    fn load_chunk(&mut self, coords: (i64, i64)) -> Chunk;
+
+    /// Keeps a chunk that is no longer around the player. Discards it by default, which is fine
+    /// as long as chunks are generated and never changed.
+    fn store_chunk(&mut self, _coords: (i64, i64), _chunk: Chunk) {}
+}
+
+/// A chunk source creating chunks consisting of a single field type
+pub struct FlatSource {
+    field_type: FieldType,
+}
+
+impl FlatSource {
+    /// Creates a new instance of `FlatSource`
+    pub fn new(field_type: FieldType) -> FlatSource {
+        FlatSource { field_type }
+    }
+}
+
+impl ChunkSource for FlatSource {
+    fn load_chunk(&mut self, _coords: (i64, i64)) -> Chunk {
+        Chunk::new(Field::new(self.field_type.clone()))
+    }
+}
+
+/// A world without fixed bounds, e.g. a large overworld. Only the chunks around the player are
+/// kept in memory, all others are handed back to the chunk source.
+pub struct ChunkedWorld {
+    /// The name or title of the world
+    pub name: String,
+    /// The point where the world is finished (optional)
+    pub end_point: Option<(i64, i64)>,
+    /// The amount of chunks in each direction that are kept around the player
+    pub view_distance: i64,
+    current_position: (i64, i64),
+    chunks: HashMap<(i64, i64), Chunk>,
+    source: Box<dyn ChunkSource>,
+}
+
+impl ChunkedWorld {
+    /// Creates a new instance of `ChunkedWorld` loading the chunks around the origin
+    pub fn new<S: ChunkSource + 'static>(name: &str, source: S) -> ChunkedWorld {
+        let mut world = ChunkedWorld {
+            name: name.to_owned(),
+            end_point: None,
+            view_distance: 1,
+            current_position: (0, 0),
+            chunks: HashMap::new(),
+            source: Box::new(source),
+        };
+
+        world.update_chunks();
+        world
+    }
+
+    /// A builder method for setting the end point of the world
+    pub fn end_point(mut self, end_point: (i64, i64)) -> ChunkedWorld {
+        self.end_point = Some(end_point);
+        self
+    }
+
+    /// A builder method for setting the amount of chunks kept around the player
+    pub fn view_distance(mut self, view_distance: i64) -> ChunkedWorld {
+        self.view_distance = view_distance;
+        self.update_chunks();
+        self
+    }
+
+    /// Returns the coordinates of the chunk containing the given position
+    pub fn chunk_of(position: (i64, i64)) -> (i64, i64) {
+        (floor_div(position.0), floor_div(position.1))
+    }
+
+    /// Returns the amount of chunks in memory
+    pub fn loaded_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns `true` if the chunk with the given chunk coordinates is in memory
+    pub fn is_loaded(&self, coords: (i64, i64)) -> bool {
+        self.chunks.contains_key(&coords)
+    }
+
+    /// Returns the field at the given position, or `None` if its chunk is not loaded
+    pub fn get_field(&self, position: (i64, i64)) -> Option<&Field> {
+        let (x, y) = local(position);
+        self.chunks.get(&ChunkedWorld::chunk_of(position)).map(|chunk| chunk.get(x, y))
+    }
+
+    /// Sets the given field at the given position, loading its chunk if necessary
+    pub fn set_field(&mut self, field: Field, position: (i64, i64)) {
+        let (x, y) = local(position);
+        self.chunk_mut(ChunkedWorld::chunk_of(position)).set(x, y, field);
+    }
+
+    /// Returns `true` if the field at the given position is loaded and can be walked on
+    pub fn is_passable(&self, position: (i64, i64)) -> bool {
+        self.get_field(position).is_some_and(|field| field.field_type.is_passable())
+    }
+
+    fn chunk_mut(&mut self, coords: (i64, i64)) -> &mut Chunk {
+        if !self.chunks.contains_key(&coords) {
+            let chunk = self.source.load_chunk(coords);
+            self.chunks.insert(coords, chunk);
+        }
+
+        self.chunks.get_mut(&coords).unwrap()
+    }
+
+    fn update_chunks(&mut self) {
+        let (center_x, center_y) = ChunkedWorld::chunk_of(self.current_position);
+        let distance = self.view_distance;
+
+        let far: Vec<(i64, i64)> = self.chunks
+            .keys()
+            .filter(|&&(x, y)| (x - center_x).abs() > distance || (y - center_y).abs() > distance)
+            .cloned()
+            .collect();
+
+        for coords in far {
+            if let Some(chunk) = self.chunks.remove(&coords) {
+                self.source.store_chunk(coords, chunk);
+            }
+        }
+
+        for x in (center_x - distance)..(center_x + distance + 1) {
+            for y in (center_y - distance)..(center_y + distance + 1) {
+                self.chunk_mut((x, y));
+            }
+        }
+    }
+}
+
+impl World for ChunkedWorld {
+    type Position = (i64, i64);
+
+    type Movement = (i64, i64);
+
+    fn get_position(&self) -> Self::Position {
+        self.current_position
+    }
+
+    fn is_finished(&self) -> bool {
+        self.end_point == Some(self.current_position)
+    }
+
+    fn move_to(&mut self, movement: Self::Movement) {
+        let (pos_x, pos_y) = self.current_position;
+        self.current_position = (pos_x + movement.0, pos_y + movement.1);
+        self.update_chunks();
+    }
+}
+
+impl Teleport for ChunkedWorld {
+    fn set_position(&mut self, position: Self::Position) {
+        self.current_position = position;
+        self.update_chunks();
+    }
+}
+
+/// Divides a coordinate by the chunk size, rounding towards negative infinity
+fn floor_div(coord: i64) -> i64 {
+    let size = CHUNK_SIZE as i64;
+
+    if coord >= 0 { coord / size } else { (coord + 1) / size - 1 }
+}
+
+/// Returns the position inside of the chunk containing the given position
+fn local(position: (i64, i64)) -> (usize, usize) {
+    let size = CHUNK_SIZE as i64;
+
+    (((position.0 % size + size) % size) as usize, ((position.1 % size + size) % size) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use world::World;
+    use world::two_dimensional::{Field, FieldType};
+
+    struct MemorySource {
+        stored: HashMap<(i64, i64), Chunk>,
+    }
+
+    impl ChunkSource for MemorySource {
+        fn load_chunk(&mut self, coords: (i64, i64)) -> Chunk {
+            self.stored.remove(&coords).unwrap_or_else(|| Chunk::new(Field::new(FieldType::Grass)))
+        }
+
+        fn store_chunk(&mut self, coords: (i64, i64), chunk: Chunk) {
+            self.stored.insert(coords, chunk);
+        }
+    }
+
+    #[test]
+    fn chunk_coordinates() {
+        assert_eq!(ChunkedWorld::chunk_of((0, 31)), (0, 0));
+        assert_eq!(ChunkedWorld::chunk_of((32, -1)), (1, -1));
+        assert_eq!(ChunkedWorld::chunk_of((-32, -33)), (-1, -2));
+        assert_eq!(local((-1, 33)), (31, 1));
+    }
+
+    #[test]
+    fn streaming_chunks() {
+        let mut world = ChunkedWorld::new("Overworld", FlatSource::new(FieldType::Sand));
+        assert_eq!(world.loaded_chunks(), 9);
+        assert!(world.is_passable((-20, 40)));
+
+        world.move_to((200, 0));
+        assert_eq!(world.loaded_chunks(), 9);
+        assert!(world.is_loaded((6, 0)));
+        assert!(!world.is_loaded((0, 0)));
+        assert!(world.get_field((0, 0)).is_none());
+    }
+
+    #[test]
+    fn keep_changes() {
+        let source = MemorySource { stored: HashMap::new() };
+        let mut world = ChunkedWorld::new("Overworld", source).end_point((500, 500));
+        world.set_field(Field::new(FieldType::StoneWall), (-5, 3));
+
+        world.set_position((500, 500));
+        assert!(world.is_finished());
+        assert!(world.get_field((-5, 3)).is_none());
+
+        world.set_position((0, 0));
+        assert_eq!(world.get_field((-5, 3)).unwrap().field_type, FieldType::StoneWall);
+    }
+}
@@ -0,0 +1,219 @@
+use data::{self, DataError};
+use item::Item;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeMap;
+use summon::Ally;
+use time::{Tick, WorldClock};
+
+/// The id of an entity, unique within its registry
+pub type EntityId = u64;
+
+/// Something on the map that is not part of a field, e.g. an item lying on the ground
+#[derive(Clone)]
+pub enum MapEntity {
+    /// An item lying on the ground
+    Item(Item),
+    /// A summoned monster following the player, see `summon::Summon::on_map`
+    Ally(Box<Ally>),
+}
+
+/// An entity and where it is
+#[derive(Clone)]
+pub struct PlacedEntity {
+    /// The position of the entity
+    pub position: (usize, usize),
+    /// The entity itself
+    pub entity: MapEntity,
+}
+
+/// The entities of a world by their id. Iterates in the order the entities were added.
+#[derive(Clone, Default)]
+pub struct EntityRegistry {
+    entities: BTreeMap<EntityId, PlacedEntity>,
+    next_id: EntityId,
+}
+
+impl EntityRegistry {
+    /// Creates a new instance of `EntityRegistry` without entities
+    pub fn new() -> EntityRegistry {
+        EntityRegistry::default()
+    }
+
+    /// Places an entity at the given position and returns its id
+    pub fn add(&mut self, position: (usize, usize), entity: MapEntity) -> EntityId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entities.insert(id, PlacedEntity { position, entity });
+
+        id
+    }
+
+    /// Removes the entity with the given id and returns it
+    pub fn remove(&mut self, id: EntityId) -> Option<MapEntity> {
+        self.entities.remove(&id).map(|placed| placed.entity)
+    }
+
+    /// Returns the entity with the given id
+    pub fn get(&self, id: EntityId) -> Option<&PlacedEntity> {
+        self.entities.get(&id)
+    }
+
+    /// Returns the entity with the given id as mutable
+    pub fn get_mut(&mut self, id: EntityId) -> Option<&mut PlacedEntity> {
+        self.entities.get_mut(&id)
+    }
+
+    /// Moves the entity with the given id to the given position. Returns `false` if there is no
+    /// such entity.
+    pub fn move_to(&mut self, id: EntityId, position: (usize, usize)) -> bool {
+        match self.entities.get_mut(&id) {
+            Some(placed) => {
+                placed.position = position;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the ids of the entities at the given position
+    pub fn at(&self, position: (usize, usize)) -> Vec<EntityId> {
+        self.entities
+            .iter()
+            .filter(|&(_, placed)| placed.position == position)
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
+    /// Returns the items lying at the given position and their ids
+    pub fn items_at(&self, position: (usize, usize)) -> Vec<(EntityId, &Item)> {
+        self.entities
+            .iter()
+            .filter(|&(_, placed)| placed.position == position)
+            .filter_map(|(&id, placed)| match placed.entity {
+                MapEntity::Item(ref item) => Some((id, item)),
+                MapEntity::Ally(_) => None,
+            })
+            .collect()
+    }
+
+    /// Moves all allies to the given position, e.g. the field the player just left
+    pub fn move_allies(&mut self, position: (usize, usize)) {
+        for placed in self.entities.values_mut() {
+            if let MapEntity::Ally(_) = placed.entity {
+                placed.position = position;
+            }
+        }
+    }
+
+    /// Returns all entities and their ids
+    pub fn iter(&self) -> Vec<(EntityId, &PlacedEntity)> {
+        self.entities.iter().map(|(&id, placed)| (id, placed)).collect()
+    }
+
+    /// Returns the amount of entities
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no entities
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Reads the entities from a JSON array as written by `to_json`, keeping their ids. Allies
+    /// are not saved, they vanish when the game is loaded.
+    pub fn from_json(json: &Json) -> Result<EntityRegistry, DataError> {
+        let mut registry = EntityRegistry::new();
+
+        for entity in data::as_array(json, "entities")? {
+            let object = data::as_object(entity, "entities")?;
+            let id = data::unsigned(object, "id")?;
+            let entity = MapEntity::Item(Item::from_json(data::field(object, "item")?)?);
+            let placed = PlacedEntity {
+                position: data::position(object, "position")?,
+                entity,
+            };
+
+            if registry.entities.insert(id, placed).is_some() {
+                return Err(DataError::Invalid("id".to_owned()));
+            }
+            registry.next_id = registry.next_id.max(id + 1);
+        }
+
+        Ok(registry)
+    }
+}
+
+impl Tick for EntityRegistry {
+    fn tick(&mut self, _: &WorldClock) {
+        for placed in self.entities.values_mut() {
+            if let MapEntity::Ally(ref mut ally) = placed.entity {
+                ally.turns_left = ally.turns_left.saturating_sub(1);
+            }
+        }
+        self.entities.retain(|_, placed| match placed.entity {
+            MapEntity::Ally(ref ally) => ally.turns_left > 0,
+            _ => true,
+        });
+    }
+}
+
+impl ToJson for EntityRegistry {
+    fn to_json(&self) -> Json {
+        let entities: Vec<Json> = self.entities
+            .iter()
+            .filter_map(|(id, placed)| {
+                let item = match placed.entity {
+                    MapEntity::Item(ref item) => item,
+                    MapEntity::Ally(_) => return None,
+                };
+
+                let mut object = Object::new();
+                object.insert("id".to_owned(), id.to_json());
+                object.insert("position".to_owned(), placed.position.to_json());
+                object.insert("item".to_owned(), item.to_json());
+                Some(Json::Object(object))
+            })
+            .collect();
+
+        Json::Array(entities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use item_generator::ItemGenerator;
+
+    #[test]
+    fn add_and_move() {
+        let mut registry = EntityRegistry::new();
+        let coin = ItemGenerator::new().name("Coin").gen();
+        let ring = ItemGenerator::new().name("Ring").gen();
+
+        let first = registry.add((1, 1), MapEntity::Item(coin.clone()));
+        let second = registry.add((1, 1), MapEntity::Item(ring.clone()));
+        assert_ne!(first, second);
+        assert_eq!(registry.items_at((1, 1)), vec![(first, &coin), (second, &ring)]);
+
+        assert!(registry.move_to(second, (2, 0)));
+        assert_eq!(registry.at((2, 0)), vec![second]);
+        assert!(registry.remove(first).is_some());
+        assert!(registry.at((1, 1)).is_empty());
+        assert!(!registry.move_to(first, (0, 0)));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut registry = EntityRegistry::new();
+        let coin = ItemGenerator::new().name("Coin").gen();
+        registry.add((0, 0), MapEntity::Item(coin.clone()));
+        let id = registry.add((3, 2), MapEntity::Item(coin.clone()));
+        registry.remove(0);
+
+        let mut loaded = EntityRegistry::from_json(&registry.to_json()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get(id).unwrap().position, (3, 2));
+        assert!(loaded.add((0, 0), MapEntity::Item(coin)) > id);
+    }
+}
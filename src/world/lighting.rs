@@ -0,0 +1,199 @@
+use character::Character;
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use time::LightLevel;
+
+/// The name of the item carried as a small light source
+pub const TORCH: &str = "Torch";
+
+/// The name of the item carried as a large light source
+pub const LANTERN: &str = "Lantern";
+
+/// The brightness a field needs for the player to see it
+pub const VISIBLE_BRIGHTNESS: f64 = 0.3;
+
+/// Something emitting light, e.g. a torch on a wall or in the hands of the player
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LightSource {
+    /// The amount of fields the light reaches
+    pub radius: u32,
+    /// The brightness at the source from `0.0` to `1.0`. It falls off towards the radius.
+    pub intensity: f64,
+}
+
+impl LightSource {
+    /// Creates a new instance of `LightSource`
+    ///
+    /// # Panics
+    /// Panics if `intensity` is not between `0.0` and `1.0`
+    pub fn new(radius: u32, intensity: f64) -> LightSource {
+        assert!((0.0..=1.0).contains(&intensity));
+        LightSource {
+            radius,
+            intensity,
+        }
+    }
+
+    /// A torch lighting 4 fields
+    pub fn torch() -> LightSource {
+        LightSource::new(4, 0.8)
+    }
+
+    /// A lantern lighting 6 fields
+    pub fn lantern() -> LightSource {
+        LightSource::new(6, 1.0)
+    }
+
+    /// A campfire lighting 5 fields
+    pub fn campfire() -> LightSource {
+        LightSource::new(5, 0.9)
+    }
+
+    /// Returns the light carried by the character, preferring a lantern over a torch
+    pub fn carried_by(character: &Character) -> Option<LightSource> {
+        let inventory = character.inventory();
+
+        if inventory.find_by_name(LANTERN).is_some() {
+            Some(LightSource::lantern())
+        } else if inventory.find_by_name(TORCH).is_some() {
+            Some(LightSource::torch())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the brightness the source adds to a field at the given distance
+    pub fn brightness_at(&self, distance: f64) -> f64 {
+        if distance > self.radius as f64 {
+            return 0.0;
+        }
+
+        self.intensity * (1.0 - distance / (self.radius as f64 + 1.0))
+    }
+
+    /// Reads a light source from a JSON object like `{"radius": 4, "intensity": 0.8}`
+    pub fn from_json(json: &Json) -> Result<LightSource, DataError> {
+        let object = data::as_object(json, "light_source")?;
+        let intensity = data::float(object, "intensity")?;
+        if !(0.0..=1.0).contains(&intensity) {
+            return Err(DataError::Invalid("intensity".to_owned()));
+        }
+
+        Ok(LightSource::new(data::unsigned(object, "radius")? as u32, intensity))
+    }
+}
+
+impl ToJson for LightSource {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("radius".to_owned(), self.radius.to_json());
+        object.insert("intensity".to_owned(), self.intensity.to_json());
+
+        Json::Object(object)
+    }
+}
+
+/// The brightness of every field of a world, made of the daylight and all light sources. Light
+/// passes through walls.
+#[derive(Clone, Debug)]
+pub struct LightMap {
+    size: (usize, usize),
+    brightness: Vec<f64>,
+}
+
+impl LightMap {
+    /// Creates a new instance of `LightMap` lit by the daylight of the given light level only
+    pub fn new(size: (usize, usize), light_level: LightLevel) -> LightMap {
+        LightMap {
+            size,
+            brightness: vec![light_level.brightness(); size.0 * size.1],
+        }
+    }
+
+    /// Adds the light of a source at the given position. Overlapping lights add up to at most
+    /// `1.0`.
+    pub fn add_light(&mut self, position: (usize, usize), source: LightSource) {
+        let (width, height) = self.size;
+        let radius = source.radius as usize;
+
+        for y in position.1.saturating_sub(radius)..(position.1 + radius + 1).min(height) {
+            for x in position.0.saturating_sub(radius)..(position.0 + radius + 1).min(width) {
+                let dx = x as f64 - position.0 as f64;
+                let dy = y as f64 - position.1 as f64;
+                let index = y * width + x;
+
+                self.brightness[index] = (self.brightness[index] +
+                                          source.brightness_at((dx * dx + dy * dy).sqrt()))
+                    .min(1.0);
+            }
+        }
+    }
+
+    /// Returns the brightness of the field at the given position from `0.0` (pitch black) to
+    /// `1.0` (broad daylight). Positions outside of the map are pitch black.
+    pub fn brightness(&self, position: (usize, usize)) -> f64 {
+        let (width, height) = self.size;
+        if position.0 >= width || position.1 >= height {
+            return 0.0;
+        }
+
+        self.brightness[position.1 * width + position.0]
+    }
+
+    /// Returns `true` if the field at the given position is bright enough to be seen
+    pub fn is_visible(&self, position: (usize, usize)) -> bool {
+        self.brightness(position) >= VISIBLE_BRIGHTNESS
+    }
+
+    /// Darkens the given RGB color according to the brightness of the field at the given
+    /// position. Used by renderers
+    pub fn shade(&self, position: (usize, usize), color: (u8, u8, u8)) -> (u8, u8, u8) {
+        let brightness = self.brightness(position);
+        let (r, g, b) = color;
+
+        (((r as f64) * brightness) as u8,
+         ((g as f64) * brightness) as u8,
+         ((b as f64) * brightness) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use item_generator::ItemGenerator;
+    use item::ItemType;
+    use time::LightLevel;
+
+    #[test]
+    fn falloff() {
+        let mut map = LightMap::new((9, 9), LightLevel::Night);
+        map.add_light((4, 4), LightSource::torch());
+
+        assert_eq!(map.brightness((4, 4)), 1.0);
+        assert!(map.brightness((5, 4)) > map.brightness((7, 4)));
+        assert_eq!(map.brightness((0, 0)), LightLevel::Night.brightness());
+        assert!(map.is_visible((6, 4)));
+        assert!(!map.is_visible((0, 0)));
+        assert_eq!(map.brightness((20, 20)), 0.0);
+    }
+
+    #[test]
+    fn daylight() {
+        let mut map = LightMap::new((3, 3), LightLevel::Day);
+        map.add_light((1, 1), LightSource::campfire());
+
+        assert_eq!(map.brightness((0, 0)), 1.0);
+        assert_eq!(map.shade((0, 0), (200, 100, 50)), (200, 100, 50));
+    }
+
+    #[test]
+    fn carried_light() {
+        let mut character = Character::new("Gollum");
+        assert_eq!(LightSource::carried_by(&character), None);
+
+        let torch = ItemGenerator::new().name(TORCH).item_type(ItemType::Usable).gen();
+        character.inventory_mut().add_item(torch).unwrap();
+        assert_eq!(LightSource::carried_by(&character), Some(LightSource::torch()));
+    }
+}
@@ -0,0 +1,200 @@
+use data::{self, DataError};
+use format::Format;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::path::Path;
+
+/// The version of this crate, which campaigns can require
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Parses a version like `1.2.3` into its major, minor and patch number
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let parts: Vec<&str> = version.trim().split('.').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    match (parts[0].parse(), parts[1].parse(), parts[2].parse()) {
+        (Ok(major), Ok(minor), Ok(patch)) => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the available version satisfies the required one. Like Cargo's caret
+/// requirements, the versions have to share the first non-zero number.
+pub fn is_compatible(required: (u64, u64, u64), available: (u64, u64, u64)) -> bool {
+    let same_series = match required {
+        (0, 0, _) => available.0 == 0 && available.1 == 0 && available.2 == required.2,
+        (0, minor, _) => available.0 == 0 && available.1 == minor,
+        (major, _, _) => available.0 == major,
+    };
+
+    same_series && available >= required
+}
+
+/// The self-describing header of a shared campaign file
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CampaignManifest {
+    /// The author of the campaign
+    pub author: String,
+    /// The version of the campaign itself
+    pub version: String,
+    /// A short description shown before starting the campaign
+    pub description: String,
+    /// The lowest and highest character level the campaign is made for
+    pub level_range: (u32, u32),
+    /// The version of this crate the campaign needs
+    pub required_version: String,
+}
+
+impl CampaignManifest {
+    /// Creates a new instance of `CampaignManifest` for any level, requiring the current version
+    /// of this crate
+    pub fn new(author: &str, version: &str) -> CampaignManifest {
+        CampaignManifest {
+            author: author.to_owned(),
+            version: version.to_owned(),
+            description: String::new(),
+            level_range: (1, u32::MAX),
+            required_version: CRATE_VERSION.to_owned(),
+        }
+    }
+
+    /// A builder method for setting the description
+    pub fn description(mut self, description: &str) -> CampaignManifest {
+        self.description = description.to_owned();
+        self
+    }
+
+    /// A builder method for setting the recommended level range
+    pub fn level_range(mut self, lowest: u32, highest: u32) -> CampaignManifest {
+        self.level_range = (lowest, highest);
+        self
+    }
+
+    /// A builder method for setting the required version of this crate
+    pub fn required_version(mut self, required_version: &str) -> CampaignManifest {
+        self.required_version = required_version.to_owned();
+        self
+    }
+
+    /// Returns `true` if a character of the given level is within the recommended range
+    pub fn is_recommended_for(&self, level: u32) -> bool {
+        level >= self.level_range.0 && level <= self.level_range.1
+    }
+
+    /// Checks that the versions are well-formed, the level range is not empty and this crate
+    /// satisfies the required version
+    pub fn validate(&self) -> Result<(), DataError> {
+        if parse_version(&self.version).is_none() {
+            return Err(DataError::Invalid("version".to_owned()));
+        }
+
+        if self.level_range.0 > self.level_range.1 {
+            return Err(DataError::Invalid("level_range".to_owned()));
+        }
+
+        let required = parse_version(&self.required_version)
+            .ok_or_else(|| DataError::Invalid("required_version".to_owned()))?;
+        let available = parse_version(CRATE_VERSION).expect("Invalid crate version");
+
+        if !is_compatible(required, available) {
+            return Err(DataError::Incompatible(self.required_version.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Reads and validates a manifest from a JSON object like
+    /// `{"author": "Jane", "version": "1.0.0", "description": "...", "min_level": 1,
+    /// "max_level": 5, "required_version": "0.0.2"}`
+    pub fn from_json(json: &Json) -> Result<CampaignManifest, DataError> {
+        let object = data::as_object(json, "manifest")?;
+
+        let lowest = data::optional(object, "min_level", data::unsigned)?
+            .map_or(1, |level| level as u32);
+        let highest = data::optional(object, "max_level", data::unsigned)?
+            .map_or(u32::MAX, |level| level as u32);
+
+        let manifest = CampaignManifest {
+            author: data::string(object, "author")?,
+            version: data::string(object, "version")?,
+            description: data::optional(object, "description", data::string)?
+                .unwrap_or_default(),
+            level_range: (lowest, highest),
+            required_version: data::string(object, "required_version")?,
+        };
+
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Reads and validates a manifest from a file. The format is chosen by the extension, JSON by
+    /// default.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<CampaignManifest, DataError> {
+        let format = Format::from_path(&path).unwrap_or(Format::Json);
+        CampaignManifest::from_json(&format.read_file(path)?)
+    }
+}
+
+impl ToJson for CampaignManifest {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("author".to_owned(), self.author.to_json());
+        object.insert("version".to_owned(), self.version.to_json());
+        object.insert("description".to_owned(), self.description.to_json());
+        object.insert("min_level".to_owned(), self.level_range.0.to_json());
+        object.insert("max_level".to_owned(), self.level_range.1.to_json());
+        object.insert("required_version".to_owned(), self.required_version.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data::DataError;
+    use rustc_serialize::json::Json;
+    use std::env;
+
+    #[test]
+    fn compatibility() {
+        assert!(is_compatible((1, 2, 0), (1, 4, 1)));
+        assert!(!is_compatible((1, 2, 0), (2, 0, 0)));
+        assert!(!is_compatible((1, 2, 0), (1, 1, 9)));
+        assert!(is_compatible((0, 3, 1), (0, 3, 4)));
+        assert!(!is_compatible((0, 3, 1), (0, 4, 0)));
+        assert_eq!(parse_version("1.x.0"), None);
+    }
+
+    #[test]
+    fn load() {
+        let json = Json::from_str(&format!(r#"{{"author": "Tolkien", "version": "1.0.0",
+                                              "min_level": 3, "max_level": 8,
+                                              "required_version": "{}"}}"#,
+                                           CRATE_VERSION))
+            .unwrap();
+        let manifest = CampaignManifest::from_json(&json).unwrap();
+
+        assert_eq!(manifest.author, "Tolkien");
+        assert!(manifest.is_recommended_for(5));
+        assert!(!manifest.is_recommended_for(9));
+
+        let path = env::temp_dir().join("txtrpg_manifest.toml");
+        Format::Toml.write_file(&path, &manifest.to_json()).unwrap();
+        assert_eq!(CampaignManifest::load_file(&path).unwrap().author, "Tolkien");
+    }
+
+    #[test]
+    fn validation() {
+        assert!(CampaignManifest::new("Tolkien", "1.0.0").validate().is_ok());
+        assert!(CampaignManifest::new("Tolkien", "first").validate().is_err());
+        assert!(CampaignManifest::new("Tolkien", "1.0.0").level_range(5, 2).validate().is_err());
+
+        match CampaignManifest::new("Tolkien", "1.0.0").required_version("99.0.0").validate() {
+            Err(DataError::Incompatible(version)) => assert_eq!(version, "99.0.0"),
+            _ => panic!("A campaign for version 99 should be incompatible"),
+        }
+    }
+}
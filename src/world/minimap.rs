@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use super::World;
+use super::two_dimensional::{FieldType, World2d};
+
+/// A single cell of a minimap, summarizing a square block of fields
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MinimapCell {
+    /// The character used to draw the cell
+    pub glyph: char,
+    /// The RGB color used to draw the cell
+    pub color: (u8, u8, u8),
+}
+
+/// A downscaled view of a world, e.g. for a map in the corner of the screen
+#[derive(Clone, PartialEq, Debug)]
+pub struct Minimap {
+    size: (usize, usize),
+    cells: Vec<Option<MinimapCell>>,
+    player: (usize, usize),
+}
+
+impl Minimap {
+    /// Returns the amount of cells in each direction
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Returns the cell at the given position. Cells the player has not explored yet are `None`.
+    pub fn get(&self, x: usize, y: usize) -> Option<&MinimapCell> {
+        if x >= self.size.0 || y >= self.size.1 {
+            return None;
+        }
+
+        self.cells[y * self.size.0 + x].as_ref()
+    }
+
+    /// Returns the cell containing the player
+    pub fn player(&self) -> (usize, usize) {
+        self.player
+    }
+
+    /// Draws the minimap as text, one line per row. The player is drawn as `@` and unexplored
+    /// cells as spaces.
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+
+        for y in 0..self.size.1 {
+            for x in 0..self.size.0 {
+                text.push(if (x, y) == self.player {
+                    '@'
+                } else {
+                    self.get(x, y).map_or(' ', |cell| cell.glyph)
+                });
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl World2d {
+    /// Creates a minimap in which each cell summarizes a block of `scale` by `scale` fields by
+    /// its most common field type. If the fields explored by the player are given, blocks
+    /// without any explored field stay empty and only explored fields are summarized.
+    ///
+    /// # Panics
+    /// Panics if `scale` is `0`
+    pub fn minimap(&self, scale: usize, explored: Option<&HashSet<(usize, usize)>>) -> Minimap {
+        assert!(scale > 0);
+        let (width, height) = self.size();
+        let size = (width.div_ceil(scale), height.div_ceil(scale));
+
+        let mut cells = Vec::with_capacity(size.0 * size.1);
+        for block_y in 0..size.1 {
+            for block_x in 0..size.0 {
+                let mut counts: Vec<(&FieldType, usize)> = Vec::new();
+
+                for y in block_y * scale..((block_y + 1) * scale).min(height) {
+                    for x in block_x * scale..((block_x + 1) * scale).min(width) {
+                        if explored.is_some_and(|explored| !explored.contains(&(x, y))) {
+                            continue;
+                        }
+
+                        let field_type = &self.get_field((x, y)).field_type;
+                        match counts.iter().position(|&(other, _)| other == field_type) {
+                            Some(index) => counts[index].1 += 1,
+                            None => counts.push((field_type, 1)),
+                        }
+                    }
+                }
+
+                let mut most_common: Option<(&FieldType, usize)> = None;
+                for (field_type, count) in counts {
+                    if most_common.is_none_or(|(_, highest)| count > highest) {
+                        most_common = Some((field_type, count));
+                    }
+                }
+
+                cells.push(most_common.map(|(field_type, _)| {
+                    MinimapCell {
+                        glyph: self.field_types.glyph(field_type),
+                        color: field_type.color(),
+                    }
+                }));
+            }
+        }
+
+        let position = self.get_position();
+        Minimap {
+            size,
+            cells,
+            player: (position.0 / scale, position.1 / scale),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn lake() -> World2d {
+        let mut world = World2d::new("Lake", (5, 4)).starting_point((4, 3));
+        for x in 0..2 {
+            for y in 0..2 {
+                world.set_field(Field::new(FieldType::Water), (x, y));
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn downscale() {
+        let minimap = lake().minimap(2, None);
+
+        assert_eq!(minimap.size(), (3, 2));
+        assert_eq!(minimap.get(0, 0).unwrap().glyph, '~');
+        assert_eq!(minimap.get(1, 0).unwrap().color, FieldType::Grass.color());
+        assert_eq!(minimap.player(), (2, 1));
+        assert_eq!(minimap.render(), "~,,\n,,@\n");
+    }
+
+    #[test]
+    fn fog_of_war() {
+        let mut explored = HashSet::new();
+        explored.insert((1, 1));
+        explored.insert((4, 3));
+
+        let minimap = lake().minimap(2, Some(&explored));
+        assert_eq!(minimap.get(0, 0).unwrap().glyph, '~');
+        assert_eq!(minimap.get(1, 0), None);
+        assert_eq!(minimap.render(), "~  \n  @\n");
+    }
+}
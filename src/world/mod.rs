@@ -2,9 +2,41 @@
 pub mod linear;
 /// Structures for creating a 2d world
 pub mod two_dimensional;
+/// Field types defined by campaigns
+pub mod terrain;
+/// Structures for creating large 2d worlds loaded in chunks
+pub mod chunked;
+/// Structures for creating a world of connected rooms
+pub mod rooms;
+/// Generating overworlds made of coherent biomes
+pub mod biome;
+/// Importing maps made with the Tiled editor
+pub mod tiled;
 /// A trait for implementing new world types
 mod world_trait;
 /// A container holding an arbitrary amount of worlds
 pub mod campaign;
+/// The author, version and other details of a campaign
+pub mod manifest;
+/// Weather and climate of a world
+pub mod weather;
+/// Light sources and the brightness of fields
+pub mod lighting;
+/// Downscaled maps of a world
+pub mod minimap;
+/// Items and other things placed on the map of a world
+pub mod entities;
+/// Spawn points and random encounters
+pub mod spawn;
+/// Links between the worlds of a campaign
+pub mod portal;
+/// Discovered places the player can fast-travel between
+pub mod waypoint;
+/// The progress of the player in a campaign
+pub mod progress;
+/// Checks for finding mistakes in campaigns before shipping them
+pub mod validation;
+/// Loading the worlds of large campaigns on demand
+pub mod streaming;
 
-pub use self::world_trait::World;
+pub use self::world_trait::{Teleport, World};
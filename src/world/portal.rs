@@ -0,0 +1,71 @@
+/// A link from a position in one world of a campaign to a position in another (or the same)
+/// world. Portals only work in one direction, use `reversed` to get the way back.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Portal<P> {
+    /// The index of the world the portal is in
+    pub from_world: usize,
+    /// The position of the portal
+    pub from: P,
+    /// The index of the world the portal leads to
+    pub to_world: usize,
+    /// The position the player arrives at
+    pub to: P,
+}
+
+impl<P: Clone> Portal<P> {
+    /// Creates a new instance of `Portal`
+    pub fn new(from_world: usize, from: P, to_world: usize, to: P) -> Portal<P> {
+        Portal {
+            from_world,
+            from,
+            to_world,
+            to,
+        }
+    }
+
+    /// Returns a portal leading back from the destination of this portal
+    pub fn reversed(&self) -> Portal<P> {
+        Portal::new(self.to_world, self.to.clone(), self.from_world, self.from.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use world::{Teleport, World};
+    use world::campaign::Campaign;
+    use world::two_dimensional::World2d;
+
+    fn hub() -> Campaign<World2d> {
+        let mut campaign = Campaign::new("Hub World");
+        campaign.add_world(World2d::new("Castle", (10, 10)));
+        campaign.add_world(World2d::new("Dungeon", (10, 10)));
+
+        let portal = Portal::new(0, (5, 5), 1, (0, 9));
+        campaign.add_portal(portal.reversed());
+        campaign.add_portal(portal);
+        campaign
+    }
+
+    #[test]
+    fn travel() {
+        let mut campaign = hub();
+        assert!(!campaign.use_portal());
+
+        campaign.current_world_mut().unwrap().set_position((5, 5));
+        assert!(campaign.use_portal());
+        assert_eq!(campaign.current_index(), 1);
+        assert_eq!(campaign.current_world().unwrap().get_position(), (0, 9));
+
+        assert!(campaign.use_portal());
+        assert_eq!(campaign.current_index(), 0);
+        assert_eq!(campaign.current_world().unwrap().get_position(), (5, 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_world() {
+        let mut campaign = hub();
+        campaign.add_portal(Portal::new(0, (0, 0), 2, (0, 0)));
+    }
+}
@@ -0,0 +1,133 @@
+use data::{self, DataError};
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::BTreeSet;
+
+/// How far the player got in a campaign. Cloning it creates a checkpoint that can be restored
+/// later.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CampaignProgress {
+    /// The ids of all finished worlds
+    pub completed: BTreeSet<String>,
+    /// The id of the world the player is currently in
+    pub active: String,
+    /// The indices of all portals the player used so far
+    pub discovered_portals: BTreeSet<usize>,
+    /// The quest flags set so far
+    pub flags: BTreeSet<String>,
+    /// The names of all waypoints the player discovered so far
+    pub discovered_waypoints: BTreeSet<String>,
+}
+
+impl CampaignProgress {
+    /// Creates a new instance of `CampaignProgress` starting in the world with the given id
+    pub fn new(active: &str) -> CampaignProgress {
+        CampaignProgress {
+            completed: BTreeSet::new(),
+            active: active.to_owned(),
+            discovered_portals: BTreeSet::new(),
+            flags: BTreeSet::new(),
+            discovered_waypoints: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the given quest flag
+    pub fn set_flag(&mut self, flag: &str) {
+        self.flags.insert(flag.to_owned());
+    }
+
+    /// Removes the given quest flag
+    pub fn clear_flag(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+
+    /// Returns `true` if the given quest flag is set
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Returns `true` if the world with the given id was finished
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    /// Reads the progress from a JSON object as written by `to_json`. The discovered waypoints
+    /// are optional.
+    pub fn from_json(json: &Json) -> Result<CampaignProgress, DataError> {
+        let object = data::as_object(json, "progress")?;
+
+        let strings = |key: &str| -> Result<BTreeSet<String>, DataError> {
+            data::as_array(data::field(object, key)?, key)?
+                .iter()
+                .map(|value| data::parse_value(value, key))
+                .collect()
+        };
+
+        let discovered_portals = data::as_array(data::field(object, "discovered_portals")?,
+                                                "discovered_portals")?
+            .iter()
+            .map(|value| {
+                value.as_u64()
+                    .map(|index| index as usize)
+                    .ok_or_else(|| DataError::Invalid("discovered_portals".to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CampaignProgress {
+            completed: strings("completed")?,
+            active: data::string(object, "active")?,
+            discovered_portals,
+            flags: strings("flags")?,
+            discovered_waypoints: match object.get("discovered_waypoints") {
+                None => BTreeSet::new(),
+                Some(_) => strings("discovered_waypoints")?,
+            },
+        })
+    }
+}
+
+impl ToJson for CampaignProgress {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        let completed: Vec<String> = self.completed.iter().cloned().collect();
+        let discovered_portals: Vec<usize> = self.discovered_portals.iter().cloned().collect();
+        let flags: Vec<String> = self.flags.iter().cloned().collect();
+        let discovered_waypoints: Vec<String> =
+            self.discovered_waypoints.iter().cloned().collect();
+
+        object.insert("completed".to_owned(), completed.to_json());
+        object.insert("active".to_owned(), self.active.to_json());
+        object.insert("discovered_portals".to_owned(), discovered_portals.to_json());
+        object.insert("flags".to_owned(), flags.to_json());
+        object.insert("discovered_waypoints".to_owned(), discovered_waypoints.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::ToJson;
+
+    #[test]
+    fn flags() {
+        let mut progress = CampaignProgress::new("start");
+
+        progress.set_flag("met_the_king");
+        assert!(progress.has_flag("met_the_king"));
+
+        progress.clear_flag("met_the_king");
+        assert!(!progress.has_flag("met_the_king"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut progress = CampaignProgress::new("castle");
+        progress.completed.insert("village".to_owned());
+        progress.discovered_portals.insert(3);
+        progress.set_flag("dragon_slain");
+
+        assert_eq!(CampaignProgress::from_json(&progress.to_json()).unwrap(), progress);
+    }
+}
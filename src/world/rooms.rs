@@ -0,0 +1,289 @@
+use entity::Entity;
+use event::Event;
+use item::Item;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use super::{Teleport, World};
+
+/// A direction in which a room can be left
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Direction {
+    /// Towards the north
+    North,
+    /// Towards the east
+    East,
+    /// Towards the south
+    South,
+    /// Towards the west
+    West,
+    /// Upwards, e.g. a staircase
+    Up,
+    /// Downwards, e.g. a trapdoor
+    Down,
+}
+
+impl Direction {
+    /// Returns the direction leading back
+    pub fn opposite(&self) -> Direction {
+        match *self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Direction, ()> {
+        match &s.to_lowercase()[..] {
+            "north" | "n" => Ok(Direction::North),
+            "east" | "e" => Ok(Direction::East),
+            "south" | "s" => Ok(Direction::South),
+            "west" | "w" => Ok(Direction::West),
+            "up" | "u" => Ok(Direction::Up),
+            "down" | "d" => Ok(Direction::Down),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A single location of a room-based world
+#[derive(Clone)]
+pub struct Room {
+    /// The name of the room
+    pub name: String,
+    /// The text shown when entering or looking around the room
+    pub description: String,
+    /// The ids of the neighbouring rooms by direction
+    pub exits: BTreeMap<Direction, String>,
+    /// The items lying around in the room
+    pub items: Vec<Item>,
+    /// The entities and NPCs in the room
+    pub entities: Vec<Entity>,
+}
+
+impl Room {
+    /// Creates a new instance of `Room`
+    pub fn new(name: &str, description: &str) -> Room {
+        Room {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            exits: BTreeMap::new(),
+            items: Vec::new(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// A builder method for putting an item into the room
+    pub fn item(mut self, item: Item) -> Room {
+        self.items.push(item);
+        self
+    }
+
+    /// A builder method for putting an entity into the room
+    pub fn entity(mut self, entity: Entity) -> Room {
+        self.entities.push(entity);
+        self
+    }
+
+    /// Returns the description together with the visible items and exits
+    pub fn describe(&self) -> String {
+        let mut text = format!("{}\n{}", self.name, self.description);
+
+        for item in &self.items {
+            text.push_str(&format!("\nThere is {} here.", item.name));
+        }
+
+        for entity in &self.entities {
+            text.push_str(&format!("\nYou see {}.", entity.name()));
+        }
+
+        if !self.exits.is_empty() {
+            let exits: Vec<String> = self.exits
+                .keys()
+                .map(|direction| format!("{:?}", direction).to_lowercase())
+                .collect();
+            text.push_str(&format!("\nExits: {}", exits.join(", ")));
+        }
+
+        text
+    }
+}
+
+/// A world made of rooms connected by exits, like in classic text adventures
+pub struct RoomWorld {
+    /// The name or title of the world
+    pub name: String,
+    /// The id of the room where the world is finished (optional)
+    pub end_room: Option<String>,
+    rooms: BTreeMap<String, Room>,
+    current_room: String,
+}
+
+impl RoomWorld {
+    /// Creates a new instance of `RoomWorld` starting in the given room
+    pub fn new(name: &str, start_id: &str, start: Room) -> RoomWorld {
+        let mut rooms = BTreeMap::new();
+        rooms.insert(start_id.to_owned(), start);
+
+        RoomWorld {
+            name: name.to_owned(),
+            end_room: None,
+            rooms,
+            current_room: start_id.to_owned(),
+        }
+    }
+
+    /// A builder method for adding a room
+    pub fn room(mut self, id: &str, room: Room) -> RoomWorld {
+        self.rooms.insert(id.to_owned(), room);
+        self
+    }
+
+    /// A builder method for setting the room where the world is finished
+    pub fn end_room(mut self, id: &str) -> RoomWorld {
+        self.end_room = Some(id.to_owned());
+        self
+    }
+
+    /// Connects two rooms in both directions, e.g. leaving `from` to the north leads to `to` and
+    /// leaving `to` to the south leads back
+    ///
+    /// # Panics
+    /// Panics if one of the rooms does not exist
+    pub fn connect(&mut self, from: &str, direction: Direction, to: &str) {
+        assert!(self.rooms.contains_key(from) && self.rooms.contains_key(to));
+
+        self.rooms.get_mut(from).unwrap().exits.insert(direction, to.to_owned());
+        self.rooms.get_mut(to).unwrap().exits.insert(direction.opposite(), from.to_owned());
+    }
+
+    /// Returns the room with the given id
+    pub fn get_room(&self, id: &str) -> Option<&Room> {
+        self.rooms.get(id)
+    }
+
+    /// Returns the room the player is in
+    pub fn current_room(&self) -> &Room {
+        &self.rooms[&self.current_room]
+    }
+
+    /// Returns the room the player is in as mutable
+    pub fn current_room_mut(&mut self) -> &mut Room {
+        self.rooms.get_mut(&self.current_room).expect("Unable to find current room")
+    }
+
+    /// Takes the item with the given name out of the current room
+    pub fn take_item(&mut self, name: &str) -> Option<Item> {
+        let items = &mut self.current_room_mut().items;
+
+        items.iter().position(|item| item.name == name).map(|index| items.remove(index))
+    }
+
+    /// Puts an item into the current room
+    pub fn drop_item(&mut self, item: Item) {
+        self.current_room_mut().items.push(item);
+    }
+
+    /// Sends an event to the entity with the given name in the current room. Returns `None` if
+    /// there is no such entity.
+    pub fn send_event(&self, entity: &str, event: Event) -> Option<Event> {
+        self.current_room()
+            .entities
+            .iter()
+            .find(|candidate| candidate.name() == entity)
+            .map(|entity| entity.send_event(event))
+    }
+}
+
+impl World for RoomWorld {
+    type Position = String;
+
+    type Movement = Direction;
+
+    fn get_position(&self) -> Self::Position {
+        self.current_room.clone()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.end_room.as_ref() == Some(&self.current_room)
+    }
+
+    /// Leaves the current room in the given direction. Nothing happens if there is no exit.
+    fn move_to(&mut self, movement: Self::Movement) {
+        if let Some(next) = self.current_room().exits.get(&movement).cloned() {
+            self.current_room = next;
+        }
+    }
+}
+
+impl Teleport for RoomWorld {
+    fn set_position(&mut self, position: Self::Position) {
+        assert!(self.rooms.contains_key(&position));
+        self.current_room = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use behaviour::DefaultResponse;
+    use entity::Entity;
+    use event::Event;
+    use item_generator::ItemGenerator;
+    use world::World;
+
+    fn house() -> RoomWorld {
+        let mut hermit = Entity::new("hermit");
+        hermit.append_behaviour(DefaultResponse::new("Leave me alone."));
+
+        let mut world = RoomWorld::new("House",
+                                       "hall",
+                                       Room::new("Hall", "A dusty hall.").entity(hermit))
+            .room("kitchen",
+                  Room::new("Kitchen", "It smells of onions.")
+                      .item(ItemGenerator::new().name("Knife").gen()))
+            .room("attic", Room::new("Attic", "Cobwebs everywhere."))
+            .end_room("attic");
+        world.connect("hall", Direction::North, "kitchen");
+        world.connect("hall", Direction::Up, "attic");
+        world
+    }
+
+    #[test]
+    fn walk_around() {
+        let mut world = house();
+
+        world.move_to(Direction::West);
+        assert_eq!(world.get_position(), "hall");
+
+        world.move_to("n".parse().unwrap());
+        assert_eq!(world.current_room().name, "Kitchen");
+        assert!(world.current_room().describe().ends_with("Exits: south"));
+
+        world.move_to(Direction::South);
+        world.move_to(Direction::Up);
+        assert!(world.is_finished());
+    }
+
+    #[test]
+    fn items_and_entities() {
+        let mut world = house();
+        assert_eq!(world.send_event("hermit", Event::Nothing),
+                   Some(Event::Tell("Leave me alone.".to_owned())));
+        assert_eq!(world.send_event("ghost", Event::Nothing), None);
+
+        world.move_to(Direction::North);
+        let knife = world.take_item("Knife").unwrap();
+        assert!(world.take_item("Knife").is_none());
+
+        world.move_to(Direction::South);
+        world.drop_item(knife);
+        assert!(world.current_room().describe().contains("There is Knife here."));
+    }
+}
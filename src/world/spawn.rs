@@ -0,0 +1,289 @@
+use bestiary::Bestiary;
+use combat::Combatant;
+use data::{self, DataError};
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use time::LightLevel;
+use types::{AttributeValue, Health};
+
+/// The amount of character levels that add one monster to a spawn point
+const LEVELS_PER_EXTRA_MONSTER: u32 = 5;
+
+/// The share by which health and damage of spawned monsters grow per character level
+const SCALING_PER_LEVEL: f64 = 0.1;
+
+/// A fixed position on the map where monsters appear
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpawnPoint {
+    /// The position of the spawn point
+    pub position: (usize, usize),
+    /// The id of the monster in the bestiary
+    pub monster: String,
+    /// The base amount of monsters
+    pub count: u32,
+}
+
+impl SpawnPoint {
+    /// Creates a new instance of `SpawnPoint`
+    pub fn new(position: (usize, usize), monster: &str, count: u32) -> SpawnPoint {
+        SpawnPoint {
+            position,
+            monster: monster.to_owned(),
+            count,
+        }
+    }
+}
+
+/// A single possible encounter of an `EncounterTable`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Encounter {
+    /// The id of the monster in the bestiary
+    pub monster: String,
+    /// The base amount of monsters
+    pub count: u32,
+    /// The relative chance of this encounter
+    pub weight: u32,
+    /// Whether the encounter can only happen in the dark
+    pub nocturnal: bool,
+    /// The text of a narrative event happening instead of a fight (optional)
+    pub narration: Option<String>,
+}
+
+impl Encounter {
+    /// Returns `true` if the encounter is a fight against monsters instead of a narrative event
+    pub fn is_fight(&self) -> bool {
+        self.narration.is_none()
+    }
+}
+
+/// The random encounters that can happen on a certain field type
+#[derive(Clone, PartialEq, Debug)]
+pub struct EncounterTable {
+    /// The chance (`0.0` to `1.0`) of an encounter per step
+    pub chance: f64,
+    /// The possible encounters
+    pub encounters: Vec<Encounter>,
+}
+
+impl EncounterTable {
+    /// Creates a new, empty instance of `EncounterTable`
+    pub fn new(chance: f64) -> EncounterTable {
+        EncounterTable {
+            chance,
+            encounters: Vec::new(),
+        }
+    }
+
+    /// A builder method for adding an encounter
+    pub fn encounter(mut self, monster: &str, count: u32, weight: u32) -> EncounterTable {
+        self.encounters.push(Encounter {
+            monster: monster.to_owned(),
+            count,
+            weight,
+            nocturnal: false,
+            narration: None,
+        });
+        self
+    }
+
+    /// A builder method for adding a narrative event, e.g. a traveling merchant or a storm
+    pub fn event(mut self, narration: &str, weight: u32) -> EncounterTable {
+        self.encounters.push(Encounter {
+            monster: String::new(),
+            count: 0,
+            weight,
+            nocturnal: false,
+            narration: Some(narration.to_owned()),
+        });
+        self
+    }
+
+    /// A builder method for adding an encounter that only happens in the dark
+    pub fn nocturnal_encounter(mut self, monster: &str, count: u32, weight: u32) -> EncounterTable {
+        self = self.encounter(monster, count, weight);
+        self.encounters.last_mut().unwrap().nocturnal = true;
+        self
+    }
+
+    /// Rolls whether an encounter happens and which one
+    pub fn roll<R: Rng>(&self, light_level: LightLevel, rng: &mut R) -> Option<&Encounter> {
+        self.roll_with_danger(light_level, 1.0, rng)
+    }
+
+    /// Rolls whether an encounter happens and which one, multiplying the chance by the danger
+    /// rating of the level
+    pub fn roll_with_danger<R: Rng>(&self,
+                                    light_level: LightLevel,
+                                    danger: f64,
+                                    rng: &mut R)
+                                    -> Option<&Encounter> {
+        if rng.gen::<f64>() >= self.chance * danger {
+            return None;
+        }
+
+        let possible: Vec<&Encounter> = self.encounters
+            .iter()
+            .filter(|encounter| !encounter.nocturnal || light_level.is_dark())
+            .collect();
+        let total = possible.iter().fold(0, |sum, encounter| sum + encounter.weight);
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0, total);
+        for encounter in possible {
+            if roll < encounter.weight {
+                return Some(encounter);
+            }
+            roll -= encounter.weight;
+        }
+
+        None
+    }
+
+    /// Reads an encounter table from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<EncounterTable, DataError> {
+        let object = data::as_object(json, "encounter_table")?;
+
+        let mut table = EncounterTable::new(data::float(object, "chance")?);
+        for encounter in data::as_array(data::field(object, "encounters")?, "encounters")? {
+            let encounter = data::as_object(encounter, "encounters")?;
+            table.encounters.push(Encounter {
+                monster: data::optional(encounter, "monster", data::string)?.unwrap_or_default(),
+                count: data::optional(encounter, "count", data::unsigned)?.unwrap_or(0) as u32,
+                weight: data::unsigned(encounter, "weight")? as u32,
+                nocturnal: data::optional(encounter, "nocturnal", data::boolean)?
+                    .unwrap_or(false),
+                narration: data::optional(encounter, "narration", data::string)?,
+            });
+        }
+
+        Ok(table)
+    }
+}
+
+impl ToJson for EncounterTable {
+    fn to_json(&self) -> Json {
+        let encounters: Vec<Json> = self.encounters
+            .iter()
+            .map(|encounter| {
+                let mut object = Object::new();
+                object.insert("weight".to_owned(), encounter.weight.to_json());
+                match encounter.narration {
+                    Some(ref narration) => {
+                        object.insert("narration".to_owned(), narration.to_json());
+                    }
+                    None => {
+                        object.insert("monster".to_owned(), encounter.monster.to_json());
+                        object.insert("count".to_owned(), encounter.count.to_json());
+                        object.insert("nocturnal".to_owned(), encounter.nocturnal.to_json());
+                    }
+                }
+                Json::Object(object)
+            })
+            .collect();
+
+        let mut object = Object::new();
+        object.insert("chance".to_owned(), self.chance.to_json());
+        object.insert("encounters".to_owned(), Json::Array(encounters));
+
+        Json::Object(object)
+    }
+}
+
+/// Creates monsters scaled to the level of the character
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Spawner {
+    /// The level of the character the monsters are scaled to
+    pub level: u32,
+}
+
+impl Spawner {
+    /// Creates a new instance of `Spawner` for the given character level
+    pub fn new(level: u32) -> Spawner {
+        Spawner { level }
+    }
+
+    /// Returns the amount of monsters spawned for the given base amount
+    pub fn scaled_count(&self, count: u32) -> u32 {
+        count + self.level / LEVELS_PER_EXTRA_MONSTER
+    }
+
+    fn scale(&self, mut combatant: Combatant) -> Combatant {
+        let factor = 1.0 + SCALING_PER_LEVEL * (self.level.saturating_sub(1) as f64);
+
+        combatant.max_health = ((combatant.max_health as f64) * factor) as Health;
+        combatant.health = combatant.max_health;
+        combatant.attack_damage = ((combatant.attack_damage as f64) * factor) as AttributeValue;
+        combatant
+    }
+
+    /// Creates the given amount of scaled monsters. Unknown monsters spawn nothing
+    pub fn spawn(&self, bestiary: &Bestiary, monster: &str, count: u32) -> Vec<Combatant> {
+        (0..self.scaled_count(count))
+            .filter_map(|_| bestiary.spawn(monster))
+            .map(|combatant| self.scale(combatant))
+            .collect()
+    }
+
+    /// Creates the scaled monsters of a spawn point
+    pub fn spawn_point(&self, bestiary: &Bestiary, spawn_point: &SpawnPoint) -> Vec<Combatant> {
+        self.spawn(bestiary, &spawn_point.monster, spawn_point.count)
+    }
+
+    /// Creates the scaled monsters of an encounter
+    pub fn encounter(&self, bestiary: &Bestiary, encounter: &Encounter) -> Vec<Combatant> {
+        self.spawn(bestiary, &encounter.monster, encounter.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bestiary::Bestiary;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::Json;
+    use time::LightLevel;
+
+    fn bestiary() -> Bestiary {
+        let mut bestiary = Bestiary::new();
+        bestiary.load_json(&Json::from_str(r#"[{"id": "wolf", "name": "Wolf", "health": 10,
+                                                 "attack_damage": 5}]"#)
+                .unwrap())
+            .unwrap();
+        bestiary
+    }
+
+    #[test]
+    fn scaled_spawn() {
+        let bestiary = bestiary();
+
+        let wolves = Spawner::new(1).spawn_point(&bestiary, &SpawnPoint::new((1, 1), "wolf", 2));
+        assert_eq!(wolves.len(), 2);
+        assert_eq!(wolves[0].max_health, 10);
+
+        let wolves = Spawner::new(11).spawn(&bestiary, "wolf", 2);
+        assert_eq!(wolves.len(), 4);
+        assert_eq!(wolves[0].max_health, 20);
+        assert_eq!(wolves[0].attack_damage, 10);
+
+        assert!(Spawner::new(1).spawn(&bestiary, "bear", 2).is_empty());
+    }
+
+    #[test]
+    fn encounter_table() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let table = EncounterTable::new(1.0).nocturnal_encounter("wolf", 3, 1);
+
+        assert_eq!(table.roll(LightLevel::Day, &mut rng), None);
+        assert_eq!(table.roll(LightLevel::Night, &mut rng).unwrap().monster, "wolf");
+
+        let table = EncounterTable::new(0.0).encounter("wolf", 3, 1);
+        assert_eq!(table.roll(LightLevel::Day, &mut rng), None);
+
+        let table = EncounterTable::new(0.5).event("A merchant passes by.", 1);
+        assert_eq!(table.roll_with_danger(LightLevel::Day, 0.0, &mut rng), None);
+        assert!(!table.roll_with_danger(LightLevel::Day, 2.0, &mut rng).unwrap().is_fight());
+    }
+}
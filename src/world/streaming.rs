@@ -0,0 +1,335 @@
+use data::{self, DataError};
+use format::Format;
+use locale::Catalog;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::campaign::Campaign;
+use super::manifest::CampaignManifest;
+use super::portal::Portal;
+use super::two_dimensional::World2d;
+use super::waypoint::Waypoint;
+use time::HOURS_PER_DAY;
+
+/// The name of the file listing all levels of a campaign directory, without its extension
+pub const INDEX_NAME: &str = "campaign";
+
+/// A place the worlds of a campaign are loaded from on demand
+pub trait LevelSource<T> {
+    /// Loads the world with the given id
+    fn load_level(&self, id: &str) -> Result<T, DataError>;
+}
+
+/// A directory containing one file per level, as listed by the index file. The format of each
+/// file is chosen by its extension.
+pub struct LevelDirectory {
+    directory: PathBuf,
+    files: HashMap<String, String>,
+}
+
+impl LevelDirectory {
+    /// Creates a new instance of `LevelDirectory` reading the files of the given level ids
+    pub fn new<P: AsRef<Path>>(directory: P, files: HashMap<String, String>) -> LevelDirectory {
+        LevelDirectory {
+            directory: directory.as_ref().to_path_buf(),
+            files,
+        }
+    }
+}
+
+impl LevelSource<World2d> for LevelDirectory {
+    fn load_level(&self, id: &str) -> Result<World2d, DataError> {
+        let file = self.files.get(id).ok_or_else(|| DataError::Missing(id.to_owned()))?;
+        let format = Format::from_path(file).unwrap_or(Format::Json);
+
+        World2d::from_json(&format.read_file(self.directory.join(file))?)
+    }
+}
+
+impl Campaign<World2d> {
+    /// Opens the campaign in the given directory, whose index file is stored in the given
+    /// format. Only the index file and the starting world are read, all other worlds are loaded
+    /// when the player enters them.
+    ///
+    /// The index file looks like `{"title": "...", "levels": [{"id": "village", "file":
+    /// "village.json", "successors": [{"id": "forest", "required_flags": []}]}], "portals": []}`.
+    /// The keys `day_length`, `survival`, `waypoints`, `translations`, `manifest` and the
+    /// `fast_travel` key of each level are optional.
+    pub fn open<P: AsRef<Path>>(directory: P,
+                                format: Format)
+                                -> Result<Campaign<World2d>, DataError> {
+        let index_file = format!("{}.{}", INDEX_NAME, format.extension());
+        let json = format.read_file(directory.as_ref().join(index_file))?;
+        let object = data::as_object(&json, "campaign")?;
+        let levels = data::as_array(data::field(object, "levels")?, "levels")?;
+
+        let mut files = HashMap::new();
+        let mut links = Vec::new();
+        for level in levels {
+            let level = data::as_object(level, "levels")?;
+            let id = data::string(level, "id")?;
+
+            for successor in data::as_array(data::field(level, "successors")?, "successors")? {
+                let successor = data::as_object(successor, "successors")?;
+                let required_flags: Vec<String> =
+                    data::as_array(data::field(successor, "required_flags")?, "required_flags")?
+                        .iter()
+                        .map(|flag| data::parse_value(flag, "required_flags"))
+                        .collect::<Result<_, _>>()?;
+                links.push((id.clone(), data::string(successor, "id")?, required_flags));
+            }
+
+            if files.insert(id, data::string(level, "file")?).is_some() {
+                return Err(DataError::Invalid("id".to_owned()));
+            }
+        }
+
+        let mut campaign = Campaign::new(&data::string(object, "title")?);
+        for level in levels {
+            let level = data::as_object(level, "levels")?;
+            let id = data::string(level, "id")?;
+            campaign.add_streamed_level(&id);
+            if let Some(fast_travel) = data::optional(level, "fast_travel", data::boolean)? {
+                campaign.set_fast_travel(&id, fast_travel);
+            }
+        }
+
+        if let Some(day_length) = data::optional(object, "day_length", data::unsigned)? {
+            if day_length == 0 || day_length % HOURS_PER_DAY != 0 {
+                return Err(DataError::Invalid("day_length".to_owned()));
+            }
+            campaign = campaign.day_length(day_length);
+        }
+        if let Some(survival) = data::optional(object, "survival", data::boolean)? {
+            campaign.survival = survival;
+        }
+
+        for (from, to, required_flags) in links {
+            if campaign.index_of(&to).is_none() {
+                return Err(DataError::Invalid("successors".to_owned()));
+            }
+
+            let required_flags: Vec<&str> = required_flags.iter().map(|flag| &flag[..]).collect();
+            campaign.link(&from, &to, &required_flags);
+        }
+
+        for portal in data::as_array(data::field(object, "portals")?, "portals")? {
+            let portal = data::as_object(portal, "portals")?;
+            let from_world = data::unsigned(portal, "from_world")? as usize;
+            let to_world = data::unsigned(portal, "to_world")? as usize;
+            if from_world >= campaign.len() || to_world >= campaign.len() {
+                return Err(DataError::Invalid("portals".to_owned()));
+            }
+
+            campaign.add_portal(Portal::new(from_world,
+                                            data::position(portal, "from")?,
+                                            to_world,
+                                            data::position(portal, "to")?));
+        }
+
+        if let Some(waypoints) = object.get("waypoints") {
+            for waypoint in data::as_array(waypoints, "waypoints")? {
+                let waypoint = data::as_object(waypoint, "waypoints")?;
+                let name = data::string(waypoint, "name")?;
+                let world = data::unsigned(waypoint, "world")? as usize;
+                if world >= campaign.len() ||
+                   campaign.waypoints().iter().any(|other| other.name == name) {
+                    return Err(DataError::Invalid("waypoints".to_owned()));
+                }
+
+                campaign.add_waypoint(Waypoint::new(&name,
+                                                    world,
+                                                    data::position(waypoint, "position")?));
+            }
+        }
+
+        if let Some(translations) = object.get("translations") {
+            for catalog in data::as_array(translations, "translations")? {
+                campaign = campaign.translation(Catalog::from_json(catalog)?);
+            }
+        }
+
+        if let Some(manifest) = object.get("manifest") {
+            campaign.manifest = Some(CampaignManifest::from_json(manifest)?);
+        }
+
+        campaign = campaign.stream_from(LevelDirectory::new(directory, files));
+        campaign.load_active()?;
+        Ok(campaign)
+    }
+
+    /// Writes the campaign into the given directory in the given format, with one file per
+    /// loaded world. Worlds that are not loaded are expected to be in the directory already.
+    pub fn save<P: AsRef<Path>>(&self, directory: P, format: Format) -> Result<(), DataError> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        let mut levels = Vec::new();
+        for node in (0..self.len()).filter_map(|index| self.node(index)) {
+            let file = format!("{}.{}", node.id, format.extension());
+            if let Some(ref world) = node.world {
+                format.write_file(directory.join(&file), &world.to_json())?;
+            }
+
+            let successors: Vec<Json> = node.successors
+                .iter()
+                .map(|successor| {
+                    let mut object = Object::new();
+                    object.insert("id".to_owned(), successor.id.to_json());
+                    object.insert("required_flags".to_owned(), successor.required_flags.to_json());
+                    Json::Object(object)
+                })
+                .collect();
+
+            let mut level = Object::new();
+            level.insert("id".to_owned(), node.id.to_json());
+            level.insert("file".to_owned(), file.to_json());
+            level.insert("successors".to_owned(), successors.to_json());
+            level.insert("fast_travel".to_owned(), node.fast_travel.to_json());
+            levels.push(Json::Object(level));
+        }
+
+        let portals: Vec<Json> = self.portals()
+            .iter()
+            .map(|portal| {
+                let mut object = Object::new();
+                object.insert("from_world".to_owned(), portal.from_world.to_json());
+                object.insert("from".to_owned(), portal.from.to_json());
+                object.insert("to_world".to_owned(), portal.to_world.to_json());
+                object.insert("to".to_owned(), portal.to.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let waypoints: Vec<Json> = self.waypoints()
+            .iter()
+            .map(|waypoint| {
+                let mut object = Object::new();
+                object.insert("name".to_owned(), waypoint.name.to_json());
+                object.insert("world".to_owned(), waypoint.world.to_json());
+                object.insert("position".to_owned(), waypoint.position.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let mut index = Object::new();
+        index.insert("title".to_owned(), self.title.to_json());
+        index.insert("day_length".to_owned(), self.get_day_length().to_json());
+        index.insert("survival".to_owned(), self.survival.to_json());
+        index.insert("levels".to_owned(), levels.to_json());
+        index.insert("portals".to_owned(), portals.to_json());
+        index.insert("waypoints".to_owned(), waypoints.to_json());
+        index.insert("translations".to_owned(), self.translations().to_json());
+        if let Some(ref manifest) = self.manifest {
+            index.insert("manifest".to_owned(), manifest.to_json());
+        }
+
+        let index_file = format!("{}.{}", INDEX_NAME, format.extension());
+        format.write_file(directory.join(index_file), &Json::Object(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use item_generator::ItemGenerator;
+    use locale::Catalog;
+    use std::env;
+    use world::Teleport;
+    use world::campaign::Campaign;
+    use world::manifest::CampaignManifest;
+    use world::two_dimensional::{Field, FieldType, World2d};
+    use world::waypoint::Waypoint;
+
+    #[test]
+    fn stream_levels() {
+        let directory = env::temp_dir().join("txtrpg_stream_levels");
+
+        let mut campaign = Campaign::new("Long Journey");
+        campaign.add_level("shire", World2d::new("Shire", (5, 5)).end_point((4, 4)));
+        campaign.add_level("bree", World2d::new("Bree", (3, 3)).end_point((2, 0)));
+        campaign.add_level("rivendell", World2d::new("Rivendell", (8, 8)));
+        campaign.link("shire", "bree", &[]);
+        campaign.link("bree", "rivendell", &["met_strider"]);
+        campaign.save(&directory, Format::Json).unwrap();
+
+        let mut campaign = Campaign::open(&directory, Format::Json).unwrap();
+        assert!(campaign.is_loaded("shire"));
+        assert!(!campaign.is_loaded("bree"));
+
+        campaign.current_world_mut().unwrap().set_position((4, 4));
+        assert!(campaign.advance("bree"));
+        assert!(campaign.is_loaded("bree"));
+        assert!(!campaign.is_loaded("shire"));
+        assert_eq!(campaign.current_world().unwrap().name, "Bree");
+        assert_eq!(campaign.available_successors(), Vec::<&str>::new());
+
+        campaign.set_flag("met_strider");
+        campaign.current_world_mut().unwrap().set_position((2, 0));
+        assert!(campaign.advance("rivendell"));
+        assert_eq!(campaign.current_world().unwrap().size(), (8, 8));
+    }
+
+    #[test]
+    fn other_formats() {
+        for format in &[Format::Ron, Format::Toml] {
+            let directory = env::temp_dir().join(format!("txtrpg_{}_format", format.extension()));
+
+            let mut moria = World2d::new("Moria", (3, 3)).end_point((2, 2));
+            let mellon = ItemGenerator::new().name("Mellon").gen();
+            moria.set_field(Field::new(FieldType::DoorLocked).key(&mellon.id), (1, 1));
+
+            let mut campaign = Campaign::new("Fellowship")
+                .manifest(CampaignManifest::new("Tolkien", "1.0.0").level_range(3, 8));
+            campaign.add_level("moria", moria);
+            campaign.save(&directory, *format).unwrap();
+
+            let campaign = Campaign::open(&directory, *format).unwrap();
+            let moria = campaign.current_world().unwrap();
+            assert_eq!(moria.get_field((1, 1)).key, Some(mellon.id));
+            assert_eq!(moria.end_point, (2, 2));
+            assert_eq!(campaign.manifest.as_ref().unwrap().level_range, (3, 8));
+        }
+    }
+
+    #[test]
+    fn campaign_settings() {
+        let directory = env::temp_dir().join("txtrpg_campaign_settings");
+
+        let mut campaign = Campaign::new("Wilderness")
+            .day_length(48)
+            .survival(true)
+            .translation(Catalog::new("de").string("field.Grass", "Gras"));
+        campaign.add_level("plains", World2d::new("Plains", (5, 5)));
+        campaign.add_level("tomb", World2d::new("Tomb", (3, 3)));
+        campaign.add_waypoint(Waypoint::new("Old Oak", 0, (2, 3)));
+        campaign.set_fast_travel("tomb", false);
+        campaign.save(&directory, Format::Json).unwrap();
+
+        let campaign = Campaign::open(&directory, Format::Json).unwrap();
+        assert_eq!(campaign.get_day_length(), 48);
+        assert!(campaign.survival);
+        assert_eq!(campaign.waypoints(), &[Waypoint::new("Old Oak", 0, (2, 3))]);
+        assert!(campaign.node(0).unwrap().fast_travel);
+        assert!(!campaign.node(1).unwrap().fast_travel);
+        assert_eq!(campaign.catalog("de").get("field.Grass"), "Gras");
+    }
+
+    #[test]
+    fn missing_level_file() {
+        let directory = env::temp_dir().join("txtrpg_missing_level_file");
+
+        let mut campaign = Campaign::new("Lost Pages");
+        campaign.add_level("first", World2d::new("First", (2, 2)).end_point((1, 1)));
+        campaign.add_level("second", World2d::new("Second", (2, 2)));
+        campaign.link("first", "second", &[]);
+        campaign.save(&directory, Format::Json).unwrap();
+        ::std::fs::remove_file(directory.join("second.json")).unwrap();
+
+        let mut campaign = Campaign::open(&directory, Format::Json).unwrap();
+        campaign.current_world_mut().unwrap().set_position((1, 1));
+        assert!(!campaign.advance("second"));
+        assert_eq!(campaign.current_world().unwrap().name, "First");
+    }
+}
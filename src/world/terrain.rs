@@ -0,0 +1,319 @@
+use character::Character;
+use condition::{Condition, ConditionKind};
+use data::{self, DataError};
+use event::Event;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::HashMap;
+use std::path::Path;
+use super::two_dimensional::FieldType;
+use types::Health;
+
+/// Something that happens to a character stepping onto a field
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FieldEffect {
+    /// The character takes damage, e.g. from lava
+    Damage(Health),
+    /// The character contracts a condition, e.g. from poisonous thorns
+    Afflict(ConditionKind),
+    /// The character loses stamina, e.g. from deep snow
+    DrainStamina(u32),
+}
+
+impl FieldEffect {
+    /// Reads an effect from a JSON object like `{"damage": 5}`, `{"condition": "Poison"}` or
+    /// `{"stamina": 2}`
+    pub fn from_json(json: &Json) -> Result<FieldEffect, DataError> {
+        let object = data::as_object(json, "on_enter")?;
+
+        if object.contains_key("damage") {
+            Ok(FieldEffect::Damage(data::unsigned(object, "damage")? as Health))
+        } else if object.contains_key("condition") {
+            Ok(FieldEffect::Afflict(data::parse(object, "condition")?))
+        } else if object.contains_key("stamina") {
+            Ok(FieldEffect::DrainStamina(data::unsigned(object, "stamina")? as u32))
+        } else {
+            Err(DataError::Invalid("on_enter".to_owned()))
+        }
+    }
+}
+
+impl ToJson for FieldEffect {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        match *self {
+            FieldEffect::Damage(damage) => object.insert("damage".to_owned(), damage.to_json()),
+            FieldEffect::Afflict(kind) => {
+                object.insert("condition".to_owned(), format!("{:?}", kind).to_json())
+            }
+            FieldEffect::DrainStamina(amount) => {
+                object.insert("stamina".to_owned(), amount.to_json())
+            }
+        };
+
+        Json::Object(object)
+    }
+}
+
+/// The properties of a field type defined by a campaign
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FieldDefinition {
+    /// The name shown to the player
+    pub name: String,
+    /// The character used to draw the field
+    pub glyph: char,
+    /// Whether characters can walk onto the field
+    pub passable: bool,
+    /// The amount of turns it takes to cross the field
+    pub movement_cost: u32,
+    /// What happens to a character stepping onto the field
+    pub on_enter: Option<FieldEffect>,
+}
+
+impl FieldDefinition {
+    /// Creates a new instance of `FieldDefinition`, which is passable and costs one turn to
+    /// cross
+    pub fn new(name: &str, glyph: char) -> FieldDefinition {
+        FieldDefinition {
+            name: name.to_owned(),
+            glyph,
+            passable: true,
+            movement_cost: 1,
+            on_enter: None,
+        }
+    }
+
+    /// A builder method for setting whether characters can walk onto the field
+    pub fn passable(mut self, passable: bool) -> FieldDefinition {
+        self.passable = passable;
+        self
+    }
+
+    /// A builder method for setting the amount of turns it takes to cross the field
+    pub fn movement_cost(mut self, movement_cost: u32) -> FieldDefinition {
+        self.movement_cost = movement_cost;
+        self
+    }
+
+    /// A builder method for setting what happens to a character stepping onto the field
+    pub fn on_enter(mut self, effect: FieldEffect) -> FieldDefinition {
+        self.on_enter = Some(effect);
+        self
+    }
+
+    /// Reads a definition from a JSON object as written by `to_json`
+    pub fn from_json(json: &Json) -> Result<FieldDefinition, DataError> {
+        let object = data::as_object(json, "field_types")?;
+        let glyph = data::string(object, "glyph")?;
+        let mut chars = glyph.chars();
+
+        let mut definition = match (chars.next(), chars.next()) {
+            (Some(glyph), None) => FieldDefinition::new(&data::string(object, "name")?, glyph),
+            _ => return Err(DataError::Invalid("glyph".to_owned())),
+        };
+        definition.passable = data::optional(object, "passable", data::boolean)?.unwrap_or(true);
+        definition.movement_cost = data::optional(object, "movement_cost", data::unsigned)?
+            .unwrap_or(1) as u32;
+        if let Some(effect) = object.get("on_enter") {
+            definition.on_enter = Some(FieldEffect::from_json(effect)?);
+        }
+
+        Ok(definition)
+    }
+}
+
+impl ToJson for FieldDefinition {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("glyph".to_owned(), self.glyph.to_string().to_json());
+        object.insert("passable".to_owned(), self.passable.to_json());
+        object.insert("movement_cost".to_owned(), self.movement_cost.to_json());
+        if let Some(ref effect) = self.on_enter {
+            object.insert("on_enter".to_owned(), effect.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+/// The definitions of all custom field types of a world, by id. Built-in field types keep their
+/// usual behavior.
+#[derive(Clone, Default, Debug)]
+pub struct FieldRegistry {
+    definitions: HashMap<String, FieldDefinition>,
+}
+
+impl FieldRegistry {
+    /// Creates a new instance of `FieldRegistry`
+    pub fn new() -> FieldRegistry {
+        FieldRegistry { definitions: HashMap::new() }
+    }
+
+    /// Defines the custom field type with the given id, replacing an earlier definition
+    pub fn register(&mut self, id: &str, definition: FieldDefinition) {
+        self.definitions.insert(id.to_owned(), definition);
+    }
+
+    /// Adds the definitions of the other registry whose ids this one does not define yet
+    pub fn merge(&mut self, other: &FieldRegistry) {
+        for (id, definition) in &other.definitions {
+            self.definitions.entry(id.clone()).or_insert_with(|| definition.clone());
+        }
+    }
+
+    /// Returns the definition of the custom field type with the given id
+    pub fn get(&self, id: &str) -> Option<&FieldDefinition> {
+        self.definitions.get(id)
+    }
+
+    /// Returns `true` if no field types are defined
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Returns `true` if characters can walk onto a field of the given type. Undefined custom
+    /// field types are not passable.
+    pub fn is_passable(&self, field_type: &FieldType) -> bool {
+        match *field_type {
+            FieldType::Custom(ref id) => {
+                self.get(id).is_some_and(|definition| definition.passable)
+            }
+            ref field_type => field_type.is_passable(),
+        }
+    }
+
+    /// Returns the amount of turns it takes to cross a field of the given type
+    pub fn movement_cost(&self, field_type: &FieldType) -> u32 {
+        match *field_type {
+            FieldType::Custom(ref id) => {
+                self.get(id).map_or(1, |definition| definition.movement_cost)
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns the character used to draw a field of the given type
+    pub fn glyph(&self, field_type: &FieldType) -> char {
+        match *field_type {
+            FieldType::Custom(ref id) => self.get(id).map_or('?', |definition| definition.glyph),
+            ref field_type => field_type.glyph(),
+        }
+    }
+
+    /// Applies the effect of stepping onto a field of the given type to the character
+    pub fn enter(&self, field_type: &FieldType, character: &mut Character) -> Event {
+        let effect = match *field_type {
+            FieldType::Custom(ref id) => {
+                self.get(id).and_then(|definition| definition.on_enter.clone())
+            }
+            _ => None,
+        };
+
+        match effect {
+            Some(FieldEffect::Damage(damage)) => character.take_damage(damage),
+            Some(FieldEffect::Afflict(ConditionKind::Poison)) => {
+                character.afflict(Condition::poison());
+                Event::Nothing
+            }
+            Some(FieldEffect::Afflict(ConditionKind::Disease)) => {
+                character.afflict(Condition::disease());
+                Event::Nothing
+            }
+            Some(FieldEffect::DrainStamina(amount)) => {
+                character.drain_stamina(amount);
+                Event::Nothing
+            }
+            None => Event::Nothing,
+        }
+    }
+
+    /// Reads the definitions from a JSON object mapping ids to definitions
+    pub fn from_json(json: &Json) -> Result<FieldRegistry, DataError> {
+        let mut registry = FieldRegistry::new();
+
+        for (id, definition) in data::as_object(json, "field_types")? {
+            registry.register(id, FieldDefinition::from_json(definition)?);
+        }
+
+        Ok(registry)
+    }
+
+    /// Reads the definitions from a JSON file
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<FieldRegistry, DataError> {
+        FieldRegistry::from_json(&data::read_json(path)?)
+    }
+}
+
+impl ToJson for FieldRegistry {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+        for (id, definition) in &self.definitions {
+            object.insert(id.clone(), definition.to_json());
+        }
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Character;
+    use condition::ConditionKind;
+    use rustc_serialize::json::ToJson;
+    use world::two_dimensional::FieldType;
+
+    fn registry() -> FieldRegistry {
+        let mut registry = FieldRegistry::new();
+        registry.register("lava",
+                          FieldDefinition::new("Lava", '*').on_enter(FieldEffect::Damage(5)));
+        registry.register("snow",
+                          FieldDefinition::new("Deep Snow", '\'')
+                              .movement_cost(3)
+                              .on_enter(FieldEffect::DrainStamina(2)));
+        registry.register("bars", FieldDefinition::new("Iron Bars", 'H').passable(false));
+        registry
+    }
+
+    #[test]
+    fn properties() {
+        let registry = registry();
+        let snow = FieldType::Custom("snow".to_owned());
+
+        assert!(registry.is_passable(&snow));
+        assert!(!registry.is_passable(&FieldType::Custom("bars".to_owned())));
+        assert!(!registry.is_passable(&FieldType::Custom("unknown".to_owned())));
+        assert!(!registry.is_passable(&FieldType::StoneWall));
+        assert_eq!(registry.movement_cost(&snow), 3);
+        assert_eq!(registry.movement_cost(&FieldType::Grass), 1);
+        assert_eq!(registry.glyph(&snow), '\'');
+        assert_eq!(registry.glyph(&FieldType::StoneWall), '#');
+    }
+
+    #[test]
+    fn enter() {
+        let registry = registry();
+        let mut character = Character::new("Frodo");
+        let health = character.health();
+
+        registry.enter(&FieldType::Custom("lava".to_owned()), &mut character);
+        assert_eq!(character.health(), health - 5);
+
+        registry.enter(&FieldType::Grass, &mut character);
+        assert_eq!(character.health(), health - 5);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut registry = registry();
+        registry.register("thorns",
+                          FieldDefinition::new("Thorns", '}')
+                              .on_enter(FieldEffect::Afflict(ConditionKind::Poison)));
+
+        let loaded = FieldRegistry::from_json(&registry.to_json()).unwrap();
+        for id in &["lava", "snow", "bars", "thorns"] {
+            assert_eq!(loaded.get(id), registry.get(id));
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use data::{self, DataError};
+use entity::Entity;
+use rustc_serialize::json::{Json, Object};
+use std::collections::HashMap;
+use std::path::Path;
+use super::Teleport;
+use super::spawn::SpawnPoint;
+use super::two_dimensional::{Field, World2d};
+
+/// The bits of a global tile id used by Tiled to store whether the tile is flipped
+const FLIP_FLAGS: u64 = 0xE000_0000;
+
+/// Converts maps of the Tiled editor, exported as JSON, into worlds.
+///
+/// Tiles are turned into fields by their custom properties `field_type`, `height` and `key`, the
+/// id of the key item, unless a tile is mapped explicitly. All other string properties become
+/// metadata of the field. Objects of the type `start` and `end` set the starting and end point,
+/// objects of the type `spawn` with the properties `monster` and `count` add a spawn point and
+/// all other objects are placed as entities on their field.
+pub struct TiledImporter {
+    tiles: HashMap<u64, Field>,
+}
+
+impl Default for TiledImporter {
+    fn default() -> TiledImporter {
+        TiledImporter::new()
+    }
+}
+
+impl TiledImporter {
+    /// Creates a new instance of `TiledImporter`
+    pub fn new() -> TiledImporter {
+        TiledImporter { tiles: HashMap::new() }
+    }
+
+    /// A builder method for mapping the tile with the given global id to a field, regardless
+    /// of its properties
+    pub fn tile(mut self, gid: u64, field: Field) -> TiledImporter {
+        self.tiles.insert(gid, field);
+        self
+    }
+
+    /// Converts a Tiled map into a world with the given name
+    pub fn import(&self, name: &str, json: &Json) -> Result<World2d, DataError> {
+        let map = data::as_object(json, "map")?;
+        let size = (data::unsigned(map, "width")? as usize,
+                    data::unsigned(map, "height")? as usize);
+        let tile_size = (data::unsigned(map, "tilewidth")?, data::unsigned(map, "tileheight")?);
+        if tile_size.0 == 0 || tile_size.1 == 0 {
+            return Err(DataError::Invalid("tilewidth".to_owned()));
+        }
+
+        let mut tiles = self.tileset_fields(map)?;
+        for (gid, field) in &self.tiles {
+            tiles.insert(*gid, field.clone());
+        }
+
+        let mut world = World2d::new(name, size);
+        for layer in data::as_array(data::field(map, "layers")?, "layers")? {
+            let layer = data::as_object(layer, "layers")?;
+
+            match &data::string(layer, "type")?[..] {
+                "tilelayer" => self.import_tiles(&mut world, layer, &tiles)?,
+                "objectgroup" => import_objects(&mut world, layer, tile_size)?,
+                _ => {}
+            }
+        }
+
+        let start = world.starting_point;
+        world.set_position(start);
+        Ok(world)
+    }
+
+    /// Converts the Tiled map in the given JSON file into a world with the given name
+    pub fn import_file<P: AsRef<Path>>(&self, name: &str, path: P) -> Result<World2d, DataError> {
+        self.import(name, &data::read_json(path)?)
+    }
+
+    fn tileset_fields(&self, map: &Object) -> Result<HashMap<u64, Field>, DataError> {
+        let mut fields = HashMap::new();
+
+        for tileset in data::as_array(data::field(map, "tilesets")?, "tilesets")? {
+            let tileset = data::as_object(tileset, "tilesets")?;
+            let first_gid = data::unsigned(tileset, "firstgid")?;
+            let tiles = match tileset.get("tiles") {
+                Some(tiles) => data::as_array(tiles, "tiles")?,
+                None => continue,
+            };
+
+            for tile in tiles {
+                let tile = data::as_object(tile, "tiles")?;
+                let properties = properties(tile)?;
+
+                if let Some(field_type) = properties.get("field_type") {
+                    let mut field = Field::new(data::parse_value(field_type, "field_type")?);
+                    if let Some(height) = properties.get("height") {
+                        field = field.height(height.as_i64()
+                            .ok_or_else(|| DataError::Invalid("height".to_owned()))? as i32);
+                    }
+                    if let Some(key) = properties.get("key") {
+                        field = field.key(&data::parse_value(key, "key")?);
+                    }
+                    for (name, value) in &properties {
+                        if let Some(value) = value.as_string() {
+                            if !["field_type", "key"].contains(&&name[..]) {
+                                field = field.meta(name, value);
+                            }
+                        }
+                    }
+
+                    fields.insert(first_gid + data::unsigned(tile, "id")?, field);
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+
+    fn import_tiles(&self,
+                    world: &mut World2d,
+                    layer: &Object,
+                    tiles: &HashMap<u64, Field>)
+                    -> Result<(), DataError> {
+        let (width, height) = world.size();
+        let gids = data::as_array(data::field(layer, "data")?, "data")?;
+        if gids.len() != width * height {
+            return Err(DataError::Invalid("data".to_owned()));
+        }
+
+        for (index, gid) in gids.iter().enumerate() {
+            let gid = gid.as_u64().ok_or_else(|| DataError::Invalid("data".to_owned()))? &
+                      !FLIP_FLAGS;
+            if gid == 0 {
+                continue;
+            }
+
+            let field = tiles.get(&gid).ok_or_else(|| DataError::Missing(format!("tile {}", gid)))?;
+            world.set_field(field.clone(), (index % width, index / width));
+        }
+
+        Ok(())
+    }
+}
+
+/// Places the objects of an object layer into the world
+fn import_objects(world: &mut World2d,
+                  layer: &Object,
+                  tile_size: (u64, u64))
+                  -> Result<(), DataError> {
+    let (width, height) = world.size();
+
+    for object in data::as_array(data::field(layer, "objects")?, "objects")? {
+        let object = data::as_object(object, "objects")?;
+        let position = ((data::float(object, "x")? as u64 / tile_size.0) as usize,
+                        (data::float(object, "y")? as u64 / tile_size.1) as usize);
+        if position.0 >= width || position.1 >= height {
+            return Err(DataError::Invalid("objects".to_owned()));
+        }
+
+        let properties = properties(object)?;
+        match &data::optional(object, "type", data::string)?.unwrap_or_default()[..] {
+            "start" => world.starting_point = position,
+            "end" => world.end_point = position,
+            "spawn" => {
+                let monster = properties.get("monster")
+                    .and_then(Json::as_string)
+                    .ok_or_else(|| DataError::Missing("monster".to_owned()))?;
+                let count = properties.get("count").and_then(Json::as_u64).unwrap_or(1);
+                world.spawn_points.push(SpawnPoint::new(position, monster, count as u32));
+            }
+            _ => {
+                let field = world.get_field(position).clone();
+                let name = data::string(object, "name")?;
+                world.set_field(field.entity(Entity::new(&name)), position);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the custom properties of a tile or object by name
+fn properties(object: &Object) -> Result<HashMap<String, Json>, DataError> {
+    let mut properties = HashMap::new();
+
+    if let Some(list) = object.get("properties") {
+        for property in data::as_array(list, "properties")? {
+            let property = data::as_object(property, "properties")?;
+            properties.insert(data::string(property, "name")?,
+                              data::field(property, "value")?.clone());
+        }
+    }
+
+    Ok(properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_serialize::json::Json;
+    use world::World;
+    use world::two_dimensional::{Field, FieldType};
+
+    fn map() -> Json {
+        Json::from_str(r#"{
+            "width": 3, "height": 2, "tilewidth": 16, "tileheight": 16,
+            "tilesets": [{"firstgid": 1, "tiles": [
+                {"id": 0, "properties": [{"name": "field_type", "type": "string",
+                                          "value": "Grass"},
+                                         {"name": "region", "type": "string",
+                                          "value": "Cellar"}]},
+                {"id": 1, "properties": [{"name": "field_type", "type": "string",
+                                          "value": "StoneWall"},
+                                         {"name": "height", "type": "int", "value": 3}]}
+            ]}],
+            "layers": [
+                {"type": "tilelayer", "name": "ground", "data": [1, 2, 1, 1, 2147483650, 3]},
+                {"type": "objectgroup", "name": "objects", "objects": [
+                    {"name": "", "type": "start", "x": 0, "y": 16},
+                    {"name": "", "type": "end", "x": 32, "y": 0},
+                    {"name": "Chest", "type": "", "x": 40, "y": 20},
+                    {"name": "", "type": "spawn", "x": 16, "y": 0,
+                     "properties": [{"name": "monster", "type": "string", "value": "rat"}]}
+                ]}
+            ]
+        }"#)
+            .unwrap()
+    }
+
+    #[test]
+    fn import() {
+        let world = TiledImporter::new()
+            .tile(3, Field::new(FieldType::Water))
+            .import("Cellar", &map())
+            .unwrap();
+
+        assert_eq!(world.size(), (3, 2));
+        assert_eq!(world.get_field((1, 0)).field_type, FieldType::StoneWall);
+        assert_eq!(world.get_field((1, 0)).height, 3);
+        assert_eq!(world.get_field((1, 1)).field_type, FieldType::StoneWall);
+        assert_eq!(world.get_field((2, 1)).field_type, FieldType::Water);
+        assert_eq!(world.get_field((0, 0)).get_meta("region"), Some("Cellar"));
+
+        assert_eq!(world.get_position(), (0, 1));
+        assert_eq!(world.end_point, (2, 0));
+        assert!(world.get_field((2, 1)).entity.is_some());
+        assert_eq!(world.spawn_points, vec![SpawnPoint::new((1, 0), "rat", 1)]);
+    }
+
+    #[test]
+    fn unknown_tile() {
+        match TiledImporter::new().import("Cellar", &map()) {
+            Err(DataError::Missing(ref tile)) => assert_eq!(tile, "tile 3"),
+            _ => panic!("expected a missing tile"),
+        }
+    }
+}
@@ -1,15 +1,62 @@
+use bestiary::Bestiary;
+use character::{Attribute, Character};
+use crafting::Station;
+use data::{self, DataError};
 use entity::Entity;
-use super::World;
+use fall;
+use inventory::Inventory;
+use item::{Item, ItemId};
+use loot::LootTable;
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use stealth;
+use super::{Teleport, World};
+use super::entities::{EntityId, EntityRegistry, MapEntity};
+use super::lighting::{LightMap, LightSource};
+use super::spawn::{Encounter, EncounterTable, SpawnPoint};
+use super::terrain::{FieldDefinition, FieldRegistry};
+use super::weather::{Climate, Weather};
+use swimming::{self, Stroke};
+use time::LightLevel;
+use types::Health;
+
+/// The prefix of custom field types when written as text, e.g. `custom:lava`
+pub const CUSTOM_PREFIX: &str = "custom:";
+
+/// The character a renderer should use to draw a field items lie on
+pub const ITEM_GLYPH: char = '*';
+
+/// What happened to a character walking onto a field, see `World2d::walk`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Step {
+    /// The damage the character took by falling down to the field
+    pub fall_damage: Health,
+    /// How the character got across the field if it is water
+    pub stroke: Stroke,
+    /// The amount of turns the step took, see `World2d::movement_cost`
+    pub turns: u32,
+    /// The encounter that noticed the character (optional)
+    pub encounter: Option<Encounter>,
+}
 
 /// A single field of the world
 #[derive(Clone)]
 pub struct Field {
     /// The type of the field
     pub field_type: FieldType,
-    /// The height of the field. Used for collision detection
+    /// The height of the field. Used for collision detection and fall damage
     pub height: i32,
     /// The id if the contained entity (optional)
     pub entity: Option<Entity>,
+    /// The id of the key item unlocking a locked door (optional)
+    pub key: Option<ItemId>,
+    /// Arbitrary values attached to the field by campaigns and scripts, e.g. a region name
+    pub metadata: BTreeMap<String, String>,
+    /// The crafting station standing on the field (optional)
+    pub station: Option<Station>,
 }
 
 impl Field {
@@ -19,6 +66,9 @@ impl Field {
             field_type: field_type,
             height: 0,
             entity: None,
+            key: None,
+            metadata: BTreeMap::new(),
+            station: None,
         }
     }
 
@@ -33,10 +83,87 @@ impl Field {
         self.height = height;
         self
     }
+
+    /// A builder method for setting the id of the key unlocking the door on this field
+    pub fn key(mut self, key: &ItemId) -> Field {
+        self.key = Some(key.clone());
+        self
+    }
+
+    /// A builder method for attaching a value to the field under the given key
+    pub fn meta(mut self, key: &str, value: &str) -> Field {
+        self.metadata.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// A builder method for placing a crafting station on the field
+    pub fn station(mut self, station: Station) -> Field {
+        self.station = Some(station);
+        self
+    }
+
+    /// Returns the value attached to the field under the given key
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|value| &value[..])
+    }
+
+    /// Reads a field from a JSON object like `{"type": "DoorLocked", "height": 1, "key": "<id>"}`,
+    /// where the key is the id of the key item
+    ///
+    /// The metadata is an optional object of strings, e.g. `"metadata": {"region": "Moria"}`.
+    /// A crafting station is an optional string like `"station": "Forge"`, an entity an optional
+    /// object as written by `Entity::to_json`.
+    pub fn from_json(json: &Json) -> Result<Field, DataError> {
+        let object = data::as_object(json, "field")?;
+
+        let mut metadata = BTreeMap::new();
+        if let Some(values) = object.get("metadata") {
+            for (key, value) in data::as_object(values, "metadata")? {
+                metadata.insert(key.clone(), data::parse_value(value, "metadata")?);
+            }
+        }
+
+        let entity = match object.get("entity") {
+            Some(entity) => Some(Entity::from_json(entity)?),
+            None => None,
+        };
+
+        Ok(Field {
+            field_type: data::parse(object, "type")?,
+            height: data::optional(object, "height", data::signed)?.unwrap_or(0) as i32,
+            entity,
+            key: data::optional(object, "key", data::parse)?,
+            metadata,
+            station: data::optional(object, "station", data::parse)?,
+        })
+    }
+}
+
+impl ToJson for Field {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        object.insert("type".to_owned(), self.field_type.to_string().to_json());
+        object.insert("height".to_owned(), self.height.to_json());
+        if let Some(ref key) = self.key {
+            object.insert("key".to_owned(), key.as_str().to_json());
+        }
+        if !self.metadata.is_empty() {
+            object.insert("metadata".to_owned(), self.metadata.to_json());
+        }
+        if let Some(station) = self.station {
+            object.insert("station".to_owned(), format!("{:?}", station).to_json());
+        }
+        if let Some(ref entity) = self.entity {
+            object.insert("entity".to_owned(), entity.to_json());
+        }
+
+        Json::Object(object)
+    }
 }
 
 /// The field type. Used to determine the optical properties of the ground
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum FieldType {
     /// A field consists of dirt
     Dirt,
@@ -62,6 +189,138 @@ pub enum FieldType {
     Wood,
     /// A field is a wooded fence
     WoodenFence,
+    /// A field is a closed door
+    DoorClosed,
+    /// A field is an open door
+    DoorOpen,
+    /// A field is a locked door. The key is stored in `Field::key`
+    DoorLocked,
+    /// A field type defined by the campaign, e.g. lava or ice. Its properties are looked up by
+    /// its id in the `FieldRegistry` of the world
+    Custom(String),
+}
+
+impl FieldType {
+    /// Returns `true` if characters can walk onto a field of this type. Custom field types are
+    /// always passable here, use `FieldRegistry::is_passable` to respect their definition.
+    pub fn is_passable(&self) -> bool {
+        !matches!(*self,
+                  FieldType::StoneWall | FieldType::WoodenFence | FieldType::DoorClosed |
+                  FieldType::DoorLocked)
+    }
+
+    /// Returns `true` if characters have to swim to cross a field of this type
+    pub fn is_water(&self) -> bool {
+        matches!(*self, FieldType::Water | FieldType::SwampWater)
+    }
+
+    /// Returns `true` if a field of this type can not be looked or shot through
+    pub fn blocks_sight(&self) -> bool {
+        matches!(*self, FieldType::StoneWall | FieldType::DoorClosed | FieldType::DoorLocked)
+    }
+
+    /// Returns `true` if the field is a door, no matter if open, closed or locked
+    pub fn is_door(&self) -> bool {
+        matches!(*self, FieldType::DoorClosed | FieldType::DoorOpen | FieldType::DoorLocked)
+    }
+
+    /// Returns the character a renderer should use to draw a field of this type. Custom field
+    /// types are drawn as `?`, use `FieldRegistry::glyph` to respect their definition.
+    pub fn glyph(&self) -> char {
+        match *self {
+            FieldType::Dirt => '.',
+            FieldType::Grass => ',',
+            FieldType::Hole => 'O',
+            FieldType::Mud => '%',
+            FieldType::Quicksand => ':',
+            FieldType::Sand => '_',
+            FieldType::Stone => '^',
+            FieldType::StoneWall => '#',
+            FieldType::SwampWater => '"',
+            FieldType::Water => '~',
+            FieldType::Wood => '=',
+            FieldType::WoodenFence => '|',
+            FieldType::DoorClosed => '+',
+            FieldType::DoorOpen => '/',
+            FieldType::DoorLocked => '&',
+            FieldType::Custom(_) => '?',
+        }
+    }
+
+    /// Returns the RGB color a renderer should use to draw a field of this type
+    pub fn color(&self) -> (u8, u8, u8) {
+        match *self {
+            FieldType::Dirt | FieldType::Mud => (120, 80, 40),
+            FieldType::Grass => (60, 160, 60),
+            FieldType::Hole => (20, 20, 20),
+            FieldType::Quicksand | FieldType::Sand => (220, 200, 130),
+            FieldType::Stone | FieldType::StoneWall => (128, 128, 128),
+            FieldType::SwampWater => (70, 100, 60),
+            FieldType::Water => (40, 90, 200),
+            FieldType::Wood | FieldType::WoodenFence | FieldType::DoorClosed |
+            FieldType::DoorOpen | FieldType::DoorLocked => (140, 100, 50),
+            FieldType::Custom(_) => (200, 0, 200),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FieldType::Custom(ref id) => write!(f, "{}{}", CUSTOM_PREFIX, id),
+            ref field_type => write!(f, "{:?}", field_type),
+        }
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<FieldType, ()> {
+        match s {
+            "Dirt" => Ok(FieldType::Dirt),
+            "Grass" => Ok(FieldType::Grass),
+            "Hole" => Ok(FieldType::Hole),
+            "Mud" => Ok(FieldType::Mud),
+            "Quicksand" => Ok(FieldType::Quicksand),
+            "Sand" => Ok(FieldType::Sand),
+            "Stone" => Ok(FieldType::Stone),
+            "StoneWall" => Ok(FieldType::StoneWall),
+            "SwampWater" => Ok(FieldType::SwampWater),
+            "Water" => Ok(FieldType::Water),
+            "Wood" => Ok(FieldType::Wood),
+            "WoodenFence" => Ok(FieldType::WoodenFence),
+            "DoorClosed" => Ok(FieldType::DoorClosed),
+            "DoorOpen" => Ok(FieldType::DoorOpen),
+            "DoorLocked" => Ok(FieldType::DoorLocked),
+            custom if custom.starts_with(CUSTOM_PREFIX) && custom.len() > CUSTOM_PREFIX.len() => {
+                Ok(FieldType::Custom(custom[CUSTOM_PREFIX.len()..].to_owned()))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+/// The reasons why a door could not be opened, closed or unlocked
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DoorError {
+    /// There is no door at the given position
+    NotADoor,
+    /// The door is locked
+    Locked,
+    /// The door is not locked
+    NotLocked,
+    /// The key does not fit the door
+    WrongKey,
+}
+
+/// The reasons why an item could not be picked up
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PickUpError {
+    /// No item with the given name lies on the field
+    NotHere,
+    /// The inventory has no room for the item, which stays on the ground
+    InventoryFull,
 }
 
 /// A larger section of a campaign containing a starting point and end point. The starting point
@@ -74,12 +333,32 @@ pub struct World2d {
     pub starting_point: (usize, usize),
     /// The point where the world is finished
     pub end_point: (usize, usize),
+    /// The climate defining the weather of the world
+    pub climate: Climate,
+    /// The current weather
+    weather: Weather,
+    /// The fixed positions where monsters appear
+    pub spawn_points: Vec<SpawnPoint>,
+    /// The random encounters per field type
+    pub encounter_tables: HashMap<FieldType, EncounterTable>,
+    /// How dangerous the world is, multiplying the chance of random encounters
+    pub danger: f64,
+    /// If `false`, no random encounters happen in the world
+    pub encounters_enabled: bool,
+    /// The items that can be found per field type
+    pub loot_tables: HashMap<FieldType, LootTable>,
+    /// The definitions of the custom field types used in the world
+    pub field_types: FieldRegistry,
+    /// The light sources placed in the world, e.g. torches on walls
+    pub light_sources: HashMap<(usize, usize), LightSource>,
+    /// The things on the map that are not part of a field, e.g. items lying on the ground
+    pub entities: EntityRegistry,
     /// The current position of the player
     current_position: (usize, usize),
     /// The actual size of the world
     size: (usize, usize),
-    /// The actual fields, the world consists of
-    data: Vec<Vec<Field>>,
+    /// The actual fields, the world consists of, stored row by row
+    data: Vec<Field>,
 }
 
 impl World2d {
@@ -90,9 +369,19 @@ impl World2d {
             name: name.to_owned(),
             starting_point: (0, 0),
             end_point: (0, 0),
+            climate: Climate::temperate(),
+            weather: Weather::Clear,
+            spawn_points: Vec::new(),
+            encounter_tables: HashMap::new(),
+            danger: 1.0,
+            encounters_enabled: true,
+            loot_tables: HashMap::new(),
+            field_types: FieldRegistry::new(),
+            light_sources: HashMap::new(),
+            entities: EntityRegistry::new(),
             current_position: (0, 0),
             size: (width, height),
-            data: vec![vec![Field::new(FieldType::Grass); height]; width],
+            data: vec![Field::new(FieldType::Grass); width * height],
         }
     }
 
@@ -111,10 +400,539 @@ impl World2d {
         self
     }
 
+    /// A builder method for setting the climate of the world
+    pub fn climate(mut self, climate: Climate) -> World2d {
+        self.climate = climate;
+        self
+    }
+
+    /// Returns the current weather
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    /// Rolls the next weather according to the climate of the world
+    pub fn update_weather<R: Rng>(&mut self, rng: &mut R) {
+        self.weather = self.climate.next(self.weather, rng);
+    }
+
+    /// A builder method for adding a spawn point
+    pub fn spawn_point(mut self, spawn_point: SpawnPoint) -> World2d {
+        assert!(self.is_valid_coord(spawn_point.position));
+        self.spawn_points.push(spawn_point);
+        self
+    }
+
+    /// A builder method for setting the random encounters of a field type
+    pub fn encounter_table(mut self, field_type: FieldType, table: EncounterTable) -> World2d {
+        self.encounter_tables.insert(field_type, table);
+        self
+    }
+
+    /// A builder method for setting the items that can be found on a field type
+    pub fn loot_table(mut self, field_type: FieldType, table: LootTable) -> World2d {
+        self.loot_tables.insert(field_type, table);
+        self
+    }
+
+    /// A builder method for defining a custom field type used in the world
+    pub fn field_type(mut self, id: &str, definition: FieldDefinition) -> World2d {
+        self.field_types.register(id, definition);
+        self
+    }
+
+    /// A builder method for placing a light source
+    pub fn light_source(mut self, position: (usize, usize), source: LightSource) -> World2d {
+        assert!(self.is_valid_coord(position));
+        self.light_sources.insert(position, source);
+        self
+    }
+
+    /// Computes the brightness of all fields under the given daylight. The light carried by the
+    /// player, e.g. from `LightSource::carried_by`, shines from the current position.
+    pub fn light_map(&self, light_level: LightLevel, carried: Option<LightSource>) -> LightMap {
+        let mut map = LightMap::new(self.size, light_level);
+        for (position, source) in &self.light_sources {
+            map.add_light(*position, *source);
+        }
+        if let Some(source) = carried {
+            map.add_light(self.current_position, source);
+        }
+
+        map
+    }
+
+    /// Rolls the items found when searching the field at the current position
+    pub fn search<R: Rng>(&self, rng: &mut R) -> Vec<Item> {
+        let field_type = &self.get_field(self.current_position).field_type;
+
+        self.loot_tables.get(field_type).map_or(Vec::new(), |table| table.roll(rng))
+    }
+
+    /// A builder method for setting the danger rating multiplying the chance of encounters
+    pub fn danger(mut self, danger: f64) -> World2d {
+        self.danger = danger;
+        self
+    }
+
+    /// A builder method for turning random encounters on or off
+    pub fn encounters(mut self, enabled: bool) -> World2d {
+        self.encounters_enabled = enabled;
+        self
+    }
+
+    /// Rolls a random encounter for the field at the current position
+    pub fn roll_encounter<R: Rng>(&self,
+                                  light_level: LightLevel,
+                                  rng: &mut R)
+                                  -> Option<&Encounter> {
+        if !self.encounters_enabled {
+            return None;
+        }
+
+        let field_type = &self.get_field(self.current_position).field_type;
+        self.encounter_tables
+            .get(field_type)
+            .and_then(|table| table.roll_with_danger(light_level, self.danger, rng))
+    }
+
+    /// Moves the player by one step if the target is passable and rolls a random encounter for
+    /// the field they arrive on, which interrupts the travel. Returns `None` if the player
+    /// could not move or nothing happened.
+    pub fn travel<R: Rng>(&mut self,
+                          movement: (i64, i64),
+                          light_level: LightLevel,
+                          rng: &mut R)
+                          -> Option<&Encounter> {
+        let (x, y) = self.current_position;
+        let target = ((x as i64 + movement.0) as usize, (y as i64 + movement.1) as usize);
+        if !self.is_passable(target) {
+            return None;
+        }
+
+        self.move_to(movement);
+        self.roll_encounter(light_level, rng)
+    }
+
+    /// Lets the character walk one step like `travel`. The character takes fall damage when
+    /// dropping down to the target, swims across water and sneaks past the monsters of a fight
+    /// encounter that fail to detect them. Returns `None` if the character could not move.
+    pub fn walk<R: Rng>(&mut self,
+                        character: &mut Character,
+                        movement: (i64, i64),
+                        light_level: LightLevel,
+                        bestiary: &Bestiary,
+                        rng: &mut R)
+                        -> Option<Step> {
+        let from = self.current_position;
+        let target = ((from.0 as i64 + movement.0) as usize, (from.1 as i64 + movement.1) as usize);
+        if !self.is_passable(target) {
+            return None;
+        }
+
+        let turns = self.movement_cost(character, target);
+        self.move_to(movement);
+        let fall_damage = fall::fall(character, self, from, target, rng);
+        let field_type = self.get_field(target).field_type.clone();
+        let stroke = swimming::swim(character, &field_type, rng);
+        let encounter = self.roll_encounter(light_level, rng).cloned().filter(|encounter| {
+            if !encounter.is_fight() {
+                return true;
+            }
+            match bestiary.spawn(&encounter.monster) {
+                Some(monster) => {
+                    let perception = monster.get_attribute_value(&Attribute::Perception);
+                    stealth::detects(perception, character, light_level, &field_type, rng)
+                }
+                None => true,
+            }
+        });
+
+        Some(Step {
+            fall_damage,
+            stroke,
+            turns,
+            encounter,
+        })
+    }
+
+    /// Returns the field at the given position
+    ///
+    /// # Panics
+    /// Panics if the position is outside of the world
+    pub fn get_field(&self, position: (usize, usize)) -> &Field {
+        &self.data[self.index(position)]
+    }
+
+    /// Returns the field at the given coordinates, or `None` if they are outside of the world
+    pub fn get(&self, x: usize, y: usize) -> Option<&Field> {
+        if x < self.size.0 && y < self.size.1 {
+            self.data.get(y * self.size.0 + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the field at the given coordinates as mutable, or `None` if they are outside of
+    /// the world
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Field> {
+        if x < self.size.0 && y < self.size.1 {
+            self.data.get_mut(y * self.size.0 + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the items lying on the field at the given position
+    pub fn items_at(&self, position: (usize, usize)) -> Vec<&Item> {
+        self.entities.items_at(position).into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Drops an item onto the field the player stands on. Returns its id in the registry of
+    /// entities.
+    pub fn drop_item(&mut self, item: Item) -> EntityId {
+        self.entities.add(self.current_position, MapEntity::Item(item))
+    }
+
+    /// Picks up the item with the given name from the field the player stands on and puts it
+    /// into the inventory. If the inventory is full, the item stays on the ground.
+    pub fn pick_up(&mut self, name: &str, inventory: &mut Inventory) -> Result<(), PickUpError> {
+        let position = self.current_position;
+        let id = self.entities
+            .items_at(position)
+            .into_iter()
+            .find(|&(_, item)| item.name == name)
+            .map(|(id, _)| id)
+            .ok_or(PickUpError::NotHere)?;
+
+        match self.entities.remove(id) {
+            Some(MapEntity::Item(item)) => {
+                inventory.add_item(item).map_err(|item| {
+                    self.entities.add(position, MapEntity::Item(*item));
+                    PickUpError::InventoryFull
+                })
+            }
+            _ => Err(PickUpError::NotHere),
+        }
+    }
+
+    /// Places a container, e.g. the corpse of a monster, on the field at the given position. If
+    /// another entity occupies the field, the items of the container are dropped onto the
+    /// ground instead. Returns `false` in that case.
+    pub fn spawn_container(&mut self, position: (usize, usize), mut container: Entity) -> bool {
+        let index = self.index(position);
+        if self.data[index].entity.is_some() {
+            for item in container.take_all() {
+                self.entities.add(position, MapEntity::Item(item));
+            }
+            return false;
+        }
+
+        self.data[index].entity = Some(container);
+        true
+    }
+
+    /// Returns the character a renderer should use to draw the field at the given position:
+    /// `ITEM_GLYPH` if items lie on it, otherwise the glyph of its field type
+    pub fn glyph_at(&self, position: (usize, usize)) -> char {
+        if self.entities.items_at(position).is_empty() {
+            self.field_types.glyph(&self.get_field(position).field_type)
+        } else {
+            ITEM_GLYPH
+        }
+    }
+
+    /// Returns the size of the world
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Returns `true` if nothing blocks the straight line between the two positions. The fields at
+    /// both ends are not checked. Positions outside of the world are never in sight.
+    pub fn line_of_sight(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if self.get(from.0, from.1).is_none() || self.get(to.0, to.1).is_none() {
+            return false;
+        }
+
+        let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+        let (to_x, to_y) = (to.0 as i64, to.1 as i64);
+
+        let delta_x = (to_x - x).abs();
+        let delta_y = -(to_y - y).abs();
+        let step_x = if x < to_x { 1 } else { -1 };
+        let step_y = if y < to_y { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+
+        loop {
+            if (x, y) != (from.0 as i64, from.1 as i64) && (x, y) != (to_x, to_y) &&
+               self.get_field((x as usize, y as usize)).field_type.blocks_sight() {
+                return false;
+            }
+
+            if x == to_x && y == to_y {
+                return true;
+            }
+
+            let doubled_error = 2 * error;
+            if doubled_error >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+            if doubled_error <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Returns the distance between two positions in steps, counting diagonal steps as one
+    pub fn distance(from: (usize, usize), to: (usize, usize)) -> usize {
+        let distance_x = from.0.abs_diff(to.0);
+        let distance_y = from.1.abs_diff(to.1);
+
+        if distance_x > distance_y { distance_x } else { distance_y }
+    }
+
+    /// Returns how far a character drops when stepping from one position to the other. Steps
+    /// upwards count as no drop at all.
+    pub fn height_drop(&self, from: (usize, usize), to: (usize, usize)) -> u32 {
+        let drop = self.get_field(from).height - self.get_field(to).height;
+
+        if drop > 0 { drop as u32 } else { 0 }
+    }
+
+    /// Opens the door at the given position
+    pub fn open_door(&mut self, position: (usize, usize)) -> Result<(), DoorError> {
+        let index = self.index(position);
+        let field = &mut self.data[index];
+
+        match field.field_type {
+            FieldType::DoorClosed | FieldType::DoorOpen => {
+                field.field_type = FieldType::DoorOpen;
+                Ok(())
+            }
+            FieldType::DoorLocked => Err(DoorError::Locked),
+            _ => Err(DoorError::NotADoor),
+        }
+    }
+
+    /// Closes the door at the given position
+    pub fn close_door(&mut self, position: (usize, usize)) -> Result<(), DoorError> {
+        let index = self.index(position);
+        let field = &mut self.data[index];
+
+        match field.field_type {
+            FieldType::DoorClosed | FieldType::DoorOpen => {
+                field.field_type = FieldType::DoorClosed;
+                Ok(())
+            }
+            FieldType::DoorLocked => Err(DoorError::Locked),
+            _ => Err(DoorError::NotADoor),
+        }
+    }
+
+    /// Unlocks the door at the given position if the given item is its key. The door stays
+    /// closed.
+    pub fn unlock_door(&mut self, position: (usize, usize), key: &Item) -> Result<(), DoorError> {
+        self.check_locked_door(position)?;
+
+        let index = self.index(position);
+        let field = &mut self.data[index];
+        if field.key.as_ref() != Some(&key.id) {
+            return Err(DoorError::WrongKey);
+        }
+
+        field.field_type = FieldType::DoorClosed;
+        Ok(())
+    }
+
+    /// Unlocks the door at the given position if its key is in the inventory
+    pub fn unlock_door_with_inventory(&mut self,
+                                      position: (usize, usize),
+                                      inventory: &Inventory)
+                                      -> Result<(), DoorError> {
+        self.check_locked_door(position)?;
+
+        let key = match self.get_field(position).key {
+            Some(ref key) => inventory.find_by_id(key).cloned(),
+            None => None,
+        };
+
+        match key {
+            Some(key) => self.unlock_door(position, &key),
+            None => Err(DoorError::WrongKey),
+        }
+    }
+
+    fn check_locked_door(&self, position: (usize, usize)) -> Result<(), DoorError> {
+        match self.get_field(position).field_type {
+            FieldType::DoorLocked => Ok(()),
+            FieldType::DoorClosed | FieldType::DoorOpen => Err(DoorError::NotLocked),
+            _ => Err(DoorError::NotADoor),
+        }
+    }
+
+    /// Returns the amount of turns it takes the character to step onto the given position. The
+    /// cost of the field type is multiplied by the weather, and every leg wound of the character
+    /// adds another turn.
+    pub fn movement_cost(&self, character: &Character, position: (usize, usize)) -> u32 {
+        let field_cost = self.field_types.movement_cost(&self.get_field(position).field_type);
+
+        field_cost * self.weather.movement_cost() + character.movement_cost() - 1
+    }
+
+    /// Returns `true` if the position is inside the world and can be walked on
+    pub fn is_passable(&self, position: (usize, usize)) -> bool {
+        let (width, height) = self.size;
+        position.0 < width && position.1 < height &&
+        self.field_types.is_passable(&self.get_field(position).field_type)
+    }
+
+    /// Searches the shortest way between two positions, including both ends. Doors count as
+    /// walkable, as the player can open them. Returns `None` if there is no way.
+    pub fn find_path(&self,
+                     from: (usize, usize),
+                     to: (usize, usize))
+                     -> Option<Vec<(usize, usize)>> {
+        let (width, height) = self.size;
+        if from.0 >= width || from.1 >= height || to.0 >= width || to.1 >= height {
+            return None;
+        }
+
+        let mut previous: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut queue = VecDeque::new();
+        previous.insert(from, from);
+        queue.push_back(from);
+
+        while let Some(position) = queue.pop_front() {
+            if position == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = previous[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for &(step_x, step_y) in &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1),
+                                       (0, 1), (1, 1)] {
+                let x = position.0 as i64 + step_x;
+                let y = position.1 as i64 + step_y;
+                if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+                    continue;
+                }
+
+                let next = (x as usize, y as usize);
+                let field_type = &self.get_field(next).field_type;
+                if !previous.contains_key(&next) &&
+                   (self.field_types.is_passable(field_type) || field_type.is_door()) {
+                    previous.insert(next, position);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Sets the given field at the given position
     pub fn set_field(&mut self, field: Field, position: (usize, usize)) {
         assert!(self.is_valid_coord(position));
-        self.data[position.0][position.1] = field;
+        let index = self.index(position);
+        self.data[index] = field;
+    }
+
+    /// Reads a world from a JSON object as written by `to_json`, including the entities of the
+    /// fields, the weather and the encounter and loot tables. Behaviours of entities and allies
+    /// are not saved. The fields are stored row by row, older files with one array per column
+    /// are migrated.
+    pub fn from_json(json: &Json) -> Result<World2d, DataError> {
+        let object = data::as_object(json, "world")?;
+        let size = data::position(object, "size")?;
+
+        let mut world = World2d::new(&data::string(object, "name")?, size);
+        world.starting_point = data::position(object, "starting_point")?;
+        world.current_position = data::optional(object, "position", data::position)?
+            .unwrap_or(world.starting_point);
+        world.end_point = data::position(object, "end_point")?;
+        world.danger = data::optional(object, "danger", data::float)?.unwrap_or(1.0);
+        world.encounters_enabled = data::optional(object, "encounters_enabled", data::boolean)?
+            .unwrap_or(true);
+        if let Some(field_types) = object.get("field_types") {
+            world.field_types = FieldRegistry::from_json(field_types)?;
+        }
+        if let Some(entities) = object.get("entities") {
+            world.entities = EntityRegistry::from_json(entities)?;
+        }
+        if let Some(climate) = object.get("climate") {
+            world.climate = Climate::from_json(climate)?;
+        }
+        world.weather = data::optional(object, "weather", data::parse)?.unwrap_or(Weather::Clear);
+        if let Some(tables) = object.get("encounter_tables") {
+            for (field_type, table) in data::as_object(tables, "encounter_tables")? {
+                let field_type = field_type.parse()
+                    .map_err(|_| DataError::Invalid("encounter_tables".to_owned()))?;
+                world.encounter_tables.insert(field_type, EncounterTable::from_json(table)?);
+            }
+        }
+        if let Some(tables) = object.get("loot_tables") {
+            for (field_type, table) in data::as_object(tables, "loot_tables")? {
+                let field_type = field_type.parse()
+                    .map_err(|_| DataError::Invalid("loot_tables".to_owned()))?;
+                world.loot_tables.insert(field_type, LootTable::from_json(table)?);
+            }
+        }
+
+        let fields = data::as_array(data::field(object, "fields")?, "fields")?;
+        if fields.iter().all(Json::is_array) && !fields.is_empty() {
+            if fields.len() != size.0 {
+                return Err(DataError::Invalid("fields".to_owned()));
+            }
+
+            for (x, column) in fields.iter().enumerate() {
+                let column = data::as_array(column, "fields")?;
+                if column.len() != size.1 {
+                    return Err(DataError::Invalid("fields".to_owned()));
+                }
+
+                for (y, field) in column.iter().enumerate() {
+                    world.data[y * size.0 + x] = Field::from_json(field)?;
+                }
+            }
+        } else {
+            if fields.len() != world.data.len() {
+                return Err(DataError::Invalid("fields".to_owned()));
+            }
+
+            world.data = fields.iter().map(Field::from_json).collect::<Result<_, _>>()?;
+        }
+
+        if let Some(light_sources) = object.get("light_sources") {
+            for light_source in data::as_array(light_sources, "light_sources")? {
+                let position = data::position(data::as_object(light_source, "light_sources")?,
+                                              "position")?;
+                world.light_sources.insert(position, LightSource::from_json(light_source)?);
+            }
+        }
+
+        for spawn_point in data::as_array(data::field(object, "spawn_points")?, "spawn_points")? {
+            let spawn_point = data::as_object(spawn_point, "spawn_points")?;
+            let position = data::position(spawn_point, "position")?;
+            world.spawn_points.push(SpawnPoint::new(position,
+                                                    &data::string(spawn_point, "monster")?,
+                                                    data::unsigned(spawn_point, "count")? as u32));
+        }
+
+        Ok(world)
+    }
+
+    fn index(&self, position: (usize, usize)) -> usize {
+        let (width, height) = self.size;
+        assert!(position.0 < width && position.1 < height);
+
+        position.1 * width + position.0
     }
 
     fn is_valid_coord(&mut self, coords: (usize, usize)) -> bool {
@@ -137,6 +955,70 @@ impl World2d {
     }
 }
 
+impl ToJson for World2d {
+    fn to_json(&self) -> Json {
+        let mut object = Object::new();
+
+        let spawn_points: Vec<Json> = self.spawn_points
+            .iter()
+            .map(|spawn_point| {
+                let mut object = Object::new();
+                object.insert("position".to_owned(), spawn_point.position.to_json());
+                object.insert("monster".to_owned(), spawn_point.monster.to_json());
+                object.insert("count".to_owned(), spawn_point.count.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        object.insert("name".to_owned(), self.name.to_json());
+        object.insert("size".to_owned(), self.size.to_json());
+        object.insert("starting_point".to_owned(), self.starting_point.to_json());
+        object.insert("position".to_owned(), self.current_position.to_json());
+        object.insert("end_point".to_owned(), self.end_point.to_json());
+        object.insert("danger".to_owned(), self.danger.to_json());
+        object.insert("encounters_enabled".to_owned(), self.encounters_enabled.to_json());
+        object.insert("fields".to_owned(), self.data.to_json());
+        object.insert("spawn_points".to_owned(), spawn_points.to_json());
+        if !self.light_sources.is_empty() {
+            let light_sources: Vec<Json> = self.light_sources
+                .iter()
+                .map(|(position, source)| {
+                    let mut object = Object::new();
+                    object.insert("position".to_owned(), position.to_json());
+                    object.insert("radius".to_owned(), source.radius.to_json());
+                    object.insert("intensity".to_owned(), source.intensity.to_json());
+                    Json::Object(object)
+                })
+                .collect();
+            object.insert("light_sources".to_owned(), light_sources.to_json());
+        }
+        if !self.field_types.is_empty() {
+            object.insert("field_types".to_owned(), self.field_types.to_json());
+        }
+        if !self.entities.is_empty() {
+            object.insert("entities".to_owned(), self.entities.to_json());
+        }
+        object.insert("climate".to_owned(), self.climate.to_json());
+        object.insert("weather".to_owned(), format!("{:?}", self.weather).to_json());
+        if !self.encounter_tables.is_empty() {
+            let tables: Object = self.encounter_tables
+                .iter()
+                .map(|(field_type, table)| (field_type.to_string(), table.to_json()))
+                .collect();
+            object.insert("encounter_tables".to_owned(), Json::Object(tables));
+        }
+        if !self.loot_tables.is_empty() {
+            let tables: Object = self.loot_tables
+                .iter()
+                .map(|(field_type, table)| (field_type.to_string(), table.to_json()))
+                .collect();
+            object.insert("loot_tables".to_owned(), Json::Object(tables));
+        }
+
+        Json::Object(object)
+    }
+}
+
 impl World for World2d {
     type Position = (usize, usize);
 
@@ -157,15 +1039,74 @@ impl World for World2d {
         pos_x = ((pos_x as i64) + mov_x) as usize;
         pos_y = ((pos_y as i64) + mov_y) as usize;
 
+        // Allies follow the player onto the field they left
+        self.entities.move_allies(self.current_position);
         self.current_position = (pos_x, pos_y);
     }
 }
 
+impl Teleport for World2d {
+    fn set_position(&mut self, position: Self::Position) {
+        self.entities.move_allies(position);
+        self.current_position = position;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bestiary::MonsterDefinition;
+    use injury::BodyPart;
     use world::campaign::Campaign;
+    use world::weather::{Climate, Weather};
+    use world::spawn::{EncounterTable, SpawnPoint};
+    use world::lighting::LightSource;
+    use world::terrain::FieldDefinition;
     use entity::Entity;
+    use inventory::Inventory;
+    use item_generator::ItemGenerator;
+    use lock::Lock;
+    use loot::{LootEntry, LootTable};
+    use item::ItemType;
+    use time::LightLevel;
+    use rand::{SeedableRng, XorShiftRng};
+    use rustc_serialize::json::{Json, ToJson};
+
+    #[test]
+    fn ground_items() {
+        let mut world = World2d::new("Meadow", (3, 3)).starting_point((1, 1));
+        let flower = ItemGenerator::new().name("Athelas").gen();
+        world.drop_item(flower.clone());
+
+        assert_eq!(world.items_at((1, 1)), vec![&flower]);
+        assert_eq!(world.glyph_at((1, 1)), ITEM_GLYPH);
+        assert_eq!(world.glyph_at((0, 0)), ',');
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        assert_eq!(loaded.items_at((1, 1)), vec![&flower]);
+
+        assert_eq!(world.pick_up("Athelas", &mut Inventory::new(0)),
+                   Err(PickUpError::InventoryFull));
+        assert_eq!(world.items_at((1, 1)), vec![&flower]);
+
+        let mut inventory = Inventory::new(5);
+        assert_eq!(world.pick_up("Athelas", &mut inventory), Ok(()));
+        assert_eq!(world.pick_up("Athelas", &mut inventory), Err(PickUpError::NotHere));
+        assert!(inventory.find_by_name("Athelas").is_some());
+        assert!(world.items_at((1, 1)).is_empty());
+    }
+
+    #[test]
+    fn spawn_container() {
+        let mut world = World2d::new("Battlefield", (2, 1));
+        let sword = ItemGenerator::new().name("Orcrist").gen();
+
+        assert!(world.spawn_container((0, 0), Entity::new("Corpse of Thorin")));
+        assert!(!world.spawn_container((0, 0), Entity::new("Chest").container(vec![sword])));
+        assert_eq!(world.get_field((0, 0)).entity.as_ref().map(Entity::name),
+                   Some("Corpse of Thorin"));
+        assert_eq!(world.items_at((0, 0)).len(), 1);
+    }
 
     #[test]
     fn build_campaign() {
@@ -190,6 +1131,300 @@ mod tests {
         assert_eq!(world.size.1, 10);
     }
 
+    #[test]
+    fn world_weather() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut world = World2d::new("Hunger Game", (10, 10))
+            .climate(Climate::new(vec![(Weather::Fog, 1)], 1.0));
+
+        assert_eq!(world.weather(), Weather::Clear);
+
+        world.update_weather(&mut rng);
+
+        assert_eq!(world.weather(), Weather::Fog);
+    }
+
+    #[test]
+    fn movement_cost() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut world = World2d::new("Hunger Game", (10, 10))
+            .climate(Climate::new(vec![(Weather::Snow, 1)], 1.0))
+            .field_type("bog", FieldDefinition::new("Bog", '~').movement_cost(3));
+        world.set_field(Field::new(FieldType::Custom("bog".to_owned())), (1, 0));
+        let mut character = Character::new("Katniss");
+
+        assert_eq!(world.movement_cost(&character, (0, 0)), 1);
+        assert_eq!(world.movement_cost(&character, (1, 0)), 3);
+
+        world.update_weather(&mut rng);
+        character.injure(BodyPart::Leg);
+        assert_eq!(world.movement_cost(&character, (0, 0)), 3);
+        assert_eq!(world.movement_cost(&character, (1, 0)), 7);
+    }
+
+    #[test]
+    fn walking() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut bestiary = Bestiary::new();
+        let wolf = Json::from_str(r#"{"id": "wolf", "name": "Wolf", "health": 10,
+                                      "attack_damage": 2, "attributes": {"Perception": -1000}}"#)
+            .unwrap();
+        bestiary.add_monster(MonsterDefinition::from_json(&wolf).unwrap()).unwrap();
+        let mut world = World2d::new("Hunger Game", (10, 10))
+            .encounter_table(FieldType::Grass, EncounterTable::new(1.0).encounter("wolf", 1, 1));
+        world.set_field(Field::new(FieldType::Stone).height(5), (0, 0));
+        world.set_field(Field::new(FieldType::Water), (0, 1));
+        world.set_field(Field::new(FieldType::StoneWall), (0, 2));
+
+        let mut character = Character::new("Katniss");
+        let health = character.health();
+        let step = world.walk(&mut character, (1, 0), LightLevel::Day, &bestiary, &mut rng)
+            .unwrap();
+        assert_eq!(step.fall_damage, 12);
+        assert_eq!(character.health(), health - step.fall_damage);
+        assert_eq!(step.stroke, Stroke::Dry);
+        assert_eq!(step.turns, 1);
+        assert_eq!(step.encounter.unwrap().monster, "wolf");
+
+        character.update_attribute(&Attribute::Dexterity, 1000);
+        character.set_sneaking(true);
+        let step = world.walk(&mut character, (1, 0), LightLevel::Day, &bestiary, &mut rng)
+            .unwrap();
+        assert_eq!(step.fall_damage, 0);
+        assert_eq!(step.encounter, None);
+
+        world.set_position((0, 0));
+        let step = world.walk(&mut character, (0, 1), LightLevel::Day, &bestiary, &mut rng)
+            .unwrap();
+        assert_eq!(step.stroke, Stroke::Swam);
+        assert!(world.walk(&mut character, (0, 1), LightLevel::Day, &bestiary, &mut rng)
+            .is_none());
+        assert_eq!(world.get_position(), (0, 1));
+    }
+
+    #[test]
+    fn world_encounters() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut world = World2d::new("Hunger Game", (10, 10))
+            .spawn_point(SpawnPoint::new((5, 5), "wolf", 2))
+            .encounter_table(FieldType::Grass, EncounterTable::new(1.0).encounter("wolf", 1, 1));
+
+        assert_eq!(world.spawn_points.len(), 1);
+        assert_eq!(world.roll_encounter(LightLevel::Day, &mut rng).unwrap().monster,
+                   "wolf");
+
+        world.set_field(Field::new(FieldType::Stone), (0, 0));
+        assert_eq!(world.roll_encounter(LightLevel::Day, &mut rng), None);
+
+        world.set_field(Field::new(FieldType::StoneWall), (0, 1));
+        assert_eq!(world.travel((0, 1), LightLevel::Day, &mut rng), None);
+        assert_eq!(world.get_position(), (0, 0));
+        assert_eq!(world.travel((1, 0), LightLevel::Day, &mut rng).unwrap().monster, "wolf");
+
+        let mut world = world.encounters(false);
+        assert_eq!(world.travel((1, 0), LightLevel::Day, &mut rng), None);
+        assert_eq!(world.get_position(), (2, 0));
+    }
+
+    #[test]
+    fn passability() {
+        let mut world = World2d::new("Hunger Game", (10, 10));
+        world.set_field(Field::new(FieldType::StoneWall), (1, 1));
+
+        assert!(world.is_passable((0, 0)));
+        assert!(!world.is_passable((1, 1)));
+        assert!(!world.is_passable((10, 0)));
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let mut world = World2d::new("Hunger Game", (10, 10));
+        world.set_field(Field::new(FieldType::StoneWall), (2, 2));
+        world.set_field(Field::new(FieldType::WoodenFence), (5, 5));
+
+        assert!(world.line_of_sight((0, 0), (1, 1)));
+        assert!(!world.line_of_sight((0, 0), (4, 4)));
+        assert!(world.line_of_sight((0, 0), (2, 2)));
+        assert!(world.line_of_sight((4, 4), (7, 7)));
+        assert!(world.line_of_sight((0, 3), (9, 3)));
+        assert!(!world.line_of_sight((9, 9), (15, 15)));
+        assert!(!world.line_of_sight((12, 0), (9, 0)));
+
+        assert_eq!(World2d::distance((0, 0), (3, 7)), 7);
+    }
+
+    #[test]
+    fn doors() {
+        let mut world = World2d::new("Moria", (5, 5));
+        let mellon = ItemGenerator::new().name("Mellon").gen();
+        world.set_field(Field::new(FieldType::DoorLocked).key(&mellon.id), (2, 0));
+
+        assert!(!world.is_passable((2, 0)));
+        assert!(!world.line_of_sight((0, 0), (4, 0)));
+        assert_eq!(world.open_door((2, 0)), Err(DoorError::Locked));
+        assert_eq!(world.open_door((1, 0)), Err(DoorError::NotADoor));
+
+        let mut inventory = Inventory::new(5);
+        let wrong_key = ItemGenerator::new().name("Rusty Key").gen();
+        assert_eq!(world.unlock_door((2, 0), &wrong_key), Err(DoorError::WrongKey));
+        // A key of the same name does not fit either
+        let forged_key = ItemGenerator::new().name("Mellon").gen();
+        assert_eq!(world.unlock_door((2, 0), &forged_key), Err(DoorError::WrongKey));
+        assert_eq!(world.unlock_door_with_inventory((2, 0), &inventory),
+                   Err(DoorError::WrongKey));
+
+        let _ = inventory.add_item(mellon);
+        assert_eq!(world.unlock_door_with_inventory((2, 0), &inventory), Ok(()));
+        assert_eq!(world.get_field((2, 0)).field_type, FieldType::DoorClosed);
+
+        assert_eq!(world.open_door((2, 0)), Ok(()));
+        assert!(world.is_passable((2, 0)));
+        assert!(world.line_of_sight((0, 0), (4, 0)));
+
+        assert_eq!(world.close_door((2, 0)), Ok(()));
+        assert!(!world.is_passable((2, 0)));
+    }
+
+    #[test]
+    fn find_path() {
+        let mut world = World2d::new("Labyrinth", (5, 5));
+        for y in 0..4 {
+            world.set_field(Field::new(FieldType::StoneWall), (2, y));
+        }
+
+        let path = world.find_path((0, 0), (4, 0)).unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!(path[4], (2, 4));
+
+        world.set_field(Field::new(FieldType::DoorLocked), (2, 4));
+        assert!(world.find_path((0, 0), (4, 0)).is_some());
+
+        world.set_field(Field::new(FieldType::StoneWall), (2, 4));
+        assert_eq!(world.find_path((0, 0), (4, 0)), None);
+    }
+
+    #[test]
+    fn flat_storage() {
+        let mut world = World2d::new("Hunger Game", (3, 2));
+        world.set_field(Field::new(FieldType::Stone), (2, 1));
+        world.get_mut(0, 1).unwrap().height = 4;
+
+        assert_eq!(world.data.len(), 6);
+        assert_eq!(world.get(2, 1).unwrap().field_type, FieldType::Stone);
+        assert_eq!(world.get_field((0, 1)).height, 4);
+        assert!(world.get(3, 0).is_none());
+        assert!(world.get(0, 2).is_none());
+    }
+
+    #[test]
+    fn legacy_json() {
+        let json = Json::from_str(r#"{"name": "Old", "size": [2, 1], "starting_point": [0, 0],
+                                      "end_point": [1, 0], "spawn_points": [],
+                                      "fields": [[{"type": "Grass"}], [{"type": "Sand"}]]}"#)
+            .unwrap();
+        let world = World2d::from_json(&json).unwrap();
+
+        assert_eq!(world.get(1, 0).unwrap().field_type, FieldType::Sand);
+        assert_eq!(World2d::from_json(&world.to_json()).unwrap().to_json(), world.to_json());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut world = World2d::new("Moria", (4, 3))
+            .starting_point((0, 1))
+            .end_point((3, 2))
+            .spawn_point(SpawnPoint::new((2, 2), "orc", 3));
+        let mellon = ItemGenerator::new().name("Mellon").gen();
+        world.set_field(Field::new(FieldType::DoorLocked).key(&mellon.id).height(2), (1, 2));
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        assert_eq!(loaded.size(), (4, 3));
+        assert_eq!(loaded.get_position(), (0, 1));
+        assert_eq!(loaded.spawn_points, world.spawn_points);
+        assert_eq!(loaded.get_field((1, 2)).key, Some(mellon.id));
+        assert_eq!(loaded.get_field((1, 2)).height, 2);
+        assert_eq!(loaded.to_json(), world.to_json());
+    }
+
+    #[test]
+    fn locked_chest_round_trip() {
+        let key = ItemGenerator::new().name("Chest Key").gen();
+        let gold = ItemGenerator::new().name("Golden Crown").gen();
+        let chest = Entity::new("Chest")
+            .with_lock(Lock::new(12).key(&key.id))
+            .container(vec![gold.clone()]);
+        let mut world = World2d::new("Vault", (2, 1))
+            .climate(Climate::arctic())
+            .encounter_table(FieldType::Grass,
+                             EncounterTable::new(0.2).encounter("wolf", 2, 3).event("A storm", 1))
+            .loot_table(FieldType::Grass,
+                        LootTable::new()
+                            .entry(LootEntry::new(ItemType::Gem, 0.5))
+                            .unique("crown", 0.1));
+        world.set_field(Field::new(FieldType::Grass).entity(chest), (1, 0));
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        let entity = loaded.get_field((1, 0)).entity.as_ref().unwrap();
+        assert_eq!(entity.name(), "Chest");
+        assert!(entity.is_locked());
+        assert_eq!(entity.lock().unwrap().key, Some(key.id));
+        assert_eq!(entity.contents().unwrap()[0].name, gold.name);
+        assert_eq!(loaded.climate.weights, Climate::arctic().weights);
+        assert_eq!(loaded.encounter_tables, world.encounter_tables);
+        assert_eq!(loaded.loot_tables, world.loot_tables);
+        assert_eq!(loaded.to_json(), world.to_json());
+    }
+
+    #[test]
+    fn lighting() {
+        let world = World2d::new("Cave", (14, 1))
+            .light_source((13, 0), LightSource::campfire());
+        let map = world.light_map(LightLevel::Night, Some(LightSource::torch()));
+
+        assert!(map.is_visible((0, 0)));
+        assert!(map.is_visible((9, 0)));
+        assert!(!map.is_visible((6, 0)));
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        assert_eq!(loaded.light_sources, world.light_sources);
+    }
+
+    #[test]
+    fn field_metadata() {
+        let mut world = World2d::new("Shire", (2, 2));
+        world.set_field(Field::new(FieldType::Grass).meta("region", "Hobbiton").meta("script", "7"),
+                        (1, 1));
+        assert_eq!(world.get_field((1, 1)).get_meta("region"), Some("Hobbiton"));
+        assert_eq!(world.get_field((0, 0)).get_meta("region"), None);
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        assert_eq!(loaded.get_field((1, 1)).metadata, world.get_field((1, 1)).metadata);
+        assert!(loaded.get_field((0, 0)).metadata.is_empty());
+    }
+
+    #[test]
+    fn parse_field_type() {
+        assert_eq!("SwampWater".parse(), Ok(FieldType::SwampWater));
+        assert_eq!("Lava".parse::<FieldType>(), Err(()));
+    }
+
+    #[test]
+    fn custom_field_types() {
+        let lava = FieldType::Custom("lava".to_owned());
+        assert_eq!("custom:lava".parse(), Ok(lava.clone()));
+        assert_eq!(lava.to_string(), "custom:lava");
+
+        let mut world = World2d::new("Mount Doom", (3, 1))
+            .field_type("lava", FieldDefinition::new("Lava", '*').passable(false));
+        world.set_field(Field::new(lava), (1, 0));
+        assert!(!world.is_passable((1, 0)));
+        assert_eq!(world.find_path((0, 0), (2, 0)), None);
+
+        let loaded = World2d::from_json(&world.to_json()).unwrap();
+        assert!(!loaded.is_passable((1, 0)));
+        assert_eq!(loaded.field_types.get("lava").unwrap().glyph, '*');
+    }
+
     #[test]
     fn new_field() {
         let mut field = Field::new(FieldType::WoodenFence);
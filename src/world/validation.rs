@@ -0,0 +1,145 @@
+use bestiary::Bestiary;
+use super::campaign::Campaign;
+use super::two_dimensional::World2d;
+
+/// The kind of problem found in a world
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum IssueKind {
+    /// The world has no fields at all
+    EmptyWorld,
+    /// The starting point lies outside of the world
+    StartOutOfBounds,
+    /// The end point lies outside of the world
+    EndOutOfBounds,
+    /// There is no way from the starting point to the end point
+    UnreachableEnd,
+    /// A spawn point or encounter refers to a monster missing in the bestiary
+    UnknownMonster(String),
+}
+
+/// A problem found when validating a campaign
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidationIssue {
+    /// The id of the world containing the problem
+    pub world: String,
+    /// What is wrong
+    pub kind: IssueKind,
+}
+
+/// Checks a single world for problems. Monster ids are looked up in the given bestiary.
+pub fn validate_world(world: &World2d, bestiary: &Bestiary) -> Vec<IssueKind> {
+    let (width, height) = world.size();
+    if width == 0 || height == 0 {
+        return vec![IssueKind::EmptyWorld];
+    }
+
+    let mut issues = Vec::new();
+    let is_inside = |(x, y): (usize, usize)| x < width && y < height;
+
+    if !is_inside(world.starting_point) {
+        issues.push(IssueKind::StartOutOfBounds);
+    }
+
+    if !is_inside(world.end_point) {
+        issues.push(IssueKind::EndOutOfBounds);
+    }
+
+    if issues.is_empty() && world.find_path(world.starting_point, world.end_point).is_none() {
+        issues.push(IssueKind::UnreachableEnd);
+    }
+
+    let spawned = world.spawn_points.iter().map(|spawn_point| &spawn_point.monster);
+    let encountered = world.encounter_tables
+        .values()
+        .flat_map(|table| table.encounters.iter())
+        .filter(|encounter| encounter.is_fight())
+        .map(|encounter| &encounter.monster);
+
+    for monster in spawned.chain(encountered) {
+        let issue = IssueKind::UnknownMonster(monster.clone());
+        if bestiary.get(monster).is_none() && !issues.contains(&issue) {
+            issues.push(issue);
+        }
+    }
+
+    issues
+}
+
+impl Campaign<World2d> {
+    /// Checks all loaded worlds of the campaign for problems, which should be fixed before
+    /// shipping the campaign to players. Returns an empty list if everything is fine.
+    pub fn validate(&self, bestiary: &Bestiary) -> Vec<ValidationIssue> {
+        (0..self.len())
+            .filter_map(|index| self.node(index))
+            .flat_map(|node| {
+                let issues = node.world.as_ref().map_or(Vec::new(), |world| {
+                    validate_world(world, bestiary)
+                });
+
+                issues.into_iter().map(move |kind| {
+                    ValidationIssue {
+                        world: node.id.clone(),
+                        kind,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bestiary::Bestiary;
+    use rustc_serialize::json::Json;
+    use world::campaign::Campaign;
+    use world::spawn::{EncounterTable, SpawnPoint};
+    use world::two_dimensional::{Field, FieldType, World2d};
+
+    fn bestiary() -> Bestiary {
+        let mut bestiary = Bestiary::new();
+        bestiary.load_json(&Json::from_str(r#"[{"id": "wolf", "name": "Wolf", "health": 10,
+                                                 "attack_damage": 5}]"#)
+                .unwrap())
+            .unwrap();
+        bestiary
+    }
+
+    #[test]
+    fn valid_campaign() {
+        let mut campaign = Campaign::new("Flawless");
+        campaign.add_world(World2d::new("Meadow", (5, 5))
+            .end_point((4, 4))
+            .spawn_point(SpawnPoint::new((2, 2), "wolf", 1)));
+
+        assert!(campaign.validate(&bestiary()).is_empty());
+    }
+
+    #[test]
+    fn broken_campaign() {
+        let mut walled = World2d::new("Prison", (5, 5)).end_point((4, 4));
+        for x in 0..5 {
+            walled.set_field(Field::new(FieldType::StoneWall), (x, 2));
+        }
+
+        let mut out_of_bounds = World2d::new("Void", (3, 3))
+            .encounter_table(FieldType::Grass,
+                             EncounterTable::new(0.5).encounter("dragon", 1, 1));
+        out_of_bounds.end_point = (7, 1);
+
+        let mut campaign = Campaign::new("Broken");
+        campaign.add_level("prison", walled);
+        campaign.add_level("void", out_of_bounds);
+        campaign.add_level("nothing", World2d::new("Nothing", (0, 0)));
+
+        let issues = campaign.validate(&bestiary());
+        let kinds: Vec<(&str, &IssueKind)> =
+            issues.iter().map(|issue| (&issue.world[..], &issue.kind)).collect();
+
+        assert_eq!(kinds,
+                   vec![("prison", &IssueKind::UnreachableEnd),
+                        ("void", &IssueKind::EndOutOfBounds),
+                        ("void", &IssueKind::UnknownMonster("dragon".to_owned())),
+                        ("nothing", &IssueKind::EmptyWorld)]);
+    }
+}
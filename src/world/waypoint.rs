@@ -0,0 +1,115 @@
+use item::{Item, ItemType};
+use item_generator::ItemGenerator;
+
+/// The name of the consumable that teleports to a discovered waypoint from anywhere
+pub const SCROLL_NAME: &str = "Scroll of Teleportation";
+
+/// The reasons why the player can not travel to a waypoint
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TravelError {
+    /// There is no waypoint with the given name
+    Unknown,
+    /// The player has not discovered the waypoint yet
+    Undiscovered,
+    /// The player does not stand on a discovered waypoint
+    NotAtWaypoint,
+    /// The world the player is in or the world of the waypoint does not allow fast travel
+    Forbidden,
+    /// The inventory holds no scroll of teleportation
+    NoScroll,
+    /// The world of the waypoint could not be loaded
+    NotLoaded,
+}
+
+/// A named place of a campaign the player can fast-travel to once they discovered it by
+/// standing on it
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Waypoint<P> {
+    /// The unique name of the waypoint, e.g. `Riverwood`
+    pub name: String,
+    /// The index of the world the waypoint is in
+    pub world: usize,
+    /// The position of the waypoint
+    pub position: P,
+}
+
+impl<P> Waypoint<P> {
+    /// Creates a new instance of `Waypoint`
+    pub fn new(name: &str, world: usize, position: P) -> Waypoint<P> {
+        Waypoint {
+            name: name.to_owned(),
+            world,
+            position,
+        }
+    }
+}
+
+/// Generates a scroll of teleportation
+pub fn scroll() -> Item {
+    ItemGenerator::new()
+        .name(SCROLL_NAME)
+        .item_type(ItemType::Usable)
+        .influence(None)
+        .gen()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inventory::Inventory;
+    use world::{Teleport, World};
+    use world::campaign::Campaign;
+    use world::two_dimensional::World2d;
+
+    fn realm() -> Campaign<World2d> {
+        let mut campaign = Campaign::new("Realm");
+        campaign.add_level("village", World2d::new("Village", (5, 5)));
+        campaign.add_level("city", World2d::new("City", (5, 5)));
+        campaign.add_level("crypt", World2d::new("Crypt", (5, 5)));
+        campaign.add_waypoint(Waypoint::new("Village Well", 0, (2, 2)));
+        campaign.add_waypoint(Waypoint::new("City Gate", 1, (0, 4)));
+        campaign.add_waypoint(Waypoint::new("Crypt Altar", 2, (1, 1)));
+        campaign.set_fast_travel("crypt", false);
+        campaign
+    }
+
+    #[test]
+    fn fast_travel() {
+        let mut campaign = realm();
+        assert_eq!(campaign.fast_travel("City Gate"), Err(TravelError::NotAtWaypoint));
+
+        campaign.current_world_mut().unwrap().set_position((2, 2));
+        assert_eq!(campaign.discover_waypoint(), Some("Village Well".to_owned()));
+        assert_eq!(campaign.discover_waypoint(), None);
+        assert_eq!(campaign.fast_travel("City Gate"), Err(TravelError::Undiscovered));
+        assert_eq!(campaign.fast_travel("Tower"), Err(TravelError::Unknown));
+
+        campaign.restore({
+            let mut progress = campaign.checkpoint();
+            progress.discovered_waypoints.insert("City Gate".to_owned());
+            progress.discovered_waypoints.insert("Crypt Altar".to_owned());
+            progress
+        });
+        assert_eq!(campaign.travel_destinations().len(), 2);
+        assert_eq!(campaign.fast_travel("Crypt Altar"), Err(TravelError::Forbidden));
+        assert_eq!(campaign.fast_travel("City Gate"), Ok(()));
+        assert_eq!(campaign.progress().active, "city");
+        assert_eq!(campaign.current_world().unwrap().get_position(), (0, 4));
+    }
+
+    #[test]
+    fn scroll() {
+        let mut campaign = realm();
+        let mut inventory = Inventory::new(5);
+        campaign.current_world_mut().unwrap().set_position((2, 2));
+        campaign.discover_waypoint();
+
+        assert_eq!(campaign.read_scroll("Village Well", &mut inventory),
+                   Err(TravelError::NoScroll));
+        inventory.add_item(super::scroll()).unwrap();
+        campaign.current_world_mut().unwrap().set_position((4, 4));
+        assert_eq!(campaign.read_scroll("Village Well", &mut inventory), Ok(()));
+        assert_eq!(campaign.current_world().unwrap().get_position(), (2, 2));
+        assert_eq!(inventory.count_by_name(SCROLL_NAME), 0);
+    }
+}
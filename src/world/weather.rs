@@ -0,0 +1,192 @@
+use data::{self, DataError};
+use rand::Rng;
+use rustc_serialize::json::{Json, Object, ToJson};
+use std::str::FromStr;
+
+/// The weather in a world
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Weather {
+    /// No weather effects at all
+    Clear,
+    /// It is raining
+    Rain,
+    /// A dense fog limits the view
+    Fog,
+    /// It is snowing
+    Snow,
+    /// A heavy storm with wind and rain
+    Storm,
+}
+
+impl Weather {
+    /// Returns the amount of turns it takes to move a single field in this weather
+    pub fn movement_cost(&self) -> u32 {
+        match *self {
+            Weather::Clear | Weather::Fog | Weather::Rain => 1,
+            Weather::Snow | Weather::Storm => 2,
+        }
+    }
+
+    /// Reduces the given view radius according to the weather
+    pub fn view_radius(&self, radius: usize) -> usize {
+        match *self {
+            Weather::Clear => radius,
+            Weather::Rain | Weather::Snow => radius * 3 / 4,
+            Weather::Storm => radius / 2,
+            Weather::Fog => radius / 4,
+        }
+    }
+
+    /// The glyph a renderer should use to overlay the map, if any
+    pub fn glyph(&self) -> Option<char> {
+        match *self {
+            Weather::Clear => None,
+            Weather::Rain => Some('\''),
+            Weather::Fog => Some('░'),
+            Weather::Snow => Some('*'),
+            Weather::Storm => Some('/'),
+        }
+    }
+}
+
+impl FromStr for Weather {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Weather, ()> {
+        match s {
+            "Clear" => Ok(Weather::Clear),
+            "Rain" => Ok(Weather::Rain),
+            "Fog" => Ok(Weather::Fog),
+            "Snow" => Ok(Weather::Snow),
+            "Storm" => Ok(Weather::Storm),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The climate of a world. Defines how likely each kind of weather is and how often it changes
+#[derive(Clone, Debug)]
+pub struct Climate {
+    /// The relative chances of each weather
+    pub weights: Vec<(Weather, u32)>,
+    /// The chance (`0.0` to `1.0`) for the weather to change when it is updated
+    pub change_chance: f64,
+}
+
+impl Climate {
+    /// Creates a new instance of `Climate` with the given weights
+    pub fn new(weights: Vec<(Weather, u32)>, change_chance: f64) -> Climate {
+        Climate {
+            weights,
+            change_chance,
+        }
+    }
+
+    /// A mild climate with lots of rain and the occasional storm
+    pub fn temperate() -> Climate {
+        Climate::new(vec![(Weather::Clear, 60),
+                          (Weather::Rain, 25),
+                          (Weather::Fog, 10),
+                          (Weather::Storm, 5)],
+                     0.05)
+    }
+
+    /// A cold climate with lots of snow
+    pub fn arctic() -> Climate {
+        Climate::new(vec![(Weather::Clear, 40),
+                          (Weather::Snow, 45),
+                          (Weather::Fog, 5),
+                          (Weather::Storm, 10)],
+                     0.05)
+    }
+
+    /// A dry climate where the weather is clear most of the time
+    pub fn arid() -> Climate {
+        Climate::new(vec![(Weather::Clear, 90), (Weather::Storm, 10)], 0.02)
+    }
+
+    /// Rolls the weather that follows the current weather
+    pub fn next<R: Rng>(&self, current: Weather, rng: &mut R) -> Weather {
+        let total = self.weights.iter().fold(0, |sum, &(_, weight)| sum + weight);
+
+        if total == 0 || rng.gen::<f64>() >= self.change_chance {
+            return current;
+        }
+
+        let mut roll = rng.gen_range(0, total);
+        for &(weather, weight) in &self.weights {
+            if roll < weight {
+                return weather;
+            }
+            roll -= weight;
+        }
+
+        current
+    }
+
+    /// Reads a climate from a JSON object like
+    /// `{"weights": [{"weather": "Rain", "weight": 25}], "change_chance": 0.05}`
+    pub fn from_json(json: &Json) -> Result<Climate, DataError> {
+        let object = data::as_object(json, "climate")?;
+
+        let mut weights = Vec::new();
+        for weight in data::as_array(data::field(object, "weights")?, "weights")? {
+            let weight = data::as_object(weight, "weights")?;
+            weights.push((data::parse(weight, "weather")?,
+                          data::unsigned(weight, "weight")? as u32));
+        }
+
+        Ok(Climate::new(weights, data::float(object, "change_chance")?))
+    }
+}
+
+impl ToJson for Climate {
+    fn to_json(&self) -> Json {
+        let weights: Vec<Json> = self.weights
+            .iter()
+            .map(|&(weather, weight)| {
+                let mut object = Object::new();
+                object.insert("weather".to_owned(), format!("{:?}", weather).to_json());
+                object.insert("weight".to_owned(), weight.to_json());
+                Json::Object(object)
+            })
+            .collect();
+
+        let mut object = Object::new();
+        object.insert("weights".to_owned(), Json::Array(weights));
+        object.insert("change_chance".to_owned(), self.change_chance.to_json());
+
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, XorShiftRng};
+
+    #[test]
+    fn weather_effects() {
+        assert_eq!(Weather::Clear.view_radius(8), 8);
+        assert_eq!(Weather::Fog.view_radius(8), 2);
+        assert_eq!(Weather::Snow.movement_cost(), 2);
+        assert_eq!(Weather::Clear.glyph(), None);
+    }
+
+    #[test]
+    fn climate_transitions() {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let always = Climate::new(vec![(Weather::Snow, 1)], 1.0);
+        assert_eq!(always.next(Weather::Clear, &mut rng), Weather::Snow);
+
+        let never = Climate::new(vec![(Weather::Snow, 1)], 0.0);
+        assert_eq!(never.next(Weather::Clear, &mut rng), Weather::Clear);
+
+        let arid = Climate::arid();
+        for _ in 0..1000 {
+            let weather = arid.next(Weather::Clear, &mut rng);
+            assert!(weather == Weather::Clear || weather == Weather::Storm);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 /// A playable world
 pub trait World {
     /// The type of the position in the world
-    type Position;
+    type Position: Clone + PartialEq;
 
     /// The type of the movement the player can make
     type Movement;
@@ -15,3 +15,9 @@ pub trait World {
     /// Move the position marker to the specified position
     fn move_to(&mut self, movement: Self::Movement);
 }
+
+/// A world the player can be placed in at any position, e.g. when entering through a portal
+pub trait Teleport: World {
+    /// Place the position marker at the given position
+    fn set_position(&mut self, position: Self::Position);
+}